@@ -17,8 +17,14 @@ pub mod associated_type;
 pub mod attributes;
 /// Defines the AST nodes for comments.
 pub mod comments;
+/// Defines the AST node for node types defined outside of this crate.
+pub mod custom;
+/// Defines the box that holds the deeply nestable parts of expressions.
+pub mod deep_box;
 /// Defines the AST nodes for expressions.
 pub mod expressions;
+/// Defines feature-gate annotations for nightly-only AST nodes.
+pub mod feature_gates;
 /// Defines the AST node for a file.
 pub mod file;
 /// Defines the AST nodes for generics.
@@ -61,18 +67,24 @@ pub mod item_union;
 pub mod item_use;
 /// Defines the AST nodes for items.
 pub mod items;
+/// Defines the AST node for a lifetime.
+pub mod lifetime;
 /// Defines the AST nodes for literals.
 pub mod literals;
 /// Defines the AST nodes for metadata.
 pub mod metadata;
 /// Defines the AST nodes for patterns.
 pub mod patterns;
+/// Defines the precedence of expressions.
+pub mod precedence;
 /// Defines the AST nodes for statements.
 pub mod statements;
 /// Defines the AST nodes for tokens.
 pub mod tokens;
 /// Defines the AST nodes for types.
 pub mod types;
+/// Defines the AST node for raw source text.
+pub mod verbatim;
 /// Defines the AST nodes for visibility.
 pub mod visibility;
 /// Defines the AST nodes for `where` clauses.
@@ -83,7 +95,10 @@ pub use associated_const::*;
 pub use associated_type::*;
 pub use attributes::*;
 pub use comments::*;
+pub use custom::*;
+pub use deep_box::*;
 pub use expressions::*;
+pub use feature_gates::*;
 pub use file::*;
 pub use generics::*;
 /// Defines the AST node for an identifier.
@@ -107,11 +122,14 @@ pub use item_trait_alias::*;
 pub use item_type_alias::*;
 pub use item_union::*;
 pub use item_use::*;
+pub use lifetime::*;
 pub use literals::*;
 pub use metadata::*;
 pub use patterns::*;
+pub use precedence::*;
 pub use statements::*;
 pub use tokens::*;
 pub use types::*;
+pub use verbatim::*;
 pub use visibility::*;
 pub use where_clause::*;
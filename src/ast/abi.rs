@@ -1,7 +1,7 @@
 //! The `ast` module contains the definitions for the Abstract Syntax Tree (AST) nodes
 //! that represent Rust ABIs.
 
-use crate::pretty_printer::{PrettyPrinter, Printer};
+use crate::pretty_printer::{PrettyPrinter, Printer, TokenKind};
 use std::fmt;
 
 /// Represents a Rust ABI (Application Binary Interface).
@@ -19,6 +19,9 @@ use std::fmt;
 ///
 /// // A named ABI, like "system"
 /// let system_abi = Abi::Named("system".to_string());
+///
+/// // A bare `extern`, which implies "C"
+/// let implicit_abi = Abi::Implicit;
 /// ```
 #[derive(Debug, Clone, PartialEq)]
 pub enum Abi {
@@ -26,17 +29,40 @@ pub enum Abi {
     ///
     /// For example, `"C"`, `"system"`, or `"Rust"`.
     Named(String),
+    /// Represents a bare `extern` without an ABI string, which implies `"C"`.
+    Implicit,
+}
+
+impl From<&str> for Abi {
+    /// Converts a string slice into an `Abi::Named` variant.
+    fn from(name: &str) -> Self {
+        Abi::Named(name.to_string())
+    }
+}
+
+impl From<String> for Abi {
+    /// Converts a `String` into an `Abi::Named` variant.
+    fn from(name: String) -> Self {
+        Abi::Named(name)
+    }
 }
 
 impl PrettyPrinter for Abi {
+    /// Pretty-prints the `Abi`, including the `extern` keyword.
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
+        printer.string("extern");
         match self {
             Abi::Named(name) => {
-                printer.string("\"");
-                printer.string(name);
-                printer.string("\"");
+                printer.string(" ");
+                printer.highlight(TokenKind::Literal, |printer| {
+                    printer.string(format!("\"{name}\""));
+                    Ok(())
+                })?;
             }
+            Abi::Implicit => {}
         }
         Ok(())
     }
 }
+
+impl_display!(Abi);
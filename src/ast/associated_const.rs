@@ -46,3 +46,5 @@ pub struct AssociatedConst {
     /// Metadata associated with the constant, such as attributes and comments.
     pub md: Option<Box<Md>>,
 }
+
+impl_display!(AssociatedConst);
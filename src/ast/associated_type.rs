@@ -47,3 +47,5 @@ pub struct AssociatedType {
     /// Metadata, such as attributes and comments, attached to the associated type.
     pub md: Option<Box<Md>>,
 }
+
+impl_display!(AssociatedType);
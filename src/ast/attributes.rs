@@ -59,3 +59,5 @@ impl From<&str> for Meta {
         Meta::Path(value.into())
     }
 }
+
+impl_display!(Attribute, Meta, MetaList, MetaNameValue);
@@ -26,3 +26,5 @@ pub enum Comment {
     /// ```
     Doc(String),
 }
+
+impl_display!(Comment);
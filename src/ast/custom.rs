@@ -0,0 +1,198 @@
+//! Defines the AST node for node types defined outside of this crate.
+//!
+//! A [`Custom`] node holds a value of any type that implements
+//! [`CustomNode`]. It can stand in for an item or an expression, and prints
+//! itself with the public methods of [`Printer`](crate::pretty_printer::Printer).
+
+use std::any::Any;
+use std::fmt;
+
+use crate::ast::attributes::Attribute;
+use crate::ast::comments::Comment;
+use crate::ast::expressions::Expr;
+use crate::ast::items::Item;
+use crate::ast::metadata::Md;
+use crate::ast::precedence::Precedence;
+use crate::pretty_printer::PrettyPrinter;
+
+/// A node type defined outside of this crate, such as the syntax of a DSL.
+///
+/// The node prints itself with its [`PrettyPrinter`] implementation, which can
+/// use groups and breaks like the nodes of this crate. It must also be
+/// `Debug`, `Clone` and `PartialEq`, so that the tree containing it still is.
+///
+/// # Examples
+///
+/// ```
+/// use rasto::ast::CustomNode;
+/// use rasto::builder::*;
+/// use rasto::pretty_printer::{BreakStyle, PrettyPrinter, Printer};
+/// use rasto::pretty;
+/// use std::fmt;
+///
+/// #[derive(Debug, Clone, PartialEq)]
+/// struct Query {
+///     clauses: Vec<String>,
+/// }
+///
+/// impl PrettyPrinter for Query {
+///     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
+///         printer.string("query! ");
+///         printer.begin(BreakStyle::Consistent, "{");
+///         for clause in &self.clauses {
+///             printer.break_();
+///             printer.string(clause.as_str());
+///         }
+///         printer.if_broken("", " ");
+///         printer.end("}");
+///         Ok(())
+///     }
+/// }
+///
+/// impl CustomNode for Query {}
+///
+/// let query = Query {
+///     clauses: vec!["from users".into(), "select name".into()],
+/// };
+/// let item = fn_def("f")
+///     .statement(stmt().local(pat().ident("q")).expr(expr().custom(query)))
+///     .build();
+/// assert_eq!(
+///     pretty(&item),
+///     "fn f() {\n    let q = query! { from users select name };\n}"
+/// );
+/// ```
+pub trait CustomNode: PrettyPrinter + fmt::Debug + Send + Sync + DynCustomNode {
+    /// Returns the precedence of the node in expression position, which
+    /// decides whether the printer puts it in parentheses.
+    ///
+    /// Defaults to [`Precedence::Primary`], which never needs them.
+    fn precedence(&self) -> Precedence {
+        Precedence::Primary
+    }
+}
+
+/// The object-safe operations of a [`CustomNode`].
+///
+/// This trait is implemented for every `CustomNode` that is `Clone` and
+/// `PartialEq`.
+pub trait DynCustomNode: Any {
+    /// Returns a boxed copy of the node.
+    fn clone_box(&self) -> Box<dyn CustomNode>;
+
+    /// Returns `true` if `other` is a node of the same type that is equal to
+    /// this one.
+    fn eq_dyn(&self, other: &dyn CustomNode) -> bool;
+
+    /// Returns the node as `&dyn Any`, to downcast it.
+    fn as_any(&self) -> &dyn Any;
+
+    /// Returns the node as `&mut dyn Any`, to downcast it.
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T: CustomNode + Clone + PartialEq> DynCustomNode for T {
+    fn clone_box(&self) -> Box<dyn CustomNode> {
+        Box::new(self.clone())
+    }
+
+    fn eq_dyn(&self, other: &dyn CustomNode) -> bool {
+        other.as_any().downcast_ref::<T>() == Some(self)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+impl Clone for Box<dyn CustomNode> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+/// A node of a type defined outside of this crate.
+///
+/// In expression position, the node is put in parentheses according to its
+/// [`CustomNode::precedence`].
+#[derive(Debug, Clone)]
+pub struct Custom {
+    /// The node.
+    pub node: Box<dyn CustomNode>,
+    /// The comments and attributes printed before and after the node, in
+    /// item position.
+    pub md: Option<Box<Md>>,
+}
+
+impl Custom {
+    /// Creates a new `Custom` node.
+    ///
+    /// # Parameters
+    ///
+    /// - `node`: The node.
+    pub fn new(node: impl CustomNode) -> Self {
+        Self {
+            node: Box::new(node),
+            md: None,
+        }
+    }
+
+    /// Returns the node if it is a `T`.
+    pub fn downcast_ref<T: CustomNode>(&self) -> Option<&T> {
+        self.node.as_any().downcast_ref()
+    }
+
+    /// Returns the node mutably if it is a `T`.
+    pub fn downcast_mut<T: CustomNode>(&mut self) -> Option<&mut T> {
+        self.node.as_any_mut().downcast_mut()
+    }
+
+    /// Adds an attribute to the node.
+    ///
+    /// # Parameters
+    ///
+    /// - `attr`: The attribute to add.
+    pub fn attr(mut self, attr: impl Into<Attribute>) -> Self {
+        self.md.get_or_insert_default().attrs.push(attr.into());
+        self
+    }
+
+    /// Adds a comment to the node.
+    ///
+    /// # Parameters
+    ///
+    /// - `comment`: The comment to add.
+    pub fn comment(mut self, comment: impl Into<Comment>) -> Self {
+        self.md
+            .get_or_insert_default()
+            .comments
+            .push(comment.into());
+        self
+    }
+}
+
+impl PartialEq for Custom {
+    fn eq(&self, other: &Self) -> bool {
+        self.node.eq_dyn(&*other.node) && self.md == other.md
+    }
+}
+
+impl From<Custom> for Item {
+    /// Converts a `Custom` into an `Item::Custom` variant.
+    fn from(custom: Custom) -> Self {
+        Item::Custom(custom)
+    }
+}
+
+impl From<Custom> for Expr {
+    /// Converts a `Custom` into an `Expr::Custom` variant.
+    fn from(custom: Custom) -> Self {
+        Expr::Custom(custom)
+    }
+}
+
+impl_display!(Custom);
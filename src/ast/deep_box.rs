@@ -0,0 +1,191 @@
+//! Defines the box that holds the deeply nestable parts of expressions.
+//!
+//! Generated code can nest binary operations, `else if` chains and blocks
+//! tens of thousands of levels deep. The operands of binary operations, the
+//! `else` branches of `if` expressions and the blocks of block expressions are
+//! held in a [`DeepBox`], which drops, compares and formats such trees without
+//! overflowing the stack.
+
+use crate::ast::expressions::{Expr, ExprInfer};
+use crate::ast::statements::{Block, Stmt};
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+
+/// A `Box` for an expression or a block that may be nested deeply, such as an
+/// operand of a long `a + b + c + ...` chain.
+///
+/// A `DeepBox` derefs to its contents and converts from a value or a `Box`.
+/// Dropping it does not recurse through the binary operations, `if`
+/// expressions and blocks nested in it, and comparing or formatting it with
+/// `Debug` grows the stack as needed, so that deep trees do not overflow the
+/// stack.
+///
+/// # Examples
+///
+/// ```
+/// use rasto::ast::*;
+/// use rasto::builder::expr;
+///
+/// let sum = ExprBinary {
+///     left: expr().lit(1).into(),
+///     op: BinOp::Add,
+///     right: expr().lit(2).into(),
+/// };
+/// let ExprBinary { left, .. } = sum;
+/// assert_eq!(left.into_inner(), expr().lit(1));
+/// ```
+pub struct DeepBox<T: Nested>(Box<T>);
+
+impl<T: Nested> DeepBox<T> {
+    /// Creates a new `DeepBox` holding `value`.
+    pub fn new(value: T) -> Self {
+        Self(Box::new(value))
+    }
+
+    /// Returns the value held by the box.
+    pub fn into_inner(mut self) -> T {
+        std::mem::replace(&mut *self.0, T::placeholder())
+    }
+}
+
+impl<T: Nested> Drop for DeepBox<T> {
+    fn drop(&mut self) {
+        let mut stack = Vec::new();
+        self.0.take_nested(&mut stack);
+        drop_nested(stack);
+    }
+}
+
+impl<T: Nested> Deref for DeepBox<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: Nested> DerefMut for DeepBox<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T: Nested> AsRef<T> for DeepBox<T> {
+    fn as_ref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: Nested> AsMut<T> for DeepBox<T> {
+    fn as_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T: Nested> From<T> for DeepBox<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T: Nested> From<Box<T>> for DeepBox<T> {
+    fn from(value: Box<T>) -> Self {
+        Self(value)
+    }
+}
+
+impl<T: Nested + Clone> Clone for DeepBox<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T: Nested + PartialEq> PartialEq for DeepBox<T> {
+    fn eq(&self, other: &Self) -> bool {
+        crate::stack::ensure_sufficient_stack(|| self.0 == other.0)
+    }
+}
+
+impl<T: Nested + fmt::Debug> fmt::Debug for DeepBox<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        crate::stack::ensure_sufficient_stack(|| self.0.fmt(f))
+    }
+}
+
+/// A type that a [`DeepBox`] can hold: [`Expr`] or [`Block`].
+///
+/// This trait is sealed and cannot be implemented outside of this crate.
+pub trait Nested: sealed::Sealed + Sized {
+    #[doc(hidden)]
+    fn placeholder() -> Self;
+
+    #[doc(hidden)]
+    fn take_nested(&mut self, stack: &mut Vec<Expr>);
+}
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for super::Expr {}
+    impl Sealed for super::Block {}
+}
+
+impl Nested for Expr {
+    fn placeholder() -> Self {
+        Expr::Infer(ExprInfer)
+    }
+
+    /// Moves the expression to `stack` if it is a binary operation, an `if`
+    /// expression or a block, which may be nested deeply, and leaves `_` in
+    /// its place.
+    fn take_nested(&mut self, stack: &mut Vec<Expr>) {
+        if matches!(self, Expr::Binary(_) | Expr::If(_) | Expr::Block(_)) {
+            stack.push(std::mem::replace(self, Expr::placeholder()));
+        }
+    }
+}
+
+impl Nested for Block {
+    fn placeholder() -> Self {
+        Block::default()
+    }
+
+    /// Moves the nested expressions of the statements of the block to
+    /// `stack`.
+    fn take_nested(&mut self, stack: &mut Vec<Expr>) {
+        for stmt in &mut self.stmts {
+            match stmt {
+                Stmt::Expr(stmt) => stmt.expr.take_nested(stack),
+                Stmt::Local(local) => {
+                    if let Some(expr) = &mut local.expr {
+                        expr.take_nested(stack);
+                    }
+                }
+                Stmt::Item(_) | Stmt::Verbatim(_) => {}
+            }
+        }
+    }
+}
+
+/// Drops the expressions on `stack` one at a time, moving their nested
+/// expressions to the stack first, so that dropping a deep tree does not
+/// recurse.
+fn drop_nested(mut stack: Vec<Expr>) {
+    while let Some(mut expr) = stack.pop() {
+        match &mut expr {
+            Expr::Binary(binary) => {
+                binary.left.take_nested(&mut stack);
+                binary.right.take_nested(&mut stack);
+            }
+            Expr::If(expr_if) => {
+                expr_if.cond.take_nested(&mut stack);
+                expr_if.then_branch.take_nested(&mut stack);
+                if let Some(else_branch) = &mut expr_if.else_branch {
+                    else_branch.take_nested(&mut stack);
+                }
+            }
+            Expr::Block(block) => block.block.take_nested(&mut stack),
+            _ => {}
+        }
+    }
+}
@@ -4,8 +4,11 @@
 //! This module provides the data structures for all kinds of expressions, such as binary operations,
 //! function calls, and control flow expressions like `if` and `match`.
 
+use crate::ast::precedence::Precedence;
 use crate::ast::{
-    Pat, TokenStream, generics::GenericArgs, ident::Ident, literals::Lit, statements::Block,
+    Attribute, Comment, Md, Pat, TokenStream, custom::Custom, deep_box::DeepBox,
+    generics::GenericArgs, ident::Ident, item_asm::ExprAsm, literals::Lit, statements::Block,
+    verbatim::Verbatim,
 };
 use thin_vec::ThinVec;
 
@@ -15,12 +18,16 @@ pub enum Expr {
     // Expressions sorted alphabetically
     /// An array expression: `[a, b, c]`.
     Array(ExprArray),
+    /// An inline assembly expression: `asm!(...)`.
+    Asm(ExprAsm),
     /// An assignment expression: `x = y`.
     Assign(ExprAssign),
     /// An `async` block: `async { ... }`.
     Async(ExprAsync),
     /// An `await` expression: `future.await`.
     Await(ExprAwait),
+    /// A `become` tail call: `become f(x)`.
+    Become(ExprBecome),
     /// A binary operation: `a + b`.
     Binary(ExprBinary),
     /// A block expression: `{ ... }`.
@@ -37,6 +44,8 @@ pub enum Expr {
     Const(ExprConst),
     /// A `continue` expression.
     Continue(ExprContinue),
+    /// A node of a type defined outside of this crate.
+    Custom(Custom),
     /// A field access expression: `stru.field`.
     Field(ExprField),
     /// A `for` loop expression: `for pat in iter { ... }`.
@@ -79,8 +88,12 @@ pub enum Expr {
     Tuple(ExprTuple),
     /// A unary operation: `!x` or `-x`.
     Unary(ExprUnary),
+    /// Raw source text, printed as it is.
+    Verbatim(Verbatim),
     /// A `while` loop expression: `while x { ... }`.
     While(ExprWhile),
+    /// A `yield` expression: `yield x`.
+    Yield(ExprYield),
 }
 
 /// Represents a unary operator.
@@ -90,6 +103,8 @@ pub enum UnOp {
     Not,
     /// The arithmetic negation operator `-`.
     Neg,
+    /// The dereference operator `*`.
+    Deref,
 }
 
 /// Represents a unary operation, which consists of an operator applied to a
@@ -131,14 +146,42 @@ pub struct ExprAwait {
 /// Represents a binary operation, which combines two expressions with an operator.
 ///
 /// For example, `a + b` or `x * y`.
-#[derive(Debug, Clone, PartialEq)]
+///
+/// Cloning a long chain such as `a + b + c + ...` does not recurse through
+/// its left operands, and the operands are held in a [`DeepBox`], so that
+/// such chains do not overflow the stack.
+#[derive(Debug, PartialEq)]
 pub struct ExprBinary {
     /// The expression on the left-hand side of the operator.
-    pub left: Box<Expr>,
+    pub left: DeepBox<Expr>,
     /// The binary operator, such as `+`, `-`, `*`, or `/`.
     pub op: BinOp,
     /// The expression on the right-hand side of the operator.
-    pub right: Box<Expr>,
+    pub right: DeepBox<Expr>,
+}
+
+impl Clone for ExprBinary {
+    fn clone(&self) -> Self {
+        let mut spine = vec![];
+        let mut leaf = &self.left;
+        while let Expr::Binary(binary) = &**leaf {
+            spine.push(binary);
+            leaf = &binary.left;
+        }
+        let mut left = leaf.clone();
+        for binary in spine.into_iter().rev() {
+            left = DeepBox::new(Expr::Binary(ExprBinary {
+                left,
+                op: binary.op.clone(),
+                right: binary.right.clone(),
+            }));
+        }
+        ExprBinary {
+            left,
+            op: self.op.clone(),
+            right: self.right.clone(),
+        }
+    }
 }
 
 /// Represents a `break` expression, which is used to exit a loop prematurely.
@@ -190,11 +233,52 @@ pub struct ExprCast {
 #[derive(Debug, Clone, PartialEq)]
 pub struct ExprClosure {
     /// The list of input parameters for the closure.
-    pub inputs: ThinVec<Pat>,
+    pub inputs: ThinVec<ClosureParam>,
     /// The body of the closure, which is the code that gets executed.
     pub body: Box<Expr>,
 }
 
+/// Represents a single closure parameter, such as `#[allow(unused)] x`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClosureParam {
+    /// The pattern that binds the argument.
+    pub pat: Pat,
+    /// Comments and attributes attached to the parameter.
+    pub md: Option<Box<Md>>,
+}
+
+impl ClosureParam {
+    /// Adds an attribute to the parameter.
+    ///
+    /// # Parameters
+    ///
+    /// - `attr`: The attribute to add.
+    pub fn attr(mut self, attr: impl Into<Attribute>) -> Self {
+        self.md.get_or_insert_default().attrs.push(attr.into());
+        self
+    }
+
+    /// Adds a comment before the parameter.
+    ///
+    /// # Parameters
+    ///
+    /// - `comment`: The comment to add.
+    pub fn comment(mut self, comment: impl Into<Comment>) -> Self {
+        self.md.get_or_insert_default().comments.push(comment.into());
+        self
+    }
+}
+
+impl<T: Into<Pat>> From<T> for ClosureParam {
+    /// Converts a pattern into a `ClosureParam` without metadata.
+    fn from(pat: T) -> Self {
+        Self {
+            pat: pat.into(),
+            md: None,
+        }
+    }
+}
+
 /// Represents a `const` block, which is a block of code that is evaluated at
 /// compile time.
 #[derive(Debug, Clone, PartialEq)]
@@ -267,6 +351,8 @@ pub struct Arm {
     /// The expression that is executed if the pattern matches and the guard
     /// (if present) evaluates to true.
     pub body: Box<Expr>,
+    /// Comments and attributes attached to the arm.
+    pub md: Option<Box<Md>>,
 }
 
 /// Represents a method call expression, like `object.method(arg1, arg2)`.
@@ -359,6 +445,27 @@ pub struct ExprReturn {
     pub expr: Option<Box<Expr>>,
 }
 
+/// Represents a `become` expression, which performs a guaranteed tail call.
+///
+/// For example, `become fib(n - 1, a + b)`. Requires the
+/// `explicit_tail_calls` feature gate.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExprBecome {
+    /// The call expression that replaces the current function's frame.
+    pub expr: Box<Expr>,
+}
+
+/// Represents a `yield` expression, which suspends a `gen` block, `gen fn` or
+/// coroutine and produces a value.
+///
+/// For example, `yield x`. Inside a `gen` block or `gen fn` this requires the
+/// `gen_blocks` feature gate; elsewhere it requires `coroutines`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExprYield {
+    /// The optional value being yielded. If `None`, `()` is yielded.
+    pub expr: Option<Box<Expr>>,
+}
+
 /// Represents a struct instantiation expression.
 ///
 /// This is used to create a new instance of a struct, for example:
@@ -391,6 +498,39 @@ pub struct FieldValue {
     pub member: Ident,
     /// The expression that provides the value for the field.
     pub value: Expr,
+    /// Comments and attributes attached to the field.
+    pub md: Option<Box<Md>>,
+}
+
+impl FieldValue {
+    /// Adds an attribute to the field, such as `#[cfg(feature = "x")]`.
+    ///
+    /// # Parameters
+    ///
+    /// - `attr`: The attribute to add.
+    pub fn attr(mut self, attr: impl Into<Attribute>) -> Self {
+        self.md.get_or_insert_default().attrs.push(attr.into());
+        self
+    }
+
+    /// Adds a comment before the field.
+    ///
+    /// # Parameters
+    ///
+    /// - `comment`: The comment to add.
+    pub fn comment(mut self, comment: impl Into<Comment>) -> Self {
+        self.md.get_or_insert_default().comments.push(comment.into());
+        self
+    }
+    /// Sets a comment printed after the field's comma, e.g. `x: 0, // origin`.
+    ///
+    /// # Parameters
+    ///
+    /// - `text`: The content of the comment, without the `//`.
+    pub fn eol_comment(mut self, text: impl Into<String>) -> Self {
+        self.md.get_or_insert_default().eol_comment = Some(text.into());
+        self
+    }
 }
 
 /// Represents a tuple expression, such as `(a, b, c)`.
@@ -413,6 +553,22 @@ pub enum BinOp {
     Mul,
     /// The division operator `/`.
     Div,
+    /// The remainder operator `%`.
+    Rem,
+    /// The lazy boolean and operator `&&`.
+    And,
+    /// The lazy boolean or operator `||`.
+    Or,
+    /// The bitwise xor operator `^`.
+    BitXor,
+    /// The bitwise and operator `&`.
+    BitAnd,
+    /// The bitwise or operator `|`.
+    BitOr,
+    /// The shift left operator `<<`.
+    Shl,
+    /// The shift right operator `>>`.
+    Shr,
     /// The equality operator `==`.
     Eq,
     /// The less than operator `<`.
@@ -425,18 +581,54 @@ pub enum BinOp {
     Ge,
     /// The greater than operator `>`.
     Gt,
+    /// The addition assignment operator `+=`.
+    AddAssign,
+    /// The subtraction assignment operator `-=`.
+    SubAssign,
+    /// The multiplication assignment operator `*=`.
+    MulAssign,
+    /// The division assignment operator `/=`.
+    DivAssign,
+    /// The remainder assignment operator `%=`.
+    RemAssign,
+    /// The bitwise xor assignment operator `^=`.
+    BitXorAssign,
+    /// The bitwise and assignment operator `&=`.
+    BitAndAssign,
+    /// The bitwise or assignment operator `|=`.
+    BitOrAssign,
+    /// The shift left assignment operator `<<=`.
+    ShlAssign,
+    /// The shift right assignment operator `>>=`.
+    ShrAssign,
 }
 
 impl BinOp {
     /// Returns the precedence of the operator, which is used to determine the
     /// order of operations in an expression.
-    ///
-    /// Higher values indicate higher precedence.
-    pub fn precedence(&self) -> u8 {
+    pub fn precedence(&self) -> Precedence {
         match self {
-            BinOp::Mul | BinOp::Div => 2,
-            BinOp::Add | BinOp::Sub => 1,
-            _ => 0,
+            BinOp::Mul | BinOp::Div | BinOp::Rem => Precedence::Product,
+            BinOp::Add | BinOp::Sub => Precedence::Sum,
+            BinOp::Shl | BinOp::Shr => Precedence::Shift,
+            BinOp::BitAnd => Precedence::BitAnd,
+            BinOp::BitXor => Precedence::BitXor,
+            BinOp::BitOr => Precedence::BitOr,
+            BinOp::Eq | BinOp::Lt | BinOp::Le | BinOp::Ne | BinOp::Ge | BinOp::Gt => {
+                Precedence::Compare
+            }
+            BinOp::And => Precedence::And,
+            BinOp::Or => Precedence::Or,
+            BinOp::AddAssign
+            | BinOp::SubAssign
+            | BinOp::MulAssign
+            | BinOp::DivAssign
+            | BinOp::RemAssign
+            | BinOp::BitXorAssign
+            | BinOp::BitAndAssign
+            | BinOp::BitOrAssign
+            | BinOp::ShlAssign
+            | BinOp::ShrAssign => Precedence::Assign,
         }
     }
 }
@@ -444,7 +636,11 @@ impl BinOp {
 /// Represents an `if` expression, which allows for conditional execution.
 ///
 /// An `if` expression can optionally have an `else` branch.
-#[derive(Debug, Clone, PartialEq)]
+///
+/// Cloning a long `else if` chain does not recurse through its `else`
+/// branches, and the `else` branch is held in a [`DeepBox`], so that such
+/// chains do not overflow the stack.
+#[derive(Debug, PartialEq)]
 pub struct ExprIf {
     /// The condition that is evaluated.
     pub cond: Box<Expr>,
@@ -453,7 +649,33 @@ pub struct ExprIf {
     /// The optional `else` branch, which is executed if the condition is false.
     ///
     /// This can be another `if` expression for `else if` chains.
-    pub else_branch: Option<Box<Expr>>,
+    pub else_branch: Option<DeepBox<Expr>>,
+}
+
+impl Clone for ExprIf {
+    fn clone(&self) -> Self {
+        let mut spine = vec![];
+        let mut tail = &self.else_branch;
+        while let Some(else_branch) = tail
+            && let Expr::If(expr_if) = &**else_branch
+        {
+            spine.push(expr_if);
+            tail = &expr_if.else_branch;
+        }
+        let mut else_branch = tail.clone();
+        for expr_if in spine.into_iter().rev() {
+            else_branch = Some(DeepBox::new(Expr::If(ExprIf {
+                cond: expr_if.cond.clone(),
+                then_branch: expr_if.then_branch.clone(),
+                else_branch,
+            })));
+        }
+        ExprIf {
+            cond: self.cond.clone(),
+            then_branch: self.then_branch.clone(),
+            else_branch,
+        }
+    }
 }
 
 /// Represents a block expression, which is a collection of statements enclosed
@@ -464,7 +686,7 @@ pub struct ExprIf {
 #[derive(Debug, Clone, PartialEq)]
 pub struct ExprBlock {
     /// The block containing the statements.
-    pub block: Block,
+    pub block: DeepBox<Block>,
 }
 
 /// Represents a `loop` expression, which creates an infinite loop.
@@ -514,6 +736,20 @@ pub struct ExprAssign {
     pub right: Box<Expr>,
 }
 
+impl From<ExprBecome> for Expr {
+    /// Converts an `ExprBecome` into an `Expr::Become` variant.
+    fn from(expr: ExprBecome) -> Self {
+        Expr::Become(expr)
+    }
+}
+
+impl From<ExprYield> for Expr {
+    /// Converts an `ExprYield` into an `Expr::Yield` variant.
+    fn from(expr: ExprYield) -> Self {
+        Expr::Yield(expr)
+    }
+}
+
 impl From<ExprTry> for Expr {
     /// Converts an `ExprTry` into an `Expr::Try` variant.
     fn from(expr: ExprTry) -> Self {
@@ -561,3 +797,72 @@ pub struct PathSegment {
     /// For example, in `Vec<i32>`, the arguments would be `<i32>`.
     pub args: Option<GenericArgs>,
 }
+
+impl Path {
+    /// Builds a path from `::`-separated identifiers, such as
+    /// `std::fmt::Display`, or returns `None` if `s` has any other syntax.
+    pub(crate) fn from_idents(s: &str) -> Option<Self> {
+        let segments = s
+            .split("::")
+            .map(|segment| {
+                let name = segment.strip_prefix("r#").unwrap_or(segment);
+                let mut chars = name.chars();
+                let is_ident = name != "_"
+                    && chars.next().is_some_and(|c| c == '_' || c.is_alphabetic())
+                    && chars.all(|c| c == '_' || c.is_alphanumeric());
+                is_ident.then(|| PathSegment {
+                    ident: segment.into(),
+                    args: None,
+                })
+            })
+            .collect::<Option<_>>()?;
+        Some(Path { segments })
+    }
+}
+
+impl_display!(
+    Expr,
+    ExprArray,
+    ExprAssign,
+    ExprAsync,
+    ExprAwait,
+    ExprBecome,
+    ExprBinary,
+    ExprBlock,
+    ExprBreak,
+    ExprCall,
+    ExprCast,
+    ExprClosure,
+    ExprConst,
+    ExprContinue,
+    ExprField,
+    ExprFor,
+    ExprGen,
+    ExprIf,
+    ExprIndex,
+    ExprInfer,
+    ExprLoop,
+    ExprMacroCall,
+    ExprMatch,
+    ExprMethodCall,
+    ExprParen,
+    ExprPath,
+    ExprRange,
+    ExprRawRef,
+    ExprRef,
+    ExprReturn,
+    ExprStruct,
+    ExprTry,
+    ExprTuple,
+    ExprUnary,
+    ExprWhile,
+    ExprYield,
+    Arm,
+    BinOp,
+    ClosureParam,
+    FieldValue,
+    Path,
+    PathSegment,
+    RangeLimits,
+    UnOp
+);
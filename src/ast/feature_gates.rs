@@ -0,0 +1,155 @@
+//! Defines feature-gate annotations for AST nodes that need a nightly compiler.
+//!
+//! Each node that only compiles with an unstable `#![feature(...)]` implements
+//! [`FeatureGated`], and [`File::required_features`] walks a whole file to
+//! collect the gates it needs.
+
+use crate::ast::{
+    Attribute, Expr, ExprBecome, ExprGen, ExprTry, ExprYield, File, ItemExternType, ItemFn,
+    ItemTraitAlias, Meta, MetaList, Signature,
+    items::{Item, TraitItemFn},
+};
+use crate::visit::{self, Visit};
+use std::collections::BTreeSet;
+use thin_vec::thin_vec;
+
+/// A node that needs one or more unstable `#![feature(...)]` gates.
+pub trait FeatureGated {
+    /// Returns the names of the features this node needs, not counting the
+    /// features needed by its children.
+    fn feature_gates(&self) -> &'static [&'static str];
+}
+
+impl FeatureGated for ExprBecome {
+    fn feature_gates(&self) -> &'static [&'static str] {
+        &["explicit_tail_calls"]
+    }
+}
+
+impl FeatureGated for ExprGen {
+    fn feature_gates(&self) -> &'static [&'static str] {
+        &["gen_blocks"]
+    }
+}
+
+impl FeatureGated for ExprTry {
+    fn feature_gates(&self) -> &'static [&'static str] {
+        &["try_blocks"]
+    }
+}
+
+impl FeatureGated for ExprYield {
+    /// Returns `coroutines`. A `yield` inside a `gen` block or `gen fn` is
+    /// covered by that block's `gen_blocks` gate instead.
+    fn feature_gates(&self) -> &'static [&'static str] {
+        &["coroutines"]
+    }
+}
+
+impl FeatureGated for Signature {
+    fn feature_gates(&self) -> &'static [&'static str] {
+        if self.is_gen { &["gen_blocks"] } else { &[] }
+    }
+}
+
+impl FeatureGated for ItemExternType {
+    fn feature_gates(&self) -> &'static [&'static str] {
+        &["extern_types"]
+    }
+}
+
+impl FeatureGated for ItemTraitAlias {
+    fn feature_gates(&self) -> &'static [&'static str] {
+        &["trait_alias"]
+    }
+}
+
+impl File {
+    /// Collects the names of the `#![feature(...)]` gates needed by every
+    /// node in the file, sorted and without duplicates.
+    pub fn required_features(&self) -> BTreeSet<&'static str> {
+        let mut collector = Collector::default();
+        collector.visit_file(self);
+        collector.features
+    }
+
+    /// Returns one `#![feature(...)]` inner attribute per gate reported by
+    /// [`File::required_features`].
+    pub fn feature_attrs(&self) -> Vec<Attribute> {
+        self.required_features()
+            .into_iter()
+            .map(|feature| {
+                Attribute::Inner(Meta::List(MetaList {
+                    path: "feature".into(),
+                    metas: thin_vec![Meta::Path(feature.into())],
+                }))
+            })
+            .collect()
+    }
+}
+
+/// Collects the feature gates of every node it visits.
+#[derive(Default)]
+struct Collector {
+    features: BTreeSet<&'static str>,
+    /// Whether the visited node is inside a `gen` block or `gen fn`.
+    in_gen: bool,
+}
+
+impl Collector {
+    fn add(&mut self, node: &impl FeatureGated) {
+        self.features.extend(node.feature_gates());
+    }
+
+    fn with_gen(&mut self, in_gen: bool, f: impl FnOnce(&mut Self)) {
+        let outer = std::mem::replace(&mut self.in_gen, in_gen);
+        f(self);
+        self.in_gen = outer;
+    }
+}
+
+impl<'ast> Visit<'ast> for Collector {
+    fn visit_item(&mut self, item: &'ast Item) {
+        self.with_gen(false, |c| visit::walk_item(c, item));
+    }
+
+    fn visit_item_fn(&mut self, item: &'ast ItemFn) {
+        self.with_gen(item.sig.is_gen, |c| visit::walk_item_fn(c, item));
+    }
+
+    fn visit_trait_item_fn(&mut self, item: &'ast TraitItemFn) {
+        self.with_gen(item.sig.is_gen, |c| visit::walk_trait_item_fn(c, item));
+    }
+
+    fn visit_signature(&mut self, sig: &'ast Signature) {
+        self.add(sig);
+        visit::walk_signature(self, sig);
+    }
+
+    fn visit_item_extern_type(&mut self, item: &'ast ItemExternType) {
+        self.add(item);
+        visit::walk_item_extern_type(self, item);
+    }
+
+    fn visit_item_trait_alias(&mut self, item: &'ast ItemTraitAlias) {
+        self.add(item);
+        visit::walk_item_trait_alias(self, item);
+    }
+
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        match expr {
+            Expr::Become(become_) => self.add(become_),
+            Expr::Gen(gen_) => {
+                self.add(gen_);
+                return self.with_gen(true, |c| visit::walk_expr(c, expr));
+            }
+            Expr::Try(try_) => self.add(try_),
+            Expr::Yield(yield_) if !self.in_gen => self.add(yield_),
+            Expr::Async(_) | Expr::Closure(_) | Expr::Const(_) => {
+                return self.with_gen(false, |c| visit::walk_expr(c, expr));
+            }
+            _ => {}
+        }
+        visit::walk_expr(self, expr);
+    }
+}
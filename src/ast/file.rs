@@ -2,8 +2,6 @@
 
 use crate::ast::items::Item;
 use crate::ast::metadata::Md;
-use crate::pretty_printer::{PrettyPrinter, Printer};
-use std::fmt;
 use thin_vec::ThinVec;
 
 /// Represents a Rust source file.
@@ -19,12 +17,4 @@ pub struct File {
     pub md: Option<Box<Md>>,
 }
 
-impl fmt::Display for File {
-    /// Formats the `File` using the pretty-printer, producing a string
-    /// representation of the Rust source file.
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let mut printer = Printer::new(f);
-        self.pretty_print(&mut printer)?;
-        printer.finish()
-    }
-}
+impl_display!(File);
@@ -1,8 +1,11 @@
 //! The `ast` module contains the definitions for the Abstract Syntax Tree (AST) nodes
 //! that represent Rust generics.
 
-use crate::ast::{ident::Ident, types::Type};
-use crate::pretty_printer::{PrettyPrinter, Printer};
+use crate::ast::{
+    attributes::Attribute, comments::Comment, ident::Ident, lifetime::Lifetime, metadata::Md,
+    types::Type,
+};
+use crate::pretty_printer::{PrettyPrinter, Printer, pp_inline_md};
 use std::fmt;
 
 /// Creates a new `GenericParamBuilder`.
@@ -24,6 +27,7 @@ impl GenericParamBuilder {
         TypeParam {
             ident: ident.into(),
             bounds: vec![],
+            md: None,
         }
     }
 
@@ -31,10 +35,12 @@ impl GenericParamBuilder {
     ///
     /// # Parameters
     ///
-    /// - `ident`: The name of the lifetime parameter.
-    pub fn lifetime(self, ident: impl Into<Ident>) -> LifetimeParam {
+    /// - `lifetime`: The lifetime being declared, e.g., `a` or `'a`.
+    pub fn lifetime(self, lifetime: impl Into<Lifetime>) -> LifetimeParam {
         LifetimeParam {
-            ident: ident.into(),
+            lifetime: lifetime.into(),
+            bounds: vec![],
+            md: None,
         }
     }
 
@@ -48,6 +54,7 @@ impl GenericParamBuilder {
         ConstParam {
             ident: ident.into(),
             ty: ty.into(),
+            md: None,
         }
     }
 }
@@ -98,11 +105,48 @@ impl From<ConstParam> for GenericParam {
     }
 }
 
-/// A lifetime parameter, such as `'a`.
+/// A lifetime parameter, such as `'a` or `'de: 'a`.
 #[derive(Debug, Clone, PartialEq)]
 pub struct LifetimeParam {
-    /// The name of the lifetime, without the leading apostrophe.
-    pub ident: Ident,
+    /// The lifetime being declared.
+    pub lifetime: Lifetime,
+    /// The lifetimes that the declared lifetime outlives.
+    /// For example, in `'de: 'a + 'b`, `bounds` would contain `'a` and `'b`.
+    pub bounds: Vec<Lifetime>,
+    /// Metadata about the lifetime parameter, such as `#[may_dangle]`.
+    pub md: Option<Box<Md>>,
+}
+
+impl LifetimeParam {
+    /// Adds an outlives-bound to the lifetime parameter.
+    ///
+    /// # Parameters
+    ///
+    /// * `bound` - The lifetime that the parameter outlives.
+    pub fn with_bound(mut self, bound: impl Into<Lifetime>) -> Self {
+        self.bounds.push(bound.into());
+        self
+    }
+
+    /// Adds an attribute to the lifetime parameter.
+    ///
+    /// # Parameters
+    ///
+    /// * `attr` - The attribute to add.
+    pub fn attr(mut self, attr: impl Into<Attribute>) -> Self {
+        self.md.get_or_insert_default().attrs.push(attr.into());
+        self
+    }
+
+    /// Adds a comment before the lifetime parameter.
+    ///
+    /// # Parameters
+    ///
+    /// * `comment` - The comment to add.
+    pub fn comment(mut self, comment: impl Into<Comment>) -> Self {
+        self.md.get_or_insert_default().comments.push(comment.into());
+        self
+    }
 }
 
 /// A type parameter, such as `T: Trait`.
@@ -114,6 +158,8 @@ pub struct TypeParam {
     /// For example, in `T: Clone + Debug`, `bounds` would contain the `Type`s
     /// for `Clone` and `Debug`.
     pub bounds: Vec<Type>,
+    /// Metadata about the type parameter, such as `#[may_dangle]`.
+    pub md: Option<Box<Md>>,
 }
 
 impl TypeParam {
@@ -126,6 +172,26 @@ impl TypeParam {
         self.bounds.push(bound.into());
         self
     }
+
+    /// Adds an attribute to the type parameter.
+    ///
+    /// # Parameters
+    ///
+    /// * `attr` - The attribute to add.
+    pub fn attr(mut self, attr: impl Into<Attribute>) -> Self {
+        self.md.get_or_insert_default().attrs.push(attr.into());
+        self
+    }
+
+    /// Adds a comment before the type parameter.
+    ///
+    /// # Parameters
+    ///
+    /// * `comment` - The comment to add.
+    pub fn comment(mut self, comment: impl Into<Comment>) -> Self {
+        self.md.get_or_insert_default().comments.push(comment.into());
+        self
+    }
 }
 
 /// A const parameter, such as `const N: usize`.
@@ -135,6 +201,30 @@ pub struct ConstParam {
     pub ident: Ident,
     /// The type of the const parameter.
     pub ty: Type,
+    /// Metadata about the const parameter, such as attributes.
+    pub md: Option<Box<Md>>,
+}
+
+impl ConstParam {
+    /// Adds an attribute to the const parameter.
+    ///
+    /// # Parameters
+    ///
+    /// * `attr` - The attribute to add.
+    pub fn attr(mut self, attr: impl Into<Attribute>) -> Self {
+        self.md.get_or_insert_default().attrs.push(attr.into());
+        self
+    }
+
+    /// Adds a comment before the const parameter.
+    ///
+    /// # Parameters
+    ///
+    /// * `comment` - The comment to add.
+    pub fn comment(mut self, comment: impl Into<Comment>) -> Self {
+        self.md.get_or_insert_default().comments.push(comment.into());
+        self
+    }
 }
 
 impl PrettyPrinter for GenericParams {
@@ -165,14 +255,24 @@ impl PrettyPrinter for GenericParam {
 
 impl PrettyPrinter for LifetimeParam {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
-        printer.string("'");
-        self.ident.pretty_print(printer)?;
+        pp_inline_md(&self.md, printer)?;
+        self.lifetime.pretty_print(printer)?;
+        if !self.bounds.is_empty() {
+            printer.string(": ");
+            for (i, bound) in self.bounds.iter().enumerate() {
+                if i > 0 {
+                    printer.string(" + ");
+                }
+                bound.pretty_print(printer)?;
+            }
+        }
         Ok(())
     }
 }
 
 impl PrettyPrinter for TypeParam {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
+        pp_inline_md(&self.md, printer)?;
         self.ident.pretty_print(printer)?;
         if !self.bounds.is_empty() {
             printer.string(": ");
@@ -189,6 +289,7 @@ impl PrettyPrinter for TypeParam {
 
 impl PrettyPrinter for ConstParam {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
+        pp_inline_md(&self.md, printer)?;
         printer.string("const ");
         self.ident.pretty_print(printer)?;
         printer.string(": ");
@@ -214,7 +315,7 @@ impl GenericArgs {
 #[derive(Debug, Clone, PartialEq)]
 pub enum GenericArg {
     /// A lifetime argument: `'a`.
-    Lifetime(Ident),
+    Lifetime(Lifetime),
     /// A type argument: `T`.
     Type(Type),
     /// A const argument: `N`.
@@ -229,3 +330,20 @@ impl From<&str> for GenericArg {
         GenericArg::Type(Type::from(s))
     }
 }
+
+impl From<Lifetime> for GenericArg {
+    /// Converts a `Lifetime` into a `GenericArg::Lifetime`.
+    fn from(lifetime: Lifetime) -> Self {
+        GenericArg::Lifetime(lifetime)
+    }
+}
+
+impl_display!(
+    GenericArg,
+    GenericArgs,
+    GenericParam,
+    GenericParams,
+    ConstParam,
+    LifetimeParam,
+    TypeParam
+);
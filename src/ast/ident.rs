@@ -1,5 +1,5 @@
 //! Defines the AST node for an identifier.
-use crate::pretty_printer::{PrettyPrinter, Printer};
+use crate::pretty_printer::{PrettyPrinter, Printer, TokenKind};
 use std::fmt;
 
 /// An identifier, such as `my_variable` or `r#true`.
@@ -34,10 +34,14 @@ impl<T: Into<String>> From<T> for Ident {
 
 impl PrettyPrinter for Ident {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
-        if self.is_raw {
-            printer.string("r#");
-        }
-        printer.string(&self.name);
-        Ok(())
+        printer.highlight(TokenKind::Ident, |printer| {
+            if self.is_raw {
+                printer.string("r#");
+            }
+            printer.string(&self.name);
+            Ok(())
+        })
     }
 }
+
+impl_display!(Ident);
@@ -1,36 +1,87 @@
-//! Defines the AST node for an `asm!` expression.
+//! Defines the AST nodes for the `asm!`, `global_asm!` and `naked_asm!` macros.
 
-use crate::ast::{Expr, LitStr, Path};
-use crate::pretty_printer::PrettyPrinter;
+use crate::ast::{Block, Expr, LitStr, Path};
 use thin_vec::ThinVec;
 
+/// The assembly macro of an [`ExprAsm`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum AsmMacro {
+    /// `asm!`, inline assembly inside a function body.
+    #[default]
+    Asm,
+    /// `naked_asm!`, the body of a `#[unsafe(naked)]` function.
+    NakedAsm,
+}
+
+impl AsmMacro {
+    /// Returns the name of the macro, including the `!`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            AsmMacro::Asm => "asm!",
+            AsmMacro::NakedAsm => "naked_asm!",
+        }
+    }
+}
+
+/// The arguments of an assembly macro: the template strings, followed by the
+/// operands and the options.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct AsmArgs {
+    /// A collection of string literals that make up the assembly code template.
+    pub template: ThinVec<LitStr>,
+    /// The list of input, output, and other operands for the assembly code.
+    pub operands: ThinVec<AsmOperand>,
+    /// A set of options that control the behavior of the assembly block,
+    /// such as `pure`, `nomem`, or `att_syntax`.
+    pub options: Option<AsmOptions>,
+}
+
 ast_item! {
-    /// Represents an inline assembly block.
+    /// Represents a `global_asm!(...);` item, assembly emitted at module level.
+    ///
+    /// For inline assembly inside a function body, use [`ExprAsm`] instead.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[cfg(target_arch = "x86_64")]
+    /// std::arch::global_asm!(".globl rasto_doc_symbol", "rasto_doc_symbol:", "ret");
+    /// # fn main() {}
+    /// ```
+    pub struct ItemAsm without vis and ident {
+        /// The template, operands and options of the macro.
+        pub args: AsmArgs,
+    }
+}
+
+ast_item! {
+    /// Represents an inline assembly expression, such as `asm!(...)` or
+    /// `naked_asm!(...)`.
     ///
     /// This is used for embedding assembly code directly into Rust functions.
     ///
     /// # Example
     ///
     /// ```rust
+    /// # #[cfg(target_arch = "x86_64")]
+    /// # {
     /// # use std::arch::asm;
     /// unsafe {
-///     let x: u64 = 3;
-///     let y: u64;
+    ///     let x: u64 = 3;
+    ///     let y: u64;
     ///     asm!(
     ///         "mov {0}, {1}",
-///         out(reg) y,
-///         in(reg) x,
+    ///         out(reg) y,
+    ///         in(reg) x,
     ///     );
     /// }
+    /// # }
     /// ```
-    pub struct ItemAsm without vis, ident, and md {
-        /// A collection of string literals that make up the assembly code template.
-        pub template: ThinVec<LitStr>,
-        /// The list of input, output, and other operands for the assembly code.
-        pub operands: ThinVec<AsmOperand>,
-        /// A set of options that control the behavior of the assembly block,
-        /// such as `pure`, `nomem`, or `att_syntax`.
-        pub options: Option<AsmOptions>,
+    pub struct ExprAsm without vis, ident, and md {
+        /// The assembly macro being invoked.
+        pub mac: AsmMacro,
+        /// The template, operands and options of the macro.
+        pub args: AsmArgs,
     }
 }
 
@@ -39,12 +90,16 @@ ast_item! {
 pub enum AsmOperand {
     /// A register operand.
     Reg(RegOperand),
-    /// A `sym` operand.
+    /// A `sym` operand, such as `sym handler::<u8>`.
+    ///
+    /// Generic arguments on the path are printed with a turbofish.
     Sym(Path),
     /// A `const` operand.
     Const(Expr),
     /// A `clobber_abi` operand.
     ClobberAbi(ClobberAbi),
+    /// A `label` operand, such as `label { return; }`.
+    Label(Block),
 }
 
 /// A register operand for an `asm!` expression.
@@ -120,3 +175,14 @@ pub struct ClobberAbi {
     /// The list of ABIs.
     pub abis: ThinVec<LitStr>,
 }
+
+impl_display!(
+    AsmArgs,
+    AsmDirection,
+    AsmOperand,
+    AsmOption,
+    AsmOptions,
+    ClobberAbi,
+    RegOperand,
+    RegSpec
+);
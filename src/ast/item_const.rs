@@ -3,10 +3,7 @@
 //! A `const` item is a value that is computed at compile time and can be used
 //! in any constant context.
 
-use crate::{
-    ast::{expressions::Expr, types::Type},
-    pretty_printer::PrettyPrinter,
-};
+use crate::ast::{expressions::Expr, types::Type};
 
 ast_item! {
     /// A `const` item, such as `const MAX: u16 = 234342;`.
@@ -1,7 +1,6 @@
 //! Defines the AST node for an enum definition.
 
-use crate::ast::{ident::Ident, metadata::Md};
-use crate::pretty_printer::PrettyPrinter;
+use crate::ast::{attributes::Attribute, comments::Comment, ident::Ident, metadata::Md};
 use thin_vec::ThinVec;
 
 ast_item! {
@@ -43,3 +42,49 @@ pub struct Variant {
     /// Metadata, such as attributes and comments, attached to the variant.
     pub md: Option<Box<Md>>,
 }
+
+impl Variant {
+    /// Creates a new variant with the given name, without metadata.
+    ///
+    /// # Parameters
+    ///
+    /// - `ident`: The name of the variant.
+    pub fn new(ident: impl Into<Ident>) -> Self {
+        Self {
+            ident: ident.into(),
+            md: None,
+        }
+    }
+
+    /// Adds an attribute to the variant.
+    ///
+    /// # Parameters
+    ///
+    /// - `attr`: The attribute to add.
+    pub fn attr(mut self, attr: impl Into<Attribute>) -> Self {
+        self.md.get_or_insert_default().attrs.push(attr.into());
+        self
+    }
+
+    /// Adds a comment before the variant.
+    ///
+    /// # Parameters
+    ///
+    /// - `comment`: The comment to add.
+    pub fn comment(mut self, comment: impl Into<Comment>) -> Self {
+        self.md.get_or_insert_default().comments.push(comment.into());
+        self
+    }
+
+    /// Sets a comment printed after the variant's comma, e.g. `Red, // 0xff0000`.
+    ///
+    /// # Parameters
+    ///
+    /// - `text`: The content of the comment, without the `//`.
+    pub fn eol_comment(mut self, text: impl Into<String>) -> Self {
+        self.md.get_or_insert_default().eol_comment = Some(text.into());
+        self
+    }
+}
+
+impl_display!(Variant);
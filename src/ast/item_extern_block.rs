@@ -1,9 +1,6 @@
 //! Defines the AST node for an `extern` block.
 
-use crate::ast::{
-    ItemExternType, ident::Ident, item_fn::ItemFn, item_macro::ItemMacro, types::Type,
-};
-use crate::pretty_printer::PrettyPrinter;
+use crate::ast::{ItemExternType, abi::Abi, item_fn::ItemFn, item_macro::ItemMacro, types::Type};
 use thin_vec::ThinVec;
 
 ast_item! {
@@ -22,7 +19,7 @@ ast_item! {
         pub is_unsafe: bool,
         /// The Application Binary Interface (ABI) for the `extern` block,
         /// such as `"C"` or `"system"`.
-        pub abi: Option<String>,
+        pub abi: Abi,
         /// The list of items declared within the `extern` block.
         pub items: ThinVec<ExternalItem>,
     }
@@ -32,13 +29,54 @@ ast_item! {
 #[derive(Debug, Clone, PartialEq)]
 pub enum ExternalItem {
     /// A `static` variable declared in a foreign library.
-    ///
-    /// The `String` is the name of the variable, and the `Type` is its data type.
-    Static(Ident, Type),
+    Static(ExternalStatic),
     /// A function declared in a foreign library.
+    ///
+    /// Only the signature is printed; the body is ignored. Edition 2024
+    /// safety qualifiers are set with `Signature::is_safe` and
+    /// `Signature::is_unsafe`.
     Fn(ItemFn),
     /// A macro invocation within an `extern` block.
     Macro(ItemMacro),
     /// A type declaration in a foreign library.
     Type(ItemExternType),
 }
+
+ast_item! {
+    /// Represents a `static` variable declared within an `extern` block.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// unsafe extern "C" {
+    ///     pub safe static mut ERRNO: i32;
+    /// }
+    /// ```
+    pub struct ExternalStatic {
+        /// `true` if the static is mutable (`static mut`).
+        pub is_mut: bool,
+        /// `true` if the static is marked `safe`, allowing it to be read
+        /// without an `unsafe` block. Requires an `unsafe extern` block.
+        pub is_safe: bool,
+        /// `true` if the static is explicitly marked `unsafe`.
+        pub is_unsafe: bool,
+        /// The data type of the static.
+        pub ty: Type,
+    }
+}
+
+impl From<ExternalStatic> for ExternalItem {
+    /// Converts an `ExternalStatic` into an `ExternalItem::Static` variant.
+    fn from(item: ExternalStatic) -> Self {
+        ExternalItem::Static(item)
+    }
+}
+
+impl From<ItemFn> for ExternalItem {
+    /// Converts an `ItemFn` into an `ExternalItem::Fn` variant.
+    fn from(item: ItemFn) -> Self {
+        ExternalItem::Fn(item)
+    }
+}
+
+impl_display!(ExternalItem);
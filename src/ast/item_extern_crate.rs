@@ -1,7 +1,5 @@
 //! Defines the AST node for an `extern crate` item.
 
-use crate::pretty_printer::PrettyPrinter;
-
 ast_item! {
     /// Represents an `extern crate` item, which is used to link to a crate.
     ///
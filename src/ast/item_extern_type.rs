@@ -5,8 +5,6 @@
 //! libraries. This feature is currently unstable and requires the `extern_types`
 //! feature gate.
 
-use crate::pretty_printer::PrettyPrinter;
-
 ast_item! {
     /// Represents a top-level `extern type` item, which declares an opaque type
     /// defined in a foreign library.
@@ -7,7 +7,6 @@ use crate::ast::patterns::Pat;
 use crate::ast::statements::Block;
 use crate::ast::types::Type;
 use crate::ast::where_clause::WhereClause;
-use crate::pretty_printer::PrettyPrinter;
 use thin_vec::ThinVec;
 
 ast_item! {
@@ -37,6 +36,13 @@ pub struct Signature {
     pub is_const: bool,
     /// `true` if the function is an `async fn`, meaning it returns a `Future`.
     pub is_async: bool,
+    /// `true` if the function is a `gen fn` (or `async gen fn`), meaning its
+    /// body is a generator that can `yield` values.
+    pub is_gen: bool,
+    /// `true` if the function is marked `safe`, allowing it to be called
+    /// without an `unsafe` block. This is only used in `unsafe extern` block
+    /// declarations.
+    pub is_safe: bool,
     /// `true` if the function is `unsafe`, requiring an `unsafe` block to be
     /// called.
     pub is_unsafe: bool,
@@ -60,3 +66,5 @@ pub struct Signature {
     /// generic parameters.
     pub where_clause: Option<WhereClause>,
 }
+
+impl_display!(Signature);
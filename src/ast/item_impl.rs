@@ -7,7 +7,7 @@ use crate::ast::{
     associated_const::AssociatedConst, associated_type::AssociatedType, generics::GenericParams,
     item_fn::ItemFn, metadata::Md, types::Type,
 };
-use crate::pretty_printer::{pp_begin, pp_end, BreakStyle, PrettyPrinter, Printer};
+use crate::pretty_printer::{pp_begin, pp_end, pp_items, BreakStyle, PrettyPrinter, Printer};
 use std::fmt;
 use thin_vec::ThinVec;
 
@@ -57,7 +57,7 @@ pub struct ItemImpl {
     pub md: Option<Box<Md>>,
 }
 
-impl_display_for_item!(ItemImpl);
+impl_display!(ItemImpl, ImplItem);
 
 impl PrettyPrinter for ItemImpl {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
@@ -82,13 +82,7 @@ impl PrettyPrinter for ItemImpl {
         printer.begin(BreakStyle::Consistent, "{");
         if !self.items.is_empty() {
             printer.hard_break();
-            let num_items = self.items.len();
-            for (i, item) in self.items.iter().enumerate() {
-                item.pretty_print(printer)?;
-                if i < num_items - 1 {
-                    printer.hard_break();
-                }
-            }
+            pp_items(&self.items, printer)?;
         }
         printer.end("}");
         pp_end(&self.md, printer)?;
@@ -107,13 +101,35 @@ pub enum ImplItem {
     Const(AssociatedConst),
 }
 
+impl ImplItem {
+    /// Separates the item from the one before it with an empty line.
+    pub fn blank_line_before(mut self) -> Self {
+        let md = match &mut self {
+            ImplItem::Fn(item) => &mut item.md,
+            ImplItem::Type(item) => &mut item.md,
+            ImplItem::Const(item) => &mut item.md,
+        };
+        md.get_or_insert_default().blank_line_before = true;
+        self
+    }
+
+    /// Returns a reference to the item's metadata.
+    pub(crate) fn md(&self) -> &Option<Box<Md>> {
+        match self {
+            ImplItem::Fn(item) => &item.md,
+            ImplItem::Type(item) => &item.md,
+            ImplItem::Const(item) => &item.md,
+        }
+    }
+}
+
 impl PrettyPrinter for ImplItem {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
-        match self {
+        printer.node(self, |printer| match self {
             ImplItem::Fn(item) => item.pretty_print(printer),
             ImplItem::Type(item) => item.pretty_print(printer),
             ImplItem::Const(item) => item.pretty_print(printer),
-        }
+        })
     }
 }
 
@@ -1,7 +1,6 @@
 //! Defines the AST node for a macro invocation in an item position.
 
 use crate::ast::expressions::Expr;
-use crate::pretty_printer::PrettyPrinter;
 
 ast_item! {
     /// Represents a macro invocation that appears in a position where an item
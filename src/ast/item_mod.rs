@@ -1,7 +1,6 @@
 //! Defines the AST node for a module definition.
 
 use crate::ast::items::Item;
-use crate::pretty_printer::PrettyPrinter;
 use thin_vec::ThinVec;
 
 ast_item! {
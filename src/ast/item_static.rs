@@ -2,7 +2,6 @@
 
 use crate::ast::expressions::Expr;
 use crate::ast::types::Type;
-use crate::pretty_printer::PrettyPrinter;
 
 ast_item! {
     /// Represents a `static` item, which is a value that has a fixed memory
@@ -1,7 +1,8 @@
 //! Defines the AST node for a struct definition.
 
-use crate::ast::{ident::Ident, metadata::Md, types::Type};
-use crate::pretty_printer::PrettyPrinter;
+use crate::ast::{
+    attributes::Attribute, comments::Comment, ident::Ident, metadata::Md, types::Type,
+};
 use thin_vec::ThinVec;
 
 ast_item! {
@@ -32,3 +33,54 @@ pub struct Field {
     /// Metadata, such as attributes and comments, attached to the field.
     pub md: Option<Box<Md>>,
 }
+
+impl Field {
+    /// Creates a new field with the given name and type, without metadata.
+    ///
+    /// # Parameters
+    ///
+    /// - `ident`: The name of the field.
+    /// - `ty`: The type of the field.
+    pub fn new(ident: impl Into<Ident>, ty: impl Into<Type>) -> Self {
+        Self {
+            ident: ident.into(),
+            ty: ty.into(),
+            md: None,
+        }
+    }
+
+    /// Adds an attribute to the field.
+    ///
+    /// # Parameters
+    ///
+    /// - `attr`: The attribute to add.
+    pub fn attr(mut self, attr: impl Into<Attribute>) -> Self {
+        self.md.get_or_insert_default().attrs.push(attr.into());
+        self
+    }
+
+    /// Adds a comment before the field.
+    ///
+    /// # Parameters
+    ///
+    /// - `comment`: The comment to add.
+    pub fn comment(mut self, comment: impl Into<Comment>) -> Self {
+        self.md
+            .get_or_insert_default()
+            .comments
+            .push(comment.into());
+        self
+    }
+
+    /// Sets a comment printed after the field's comma, e.g. `x: u32, // in pixels`.
+    ///
+    /// # Parameters
+    ///
+    /// - `text`: The content of the comment, without the `//`.
+    pub fn eol_comment(mut self, text: impl Into<String>) -> Self {
+        self.md.get_or_insert_default().eol_comment = Some(text.into());
+        self
+    }
+}
+
+impl_display!(Field);
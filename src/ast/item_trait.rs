@@ -2,7 +2,6 @@
 
 use crate::ast::associated_type::AssociatedType;
 use crate::ast::items::TraitItem;
-use crate::pretty_printer::PrettyPrinter;
 use thin_vec::ThinVec;
 
 ast_item! {
@@ -2,10 +2,7 @@
 //!
 //! A type alias is a new name for an existing type.
 
-use crate::{
-    ast::{generics::GenericParams, types::Type},
-    pretty_printer::PrettyPrinter,
-};
+use crate::ast::{generics::GenericParams, types::Type};
 
 ast_item! {
     /// A type alias, such as `type MyResult<T> = Result<T, MyError>;`.
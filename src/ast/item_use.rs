@@ -1,7 +1,5 @@
 //! Defines the AST node for a `use` item.
 
-use crate::pretty_printer::PrettyPrinter;
-
 ast_item! {
     /// Represents a `use` item, which is used to bring paths into scope.
     ///
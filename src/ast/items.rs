@@ -22,9 +22,10 @@ use crate::ast::item_trait_alias::ItemTraitAlias;
 use crate::ast::item_type_alias::ItemTypeAlias;
 use crate::ast::item_union::ItemUnion;
 use crate::ast::item_use::ItemUse;
+use crate::ast::custom::Custom;
 use crate::ast::metadata::Md;
 use crate::ast::statements::Block;
-use crate::pretty_printer::PrettyPrinter;
+use crate::ast::verbatim::Verbatim;
 
 /// A top-level item in a Rust file.
 #[derive(Debug, Clone, PartialEq)]
@@ -65,9 +66,73 @@ pub enum Item {
     Union(ItemUnion),
     /// A `use` item: `use std::collections::HashMap;`.
     Use(ItemUse),
+    /// Raw source text, printed as it is.
+    Verbatim(Verbatim),
+    /// A node of a type defined outside of this crate.
+    Custom(Custom),
 }
 
-impl_display_for_item!(Item);
+impl_display!(Item, TraitItem, TraitItemFn);
+
+impl Item {
+    /// Separates the item from the one before it with an empty line.
+    pub fn blank_line_before(mut self) -> Self {
+        self.md_mut().get_or_insert_default().blank_line_before = true;
+        self
+    }
+
+    /// Returns a reference to the item's metadata.
+    pub(crate) fn md(&self) -> &Option<Box<Md>> {
+        match self {
+            Item::Asm(item) => &item.md,
+            Item::Const(item) => &item.md,
+            Item::Fn(item) => &item.md,
+            Item::Struct(item) => &item.md,
+            Item::Static(item) => &item.md,
+            Item::Enum(item) => &item.md,
+            Item::Impl(item) => &item.md,
+            Item::Trait(item) => &item.md,
+            Item::ExternCrate(item) => &item.md,
+            Item::ExternType(item) => &item.md,
+            Item::ForeignMod(item) => &item.md,
+            Item::ExternBlock(item) => &item.md,
+            Item::Macro(item) => &item.md,
+            Item::Mod(item) => &item.md,
+            Item::TraitAlias(item) => &item.md,
+            Item::TypeAlias(item) => &item.md,
+            Item::Union(item) => &item.md,
+            Item::Use(item) => &item.md,
+            Item::Verbatim(item) => &item.md,
+            Item::Custom(item) => &item.md,
+        }
+    }
+
+    /// Returns a mutable reference to the item's metadata.
+    pub(crate) fn md_mut(&mut self) -> &mut Option<Box<Md>> {
+        match self {
+            Item::Asm(item) => &mut item.md,
+            Item::Const(item) => &mut item.md,
+            Item::Fn(item) => &mut item.md,
+            Item::Struct(item) => &mut item.md,
+            Item::Static(item) => &mut item.md,
+            Item::Enum(item) => &mut item.md,
+            Item::Impl(item) => &mut item.md,
+            Item::Trait(item) => &mut item.md,
+            Item::ExternCrate(item) => &mut item.md,
+            Item::ExternType(item) => &mut item.md,
+            Item::ForeignMod(item) => &mut item.md,
+            Item::ExternBlock(item) => &mut item.md,
+            Item::Macro(item) => &mut item.md,
+            Item::Mod(item) => &mut item.md,
+            Item::TraitAlias(item) => &mut item.md,
+            Item::TypeAlias(item) => &mut item.md,
+            Item::Union(item) => &mut item.md,
+            Item::Use(item) => &mut item.md,
+            Item::Verbatim(item) => &mut item.md,
+            Item::Custom(item) => &mut item.md,
+        }
+    }
+}
 
 /// An item within a trait definition.
 #[derive(Debug, Clone, PartialEq)]
@@ -0,0 +1,84 @@
+//! Defines the AST node for a lifetime.
+use crate::ast::ident::Ident;
+use crate::pretty_printer::{PrettyPrinter, Printer, TokenKind};
+use std::fmt;
+
+/// A lifetime, such as `'a`, `'static` or `'_`.
+///
+/// The leading apostrophe is not stored; it is added by the printer.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Lifetime {
+    /// A named lifetime, such as `'a`.
+    Named(Ident),
+    /// The `'static` lifetime.
+    Static,
+    /// The elided lifetime `'_`.
+    Elided,
+}
+
+impl Lifetime {
+    /// Creates a new lifetime from its name, with or without the leading
+    /// apostrophe.
+    ///
+    /// The names `static` and `_` produce `Lifetime::Static` and
+    /// `Lifetime::Elided` respectively.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: The name of the lifetime, e.g., `a` or `'a`.
+    pub fn new(name: impl Into<String>) -> Self {
+        let name = name.into();
+        let name = name.strip_prefix('\'').unwrap_or(&name);
+        match name {
+            "static" => Lifetime::Static,
+            "_" => Lifetime::Elided,
+            _ => Lifetime::Named(name.into()),
+        }
+    }
+
+    /// Returns the name of the lifetime, without the leading apostrophe.
+    pub fn name(&self) -> &str {
+        match self {
+            Lifetime::Named(ident) => &ident.name,
+            Lifetime::Static => "static",
+            Lifetime::Elided => "_",
+        }
+    }
+}
+
+impl From<&str> for Lifetime {
+    /// Converts a string slice into a `Lifetime`, see [`Lifetime::new`].
+    fn from(s: &str) -> Self {
+        Lifetime::new(s)
+    }
+}
+
+impl From<String> for Lifetime {
+    /// Converts a `String` into a `Lifetime`, see [`Lifetime::new`].
+    fn from(s: String) -> Self {
+        Lifetime::new(s)
+    }
+}
+
+impl From<Ident> for Lifetime {
+    /// Converts an `Ident` into a `Lifetime::Named` variant.
+    fn from(ident: Ident) -> Self {
+        Lifetime::Named(ident)
+    }
+}
+
+impl PrettyPrinter for Lifetime {
+    fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
+        printer.highlight(TokenKind::Lifetime, |printer| {
+            printer.string("'");
+            match self {
+                Lifetime::Named(ident) => ident.pretty_print(printer)?,
+                Lifetime::Static => printer.string("static"),
+                Lifetime::Elided => printer.string("_"),
+            }
+            Ok(())
+        })
+    }
+}
+
+impl_display!(Lifetime);
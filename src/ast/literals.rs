@@ -177,11 +177,48 @@ pub enum IntSuffix {
     Isize,
 }
 
-/// An integer literal, e.g., `42`.
+/// The radix an integer literal is written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IntRadix {
+    /// Base 10, e.g., `42`.
+    #[default]
+    Decimal,
+    /// Base 16, e.g., `0x2a`.
+    Hex,
+    /// Base 8, e.g., `0o52`.
+    Octal,
+    /// Base 2, e.g., `0b101010`.
+    Binary,
+}
+
+impl IntRadix {
+    /// Returns the prefix written before the digits, e.g., `0x`.
+    pub fn prefix(&self) -> &'static str {
+        match self {
+            IntRadix::Decimal => "",
+            IntRadix::Hex => "0x",
+            IntRadix::Octal => "0o",
+            IntRadix::Binary => "0b",
+        }
+    }
+}
+
+/// An integer literal, e.g., `42`, `-1` or `0xff_ffu32`.
+///
+/// The magnitude is stored in `value` and the sign in `is_negative`. A negative
+/// literal is printed with a leading unary minus, e.g., `-128i8`.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct LitInt {
-    /// The value of the integer literal.
+    /// The magnitude of the integer literal.
     pub value: u128,
+    /// Whether the literal is negative, i.e. printed with a leading `-`.
+    pub is_negative: bool,
+    /// The radix the digits are written in.
+    pub radix: IntRadix,
+    /// The number of digits between `_` separators, counted from the right.
+    ///
+    /// `None` prints the digits without separators.
+    pub digit_group: Option<usize>,
     /// The suffix of the integer literal, e.g., `u32`.
     pub suffix: Option<IntSuffix>,
 }
@@ -195,10 +232,25 @@ impl LitInt {
     pub fn new(value: u128) -> Self {
         Self {
             value,
+            is_negative: false,
+            radix: IntRadix::Decimal,
+            digit_group: None,
             suffix: None,
         }
     }
 
+    /// Creates a new `LitInt` from a signed value.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The signed integer value.
+    pub fn signed(value: i128) -> Self {
+        Self {
+            is_negative: value < 0,
+            ..Self::new(value.unsigned_abs())
+        }
+    }
+
     /// Adds a suffix to the `LitInt`.
     ///
     /// # Arguments
@@ -206,10 +258,74 @@ impl LitInt {
     /// * `suffix` - The integer suffix.
     pub fn with_suffix(value: u128, suffix: IntSuffix) -> Self {
         Self {
-            value,
             suffix: Some(suffix),
+            ..Self::new(value)
+        }
+    }
+
+    /// Sets the radix the literal is written in.
+    ///
+    /// # Arguments
+    ///
+    /// * `radix` - The radix, e.g., `IntRadix::Hex`.
+    pub fn radix(mut self, radix: IntRadix) -> Self {
+        self.radix = radix;
+        self
+    }
+
+    /// Separates the digits with `_` every `size` digits, e.g., `1_000_000`.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - The number of digits in each group. `0` disables grouping.
+    pub fn digit_group(mut self, size: usize) -> Self {
+        self.digit_group = (size > 0).then_some(size);
+        self
+    }
+
+    /// Sets the suffix of the literal.
+    ///
+    /// # Arguments
+    ///
+    /// * `suffix` - The integer suffix.
+    pub fn suffix(mut self, suffix: IntSuffix) -> Self {
+        self.suffix = Some(suffix);
+        self
+    }
+
+    /// Returns the signed value of the literal, if it fits in an `i128`.
+    pub fn to_i128(&self) -> Option<i128> {
+        if self.is_negative {
+            0i128.checked_sub_unsigned(self.value)
+        } else {
+            i128::try_from(self.value).ok()
         }
     }
+
+    /// Returns the digits of the literal, including the radix prefix and any
+    /// `_` separators, but without the sign or suffix.
+    pub fn digits(&self) -> String {
+        let digits = match self.radix {
+            IntRadix::Decimal => self.value.to_string(),
+            IntRadix::Hex => format!("{:x}", self.value),
+            IntRadix::Octal => format!("{:o}", self.value),
+            IntRadix::Binary => format!("{:b}", self.value),
+        };
+        let digits = match self.digit_group {
+            Some(size) => {
+                let mut grouped = String::with_capacity(digits.len() + digits.len() / size);
+                for (i, ch) in digits.chars().enumerate() {
+                    if i > 0 && (digits.len() - i) % size == 0 {
+                        grouped.push('_');
+                    }
+                    grouped.push(ch);
+                }
+                grouped
+            }
+            None => digits,
+        };
+        format!("{}{}", self.radix.prefix(), digits)
+    }
 }
 
 /// The suffix of a float literal, e.g., `f64`.
@@ -222,6 +338,10 @@ pub enum FloatSuffix {
 }
 
 /// A float literal, e.g., `1.23`.
+///
+/// The non-finite values `NaN`, `inf` and `-inf` are printed as the paths
+/// `f64::NAN`, `f64::INFINITY` and `f64::NEG_INFINITY` (or their `f32`
+/// counterparts when the suffix is `f32`), since Rust has no literal for them.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct LitFloat {
     /// The value of the float literal.
@@ -254,6 +374,50 @@ impl LitFloat {
             suffix: Some(suffix),
         }
     }
+
+    /// Creates a `LitFloat` from an `f64` whose printed form parses back to
+    /// the same value.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The float value.
+    pub fn from_f64(value: f64) -> Self {
+        Self::new(&float_repr(value))
+    }
+
+    /// Creates a `LitFloat` with an `f32` suffix whose printed form parses back
+    /// to the same value.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The float value.
+    pub fn from_f32(value: f32) -> Self {
+        Self::with_suffix(&float_repr(value), FloatSuffix::F32)
+    }
+
+    /// Returns the path to the associated constant used in place of a literal
+    /// for non-finite values, e.g., `f64::NAN`.
+    pub fn non_finite_path(&self) -> Option<&'static str> {
+        let is_f32 = self.suffix == Some(FloatSuffix::F32);
+        let path = match (self.value.as_str(), is_f32) {
+            ("NaN", false) => "f64::NAN",
+            ("NaN", true) => "f32::NAN",
+            ("inf", false) => "f64::INFINITY",
+            ("inf", true) => "f32::INFINITY",
+            ("-inf", false) => "f64::NEG_INFINITY",
+            ("-inf", true) => "f32::NEG_INFINITY",
+            _ => return None,
+        };
+        Some(path)
+    }
+}
+
+/// Formats a float so that it reads back as a float literal of the same value.
+///
+/// `Debug` yields the shortest representation that round-trips and always
+/// includes a `.` or an exponent, so `1.0` is not mistaken for an integer.
+fn float_repr<T: std::fmt::Debug>(value: T) -> String {
+    format!("{value:?}")
 }
 
 /// A boolean literal, e.g., `true` or `false`.
@@ -290,23 +454,76 @@ impl From<&str> for Lit {
     }
 }
 
-impl From<u64> for Lit {
-    /// Converts a `u64` into a `Lit::Int` variant.
-    fn from(i: u64) -> Self {
-        Lit::Int(LitInt {
-            value: i as u128,
-            suffix: None,
-        })
+/// Implements `From` for `LitInt` and `Lit` for primitive integer types.
+///
+/// The resulting literal keeps the type as its suffix, except for `i32`, which
+/// is the type an unsuffixed literal defaults to.
+macro_rules! impl_from_int {
+    ($($ty:ty => $suffix:expr),* $(,)?) => {
+        $(
+            impl From<$ty> for LitInt {
+                #[doc = concat!("Converts an `", stringify!($ty), "` into a `LitInt`.")]
+                fn from(i: $ty) -> Self {
+                    LitInt {
+                        suffix: $suffix,
+                        ..LitInt::signed(i as i128)
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_int! {
+    i8 => Some(IntSuffix::I8),
+    i16 => Some(IntSuffix::I16),
+    i32 => None,
+    i64 => Some(IntSuffix::I64),
+    i128 => Some(IntSuffix::I128),
+    isize => Some(IntSuffix::Isize),
+    u8 => Some(IntSuffix::U8),
+    u16 => Some(IntSuffix::U16),
+    u32 => Some(IntSuffix::U32),
+    u64 => Some(IntSuffix::U64),
+    usize => Some(IntSuffix::Usize),
+}
+
+impl From<u128> for LitInt {
+    /// Converts a `u128` into a `LitInt`.
+    fn from(i: u128) -> Self {
+        LitInt::with_suffix(i, IntSuffix::U128)
     }
 }
 
-impl From<i32> for Lit {
-    /// Converts an `i32` into a `Lit::Int` variant.
-    fn from(i: i32) -> Self {
-        Lit::Int(LitInt {
-            value: i as u128,
-            suffix: None,
-        })
+/// Implements `From` for `Lit` for the integer types that map to `Lit::Int`.
+///
+/// `u8` is not included, as it converts into a `Lit::Byte`.
+macro_rules! impl_lit_from_int {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for Lit {
+                #[doc = concat!("Converts an `", stringify!($ty), "` into a `Lit::Int` variant.")]
+                fn from(i: $ty) -> Self {
+                    Lit::Int(i.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_lit_from_int!(i8, i16, i32, i64, i128, isize, u16, u32, u64, u128, usize);
+
+impl From<LitInt> for Lit {
+    /// Converts a `LitInt` into a `Lit::Int` variant.
+    fn from(lit: LitInt) -> Self {
+        Lit::Int(lit)
+    }
+}
+
+impl From<LitFloat> for Lit {
+    /// Converts a `LitFloat` into a `Lit::Float` variant.
+    fn from(lit: LitFloat) -> Self {
+        Lit::Float(lit)
     }
 }
 
@@ -320,7 +537,14 @@ impl From<bool> for Lit {
 impl From<f64> for Lit {
     /// Converts a `f64` into a `Lit::Float` variant.
     fn from(f: f64) -> Self {
-        Lit::Float(LitFloat::new(&f.to_string()))
+        Lit::Float(LitFloat::from_f64(f))
+    }
+}
+
+impl From<f32> for Lit {
+    /// Converts a `f32` into a `Lit::Float` variant with an `f32` suffix.
+    fn from(f: f32) -> Self {
+        Lit::Float(LitFloat::from_f32(f))
     }
 }
 
@@ -358,3 +582,7 @@ impl<'a> From<&'a std::ffi::CStr> for Lit {
         Lit::CStr(LitCStr::new(s.to_str().unwrap()))
     }
 }
+
+impl_display!(
+    Lit, LitBool, LitByte, LitByteStr, LitCStr, LitChar, LitFloat, LitInt, LitStr
+);
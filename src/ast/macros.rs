@@ -1,16 +1,23 @@
-/// A macro for generating the `Display` implementation for an AST item.
+/// A macro for generating the `Display` implementations for AST nodes.
 #[doc(hidden)]
 #[macro_export]
-macro_rules! impl_display_for_item {
-    ($name:ident) => {
-        impl ::std::fmt::Display for $name {
-            /// Formats the item using the pretty-printer.
-            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
-                let mut printer = $crate::pretty_printer::Printer::new(f);
-                self.pretty_print(&mut printer)?;
-                printer.finish()
+macro_rules! impl_display {
+    ($($name:ident),* $(,)?) => {
+        $(
+            impl ::std::fmt::Display for $name {
+                /// Formats the node using the pretty-printer. The alternate
+                /// form, `{:#}`, prints it on a single line.
+                fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                    let config = $crate::pretty_printer::PrinterConfig {
+                        single_line: f.alternate(),
+                        ..::std::default::Default::default()
+                    };
+                    let mut printer = $crate::pretty_printer::Printer::new(f, config);
+                    $crate::pretty_printer::PrettyPrinter::pretty_print(self, &mut printer)?;
+                    printer.finish()
+                }
             }
-        }
+        )*
     };
 }
 
@@ -47,7 +54,7 @@ macro_rules! ast_item_impl {
                 $f_vis $field: $ty
             ),*
         }
-        $crate::impl_display_for_item!($name);
+        $crate::impl_display!($name);
     }
 }
 
@@ -13,6 +13,14 @@ pub struct Md {
     pub comments: ThinVec<Comment>,
     /// Comments that appear after the node.
     pub trailing_comments: ThinVec<Comment>,
+    /// The content of a `//` comment printed at the end of the node's last
+    /// line, without the `//`. For nodes in a comma-separated list, the
+    /// comment follows the comma.
+    pub eol_comment: Option<String>,
+    /// Whether an empty line separates the node from the one before it, in a
+    /// list of items or statements. It is ignored for the first node of a
+    /// list.
+    pub blank_line_before: bool,
 }
 
 /// A builder for constructing `Md` (metadata) for an AST node.
@@ -24,6 +32,8 @@ pub struct MdBuilder {
     attrs: ThinVec<Attribute>,
     comments: ThinVec<Comment>,
     trailing_comments: ThinVec<Comment>,
+    eol_comment: Option<String>,
+    blank_line_before: bool,
 }
 
 impl MdBuilder {
@@ -64,12 +74,32 @@ impl MdBuilder {
         self
     }
 
+    /// Sets the end-of-line comment of the metadata.
+    ///
+    /// # Parameters
+    ///
+    /// - `text`: The content of the comment, without the `//`.
+    pub fn eol_comment(mut self, text: impl Into<String>) -> Self {
+        self.eol_comment = Some(text.into());
+        self
+    }
+
+    /// Separates the node from the one before it with an empty line.
+    pub fn blank_line_before(mut self) -> Self {
+        self.blank_line_before = true;
+        self
+    }
+
     /// Builds and returns the `Md` struct.
     pub fn build(self) -> Md {
         Md {
             attrs: self.attrs,
             comments: self.comments,
             trailing_comments: self.trailing_comments,
+            eol_comment: self.eol_comment,
+            blank_line_before: self.blank_line_before,
         }
     }
 }
+
+impl_display!(Md);
@@ -39,6 +39,8 @@ pub enum Pat {
     TupleStruct(PatTupleStruct),
     /// A type pattern, e.g., `x: T`.
     Type(PatType),
+    /// Raw source text, printed as it is.
+    Verbatim(Verbatim),
     /// A wildcard pattern, e.g., `_`.
     Wild(PatWild),
 }
@@ -173,3 +175,24 @@ pub struct PatType {
 /// A wildcard pattern: `_`
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct PatWild;
+
+impl_display!(
+    Pat,
+    PatConst,
+    PatIdent,
+    PatLit,
+    PatMacro,
+    PatOr,
+    PatParen,
+    PatPath,
+    PatRange,
+    PatReference,
+    PatRest,
+    PatSlice,
+    PatStruct,
+    PatTuple,
+    PatTupleStruct,
+    PatType,
+    PatWild,
+    FieldPat
+);
@@ -0,0 +1,508 @@
+//! Defines the precedence of expressions, and where they need parentheses.
+//!
+//! The pretty-printer compares the [`Precedence`] of an expression with the
+//! position it is printed in, and adds the parentheses that a tree built
+//! without [`ExprParen`] needs to keep its meaning. [`RemoveRedundantParens`]
+//! removes the [`ExprParen`] nodes that are not needed.
+
+use crate::ast::{
+    Arm, BinOp, Delimiter, Expr, ExprInfer, ExprParen, Lit, Local, StmtExpr, items::Item,
+};
+use crate::visit_mut::{self, VisitMut};
+
+/// The precedence of an expression, from the loosest binding to the tightest.
+///
+/// An expression needs parentheses as the operand of another expression if it
+/// binds looser than the operand position allows. For example, a
+/// [`Precedence::Sum`] needs them as the operand of a `*`, which requires at
+/// least a [`Precedence::Product`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Precedence {
+    /// `return`, `break`, `continue`, `yield`, `become` and closures, which
+    /// take every operator that follows them into their operand.
+    Jump,
+    /// `=` and the compound assignment operators, which are right-associative.
+    Assign,
+    /// `..` and `..=`, which cannot be chained.
+    Range,
+    /// `||`.
+    Or,
+    /// `&&`.
+    And,
+    /// `==`, `!=`, `<`, `>`, `<=` and `>=`, which cannot be chained.
+    Compare,
+    /// `|`.
+    BitOr,
+    /// `^`.
+    BitXor,
+    /// `&`.
+    BitAnd,
+    /// `<<` and `>>`.
+    Shift,
+    /// `+` and `-`.
+    Sum,
+    /// `*`, `/` and `%`.
+    Product,
+    /// `as`.
+    Cast,
+    /// The unary operators, references and negative literals.
+    Prefix,
+    /// Calls, method calls, field accesses, indexing and `.await`.
+    Postfix,
+    /// Paths, literals, and expressions that are delimited, such as blocks,
+    /// arrays and parenthesized expressions.
+    Primary,
+}
+
+impl Precedence {
+    /// Returns the next tighter precedence.
+    fn tighter(self) -> Precedence {
+        match self {
+            Precedence::Jump => Precedence::Assign,
+            Precedence::Assign => Precedence::Range,
+            Precedence::Range => Precedence::Or,
+            Precedence::Or => Precedence::And,
+            Precedence::And => Precedence::Compare,
+            Precedence::Compare => Precedence::BitOr,
+            Precedence::BitOr => Precedence::BitXor,
+            Precedence::BitXor => Precedence::BitAnd,
+            Precedence::BitAnd => Precedence::Shift,
+            Precedence::Shift => Precedence::Sum,
+            Precedence::Sum => Precedence::Product,
+            Precedence::Product => Precedence::Cast,
+            Precedence::Cast => Precedence::Prefix,
+            Precedence::Prefix => Precedence::Postfix,
+            Precedence::Postfix | Precedence::Primary => Precedence::Primary,
+        }
+    }
+}
+
+impl Expr {
+    /// Returns the precedence of the expression.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rasto::ast::{BinOp, Precedence};
+    /// use rasto::builder::*;
+    ///
+    /// let sum = expr().binary(expr().lit(1), BinOp::Add, expr().lit(2));
+    /// assert_eq!(sum.precedence(), Precedence::Sum);
+    /// assert!(sum.precedence() < Precedence::Product);
+    /// ```
+    pub fn precedence(&self) -> Precedence {
+        match self {
+            Expr::Assign(_) => Precedence::Assign,
+            Expr::Binary(binary) => binary.op.precedence(),
+            Expr::Range(_) => Precedence::Range,
+            Expr::Cast(_) => Precedence::Cast,
+            Expr::Unary(_) | Expr::Reference(_) | Expr::RawRef(_) => Precedence::Prefix,
+            Expr::Lit(Lit::Int(int)) if int.is_negative => Precedence::Prefix,
+            Expr::Lit(Lit::Float(float)) if float.value.starts_with('-') => Precedence::Prefix,
+            Expr::Await(_)
+            | Expr::Call(_)
+            | Expr::Field(_)
+            | Expr::Index(_)
+            | Expr::MethodCall(_) => Precedence::Postfix,
+            Expr::Become(_)
+            | Expr::Break(_)
+            | Expr::Closure(_)
+            | Expr::Continue(_)
+            | Expr::Return(_)
+            | Expr::Yield(_) => Precedence::Jump,
+            Expr::Custom(custom) => custom.node.precedence(),
+            _ => Precedence::Primary,
+        }
+    }
+}
+
+impl BinOp {
+    /// Returns the position of the left operand of the operator.
+    pub(crate) fn left_position(&self) -> ExprPosition {
+        let min = match self.precedence() {
+            precedence @ (Precedence::Assign | Precedence::Compare) => precedence.tighter(),
+            precedence => precedence,
+        };
+        match self {
+            BinOp::Lt | BinOp::Shl => ExprPosition::BeforeLt(min),
+            _ => ExprPosition::Operand {
+                min,
+                followed: true,
+            },
+        }
+    }
+
+    /// Returns the precedence that the right operand of the operator must
+    /// bind at.
+    pub(crate) fn right_precedence(&self) -> Precedence {
+        match self.precedence() {
+            Precedence::Assign => Precedence::Assign,
+            precedence => precedence.tighter(),
+        }
+    }
+}
+
+/// Where an expression is printed, which decides whether it needs parentheses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum ExprPosition {
+    /// Delimited by brackets, commas or semicolons, such as a call argument.
+    #[default]
+    Top,
+    /// An operand that must bind at least at `min`. `followed` is whether
+    /// tokens of the enclosing expression follow the operand, which a closure
+    /// or jump expression would take into its own operand.
+    Operand { min: Precedence, followed: bool },
+    /// The left operand of `<` or `<<`, which a trailing `as` type would take
+    /// as the start of its generic arguments.
+    BeforeLt(Precedence),
+    /// The receiver of a method call, field access or `.await`.
+    Receiver,
+    /// The function of a call.
+    Callee,
+    /// The condition of `if` or `while`, the scrutinee of `match` or the
+    /// iterator of `for`, which cannot contain a struct literal because it is
+    /// followed by a block.
+    Condition,
+    /// An expression statement or the body of a match arm, which ends after a
+    /// leading block-like expression.
+    Statement,
+    /// The initializer of a `let`-`else`, which cannot end with `}` or be a
+    /// lazy boolean expression.
+    LetElse,
+}
+
+impl ExprPosition {
+    /// Returns whether tokens that are not part of the expression follow it.
+    pub(crate) fn is_followed(self) -> bool {
+        match self {
+            ExprPosition::Top | ExprPosition::Statement => false,
+            ExprPosition::Operand { followed, .. } => followed,
+            _ => true,
+        }
+    }
+
+    /// Returns whether `expr` needs parentheses to keep its meaning here.
+    pub(crate) fn needs_parens(self, expr: &Expr) -> bool {
+        let binds = |min: Precedence, followed: bool| match expr.precedence() {
+            Precedence::Jump => !followed,
+            precedence => precedence >= min,
+        };
+        match self {
+            ExprPosition::Top => false,
+            ExprPosition::Operand { min, followed } => !binds(min, followed),
+            ExprPosition::BeforeLt(min) => !binds(min, true) || ends_with_cast(expr),
+            ExprPosition::Receiver => !binds(Precedence::Postfix, true) || ends_with_dot(expr),
+            ExprPosition::Callee => {
+                !binds(Precedence::Postfix, true) || matches!(expr, Expr::Field(_))
+            }
+            ExprPosition::Condition => !binds(Precedence::Jump, true) || has_exposed_struct(expr),
+            ExprPosition::Statement => !is_block_like(expr) && starts_with_block_like(expr),
+            ExprPosition::LetElse => {
+                !binds(Precedence::Jump, true)
+                    || matches!(expr, Expr::Binary(binary) if matches!(binary.op, BinOp::And | BinOp::Or))
+                    || ends_with_brace(expr)
+            }
+        }
+    }
+}
+
+/// Returns whether `expr` is a block-like expression, which ends an
+/// expression statement that it starts.
+fn is_block_like(expr: &Expr) -> bool {
+    match expr {
+        Expr::Block(_)
+        | Expr::Const(_)
+        | Expr::For(_)
+        | Expr::If(_)
+        | Expr::Loop(_)
+        | Expr::Match(_)
+        | Expr::Try(_)
+        | Expr::While(_) => true,
+        Expr::MacroCall(mac) => mac.delimiter == Delimiter::Brace,
+        _ => false,
+    }
+}
+
+/// Returns whether the leftmost operand of `expr` is block-like, other than
+/// the receiver of a method call, field access or `.await`, after which an
+/// expression statement goes on.
+fn starts_with_block_like(mut expr: &Expr) -> bool {
+    loop {
+        expr = match expr {
+            Expr::Assign(assign) => &assign.left,
+            Expr::Binary(binary) => &binary.left,
+            Expr::Call(call) => &call.func,
+            Expr::Cast(cast) => &cast.expr,
+            Expr::Index(index) => &index.expr,
+            Expr::Range(range) => match &range.start {
+                Some(start) => start,
+                None => return false,
+            },
+            Expr::Await(await_) if !is_block_like(&await_.expr) => &await_.expr,
+            Expr::Field(field) if !is_block_like(&field.expr) => &field.expr,
+            Expr::MethodCall(call) if !is_block_like(&call.receiver) => &call.receiver,
+            expr => return is_block_like(expr),
+        };
+    }
+}
+
+/// Returns whether the rightmost operand of `expr` ends with `}`.
+fn ends_with_brace(mut expr: &Expr) -> bool {
+    loop {
+        expr = match expr {
+            Expr::Assign(assign) => &assign.right,
+            Expr::Become(become_) => &become_.expr,
+            Expr::Binary(binary) => &binary.right,
+            Expr::Closure(closure) => &closure.body,
+            Expr::Reference(reference) => &reference.expr,
+            Expr::RawRef(raw_ref) => &raw_ref.expr,
+            Expr::Unary(unary) => &unary.expr,
+            Expr::Range(range) => match &range.end {
+                Some(end) => end,
+                None => return false,
+            },
+            Expr::Return(ret) => match &ret.expr {
+                Some(value) => value,
+                None => return false,
+            },
+            Expr::Yield(yield_) => match &yield_.expr {
+                Some(value) => value,
+                None => return false,
+            },
+            Expr::Async(_) | Expr::Gen(_) | Expr::Struct(_) => return true,
+            expr => return is_block_like(expr),
+        };
+    }
+}
+
+/// Returns whether the rightmost operand of `expr` is an `as` cast.
+fn ends_with_cast(mut expr: &Expr) -> bool {
+    loop {
+        match expr {
+            Expr::Binary(binary) => expr = &binary.right,
+            expr => return matches!(expr, Expr::Cast(_)),
+        }
+    }
+}
+
+/// Returns whether `expr` is a number literal that would take a following `.`
+/// as its decimal point.
+fn ends_with_dot(expr: &Expr) -> bool {
+    match expr {
+        Expr::Lit(Lit::Int(int)) => int.suffix.is_none(),
+        Expr::Lit(Lit::Float(float)) => float.value.ends_with('.'),
+        _ => false,
+    }
+}
+
+/// Returns whether `expr` has a struct literal that is not enclosed in
+/// delimiters.
+fn has_exposed_struct(expr: &Expr) -> bool {
+    let mut stack = vec![expr];
+    while let Some(expr) = stack.pop() {
+        match expr {
+            Expr::Struct(_) => return true,
+            Expr::Assign(assign) => stack.extend([&*assign.left, &*assign.right]),
+            Expr::Binary(binary) => stack.extend([&*binary.left, &*binary.right]),
+            Expr::Range(range) => {
+                stack.extend(range.start.iter().chain(&range.end).map(|expr| &**expr))
+            }
+            Expr::Await(await_) => stack.push(&await_.expr),
+            Expr::Become(become_) => stack.push(&become_.expr),
+            Expr::Call(call) => stack.push(&call.func),
+            Expr::Cast(cast) => stack.push(&cast.expr),
+            Expr::Closure(closure) => stack.push(&closure.body),
+            Expr::Field(field) => stack.push(&field.expr),
+            Expr::Index(index) => stack.push(&index.expr),
+            Expr::MethodCall(call) => stack.push(&call.receiver),
+            Expr::RawRef(raw_ref) => stack.push(&raw_ref.expr),
+            Expr::Reference(reference) => stack.push(&reference.expr),
+            Expr::Unary(unary) => stack.push(&unary.expr),
+            Expr::Return(ret) => stack.extend(ret.expr.as_deref()),
+            Expr::Yield(yield_) => stack.extend(yield_.expr.as_deref()),
+            _ => {}
+        }
+    }
+    false
+}
+
+/// Removes the [`ExprParen`] nodes that the pretty-printer does not need.
+///
+/// An `ExprParen` is redundant if the expression in it keeps its meaning
+/// without the parentheses, such as `(a * b)` in `(a * b) + c`. The ones that
+/// are needed, such as `(a + b)` in `(a + b) * c`, are kept. Whether an
+/// operand of a closure, assignment or jump expression is followed by other
+/// tokens depends on the enclosing expression, so parentheses around a
+/// closure or jump expression there are kept.
+///
+/// # Examples
+///
+/// ```
+/// use rasto::ast::{BinOp, RemoveRedundantParens};
+/// use rasto::builder::*;
+/// use rasto::pretty;
+/// use rasto::visit_mut::VisitMut;
+///
+/// let product = |a, b| expr().binary(expr().path(a), BinOp::Mul, expr().path(b));
+/// let sum = |a, b| expr().binary(expr().path(a), BinOp::Add, expr().path(b));
+/// let mut tree = expr().binary(
+///     expr().paren(product("a", "b")),
+///     BinOp::Add,
+///     expr().paren(sum("c", "d")),
+/// );
+/// assert_eq!(pretty(&tree), "(a * b) + (c + d)");
+///
+/// RemoveRedundantParens.visit_expr_mut(&mut tree);
+/// assert_eq!(pretty(&tree), "a * b + (c + d)");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RemoveRedundantParens;
+
+impl VisitMut for RemoveRedundantParens {
+    fn visit_expr_mut(&mut self, node: &mut Expr) {
+        visit_mut::walk_expr_mut(self, node);
+        let operand = |min| ExprPosition::Operand {
+            min,
+            followed: true,
+        };
+        match node {
+            Expr::Array(array) => array.elems.iter_mut().for_each(unwrap_top),
+            Expr::Assign(assign) => {
+                unwrap_parens(&mut assign.left, operand(Precedence::Range));
+                unwrap_parens(&mut assign.right, operand(Precedence::Assign));
+            }
+            Expr::Await(await_) => unwrap_parens(&mut await_.expr, ExprPosition::Receiver),
+            Expr::Become(become_) => unwrap_parens(&mut become_.expr, operand(Precedence::Jump)),
+            Expr::Binary(binary) => {
+                unwrap_parens(&mut binary.left, binary.op.left_position());
+                unwrap_parens(&mut binary.right, operand(binary.op.right_precedence()));
+            }
+            Expr::Call(call) => {
+                unwrap_parens(&mut call.func, ExprPosition::Callee);
+                call.args.iter_mut().for_each(unwrap_top);
+            }
+            Expr::Cast(cast) => unwrap_parens(&mut cast.expr, operand(Precedence::Cast)),
+            Expr::Closure(closure) => unwrap_parens(&mut closure.body, operand(Precedence::Jump)),
+            Expr::Field(field) => unwrap_parens(&mut field.expr, ExprPosition::Receiver),
+            Expr::For(expr_for) => unwrap_parens(&mut expr_for.expr, ExprPosition::Condition),
+            Expr::If(expr_if) => unwrap_parens(&mut expr_if.cond, ExprPosition::Condition),
+            Expr::Index(index) => {
+                unwrap_parens(&mut index.expr, operand(Precedence::Postfix));
+                unwrap_top(&mut index.index);
+            }
+            Expr::Match(expr_match) => unwrap_parens(&mut expr_match.expr, ExprPosition::Condition),
+            Expr::MethodCall(call) => {
+                unwrap_parens(&mut call.receiver, ExprPosition::Receiver);
+                call.args.iter_mut().for_each(unwrap_top);
+            }
+            Expr::Paren(paren) => unwrap_top(&mut paren.expr),
+            Expr::Range(range) => {
+                for expr in range.start.iter_mut().chain(&mut range.end) {
+                    unwrap_parens(expr, operand(Precedence::Or));
+                }
+            }
+            Expr::RawRef(raw_ref) => unwrap_parens(&mut raw_ref.expr, operand(Precedence::Prefix)),
+            Expr::Reference(reference) => {
+                unwrap_parens(&mut reference.expr, operand(Precedence::Prefix))
+            }
+            Expr::Return(ret) => {
+                if let Some(expr) = &mut ret.expr {
+                    unwrap_parens(expr, operand(Precedence::Jump));
+                }
+            }
+            Expr::Struct(expr_struct) => {
+                for field in &mut expr_struct.fields {
+                    unwrap_top(&mut field.value);
+                }
+            }
+            Expr::Tuple(tuple) => tuple.elems.iter_mut().for_each(unwrap_top),
+            Expr::Unary(unary) => unwrap_parens(&mut unary.expr, operand(Precedence::Prefix)),
+            Expr::While(expr_while) => unwrap_parens(&mut expr_while.cond, ExprPosition::Condition),
+            Expr::Yield(yield_) => {
+                if let Some(expr) = &mut yield_.expr {
+                    unwrap_parens(expr, operand(Precedence::Jump));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn visit_stmt_expr_mut(&mut self, node: &mut StmtExpr) {
+        visit_mut::walk_stmt_expr_mut(self, node);
+        unwrap_statement(&mut node.expr);
+    }
+
+    fn visit_local_mut(&mut self, node: &mut Local) {
+        visit_mut::walk_local_mut(self, node);
+        let position = match node.else_block {
+            Some(_) => ExprPosition::LetElse,
+            None => ExprPosition::Top,
+        };
+        if let Some(expr) = &mut node.expr {
+            unwrap_parens(expr, position);
+        }
+    }
+
+    fn visit_arm_mut(&mut self, node: &mut Arm) {
+        visit_mut::walk_arm_mut(self, node);
+        if let Some(guard) = &mut node.guard {
+            unwrap_top(guard);
+        }
+        unwrap_statement(&mut node.body);
+    }
+
+    fn visit_item_mut(&mut self, node: &mut Item) {
+        visit_mut::walk_item_mut(self, node);
+        match node {
+            Item::Const(item) => unwrap_top(&mut item.expr),
+            Item::Static(item) => unwrap_top(&mut item.expr),
+            _ => {}
+        }
+    }
+}
+
+/// Replaces `expr` with the expression in its parentheses, as long as the
+/// parentheses are not needed at `position`.
+fn unwrap_parens(expr: &mut Expr, position: ExprPosition) {
+    while let Expr::Paren(ExprParen { expr: inner }) = expr
+        && !position.needs_parens(inner)
+    {
+        let inner = std::mem::replace(&mut **inner, Expr::Infer(ExprInfer));
+        *expr = inner;
+    }
+}
+
+fn unwrap_top(expr: &mut Expr) {
+    unwrap_parens(expr, ExprPosition::Top);
+}
+
+/// Unwraps an expression statement, keeping the parentheses around its
+/// leading block-like operand so that the statement does not end after it.
+fn unwrap_statement(expr: &mut Expr) {
+    unwrap_parens(expr, ExprPosition::Statement);
+    if !ExprPosition::Statement.needs_parens(expr) {
+        return;
+    }
+    let mut expr = expr;
+    while !is_block_like(expr) {
+        expr = match expr {
+            Expr::Assign(assign) => &mut assign.left,
+            Expr::Await(await_) => &mut await_.expr,
+            Expr::Binary(binary) => &mut binary.left,
+            Expr::Call(call) => &mut call.func,
+            Expr::Cast(cast) => &mut cast.expr,
+            Expr::Field(field) => &mut field.expr,
+            Expr::Index(index) => &mut index.expr,
+            Expr::MethodCall(call) => &mut call.receiver,
+            Expr::Range(range) => match &mut range.start {
+                Some(start) => start,
+                None => return,
+            },
+            _ => return,
+        };
+    }
+    let inner = std::mem::replace(expr, Expr::Infer(ExprInfer));
+    *expr = Expr::Paren(ExprParen {
+        expr: Box::new(inner),
+    });
+}
@@ -3,17 +3,21 @@
 //! Statements are instructions that perform an action but do not produce a value.
 //! They are the building blocks of function bodies and other code blocks.
 
-use crate::ast::Md;
-use crate::ast::expressions::Expr;
+use crate::ast::expressions::{Expr, ExprBlock};
+use crate::ast::{Attribute, Comment, Md};
 use crate::ast::items::Item;
 use crate::ast::patterns::Pat;
 use crate::ast::types::Type;
+use crate::ast::verbatim::Verbatim;
 use thin_vec::ThinVec;
 
 /// A block of code, enclosed in curly braces: `{ ... }`.
 ///
 /// A block contains a sequence of statements and is also an expression.
-#[derive(Debug, Clone, PartialEq)]
+///
+/// Dropping deeply nested blocks, and cloning blocks nested as the last
+/// statement of their block, such as `{ { { x } } }`, does not recurse.
+#[derive(Debug, PartialEq)]
 pub struct Block {
     /// The statements within the block.
     pub stmts: ThinVec<Stmt>,
@@ -23,6 +27,43 @@ pub struct Block {
     pub md: Option<Box<Md>>,
 }
 
+impl Clone for Block {
+    fn clone(&self) -> Self {
+        let mut spine = vec![self];
+        while let Some(Stmt::Expr(StmtExpr {
+            expr: Expr::Block(inner),
+            ..
+        })) = spine[spine.len() - 1].stmts.last()
+        {
+            spine.push(&inner.block);
+        }
+        let innermost = spine.pop().unwrap();
+        let mut block = Block {
+            stmts: innermost.stmts.clone(),
+            has_trailing_semicolon: innermost.has_trailing_semicolon,
+            md: innermost.md.clone(),
+        };
+        for outer in spine.into_iter().rev() {
+            let Some((Stmt::Expr(last), init)) = outer.stmts.split_last() else {
+                unreachable!()
+            };
+            let mut stmts: ThinVec<Stmt> = init.iter().cloned().collect();
+            stmts.push(Stmt::Expr(StmtExpr {
+                expr: Expr::Block(ExprBlock {
+                    block: block.into(),
+                }),
+                md: last.md.clone(),
+            }));
+            block = Block {
+                stmts,
+                has_trailing_semicolon: outer.has_trailing_semicolon,
+                md: outer.md.clone(),
+            };
+        }
+        block
+    }
+}
+
 impl Default for Block {
     fn default() -> Self {
         Self {
@@ -41,7 +82,93 @@ pub enum Stmt {
     /// An item definition, such as a function or struct.
     Item(Item),
     /// An expression statement, which is an expression followed by a semicolon.
-    Expr(Expr),
+    Expr(StmtExpr),
+    /// Raw source text, printed as it is.
+    Verbatim(Verbatim),
+}
+
+impl Stmt {
+    /// Adds an attribute to the statement, such as `#[cfg(feature = "x")]`.
+    ///
+    /// For an item statement, the attribute is added to the item.
+    ///
+    /// # Parameters
+    ///
+    /// - `attr`: The attribute to add.
+    pub fn attr(mut self, attr: impl Into<Attribute>) -> Self {
+        self.md_mut().get_or_insert_default().attrs.push(attr.into());
+        self
+    }
+
+    /// Adds a comment before the statement.
+    ///
+    /// For an item statement, the comment is added to the item.
+    ///
+    /// # Parameters
+    ///
+    /// - `comment`: The comment to add.
+    pub fn comment(mut self, comment: impl Into<Comment>) -> Self {
+        self.md_mut()
+            .get_or_insert_default()
+            .comments
+            .push(comment.into());
+        self
+    }
+
+    /// Sets a comment printed at the end of the statement's last line, e.g.
+    /// `let x = 1; // default`.
+    ///
+    /// # Parameters
+    ///
+    /// - `text`: The content of the comment, without the `//`.
+    pub fn eol_comment(mut self, text: impl Into<String>) -> Self {
+        self.md_mut().get_or_insert_default().eol_comment = Some(text.into());
+        self
+    }
+
+    /// Separates the statement from the one before it with an empty line.
+    ///
+    /// For an item statement, the flag is set on the item.
+    pub fn blank_line_before(mut self) -> Self {
+        self.md_mut().get_or_insert_default().blank_line_before = true;
+        self
+    }
+
+    /// Returns a reference to the statement's metadata.
+    pub(crate) fn md(&self) -> &Option<Box<Md>> {
+        match self {
+            Stmt::Local(local) => &local.md,
+            Stmt::Item(item) => item.md(),
+            Stmt::Expr(expr) => &expr.md,
+            Stmt::Verbatim(verbatim) => &verbatim.md,
+        }
+    }
+
+    /// Returns a mutable reference to the statement's metadata.
+    pub(crate) fn md_mut(&mut self) -> &mut Option<Box<Md>> {
+        match self {
+            Stmt::Local(local) => &mut local.md,
+            Stmt::Item(item) => item.md_mut(),
+            Stmt::Expr(expr) => &mut expr.md,
+            Stmt::Verbatim(verbatim) => &mut verbatim.md,
+        }
+    }
+}
+
+/// An expression statement, such as `foo();`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StmtExpr {
+    /// The expression.
+    pub expr: Expr,
+    /// Comments and attributes attached to the statement.
+    pub md: Option<Box<Md>>,
+}
+
+impl From<Expr> for StmtExpr {
+    /// Converts an `Expr` into a `StmtExpr` without metadata.
+    fn from(expr: Expr) -> Self {
+        Self { expr, md: None }
+    }
 }
 
 /// A `let` statement, such as `let x: i32 = 1;`.
@@ -55,4 +182,8 @@ pub struct Local {
     pub expr: Option<Expr>,
     /// The optional `else` block, for `let-else` statements.
     pub else_block: Option<Box<Block>>,
+    /// Comments and attributes attached to the `let` statement.
+    pub md: Option<Box<Md>>,
 }
+
+impl_display!(Block, Local, Stmt, StmtExpr);
@@ -88,3 +88,5 @@ pub struct Comma;
 /// The `=>` token.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct FatArrow;
+
+impl_display!(TokenStream, TokenTree, Group, Punct, Delimiter);
@@ -1,10 +1,13 @@
 //! The `ast` module contains the definitions for the Abstract Syntax Tree (AST) nodes
 //! that represent Rust types.
 
-use crate::ast::expressions::{Expr, Path, PathSegment};
+use crate::ast::abi::Abi;
+use crate::ast::expressions::{Expr, Path};
 use crate::ast::ident::Ident;
+use crate::ast::lifetime::Lifetime;
 use crate::ast::item_macro::ItemMacro;
-use thin_vec::{ThinVec, thin_vec};
+use crate::ast::verbatim::Verbatim;
+use thin_vec::ThinVec;
 
 /// A Rust type.
 #[derive(Debug, Clone, PartialEq)]
@@ -12,7 +15,8 @@ pub enum Type {
     /// A fixed-size array type: `[T; n]`.
     Array(TypeArray),
 
-    /// A bare function type: `fn(usize) -> bool`.
+    /// A bare function type: `fn(usize) -> bool` or
+    /// `for<'a> unsafe extern "C" fn(&'a u8, ...)`.
     BareFn(TypeBareFn),
 
     /// A type contained within invisible delimiters.
@@ -53,6 +57,9 @@ pub enum Type {
 
     /// A tuple type: `(A, B, C, String)`.
     Tuple(ThinVec<Type>),
+
+    /// Raw source text, printed as it is.
+    Verbatim(Verbatim),
 }
 
 /// A fixed-size array type, such as `[T; n]`.
@@ -64,15 +71,44 @@ pub struct TypeArray {
     pub len: Box<Expr>,
 }
 
-/// A bare function type, such as `fn(usize) -> bool`.
+/// A bare function type, such as `fn(usize) -> bool` or
+/// `for<'a> unsafe extern "C" fn(ptr: *const u8, ...) -> &'a i32`.
 #[derive(Debug, Clone, PartialEq)]
 pub struct TypeBareFn {
-    /// The input types of the function.
-    pub inputs: ThinVec<Type>,
+    /// The lifetimes bound by a `for<...>` binder, such as `'a` in `for<'a>`.
+    pub lifetimes: ThinVec<Lifetime>,
+    /// Whether the function is `unsafe`.
+    pub is_unsafe: bool,
+    /// The ABI of the function, if specified, such as `extern "C"`.
+    pub abi: Option<Abi>,
+    /// The parameters of the function.
+    pub inputs: ThinVec<BareFnArg>,
+    /// Whether the function is variadic, i.e. ends with `...`. This is only
+    /// valid for `extern "C"`-like ABIs.
+    pub is_variadic: bool,
     /// The output type of the function.
     pub output: Option<Box<Type>>,
 }
 
+/// A parameter of a bare function type, such as `ptr: *const u8` or `u8`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BareFnArg {
+    /// The name of the parameter, if any.
+    pub name: Option<Ident>,
+    /// The type of the parameter.
+    pub ty: Type,
+}
+
+impl<T: Into<Type>> From<T> for BareFnArg {
+    /// Converts a type into an unnamed `BareFnArg`.
+    fn from(ty: T) -> Self {
+        BareFnArg {
+            name: None,
+            ty: ty.into(),
+        }
+    }
+}
+
 /// A path to a type, such as `std::slice::Iter`.
 ///
 /// This can be optionally qualified with a self-type, as in
@@ -97,7 +133,7 @@ pub struct TypePtr {
 #[derive(Debug, Clone, PartialEq)]
 pub struct TypeReference {
     /// The lifetime of the reference.
-    pub lifetime: Option<Ident>,
+    pub lifetime: Option<Lifetime>,
     /// The referenced type.
     pub elem: Box<Type>,
     /// Whether the reference is mutable.
@@ -105,17 +141,26 @@ pub struct TypeReference {
 }
 
 impl From<&str> for Type {
-    /// Converts a string slice into a `Type::Path`.
+    /// Converts a string slice into a `Type`.
     ///
-    /// This is a convenience function for creating simple path-based types.
+    /// `::`-separated identifiers such as `std::fmt::Display` become a
+    /// `Type::Path`. Anything else, such as `Vec<u8>` or `&'a str`, becomes a
+    /// `Type::Verbatim` that prints the text unchanged. With the `parser`
+    /// feature, use [`str::parse`] to build the syntax tree of such a type.
     fn from(s: &str) -> Self {
-        Type::Path(TypePath {
-            path: Path {
-                segments: thin_vec![PathSegment {
-                    ident: s.into(),
-                    args: None,
-                }],
-            },
-        })
+        match Path::from_idents(s) {
+            Some(path) => Type::Path(TypePath { path }),
+            None => Type::Verbatim(Verbatim::new(s)),
+        }
     }
 }
+
+impl_display!(
+    Type,
+    TypeArray,
+    TypeBareFn,
+    TypePath,
+    TypePtr,
+    TypeReference,
+    BareFnArg
+);
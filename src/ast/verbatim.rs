@@ -0,0 +1,122 @@
+//! Defines the AST node for raw source text.
+//!
+//! A `Verbatim` node is an escape hatch for syntax that the AST does not
+//! cover. It can stand in for an item, a statement, an expression, a type or a
+//! pattern.
+
+use crate::ast::attributes::Attribute;
+use crate::ast::comments::Comment;
+use crate::ast::expressions::Expr;
+use crate::ast::items::Item;
+use crate::ast::metadata::Md;
+use crate::ast::patterns::Pat;
+use crate::ast::statements::Stmt;
+use crate::ast::types::Type;
+
+/// Raw source text that is printed as it is.
+///
+/// The text is not checked, so it must be valid in the position where it is
+/// used. An expression is not parenthesized, even if its precedence is lower
+/// than that of the enclosing expression.
+///
+/// The lines of multi-line text are re-indented: the indentation that they
+/// all share is removed, and the lines after the first are indented to the
+/// level of the node. In single-line mode, the lines are joined with spaces.
+///
+/// # Examples
+///
+/// ```
+/// use rasto::ast::Verbatim;
+/// use rasto::builder::*;
+/// use rasto::pretty;
+///
+/// let item = fn_def("f")
+///     .statement(stmt().verbatim("let x = some_macro! {\n    a => b,\n};"))
+///     .build();
+/// assert_eq!(
+///     pretty(&item),
+///     "fn f() {\n    let x = some_macro! {\n        a => b,\n    };\n}"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Verbatim {
+    /// The source text.
+    pub text: String,
+    /// The comments and attributes printed before and after the text, in
+    /// item and statement position.
+    pub md: Option<Box<Md>>,
+}
+
+impl Verbatim {
+    /// Creates a new `Verbatim` node with the given source text.
+    ///
+    /// # Parameters
+    ///
+    /// - `text`: The source text.
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            md: None,
+        }
+    }
+
+    /// Adds an attribute to the node.
+    ///
+    /// # Parameters
+    ///
+    /// - `attr`: The attribute to add.
+    pub fn attr(mut self, attr: impl Into<Attribute>) -> Self {
+        self.md.get_or_insert_default().attrs.push(attr.into());
+        self
+    }
+
+    /// Adds a comment to the node.
+    ///
+    /// # Parameters
+    ///
+    /// - `comment`: The comment to add.
+    pub fn comment(mut self, comment: impl Into<Comment>) -> Self {
+        self.md
+            .get_or_insert_default()
+            .comments
+            .push(comment.into());
+        self
+    }
+}
+
+impl From<Verbatim> for Item {
+    /// Converts a `Verbatim` into an `Item::Verbatim` variant.
+    fn from(verbatim: Verbatim) -> Self {
+        Item::Verbatim(verbatim)
+    }
+}
+
+impl From<Verbatim> for Stmt {
+    /// Converts a `Verbatim` into a `Stmt::Verbatim` variant.
+    fn from(verbatim: Verbatim) -> Self {
+        Stmt::Verbatim(verbatim)
+    }
+}
+
+impl From<Verbatim> for Expr {
+    /// Converts a `Verbatim` into an `Expr::Verbatim` variant.
+    fn from(verbatim: Verbatim) -> Self {
+        Expr::Verbatim(verbatim)
+    }
+}
+
+impl From<Verbatim> for Type {
+    /// Converts a `Verbatim` into a `Type::Verbatim` variant.
+    fn from(verbatim: Verbatim) -> Self {
+        Type::Verbatim(verbatim)
+    }
+}
+
+impl From<Verbatim> for Pat {
+    /// Converts a `Verbatim` into a `Pat::Verbatim` variant.
+    fn from(verbatim: Verbatim) -> Self {
+        Pat::Verbatim(verbatim)
+    }
+}
+
+impl_display!(Verbatim);
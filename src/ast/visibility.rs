@@ -29,3 +29,5 @@ impl PrettyPrinter for Visibility {
         Ok(())
     }
 }
+
+impl_display!(Visibility);
@@ -1,8 +1,8 @@
 //! The `ast` module contains the definitions for the Abstract Syntax Tree (AST) nodes
 //! that represent Rust `where` clauses.
 
-use crate::ast::types::Type;
-use crate::pretty_printer::{PrettyPrinter, Printer};
+use crate::ast::{lifetime::Lifetime, types::Type};
+use crate::pretty_printer::{BreakStyle, PrettyPrinter, Printer};
 use std::fmt;
 
 /// A `where` clause, such as `where T: Trait`.
@@ -32,9 +32,33 @@ pub enum WherePredicate {
 #[derive(Debug, Clone, PartialEq)]
 pub struct LifetimePredicate {
     /// The lifetime being bounded.
-    pub lifetime: String,
+    pub lifetime: Lifetime,
     /// The bounds on the lifetime.
-    pub bounds: Vec<String>,
+    pub bounds: Vec<Lifetime>,
+}
+
+impl LifetimePredicate {
+    /// Creates a new lifetime predicate without bounds.
+    ///
+    /// # Parameters
+    ///
+    /// * `lifetime` - The lifetime being bounded.
+    pub fn new(lifetime: impl Into<Lifetime>) -> Self {
+        Self {
+            lifetime: lifetime.into(),
+            bounds: vec![],
+        }
+    }
+
+    /// Adds a bound to the lifetime predicate.
+    ///
+    /// # Parameters
+    ///
+    /// * `bound` - The lifetime that the bounded lifetime outlives.
+    pub fn with_bound(mut self, bound: impl Into<Lifetime>) -> Self {
+        self.bounds.push(bound.into());
+        self
+    }
 }
 
 /// A type-bound predicate, such as `T: Trait`.
@@ -49,13 +73,16 @@ pub struct TypePredicate {
 impl PrettyPrinter for WhereClause {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
         if !self.predicates.is_empty() {
-            printer.string(" where ");
+            printer.hard_break();
+            printer.begin(BreakStyle::Consistent, "where");
             for (i, predicate) in self.predicates.iter().enumerate() {
                 if i > 0 {
-                    printer.string(", ");
+                    printer.string(",");
                 }
+                printer.hard_break();
                 predicate.pretty_print(printer)?;
             }
+            printer.end_inline("");
         }
         Ok(())
     }
@@ -72,16 +99,14 @@ impl PrettyPrinter for WherePredicate {
 
 impl PrettyPrinter for LifetimePredicate {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
-        printer.string("'");
-        printer.string(&self.lifetime);
+        self.lifetime.pretty_print(printer)?;
         if !self.bounds.is_empty() {
             printer.string(": ");
             for (i, bound) in self.bounds.iter().enumerate() {
                 if i > 0 {
                     printer.string(" + ");
                 }
-                printer.string("'");
-                printer.string(bound);
+                bound.pretty_print(printer)?;
             }
         }
         Ok(())
@@ -103,3 +128,10 @@ impl PrettyPrinter for TypePredicate {
         Ok(())
     }
 }
+
+impl_display!(
+    WhereClause,
+    WherePredicate,
+    LifetimePredicate,
+    TypePredicate
+);
@@ -386,7 +386,7 @@ pub fn extern_block_item() -> ItemExternBlockBuilder {
 pub struct ItemExternBlockBuilder {
     vis: Visibility,
     is_unsafe: bool,
-    abi: Option<String>,
+    abi: Abi,
     items: ThinVec<ExternalItem>,
     md: MdBuilder,
 }
@@ -427,9 +427,9 @@ impl ItemExternBlockBuilder {
     ///
     /// # Parameters
     ///
-    /// - `abi`: The ABI string (e.g., "C").
-    pub fn abi(mut self, abi: impl Into<String>) -> Self {
-        self.abi = Some(abi.into());
+    /// - `abi`: The ABI (e.g., `"C"`).
+    pub fn abi(mut self, abi: impl Into<Abi>) -> Self {
+        self.abi = abi.into();
         self
     }
 
@@ -475,7 +475,7 @@ impl Default for ItemExternBlockBuilder {
         Self {
             vis: Visibility::Default,
             is_unsafe: false,
-            abi: None,
+            abi: Abi::Implicit,
             items: thin_vec![],
             md: MdBuilder::new(),
         }
@@ -561,6 +561,7 @@ pub struct ArmBuilder {
     pat: Pat,
     guard: Option<Expr>,
     body: Expr,
+    md: MdBuilder,
 }
 
 impl ArmBuilder {
@@ -574,9 +575,40 @@ impl ArmBuilder {
             pat: pat.into(),
             guard: None,
             body: expr().tuple(vec![]),
+            md: MdBuilder::new(),
         }
     }
 
+    /// Adds an attribute to the arm, such as `#[allow(unreachable_patterns)]`.
+    ///
+    /// # Parameters
+    ///
+    /// - `attr`: The attribute to add.
+    pub fn attr(mut self, attr: impl Into<Attribute>) -> Self {
+        self.md = self.md.attr(attr.into());
+        self
+    }
+
+    /// Adds a comment before the arm.
+    ///
+    /// # Parameters
+    ///
+    /// - `comment`: The comment to add.
+    pub fn comment(mut self, comment: impl Into<Comment>) -> Self {
+        self.md = self.md.comment(comment.into());
+        self
+    }
+
+    /// Sets a comment printed after the arm's comma, e.g. `_ => 0, // fallback`.
+    ///
+    /// # Parameters
+    ///
+    /// - `text`: The content of the comment, without the `//`.
+    pub fn eol_comment(mut self, text: impl Into<String>) -> Self {
+        self.md = self.md.eol_comment(text);
+        self
+    }
+
     /// Sets the guard expression for the arm.
     ///
     /// # Parameters
@@ -607,6 +639,7 @@ impl ArmBuilder {
             pat: self.pat,
             guard: self.guard.map(Box::new),
             body: Box::new(self.body),
+            md: Some(Box::new(self.md.build())),
         }
     }
 }
@@ -1007,6 +1040,16 @@ impl EnumBuilder {
         self
     }
 
+    /// Adds a variant with metadata to the enum.
+    ///
+    /// # Parameters
+    ///
+    /// - `variant`: The `Variant` to add.
+    pub fn variant_with(mut self, variant: Variant) -> Self {
+        self.variants.push(variant);
+        self
+    }
+
     /// Adds a comment to the enum.
     ///
     /// # Parameters
@@ -1116,6 +1159,16 @@ impl StructBuilder {
         self
     }
 
+    /// Adds a field with metadata.
+    ///
+    /// # Parameters
+    ///
+    /// - `field`: The `Field` to add.
+    pub fn field_with(mut self, field: Field) -> Self {
+        self.fields.push(field);
+        self
+    }
+
     /// Adds a comment to the struct.
     ///
     /// # Parameters
@@ -1171,6 +1224,8 @@ pub struct SignatureBuilder {
     ident: Ident,
     is_const: bool,
     is_async: bool,
+    is_gen: bool,
+    is_safe: bool,
     is_unsafe: bool,
     abi: Option<Abi>,
     generics: GenericParams,
@@ -1205,6 +1260,18 @@ impl SignatureBuilder {
         self
     }
 
+    /// Sets the function as `gen`, making its body a generator.
+    pub fn gen_(mut self) -> Self {
+        self.is_gen = true;
+        self
+    }
+
+    /// Sets the function as `safe`, for use in an `unsafe extern` block.
+    pub fn safe(mut self) -> Self {
+        self.is_safe = true;
+        self
+    }
+
     /// Sets the function as `unsafe`.
     pub fn unsafe_(mut self) -> Self {
         self.is_unsafe = true;
@@ -1212,8 +1279,8 @@ impl SignatureBuilder {
     }
 
     /// Sets the ABI of the function.
-    pub fn abi(mut self, abi: Abi) -> Self {
-        self.abi = Some(abi);
+    pub fn abi(mut self, abi: impl Into<Abi>) -> Self {
+        self.abi = Some(abi.into());
         self
     }
 
@@ -1281,6 +1348,8 @@ impl SignatureBuilder {
         Signature {
             is_const: self.is_const,
             is_async: self.is_async,
+            is_gen: self.is_gen,
+            is_safe: self.is_safe,
             is_unsafe: self.is_unsafe,
             abi: self.abi,
             ident: self.ident,
@@ -1351,6 +1420,18 @@ impl FnBuilder {
         self
     }
 
+    /// Sets the function as `gen`, making its body a generator.
+    pub fn gen_(mut self) -> Self {
+        self.sig = self.sig.gen_();
+        self
+    }
+
+    /// Sets the function as `safe`, for use in an `unsafe extern` block.
+    pub fn safe(mut self) -> Self {
+        self.sig = self.sig.safe();
+        self
+    }
+
     /// Sets the function as `unsafe`.
     pub fn unsafe_(mut self) -> Self {
         self.sig = self.sig.unsafe_();
@@ -1358,7 +1439,7 @@ impl FnBuilder {
     }
 
     /// Sets the ABI of the function.
-    pub fn abi(mut self, abi: Abi) -> Self {
+    pub fn abi(mut self, abi: impl Into<Abi>) -> Self {
         self.sig = self.sig.abi(abi);
         self
     }
@@ -1518,7 +1599,7 @@ impl StmtBuilder {
     ///
     /// - `expr`: The `Expr` to be used as a statement.
     pub fn expr(self, expr: Expr) -> Stmt {
-        Stmt::Expr(expr)
+        Stmt::Expr(expr.into())
     }
 
     /// Creates a macro call statement.
@@ -1527,7 +1608,16 @@ impl StmtBuilder {
     ///
     /// - `mac`: The `ExprMacroCall` to be used as a statement.
     pub fn mac_call(self, mac: ExprMacroCall) -> Stmt {
-        Stmt::Expr(Expr::MacroCall(mac))
+        Stmt::Expr(Expr::MacroCall(mac).into())
+    }
+
+    /// Creates a statement from raw source text.
+    ///
+    /// # Parameters
+    ///
+    /// - `text`: The source text of the statement, including any `;`.
+    pub fn verbatim(self, text: impl Into<String>) -> Stmt {
+        Stmt::Verbatim(Verbatim::new(text))
     }
 }
 
@@ -1537,6 +1627,7 @@ pub struct LocalBuilder {
     ty: Option<Type>,
     expr: Option<Expr>,
     else_block: Option<Block>,
+    md: MdBuilder,
 }
 
 impl LocalBuilder {
@@ -1551,9 +1642,40 @@ impl LocalBuilder {
             ty: None,
             expr: None,
             else_block: None,
+            md: MdBuilder::new(),
         }
     }
 
+    /// Adds an attribute to the `let` statement, such as `#[cfg(feature = "x")]`.
+    ///
+    /// # Parameters
+    ///
+    /// - `attr`: The attribute to add.
+    pub fn attr(mut self, attr: impl Into<Attribute>) -> Self {
+        self.md = self.md.attr(attr.into());
+        self
+    }
+
+    /// Adds a comment before the `let` statement.
+    ///
+    /// # Parameters
+    ///
+    /// - `comment`: The comment to add.
+    pub fn comment(mut self, comment: impl Into<Comment>) -> Self {
+        self.md = self.md.comment(comment.into());
+        self
+    }
+
+    /// Sets a comment printed after the `let` statement, e.g. `let x = 1; // default`.
+    ///
+    /// # Parameters
+    ///
+    /// - `text`: The content of the comment, without the `//`.
+    pub fn eol_comment(mut self, text: impl Into<String>) -> Self {
+        self.md = self.md.eol_comment(text);
+        self
+    }
+
     /// Sets the type of the variable.
     ///
     /// # Parameters
@@ -1602,15 +1724,19 @@ impl LocalBuilder {
             ty: self.ty,
             expr: self.expr,
             else_block: self.else_block.map(Box::new),
+            md: Some(Box::new(self.md.build())),
         })
     }
 }
 
-/// Creates a new `FieldValueBuilder` to construct a field-value pair.
+/// Creates a new `FieldValue` for a struct instantiation expression.
+///
+/// Use [`FieldValue::attr`] and [`FieldValue::comment`] to attach metadata.
 pub fn field_value(member: impl Into<Ident>, value: impl Into<Expr>) -> FieldValue {
     FieldValue {
         member: member.into(),
         value: value.into(),
+        md: None,
     }
 }
 
@@ -1648,6 +1774,12 @@ impl TraitItemFnBuilder {
         self
     }
 
+    /// Sets the function as `gen`, making its body a generator.
+    pub fn gen_(mut self) -> Self {
+        self.sig = self.sig.gen_();
+        self
+    }
+
     /// Sets the function as `unsafe`.
     pub fn unsafe_(mut self) -> Self {
         self.sig = self.sig.unsafe_();
@@ -1655,7 +1787,7 @@ impl TraitItemFnBuilder {
     }
 
     /// Sets the ABI of the function.
-    pub fn abi(mut self, abi: Abi) -> Self {
+    pub fn abi(mut self, abi: impl Into<Abi>) -> Self {
         self.sig = self.sig.abi(abi);
         self
     }
@@ -1741,6 +1873,15 @@ impl PatBuilder {
         Pat::Wild(PatWild)
     }
 
+    /// Creates a pattern from raw source text.
+    ///
+    /// # Parameters
+    ///
+    /// - `text`: The source text of the pattern.
+    pub fn verbatim(self, text: impl Into<String>) -> Pat {
+        Pat::Verbatim(Verbatim::new(text))
+    }
+
     /// Sets the pattern to be mutable (e.g., `mut ident`).
     pub fn mutable(mut self) -> Self {
         self.mutability = true;
@@ -2083,11 +2224,21 @@ impl TypeBuilder {
         output: Option<impl Into<Type>>,
     ) -> Type {
         Type::BareFn(TypeBareFn {
-            inputs: inputs.into_iter().map(|t| t.into()).collect(),
+            lifetimes: thin_vec![],
+            is_unsafe: false,
+            abi: None,
+            inputs: inputs.into_iter().map(|t| t.into().into()).collect(),
+            is_variadic: false,
             output: output.map(|t| Box::new(t.into())),
         })
     }
 
+    /// Creates a builder for a function pointer type with qualifiers, named
+    /// parameters, variadics or a `for<...>` binder.
+    pub fn fn_ptr(self) -> TypeBareFnBuilder {
+        TypeBareFnBuilder::new()
+    }
+
     /// Creates a grouped type.
     ///
     /// # Parameters
@@ -2124,6 +2275,15 @@ impl TypeBuilder {
         Type::Never
     }
 
+    /// Creates a type from raw source text.
+    ///
+    /// # Parameters
+    ///
+    /// - `text`: The source text of the type.
+    pub fn verbatim(self, text: impl Into<String>) -> Type {
+        Type::Verbatim(Verbatim::new(text))
+    }
+
     /// Creates a parenthesized type.
     ///
     /// # Parameters
@@ -2193,7 +2353,7 @@ impl TypeBuilder {
 pub struct TypeReferenceBuilder {
     is_mut: bool,
     ty: Type,
-    lifetime: Option<Ident>,
+    lifetime: Option<Lifetime>,
 }
 
 impl TypeReferenceBuilder {
@@ -2216,7 +2376,7 @@ impl TypeReferenceBuilder {
     /// # Parameters
     ///
     /// - `lifetime`: The lifetime to set.
-    pub fn lifetime(mut self, lifetime: impl Into<Ident>) -> Self {
+    pub fn lifetime(mut self, lifetime: impl Into<Lifetime>) -> Self {
         self.lifetime = Some(lifetime.into());
         self
     }
@@ -2237,6 +2397,108 @@ impl From<TypeReferenceBuilder> for Type {
     }
 }
 
+/// A builder for constructing a `TypeBareFn` AST node.
+#[derive(Default)]
+pub struct TypeBareFnBuilder {
+    lifetimes: ThinVec<Lifetime>,
+    is_unsafe: bool,
+    abi: Option<Abi>,
+    inputs: ThinVec<BareFnArg>,
+    is_variadic: bool,
+    output: Option<Type>,
+}
+
+impl TypeBareFnBuilder {
+    /// Creates a new `TypeBareFnBuilder` for `fn()`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a lifetime to the `for<...>` binder.
+    ///
+    /// # Parameters
+    ///
+    /// - `lifetime`: The lifetime to bind.
+    pub fn lifetime(mut self, lifetime: impl Into<Lifetime>) -> Self {
+        self.lifetimes.push(lifetime.into());
+        self
+    }
+
+    /// Sets the function pointer as `unsafe`.
+    pub fn unsafe_(mut self) -> Self {
+        self.is_unsafe = true;
+        self
+    }
+
+    /// Sets the ABI of the function pointer.
+    ///
+    /// # Parameters
+    ///
+    /// - `abi`: The ABI, e.g., `"C"` or `Abi::Implicit`.
+    pub fn abi(mut self, abi: impl Into<Abi>) -> Self {
+        self.abi = Some(abi.into());
+        self
+    }
+
+    /// Adds an unnamed parameter.
+    ///
+    /// # Parameters
+    ///
+    /// - `ty`: The type of the parameter.
+    pub fn input(mut self, ty: impl Into<Type>) -> Self {
+        self.inputs.push(ty.into().into());
+        self
+    }
+
+    /// Adds a named parameter.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: The name of the parameter.
+    /// - `ty`: The type of the parameter.
+    pub fn input_named(mut self, name: impl Into<Ident>, ty: impl Into<Type>) -> Self {
+        self.inputs.push(BareFnArg {
+            name: Some(name.into()),
+            ty: ty.into(),
+        });
+        self
+    }
+
+    /// Sets whether the function pointer is variadic.
+    pub fn variadic(mut self, is_variadic: bool) -> Self {
+        self.is_variadic = is_variadic;
+        self
+    }
+
+    /// Sets the return type of the function pointer.
+    ///
+    /// # Parameters
+    ///
+    /// - `ty`: The return type.
+    pub fn output(mut self, ty: impl Into<Type>) -> Self {
+        self.output = Some(ty.into());
+        self
+    }
+
+    /// Builds the `TypeBareFn` AST node.
+    pub fn build(self) -> Type {
+        Type::BareFn(TypeBareFn {
+            lifetimes: self.lifetimes,
+            is_unsafe: self.is_unsafe,
+            abi: self.abi,
+            inputs: self.inputs,
+            is_variadic: self.is_variadic,
+            output: self.output.map(Box::new),
+        })
+    }
+}
+
+impl From<TypeBareFnBuilder> for Type {
+    fn from(builder: TypeBareFnBuilder) -> Self {
+        builder.build()
+    }
+}
+
 /// Creates a new `MdBuilder` to construct metadata.
 pub fn md() -> MdBuilder {
     MdBuilder::new()
@@ -2248,6 +2510,8 @@ pub struct MdBuilder {
     attrs: ThinVec<Attribute>,
     comments: ThinVec<Comment>,
     trailing_comments: ThinVec<Comment>,
+    eol_comment: Option<String>,
+    blank_line_before: bool,
 }
 
 impl MdBuilder {
@@ -2286,9 +2550,29 @@ impl MdBuilder {
         self
     }
 
+    /// Sets the end-of-line comment of the metadata.
+    ///
+    /// # Parameters
+    ///
+    /// - `text`: The content of the comment, without the `//`.
+    pub fn eol_comment(mut self, text: impl Into<String>) -> Self {
+        self.eol_comment = Some(text.into());
+        self
+    }
+
+    /// Separates the node from the one before it with an empty line.
+    pub fn blank_line_before(mut self) -> Self {
+        self.blank_line_before = true;
+        self
+    }
+
     /// Returns true if no metadata has been added.
     pub fn is_empty(&self) -> bool {
-        self.attrs.is_empty() && self.comments.is_empty() && self.trailing_comments.is_empty()
+        self.attrs.is_empty()
+            && self.comments.is_empty()
+            && self.trailing_comments.is_empty()
+            && self.eol_comment.is_none()
+            && !self.blank_line_before
     }
 
     /// Builds the `Md` AST node.
@@ -2297,6 +2581,8 @@ impl MdBuilder {
             attrs: self.attrs,
             comments: self.comments,
             trailing_comments: self.trailing_comments,
+            eol_comment: self.eol_comment,
+            blank_line_before: self.blank_line_before,
         }
     }
 }
@@ -2384,6 +2670,101 @@ impl From<ItemExternTypeBuilder> for Item {
     }
 }
 
+/// Creates a new `ExternalStaticBuilder` to construct a `static` declared in
+/// an `extern` block.
+pub fn extern_static_item(name: impl Into<Ident>, ty: impl Into<Type>) -> ExternalStaticBuilder {
+    ExternalStaticBuilder::new(name, ty)
+}
+
+/// A builder for constructing an `ExternalStatic` AST node.
+pub struct ExternalStaticBuilder {
+    ident: Ident,
+    vis: Visibility,
+    is_mut: bool,
+    is_safe: bool,
+    is_unsafe: bool,
+    ty: Type,
+    md: MdBuilder,
+}
+
+impl ExternalStaticBuilder {
+    /// Creates a new `ExternalStaticBuilder` with the given name and type.
+    pub fn new(name: impl Into<Ident>, ty: impl Into<Type>) -> Self {
+        Self {
+            ident: name.into(),
+            vis: Visibility::Default,
+            is_mut: false,
+            is_safe: false,
+            is_unsafe: false,
+            ty: ty.into(),
+            md: MdBuilder::new(),
+        }
+    }
+
+    /// Sets the visibility of the static.
+    pub fn vis(mut self, vis: Visibility) -> Self {
+        self.vis = vis;
+        self
+    }
+
+    /// Sets the static as mutable.
+    pub fn mutable(mut self) -> Self {
+        self.is_mut = true;
+        self
+    }
+
+    /// Sets the static as `safe`, for use in an `unsafe extern` block.
+    pub fn safe(mut self) -> Self {
+        self.is_safe = true;
+        self
+    }
+
+    /// Sets the static as explicitly `unsafe`.
+    pub fn unsafe_(mut self) -> Self {
+        self.is_unsafe = true;
+        self
+    }
+
+    /// Adds a comment to the static.
+    pub fn comment(mut self, comment: impl Into<Comment>) -> Self {
+        self.md = self.md.comment(comment.into());
+        self
+    }
+
+    /// Adds an attribute to the static.
+    pub fn attr(mut self, attr: impl Into<Attribute>) -> Self {
+        self.md = self.md.attr(attr.into());
+        self
+    }
+
+    /// Builds the `ExternalStatic` AST node.
+    pub fn build(self) -> ExternalStatic {
+        ExternalStatic {
+            vis: self.vis,
+            ident: self.ident,
+            is_mut: self.is_mut,
+            is_safe: self.is_safe,
+            is_unsafe: self.is_unsafe,
+            ty: self.ty,
+            md: Some(Box::new(self.md.build())),
+        }
+    }
+}
+
+impl From<ExternalStaticBuilder> for ExternalItem {
+    /// Converts an `ExternalStaticBuilder` into an `ExternalItem::Static` variant.
+    fn from(builder: ExternalStaticBuilder) -> Self {
+        ExternalItem::Static(builder.build())
+    }
+}
+
+impl From<FnBuilder> for ExternalItem {
+    /// Converts a `FnBuilder` into an `ExternalItem::Fn` variant.
+    fn from(builder: FnBuilder) -> Self {
+        ExternalItem::Fn(builder.build())
+    }
+}
+
 impl From<ItemExternType> for ExternalItem {
     /// Converts an `ItemExternType` into an `ExternalItem::Type` variant.
     fn from(item: ItemExternType) -> Self {
@@ -2433,9 +2814,9 @@ impl From<ItemUseBuilder> for Item {
     }
 }
 
-impl From<AsmBuilder> for Item {
-    /// Converts an `AsmBuilder` into an `Item::Asm` variant.
-    fn from(builder: AsmBuilder) -> Self {
+impl From<GlobalAsmBuilder> for Item {
+    /// Converts a `GlobalAsmBuilder` into an `Item::Asm` variant.
+    fn from(builder: GlobalAsmBuilder) -> Self {
         Item::Asm(builder.build())
     }
 }
@@ -2597,11 +2978,40 @@ impl From<StaticItemBuilder> for Item {
     }
 }
 
+/// Creates a `Verbatim` node, which converts into an item, a statement, an
+/// expression, a type or a pattern.
+///
+/// # Parameters
+///
+/// - `text`: The source text to print as it is.
+///
+/// # Returns
+///
+/// A `Verbatim` instance, whose attributes and comments can be added with
+/// [`Verbatim::attr`] and [`Verbatim::comment`].
+pub fn verbatim(text: impl Into<String>) -> Verbatim {
+    Verbatim::new(text)
+}
+
+/// Creates a `Custom` node, which converts into an item or an expression.
+///
+/// # Parameters
+///
+/// - `node`: The node, of a type defined outside of this crate.
+///
+/// # Returns
+///
+/// A `Custom` instance, whose attributes and comments can be added with
+/// [`Custom::attr`] and [`Custom::comment`].
+pub fn custom(node: impl CustomNode) -> Custom {
+    Custom::new(node)
+}
+
 impl From<BlockBuilder> for Expr {
     /// Converts a `BlockBuilder` into an `Expr::Block` variant.
     fn from(builder: BlockBuilder) -> Self {
         Expr::Block(ExprBlock {
-            block: builder.build(),
+            block: builder.build().into(),
         })
     }
 }
@@ -2620,13 +3030,6 @@ impl From<i32> for Expr {
     }
 }
 
-impl From<u128> for Lit {
-    /// Converts a `u128` into a `Lit::Int` variant.
-    fn from(val: u128) -> Self {
-        Lit::Int(LitInt::new(val))
-    }
-}
-
 impl From<u128> for Expr {
     /// Converts a `u128` into an `Expr::Lit` variant.
     fn from(val: u128) -> Self {
@@ -2813,9 +3216,9 @@ impl ExprBuilder {
     /// - `right`: The right-hand side expression.
     pub fn binary(self, left: Expr, op: BinOp, right: Expr) -> Expr {
         Expr::Binary(ExprBinary {
-            left: Box::new(left),
+            left: left.into(),
             op,
-            right: Box::new(right),
+            right: right.into(),
         })
     }
 
@@ -2826,7 +3229,7 @@ impl ExprBuilder {
     /// - `block`: The block of statements.
     pub fn block(self, block: impl Into<Block>) -> Expr {
         Expr::Block(ExprBlock {
-            block: block.into(),
+            block: DeepBox::new(block.into()),
         })
     }
 
@@ -2865,9 +3268,14 @@ impl ExprBuilder {
     ///
     /// # Parameters
     ///
-    /// - `inputs`: An iterator of patterns for the closure's input parameters.
+    /// - `inputs`: An iterator of patterns or `ClosureParam`s for the closure's
+    ///   input parameters.
     /// - `body`: The body of the closure.
-    pub fn closure(self, inputs: impl IntoIterator<Item = impl Into<Pat>>, body: Expr) -> Expr {
+    pub fn closure(
+        self,
+        inputs: impl IntoIterator<Item = impl Into<ClosureParam>>,
+        body: Expr,
+    ) -> Expr {
         Expr::Closure(ExprClosure {
             inputs: inputs.into_iter().map(Into::into).collect(),
             body: Box::new(body),
@@ -2945,7 +3353,7 @@ impl ExprBuilder {
         Expr::If(ExprIf {
             cond: Box::new(cond),
             then_branch: then_branch.into(),
-            else_branch: else_branch.map(Box::new),
+            else_branch: else_branch.map(DeepBox::new),
         })
     }
 
@@ -2967,6 +3375,28 @@ impl ExprBuilder {
         Expr::Infer(ExprInfer)
     }
 
+    /// Creates an expression from raw source text.
+    ///
+    /// The text is never parenthesized, so it must bind at least as tightly
+    /// as the position it is used in requires.
+    ///
+    /// # Parameters
+    ///
+    /// - `text`: The source text of the expression.
+    pub fn verbatim(self, text: impl Into<String>) -> Expr {
+        Expr::Verbatim(Verbatim::new(text))
+    }
+
+    /// Creates an expression from a node of a type defined outside of this
+    /// crate.
+    ///
+    /// # Parameters
+    ///
+    /// - `node`: The node.
+    pub fn custom(self, node: impl CustomNode) -> Expr {
+        Expr::Custom(Custom::new(node))
+    }
+
     /// Creates a literal expression.
     ///
     /// # Parameters
@@ -2982,8 +3412,8 @@ impl ExprBuilder {
     ///
     /// - `value`: The integer value.
     /// - `suffix`: The integer suffix (e.g., `u32`, `i64`).
-    pub fn int_lit_with_suffix(self, value: i32, suffix: IntSuffix) -> Expr {
-        Expr::Lit(Lit::Int(LitInt::with_suffix(value as u128, suffix)))
+    pub fn int_lit_with_suffix(self, value: i128, suffix: IntSuffix) -> Expr {
+        Expr::Lit(Lit::Int(LitInt::signed(value).suffix(suffix)))
     }
 
     /// Creates a float literal expression with a specific suffix.
@@ -3126,6 +3556,28 @@ impl ExprBuilder {
         })
     }
 
+    /// Creates a `become` tail call expression.
+    ///
+    /// # Parameters
+    ///
+    /// - `expr`: The call expression to become.
+    pub fn become_expr(self, expr: Expr) -> Expr {
+        Expr::Become(ExprBecome {
+            expr: Box::new(expr),
+        })
+    }
+
+    /// Creates a `yield` expression.
+    ///
+    /// # Parameters
+    ///
+    /// - `expr`: The optional expression to yield.
+    pub fn yield_expr(self, expr: Option<Expr>) -> Expr {
+        Expr::Yield(ExprYield {
+            expr: expr.map(Box::new),
+        })
+    }
+
     /// Creates a struct instantiation expression.
     ///
     /// # Parameters
@@ -3227,7 +3679,7 @@ impl From<ExprRawRefBuilder> for Expr {
 impl From<Expr> for Stmt {
     /// Converts an `Expr` into a `Stmt::Expr` variant.
     fn from(value: Expr) -> Stmt {
-        Stmt::Expr(value)
+        Stmt::Expr(value.into())
     }
 }
 
@@ -3695,6 +4147,16 @@ impl ItemUnionBuilder {
         self
     }
 
+    /// Adds a field with metadata.
+    ///
+    /// # Parameters
+    ///
+    /// - `field`: The `Field` to add.
+    pub fn field_with(mut self, field: Field) -> Self {
+        self.fields.push(field);
+        self
+    }
+
     /// Adds a comment to the `union` item.
     ///
     /// # Parameters
@@ -3957,8 +4419,23 @@ impl From<PathBuilder> for Path {
 
 impl From<&str> for Path {
     /// Converts a `&str` into a `Path`.
+    ///
+    /// The string is split into segments at each `::`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `value` is not made of `::`-separated identifiers, such as
+    /// `Vec<u8>`. Build such a path with [`path`], or parse it with
+    /// [`str::parse`] if the `parser` feature is enabled.
     fn from(value: &str) -> Self {
-        path(value).build()
+        Path::from_idents(value).unwrap_or_else(|| {
+            let hint = if cfg!(feature = "parser") {
+                "parse it with `str::parse`"
+            } else {
+                "build it with `rasto::builder::path`"
+            };
+            panic!("invalid path `{value}`: expected `::`-separated identifiers; {hint}")
+        })
     }
 }
 
@@ -3977,12 +4454,20 @@ impl<const N: usize> From<&[&str; N]> for Path {
 }
 
 impl From<&str> for Pat {
-    /// Converts a `&str` into a `Pat::Ident` variant.
+    /// Converts a `&str` into a `Pat`.
+    ///
+    /// An identifier becomes a `Pat::Ident` variant. Anything else, such as
+    /// `Some(x)` or `(a, b)`, becomes a `Pat::Verbatim` that prints the text
+    /// unchanged. With the `parser` feature, use [`str::parse`] to build the
+    /// syntax tree of such a pattern.
     fn from(val: &str) -> Self {
-        Pat::Ident(PatIdent {
-            ident: val.into(),
-            is_mut: false,
-        })
+        match Path::from_idents(val) {
+            Some(path) if path.segments.len() == 1 => Pat::Ident(PatIdent {
+                ident: val.into(),
+                is_mut: false,
+            }),
+            _ => Pat::Verbatim(Verbatim::new(val)),
+        }
     }
 }
 
@@ -4035,7 +4520,7 @@ impl From<Vec<Expr>> for Block {
     /// Converts a `Vec<Expr>` into a `Block`.
     fn from(array: Vec<Expr>) -> Self {
         Block {
-            stmts: array.into_iter().map(Stmt::Expr).collect(),
+            stmts: array.into_iter().map(Stmt::from).collect(),
             ..Default::default()
         }
     }
@@ -4045,65 +4530,147 @@ impl<const N: usize> From<[Expr; N]> for Block {
     /// Converts an array of `Expr` into a `Block`.
     fn from(array: [Expr; N]) -> Self {
         Block {
-            stmts: array.into_iter().map(Stmt::Expr).collect(),
+            stmts: array.into_iter().map(Stmt::from).collect(),
             ..Default::default()
         }
     }
 }
 
-/// Creates a new `AsmBuilder` to construct an `asm!` item.
-pub fn asm_item(template: impl Into<LitStr>) -> AsmBuilder {
-    AsmBuilder::new(template)
+/// Creates a new `AsmBuilder` to construct an `asm!` expression.
+pub fn asm_expr(template: impl Into<LitStr>) -> AsmBuilder {
+    AsmBuilder::new(AsmMacro::Asm, template)
 }
 
-/// A builder for constructing an `ItemAsm` AST node.
+/// Creates a new `AsmBuilder` to construct a `naked_asm!` expression.
+pub fn naked_asm_expr(template: impl Into<LitStr>) -> AsmBuilder {
+    AsmBuilder::new(AsmMacro::NakedAsm, template)
+}
+
+/// A builder for constructing an `ExprAsm` AST node.
 pub struct AsmBuilder {
-    template: ThinVec<LitStr>,
-    operands: ThinVec<AsmOperand>,
-    options: Option<AsmOptions>,
+    mac: AsmMacro,
+    args: AsmArgs,
 }
 
 impl AsmBuilder {
-    /// Creates a new `AsmBuilder` with the given template.
+    /// Creates a new `AsmBuilder` with the given macro and template.
     ///
     /// # Parameters
     ///
-    /// - `template`: The initial template string for the `asm!` item.
-    pub fn new(template: impl Into<LitStr>) -> Self {
+    /// - `mac`: The assembly macro to invoke.
+    /// - `template`: The initial template string for the macro.
+    pub fn new(mac: AsmMacro, template: impl Into<LitStr>) -> Self {
         Self {
-            template: thin_vec![template.into()],
-            operands: thin_vec![],
-            options: None,
+            mac,
+            args: asm_args(template),
+        }
+    }
+
+    /// Adds a template string to the assembly macro.
+    ///
+    /// # Parameters
+    ///
+    /// - `template`: The template string to add.
+    pub fn template(mut self, template: impl Into<LitStr>) -> Self {
+        self.args.template.push(template.into());
+        self
+    }
+
+    /// Adds an operand to the assembly macro.
+    ///
+    /// # Parameters
+    ///
+    /// - `operand`: The `AsmOperand` to add.
+    pub fn operand(mut self, operand: impl Into<AsmOperand>) -> Self {
+        self.args.operands.push(operand.into());
+        self
+    }
+
+    /// Sets the options for the assembly macro.
+    ///
+    /// # Parameters
+    ///
+    /// - `options`: The `AsmOptions` to set.
+    pub fn options(mut self, options: AsmOptions) -> Self {
+        self.args.options = Some(options);
+        self
+    }
+
+    /// Builds the `ExprAsm` AST node.
+    ///
+    /// # Returns
+    ///
+    /// An `ExprAsm` instance.
+    pub fn build(self) -> ExprAsm {
+        ExprAsm {
+            mac: self.mac,
+            args: self.args,
         }
     }
 
-    /// Adds a template string to the `asm!` item.
+    /// Builds the `ExprAsm` AST node as an expression.
+    ///
+    /// # Returns
+    ///
+    /// An `Expr::Asm` instance.
+    pub fn build_expr(self) -> Expr {
+        Expr::Asm(self.build())
+    }
+}
+
+impl From<AsmBuilder> for Expr {
+    /// Converts an `AsmBuilder` into an `Expr::Asm` variant.
+    fn from(builder: AsmBuilder) -> Self {
+        builder.build_expr()
+    }
+}
+
+/// Creates a new `GlobalAsmBuilder` to construct a `global_asm!` item.
+pub fn global_asm_item(template: impl Into<LitStr>) -> GlobalAsmBuilder {
+    GlobalAsmBuilder {
+        args: asm_args(template),
+    }
+}
+
+/// Creates a new `GlobalAsmBuilder` to construct a `global_asm!` item.
+#[deprecated(note = "use `global_asm_item` instead")]
+pub fn asm_item(template: impl Into<LitStr>) -> GlobalAsmBuilder {
+    global_asm_item(template)
+}
+
+/// A builder for constructing an `ItemAsm` AST node.
+pub struct GlobalAsmBuilder {
+    args: AsmArgs,
+}
+
+impl GlobalAsmBuilder {
+    /// Adds a template string to the `global_asm!` item.
     ///
     /// # Parameters
     ///
     /// - `template`: The template string to add.
     pub fn template(mut self, template: impl Into<LitStr>) -> Self {
-        self.template.push(template.into());
+        self.args.template.push(template.into());
         self
     }
 
-    /// Adds an operand to the `asm!` item.
+    /// Adds an operand to the `global_asm!` item.
     ///
     /// # Parameters
     ///
     /// - `operand`: The `AsmOperand` to add.
     pub fn operand(mut self, operand: impl Into<AsmOperand>) -> Self {
-        self.operands.push(operand.into());
+        self.args.operands.push(operand.into());
         self
     }
 
-    /// Sets the options for the `asm!` item.
+    /// Sets the options for the `global_asm!` item.
     ///
     /// # Parameters
     ///
     /// - `options`: The `AsmOptions` to set.
     pub fn options(mut self, options: AsmOptions) -> Self {
-        self.options = Some(options);
+        self.args.options = Some(options);
         self
     }
 
@@ -4114,13 +4681,19 @@ impl AsmBuilder {
     /// An `ItemAsm` instance.
     pub fn build(self) -> ItemAsm {
         ItemAsm {
-            template: self.template,
-            operands: self.operands,
-            options: self.options,
+            args: self.args,
+            md: None,
         }
     }
 }
 
+fn asm_args(template: impl Into<LitStr>) -> AsmArgs {
+    AsmArgs {
+        template: thin_vec![template.into()],
+        ..AsmArgs::default()
+    }
+}
+
 /// Creates a new `AsmOperandBuilder` to construct an `AsmOperand`.
 pub fn asm_operand() -> AsmOperandBuilder {
     AsmOperandBuilder
@@ -4146,9 +4719,18 @@ impl AsmOperandBuilder {
     ///
     /// # Parameters
     ///
-    /// - `path`: The `Path` to the symbol.
-    pub fn sym(self, path: Path) -> AsmOperand {
-        AsmOperand::Sym(path)
+    /// - `path`: The `Path` to the symbol, optionally with generic arguments.
+    pub fn sym(self, path: impl Into<Path>) -> AsmOperand {
+        AsmOperand::Sym(path.into())
+    }
+
+    /// Creates a `label` operand.
+    ///
+    /// # Parameters
+    ///
+    /// - `block`: The block to jump to.
+    pub fn label(self, block: impl Into<Block>) -> AsmOperand {
+        AsmOperand::Label(block.into())
     }
 
     /// Creates a `const` operand.
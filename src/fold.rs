@@ -0,0 +1,1392 @@
+//! Owning traversal of the AST.
+//!
+//! The [`Fold`](crate::fold::Fold) trait has one method per node type. Each
+//! method takes a node by value and returns the node to put in its place, so a
+//! fold can rebuild a tree or replace an [`Expr`](crate::ast::Expr) with a
+//! different variant. Each method defaults to the matching `walk_*` function,
+//! which folds the children of the node in source order and returns the node.
+//!
+//! # Example
+//!
+//! ```rust
+//! use rasto::ast::{Expr, ExprParen};
+//! use rasto::builder::*;
+//! use rasto::fold::{self, Fold};
+//! use rasto::pretty;
+//!
+//! struct Unparen;
+//!
+//! impl Fold for Unparen {
+//!     fn fold_expr(&mut self, expr: Expr) -> Expr {
+//!         match fold::walk_expr(self, expr) {
+//!             Expr::Paren(ExprParen { expr }) => *expr,
+//!             expr => expr,
+//!         }
+//!     }
+//! }
+//!
+//! let call = expr().call(expr().path("f"), [expr().paren(expr().path("x"))]);
+//! assert_eq!(pretty(&Unparen.fold_expr(call)), "f(x)");
+//! ```
+
+use crate::ast::items::{Item, TraitItem, TraitItemFn};
+use crate::ast::*;
+
+macro_rules! fold_methods {
+    (
+        $(#[$outer:meta])*
+        pub trait $trait:ident {
+            $($method:ident($ty:ident) => $walk:ident,)*
+        }
+    ) => {
+        $(#[$outer])*
+        pub trait $trait {
+            $(
+                #[doc = concat!("Folds a [`", stringify!($ty), "`] into its replacement.")]
+                #[doc = ""]
+                #[doc = concat!("Defaults to [`", stringify!($walk), "`], which folds its children.")]
+                fn $method(&mut self, node: $ty) -> $ty {
+                    $walk(self, node)
+                }
+            )*
+        }
+    };
+}
+
+fold_methods! {
+    /// A visitor that consumes AST nodes and returns their replacements, with
+    /// one method per node type.
+    ///
+    /// See the [module documentation](self) for an example.
+    pub trait Fold {
+        fold_abi(Abi) => walk_abi,
+        fold_arm(Arm) => walk_arm,
+        fold_asm_args(AsmArgs) => walk_asm_args,
+        fold_asm_operand(AsmOperand) => walk_asm_operand,
+        fold_asm_options(AsmOptions) => walk_asm_options,
+        fold_associated_const(AssociatedConst) => walk_associated_const,
+        fold_associated_type(AssociatedType) => walk_associated_type,
+        fold_attribute(Attribute) => walk_attribute,
+        fold_bare_fn_arg(BareFnArg) => walk_bare_fn_arg,
+        fold_bin_op(BinOp) => walk_bin_op,
+        fold_block(Block) => walk_block,
+        fold_clobber_abi(ClobberAbi) => walk_clobber_abi,
+        fold_closure_param(ClosureParam) => walk_closure_param,
+        fold_comment(Comment) => walk_comment,
+        fold_const_param(ConstParam) => walk_const_param,
+        fold_custom(Custom) => walk_custom,
+        fold_expr(Expr) => walk_expr,
+        fold_expr_array(ExprArray) => walk_expr_array,
+        fold_expr_asm(ExprAsm) => walk_expr_asm,
+        fold_expr_assign(ExprAssign) => walk_expr_assign,
+        fold_expr_async(ExprAsync) => walk_expr_async,
+        fold_expr_await(ExprAwait) => walk_expr_await,
+        fold_expr_become(ExprBecome) => walk_expr_become,
+        fold_expr_binary(ExprBinary) => walk_expr_binary,
+        fold_expr_block(ExprBlock) => walk_expr_block,
+        fold_expr_break(ExprBreak) => walk_expr_break,
+        fold_expr_call(ExprCall) => walk_expr_call,
+        fold_expr_cast(ExprCast) => walk_expr_cast,
+        fold_expr_closure(ExprClosure) => walk_expr_closure,
+        fold_expr_const(ExprConst) => walk_expr_const,
+        fold_expr_continue(ExprContinue) => walk_expr_continue,
+        fold_expr_field(ExprField) => walk_expr_field,
+        fold_expr_for(ExprFor) => walk_expr_for,
+        fold_expr_gen(ExprGen) => walk_expr_gen,
+        fold_expr_if(ExprIf) => walk_expr_if,
+        fold_expr_index(ExprIndex) => walk_expr_index,
+        fold_expr_infer(ExprInfer) => walk_expr_infer,
+        fold_expr_loop(ExprLoop) => walk_expr_loop,
+        fold_expr_macro_call(ExprMacroCall) => walk_expr_macro_call,
+        fold_expr_match(ExprMatch) => walk_expr_match,
+        fold_expr_method_call(ExprMethodCall) => walk_expr_method_call,
+        fold_expr_paren(ExprParen) => walk_expr_paren,
+        fold_expr_path(ExprPath) => walk_expr_path,
+        fold_expr_range(ExprRange) => walk_expr_range,
+        fold_expr_raw_ref(ExprRawRef) => walk_expr_raw_ref,
+        fold_expr_ref(ExprRef) => walk_expr_ref,
+        fold_expr_return(ExprReturn) => walk_expr_return,
+        fold_expr_struct(ExprStruct) => walk_expr_struct,
+        fold_expr_try(ExprTry) => walk_expr_try,
+        fold_expr_tuple(ExprTuple) => walk_expr_tuple,
+        fold_expr_unary(ExprUnary) => walk_expr_unary,
+        fold_expr_while(ExprWhile) => walk_expr_while,
+        fold_expr_yield(ExprYield) => walk_expr_yield,
+        fold_external_item(ExternalItem) => walk_external_item,
+        fold_external_static(ExternalStatic) => walk_external_static,
+        fold_field(Field) => walk_field,
+        fold_field_pat(FieldPat) => walk_field_pat,
+        fold_field_value(FieldValue) => walk_field_value,
+        fold_file(File) => walk_file,
+        fold_generic_arg(GenericArg) => walk_generic_arg,
+        fold_generic_args(GenericArgs) => walk_generic_args,
+        fold_generic_param(GenericParam) => walk_generic_param,
+        fold_generic_params(GenericParams) => walk_generic_params,
+        fold_group(Group) => walk_group,
+        fold_ident(Ident) => walk_ident,
+        fold_impl_item(ImplItem) => walk_impl_item,
+        fold_item(Item) => walk_item,
+        fold_item_asm(ItemAsm) => walk_item_asm,
+        fold_item_const(ItemConst) => walk_item_const,
+        fold_item_enum(ItemEnum) => walk_item_enum,
+        fold_item_extern_block(ItemExternBlock) => walk_item_extern_block,
+        fold_item_extern_crate(ItemExternCrate) => walk_item_extern_crate,
+        fold_item_extern_type(ItemExternType) => walk_item_extern_type,
+        fold_item_fn(ItemFn) => walk_item_fn,
+        fold_item_foreign_mod(ItemForeignMod) => walk_item_foreign_mod,
+        fold_item_impl(ItemImpl) => walk_item_impl,
+        fold_item_macro(ItemMacro) => walk_item_macro,
+        fold_item_mod(ItemMod) => walk_item_mod,
+        fold_item_static(ItemStatic) => walk_item_static,
+        fold_item_struct(ItemStruct) => walk_item_struct,
+        fold_item_trait(ItemTrait) => walk_item_trait,
+        fold_item_trait_alias(ItemTraitAlias) => walk_item_trait_alias,
+        fold_item_type_alias(ItemTypeAlias) => walk_item_type_alias,
+        fold_item_union(ItemUnion) => walk_item_union,
+        fold_item_use(ItemUse) => walk_item_use,
+        fold_lifetime(Lifetime) => walk_lifetime,
+        fold_lifetime_param(LifetimeParam) => walk_lifetime_param,
+        fold_lifetime_predicate(LifetimePredicate) => walk_lifetime_predicate,
+        fold_lit(Lit) => walk_lit,
+        fold_lit_str(LitStr) => walk_lit_str,
+        fold_local(Local) => walk_local,
+        fold_md(Md) => walk_md,
+        fold_meta(Meta) => walk_meta,
+        fold_meta_list(MetaList) => walk_meta_list,
+        fold_meta_name_value(MetaNameValue) => walk_meta_name_value,
+        fold_pat(Pat) => walk_pat,
+        fold_pat_const(PatConst) => walk_pat_const,
+        fold_pat_ident(PatIdent) => walk_pat_ident,
+        fold_pat_lit(PatLit) => walk_pat_lit,
+        fold_pat_macro(PatMacro) => walk_pat_macro,
+        fold_pat_or(PatOr) => walk_pat_or,
+        fold_pat_paren(PatParen) => walk_pat_paren,
+        fold_pat_path(PatPath) => walk_pat_path,
+        fold_pat_range(PatRange) => walk_pat_range,
+        fold_pat_reference(PatReference) => walk_pat_reference,
+        fold_pat_rest(PatRest) => walk_pat_rest,
+        fold_pat_slice(PatSlice) => walk_pat_slice,
+        fold_pat_struct(PatStruct) => walk_pat_struct,
+        fold_pat_tuple(PatTuple) => walk_pat_tuple,
+        fold_pat_tuple_struct(PatTupleStruct) => walk_pat_tuple_struct,
+        fold_pat_type(PatType) => walk_pat_type,
+        fold_pat_wild(PatWild) => walk_pat_wild,
+        fold_path(Path) => walk_path,
+        fold_path_segment(PathSegment) => walk_path_segment,
+        fold_punct(Punct) => walk_punct,
+        fold_reg_operand(RegOperand) => walk_reg_operand,
+        fold_signature(Signature) => walk_signature,
+        fold_stmt(Stmt) => walk_stmt,
+        fold_stmt_expr(StmtExpr) => walk_stmt_expr,
+        fold_token_stream(TokenStream) => walk_token_stream,
+        fold_token_tree(TokenTree) => walk_token_tree,
+        fold_trait_item(TraitItem) => walk_trait_item,
+        fold_trait_item_fn(TraitItemFn) => walk_trait_item_fn,
+        fold_type(Type) => walk_type,
+        fold_type_array(TypeArray) => walk_type_array,
+        fold_type_bare_fn(TypeBareFn) => walk_type_bare_fn,
+        fold_type_param(TypeParam) => walk_type_param,
+        fold_type_path(TypePath) => walk_type_path,
+        fold_type_predicate(TypePredicate) => walk_type_predicate,
+        fold_type_ptr(TypePtr) => walk_type_ptr,
+        fold_type_reference(TypeReference) => walk_type_reference,
+        fold_un_op(UnOp) => walk_un_op,
+        fold_variant(Variant) => walk_variant,
+        fold_verbatim(Verbatim) => walk_verbatim,
+        fold_visibility(Visibility) => walk_visibility,
+        fold_where_clause(WhereClause) => walk_where_clause,
+        fold_where_predicate(WherePredicate) => walk_where_predicate,
+    }
+}
+
+fn fold_md_opt<F: Fold + ?Sized>(f: &mut F, md: Option<Box<Md>>) -> Option<Box<Md>> {
+    md.map(|md| fold_boxed(md, |md| f.fold_md(md)))
+}
+
+fn fold_each<C>(nodes: C, fold: impl FnMut(C::Item) -> C::Item) -> C
+where
+    C: IntoIterator + FromIterator<C::Item>,
+{
+    nodes.into_iter().map(fold).collect()
+}
+
+fn fold_boxed<T>(mut node: Box<T>, fold: impl FnOnce(T) -> T) -> Box<T> {
+    *node = fold(*node);
+    node
+}
+
+fn fold_deep<T: Nested>(node: DeepBox<T>, fold: impl FnOnce(T) -> T) -> DeepBox<T> {
+    DeepBox::new(fold(node.into_inner()))
+}
+
+// Items.
+
+/// Folds the items of a [`File`].
+pub fn walk_file<F: Fold + ?Sized>(f: &mut F, mut node: File) -> File {
+    node.md = fold_md_opt(f, node.md);
+    node.items = fold_each(node.items, |item| f.fold_item(item));
+    node
+}
+
+/// Folds the node wrapped by an [`Item`].
+pub fn walk_item<F: Fold + ?Sized>(f: &mut F, node: Item) -> Item {
+    match node {
+        Item::Asm(item) => Item::Asm(f.fold_item_asm(item)),
+        Item::Const(item) => Item::Const(f.fold_item_const(item)),
+        Item::Fn(item) => Item::Fn(f.fold_item_fn(item)),
+        Item::Struct(item) => Item::Struct(f.fold_item_struct(item)),
+        Item::Static(item) => Item::Static(f.fold_item_static(item)),
+        Item::Enum(item) => Item::Enum(f.fold_item_enum(item)),
+        Item::Impl(item) => Item::Impl(f.fold_item_impl(item)),
+        Item::Trait(item) => Item::Trait(f.fold_item_trait(item)),
+        Item::ExternCrate(item) => Item::ExternCrate(f.fold_item_extern_crate(item)),
+        Item::ExternType(item) => Item::ExternType(f.fold_item_extern_type(item)),
+        Item::ForeignMod(item) => Item::ForeignMod(f.fold_item_foreign_mod(item)),
+        Item::ExternBlock(item) => Item::ExternBlock(f.fold_item_extern_block(item)),
+        Item::Macro(item) => Item::Macro(f.fold_item_macro(item)),
+        Item::Mod(item) => Item::Mod(f.fold_item_mod(item)),
+        Item::TraitAlias(item) => Item::TraitAlias(f.fold_item_trait_alias(item)),
+        Item::TypeAlias(item) => Item::TypeAlias(f.fold_item_type_alias(item)),
+        Item::Union(item) => Item::Union(f.fold_item_union(item)),
+        Item::Use(item) => Item::Use(f.fold_item_use(item)),
+        Item::Verbatim(item) => Item::Verbatim(f.fold_verbatim(item)),
+        Item::Custom(item) => Item::Custom(f.fold_custom(item)),
+    }
+}
+
+/// Folds the metadata and arguments of an [`ItemAsm`].
+pub fn walk_item_asm<F: Fold + ?Sized>(f: &mut F, mut node: ItemAsm) -> ItemAsm {
+    node.md = fold_md_opt(f, node.md);
+    node.args = f.fold_asm_args(node.args);
+    node
+}
+
+/// Folds the metadata, name, type and value of an [`ItemConst`].
+pub fn walk_item_const<F: Fold + ?Sized>(f: &mut F, mut node: ItemConst) -> ItemConst {
+    node.md = fold_md_opt(f, node.md);
+    node.vis = f.fold_visibility(node.vis);
+    node.ident = f.fold_ident(node.ident);
+    node.ty = f.fold_type(node.ty);
+    node.expr = fold_boxed(node.expr, |expr| f.fold_expr(expr));
+    node
+}
+
+/// Folds the metadata, name, generics and variants of an [`ItemEnum`].
+pub fn walk_item_enum<F: Fold + ?Sized>(f: &mut F, mut node: ItemEnum) -> ItemEnum {
+    node.md = fold_md_opt(f, node.md);
+    node.vis = f.fold_visibility(node.vis);
+    node.ident = f.fold_ident(node.ident);
+    node.generics = f.fold_generic_params(node.generics);
+    node.variants = fold_each(node.variants, |variant| f.fold_variant(variant));
+    node
+}
+
+/// Folds the metadata and name of a [`Variant`].
+pub fn walk_variant<F: Fold + ?Sized>(f: &mut F, mut node: Variant) -> Variant {
+    node.md = fold_md_opt(f, node.md);
+    node.ident = f.fold_ident(node.ident);
+    node
+}
+
+/// Folds the metadata, ABI and items of an [`ItemExternBlock`].
+pub fn walk_item_extern_block<F: Fold + ?Sized>(
+    f: &mut F,
+    mut node: ItemExternBlock,
+) -> ItemExternBlock {
+    node.md = fold_md_opt(f, node.md);
+    node.vis = f.fold_visibility(node.vis);
+    node.abi = f.fold_abi(node.abi);
+    node.items = fold_each(node.items, |item| f.fold_external_item(item));
+    node
+}
+
+/// Folds the node wrapped by an [`ExternalItem`].
+pub fn walk_external_item<F: Fold + ?Sized>(f: &mut F, node: ExternalItem) -> ExternalItem {
+    match node {
+        ExternalItem::Static(item) => ExternalItem::Static(f.fold_external_static(item)),
+        ExternalItem::Fn(item) => ExternalItem::Fn(f.fold_item_fn(item)),
+        ExternalItem::Macro(item) => ExternalItem::Macro(f.fold_item_macro(item)),
+        ExternalItem::Type(item) => ExternalItem::Type(f.fold_item_extern_type(item)),
+    }
+}
+
+/// Folds the metadata, name and type of an [`ExternalStatic`].
+pub fn walk_external_static<F: Fold + ?Sized>(
+    f: &mut F,
+    mut node: ExternalStatic,
+) -> ExternalStatic {
+    node.md = fold_md_opt(f, node.md);
+    node.vis = f.fold_visibility(node.vis);
+    node.ident = f.fold_ident(node.ident);
+    node.ty = f.fold_type(node.ty);
+    node
+}
+
+/// Folds the metadata and crate name of an [`ItemExternCrate`].
+pub fn walk_item_extern_crate<F: Fold + ?Sized>(
+    f: &mut F,
+    mut node: ItemExternCrate,
+) -> ItemExternCrate {
+    node.md = fold_md_opt(f, node.md);
+    node.ident = f.fold_ident(node.ident);
+    node
+}
+
+/// Folds the metadata and name of an [`ItemExternType`].
+pub fn walk_item_extern_type<F: Fold + ?Sized>(
+    f: &mut F,
+    mut node: ItemExternType,
+) -> ItemExternType {
+    node.md = fold_md_opt(f, node.md);
+    node.vis = f.fold_visibility(node.vis);
+    node.ident = f.fold_ident(node.ident);
+    node
+}
+
+/// Folds the metadata, signature and body of an [`ItemFn`].
+pub fn walk_item_fn<F: Fold + ?Sized>(f: &mut F, mut node: ItemFn) -> ItemFn {
+    node.md = fold_md_opt(f, node.md);
+    node.vis = f.fold_visibility(node.vis);
+    node.sig = f.fold_signature(node.sig);
+    node.block = f.fold_block(node.block);
+    node
+}
+
+/// Folds the ABI, name, generics, parameters, return type and where clause
+/// of a [`Signature`].
+pub fn walk_signature<F: Fold + ?Sized>(f: &mut F, mut node: Signature) -> Signature {
+    node.abi = node.abi.map(|abi| f.fold_abi(abi));
+    node.ident = f.fold_ident(node.ident);
+    node.generics = f.fold_generic_params(node.generics);
+    node.inputs = fold_each(node.inputs, |input| f.fold_pat(input));
+    node.output = node.output.map(|output| f.fold_type(output));
+    node.where_clause = node
+        .where_clause
+        .map(|where_clause| f.fold_where_clause(where_clause));
+    node
+}
+
+/// Folds the metadata and items of an [`ItemForeignMod`].
+pub fn walk_item_foreign_mod<F: Fold + ?Sized>(
+    f: &mut F,
+    mut node: ItemForeignMod,
+) -> ItemForeignMod {
+    node.md = fold_md_opt(f, node.md);
+    node.items = fold_each(node.items, |item| f.fold_item(item));
+    node
+}
+
+/// Folds the metadata, generics, trait, self type and items of an
+/// [`ItemImpl`].
+pub fn walk_item_impl<F: Fold + ?Sized>(f: &mut F, mut node: ItemImpl) -> ItemImpl {
+    node.md = fold_md_opt(f, node.md);
+    node.generics = f.fold_generic_params(node.generics);
+    node.trait_ = node.trait_.map(|trait_| f.fold_type(trait_));
+    node.ty = f.fold_type(node.ty);
+    node.items = fold_each(node.items, |item| f.fold_impl_item(item));
+    node
+}
+
+/// Folds the node wrapped by an [`ImplItem`].
+pub fn walk_impl_item<F: Fold + ?Sized>(f: &mut F, node: ImplItem) -> ImplItem {
+    match node {
+        ImplItem::Fn(item) => ImplItem::Fn(f.fold_item_fn(item)),
+        ImplItem::Type(item) => ImplItem::Type(f.fold_associated_type(item)),
+        ImplItem::Const(item) => ImplItem::Const(f.fold_associated_const(item)),
+    }
+}
+
+/// Folds the metadata, name, type and value of an [`AssociatedConst`].
+pub fn walk_associated_const<F: Fold + ?Sized>(
+    f: &mut F,
+    mut node: AssociatedConst,
+) -> AssociatedConst {
+    node.md = fold_md_opt(f, node.md);
+    node.ident = f.fold_ident(node.ident);
+    node.ty = f.fold_type(node.ty);
+    node.expr = node
+        .expr
+        .map(|expr| fold_boxed(expr, |expr| f.fold_expr(expr)));
+    node
+}
+
+/// Folds the metadata, name, generics, bounds and default of an
+/// [`AssociatedType`].
+pub fn walk_associated_type<F: Fold + ?Sized>(
+    f: &mut F,
+    mut node: AssociatedType,
+) -> AssociatedType {
+    node.md = fold_md_opt(f, node.md);
+    node.ident = f.fold_ident(node.ident);
+    node.generics = f.fold_generic_params(node.generics);
+    node.bounds = fold_each(node.bounds, |bound| f.fold_type(bound));
+    node.default = node.default.map(|default| f.fold_type(default));
+    node
+}
+
+/// Folds the metadata and macro call of an [`ItemMacro`].
+pub fn walk_item_macro<F: Fold + ?Sized>(f: &mut F, mut node: ItemMacro) -> ItemMacro {
+    node.md = fold_md_opt(f, node.md);
+    node.expr = fold_boxed(node.expr, |expr| f.fold_expr(expr));
+    node
+}
+
+/// Folds the metadata, name and items of an [`ItemMod`].
+pub fn walk_item_mod<F: Fold + ?Sized>(f: &mut F, mut node: ItemMod) -> ItemMod {
+    node.md = fold_md_opt(f, node.md);
+    node.vis = f.fold_visibility(node.vis);
+    node.ident = f.fold_ident(node.ident);
+    node.content = node
+        .content
+        .map(|items| fold_each(items, |item| f.fold_item(item)));
+    node
+}
+
+/// Folds the metadata, name, type and value of an [`ItemStatic`].
+pub fn walk_item_static<F: Fold + ?Sized>(f: &mut F, mut node: ItemStatic) -> ItemStatic {
+    node.md = fold_md_opt(f, node.md);
+    node.vis = f.fold_visibility(node.vis);
+    node.ident = f.fold_ident(node.ident);
+    node.ty = f.fold_type(node.ty);
+    node.expr = fold_boxed(node.expr, |expr| f.fold_expr(expr));
+    node
+}
+
+/// Folds the metadata, name, generics and fields of an [`ItemStruct`].
+pub fn walk_item_struct<F: Fold + ?Sized>(f: &mut F, mut node: ItemStruct) -> ItemStruct {
+    node.md = fold_md_opt(f, node.md);
+    node.vis = f.fold_visibility(node.vis);
+    node.ident = f.fold_ident(node.ident);
+    node.generics = f.fold_generic_params(node.generics);
+    node.fields = fold_each(node.fields, |field| f.fold_field(field));
+    node
+}
+
+/// Folds the metadata, name and type of a [`Field`].
+pub fn walk_field<F: Fold + ?Sized>(f: &mut F, mut node: Field) -> Field {
+    node.md = fold_md_opt(f, node.md);
+    node.ident = f.fold_ident(node.ident);
+    node.ty = f.fold_type(node.ty);
+    node
+}
+
+/// Folds the metadata, name, generics, associated types and items of an
+/// [`ItemTrait`].
+pub fn walk_item_trait<F: Fold + ?Sized>(f: &mut F, mut node: ItemTrait) -> ItemTrait {
+    node.md = fold_md_opt(f, node.md);
+    node.vis = f.fold_visibility(node.vis);
+    node.ident = f.fold_ident(node.ident);
+    node.generics = f.fold_generic_params(node.generics);
+    node.associated_types = fold_each(node.associated_types, |associated_type| {
+        f.fold_associated_type(associated_type)
+    });
+    node.items = fold_each(node.items, |item| f.fold_trait_item(item));
+    node
+}
+
+/// Folds the node wrapped by a [`TraitItem`].
+pub fn walk_trait_item<F: Fold + ?Sized>(f: &mut F, node: TraitItem) -> TraitItem {
+    match node {
+        TraitItem::Fn(item) => TraitItem::Fn(f.fold_trait_item_fn(item)),
+        TraitItem::Const(item) => TraitItem::Const(f.fold_associated_const(item)),
+    }
+}
+
+/// Folds the metadata, signature and default body of a [`TraitItemFn`].
+pub fn walk_trait_item_fn<F: Fold + ?Sized>(f: &mut F, mut node: TraitItemFn) -> TraitItemFn {
+    node.md = fold_md_opt(f, node.md);
+    node.sig = f.fold_signature(node.sig);
+    node.block = node.block.map(|block| f.fold_block(block));
+    node
+}
+
+/// Folds the metadata and name of an [`ItemTraitAlias`].
+///
+/// The bounds are plain strings and are not folded.
+pub fn walk_item_trait_alias<F: Fold + ?Sized>(
+    f: &mut F,
+    mut node: ItemTraitAlias,
+) -> ItemTraitAlias {
+    node.md = fold_md_opt(f, node.md);
+    node.ident = f.fold_ident(node.ident);
+    node
+}
+
+/// Folds the metadata, name, generics and aliased type of an
+/// [`ItemTypeAlias`].
+pub fn walk_item_type_alias<F: Fold + ?Sized>(f: &mut F, mut node: ItemTypeAlias) -> ItemTypeAlias {
+    node.md = fold_md_opt(f, node.md);
+    node.vis = f.fold_visibility(node.vis);
+    node.ident = f.fold_ident(node.ident);
+    node.generics = f.fold_generic_params(node.generics);
+    node.ty = f.fold_type(node.ty);
+    node
+}
+
+/// Folds the metadata, name, generics and fields of an [`ItemUnion`].
+pub fn walk_item_union<F: Fold + ?Sized>(f: &mut F, mut node: ItemUnion) -> ItemUnion {
+    node.md = fold_md_opt(f, node.md);
+    node.vis = f.fold_visibility(node.vis);
+    node.ident = f.fold_ident(node.ident);
+    node.generics = f.fold_generic_params(node.generics);
+    node.fields = fold_each(node.fields, |field| f.fold_field(field));
+    node
+}
+
+/// Folds the metadata and visibility of an [`ItemUse`].
+///
+/// The use tree is a plain string and is not folded.
+pub fn walk_item_use<F: Fold + ?Sized>(f: &mut F, mut node: ItemUse) -> ItemUse {
+    node.md = fold_md_opt(f, node.md);
+    node.vis = f.fold_visibility(node.vis);
+    node
+}
+
+/// Returns a [`Visibility`] unchanged, as it has no children.
+pub fn walk_visibility<F: Fold + ?Sized>(_f: &mut F, node: Visibility) -> Visibility {
+    node
+}
+
+/// Folds the metadata of a [`Verbatim`].
+pub fn walk_verbatim<F: Fold + ?Sized>(f: &mut F, mut node: Verbatim) -> Verbatim {
+    node.md = fold_md_opt(f, node.md);
+    node
+}
+
+/// Folds the metadata of a [`Custom`] node, but not the node itself, whose
+/// type is unknown.
+pub fn walk_custom<F: Fold + ?Sized>(f: &mut F, mut node: Custom) -> Custom {
+    node.md = fold_md_opt(f, node.md);
+    node
+}
+
+/// Returns an [`Abi`] unchanged, as it has no children.
+pub fn walk_abi<F: Fold + ?Sized>(_f: &mut F, node: Abi) -> Abi {
+    node
+}
+
+// Inline assembly.
+
+/// Folds the arguments of an [`ExprAsm`].
+pub fn walk_expr_asm<F: Fold + ?Sized>(f: &mut F, mut node: ExprAsm) -> ExprAsm {
+    node.args = f.fold_asm_args(node.args);
+    node
+}
+
+/// Folds the template, operands and options of an [`AsmArgs`].
+pub fn walk_asm_args<F: Fold + ?Sized>(f: &mut F, mut node: AsmArgs) -> AsmArgs {
+    node.template = fold_each(node.template, |template| f.fold_lit_str(template));
+    node.operands = fold_each(node.operands, |operand| f.fold_asm_operand(operand));
+    node.options = node.options.map(|options| f.fold_asm_options(options));
+    node
+}
+
+/// Folds the node wrapped by an [`AsmOperand`].
+pub fn walk_asm_operand<F: Fold + ?Sized>(f: &mut F, node: AsmOperand) -> AsmOperand {
+    match node {
+        AsmOperand::Reg(reg) => AsmOperand::Reg(f.fold_reg_operand(reg)),
+        AsmOperand::Sym(path) => AsmOperand::Sym(f.fold_path(path)),
+        AsmOperand::Const(expr) => AsmOperand::Const(f.fold_expr(expr)),
+        AsmOperand::ClobberAbi(clobber_abi) => {
+            AsmOperand::ClobberAbi(f.fold_clobber_abi(clobber_abi))
+        }
+        AsmOperand::Label(block) => AsmOperand::Label(f.fold_block(block)),
+    }
+}
+
+/// Folds the explicit register and expressions of a [`RegOperand`].
+pub fn walk_reg_operand<F: Fold + ?Sized>(f: &mut F, mut node: RegOperand) -> RegOperand {
+    if let RegSpec::Explicit(reg) = node.reg {
+        node.reg = RegSpec::Explicit(f.fold_lit_str(reg));
+    }
+    node.expr = f.fold_expr(node.expr);
+    node.out_expr = node.out_expr.map(|out_expr| f.fold_expr(out_expr));
+    node
+}
+
+/// Folds the ABI strings of a [`ClobberAbi`].
+pub fn walk_clobber_abi<F: Fold + ?Sized>(f: &mut F, mut node: ClobberAbi) -> ClobberAbi {
+    node.abis = fold_each(node.abis, |abi| f.fold_lit_str(abi));
+    node
+}
+
+/// Returns [`AsmOptions`] unchanged, as they only hold flags.
+pub fn walk_asm_options<F: Fold + ?Sized>(_f: &mut F, node: AsmOptions) -> AsmOptions {
+    node
+}
+
+// Statements.
+
+/// Folds the metadata and statements of a [`Block`].
+pub fn walk_block<F: Fold + ?Sized>(f: &mut F, mut node: Block) -> Block {
+    node.md = fold_md_opt(f, node.md);
+    node.stmts = fold_each(node.stmts, |stmt| f.fold_stmt(stmt));
+    node
+}
+
+/// Folds the node wrapped by a [`Stmt`].
+pub fn walk_stmt<F: Fold + ?Sized>(f: &mut F, node: Stmt) -> Stmt {
+    match node {
+        Stmt::Local(local) => Stmt::Local(f.fold_local(local)),
+        Stmt::Item(item) => Stmt::Item(f.fold_item(item)),
+        Stmt::Expr(stmt) => Stmt::Expr(f.fold_stmt_expr(stmt)),
+        Stmt::Verbatim(stmt) => Stmt::Verbatim(f.fold_verbatim(stmt)),
+    }
+}
+
+/// Folds the metadata and expression of a [`StmtExpr`].
+pub fn walk_stmt_expr<F: Fold + ?Sized>(f: &mut F, mut node: StmtExpr) -> StmtExpr {
+    node.md = fold_md_opt(f, node.md);
+    node.expr = f.fold_expr(node.expr);
+    node
+}
+
+/// Folds the metadata, pattern, type, initializer and `else` block of a
+/// [`Local`].
+pub fn walk_local<F: Fold + ?Sized>(f: &mut F, mut node: Local) -> Local {
+    node.md = fold_md_opt(f, node.md);
+    node.pat = f.fold_pat(node.pat);
+    node.ty = node.ty.map(|ty| f.fold_type(ty));
+    node.expr = node.expr.map(|expr| f.fold_expr(expr));
+    node.else_block = node
+        .else_block
+        .map(|else_block| fold_boxed(else_block, |else_block| f.fold_block(else_block)));
+    node
+}
+
+// Expressions.
+
+/// Folds the node wrapped by an [`Expr`].
+pub fn walk_expr<F: Fold + ?Sized>(f: &mut F, node: Expr) -> Expr {
+    crate::stack::ensure_sufficient_stack(|| match node {
+        Expr::Array(expr) => Expr::Array(f.fold_expr_array(expr)),
+        Expr::Asm(expr) => Expr::Asm(f.fold_expr_asm(expr)),
+        Expr::Assign(expr) => Expr::Assign(f.fold_expr_assign(expr)),
+        Expr::Async(expr) => Expr::Async(f.fold_expr_async(expr)),
+        Expr::Await(expr) => Expr::Await(f.fold_expr_await(expr)),
+        Expr::Become(expr) => Expr::Become(f.fold_expr_become(expr)),
+        Expr::Binary(expr) => Expr::Binary(f.fold_expr_binary(expr)),
+        Expr::Block(expr) => Expr::Block(f.fold_expr_block(expr)),
+        Expr::Break(expr) => Expr::Break(f.fold_expr_break(expr)),
+        Expr::Call(expr) => Expr::Call(f.fold_expr_call(expr)),
+        Expr::Cast(expr) => Expr::Cast(f.fold_expr_cast(expr)),
+        Expr::Closure(expr) => Expr::Closure(f.fold_expr_closure(expr)),
+        Expr::Const(expr) => Expr::Const(f.fold_expr_const(expr)),
+        Expr::Continue(expr) => Expr::Continue(f.fold_expr_continue(expr)),
+        Expr::Field(expr) => Expr::Field(f.fold_expr_field(expr)),
+        Expr::For(expr) => Expr::For(f.fold_expr_for(expr)),
+        Expr::Gen(expr) => Expr::Gen(f.fold_expr_gen(expr)),
+        Expr::If(expr) => Expr::If(f.fold_expr_if(expr)),
+        Expr::Index(expr) => Expr::Index(f.fold_expr_index(expr)),
+        Expr::Infer(expr) => Expr::Infer(f.fold_expr_infer(expr)),
+        Expr::Lit(lit) => Expr::Lit(f.fold_lit(lit)),
+        Expr::Loop(expr) => Expr::Loop(f.fold_expr_loop(expr)),
+        Expr::MacroCall(expr) => Expr::MacroCall(f.fold_expr_macro_call(expr)),
+        Expr::Match(expr) => Expr::Match(f.fold_expr_match(expr)),
+        Expr::MethodCall(expr) => Expr::MethodCall(f.fold_expr_method_call(expr)),
+        Expr::Paren(expr) => Expr::Paren(f.fold_expr_paren(expr)),
+        Expr::Path(expr) => Expr::Path(f.fold_expr_path(expr)),
+        Expr::Range(expr) => Expr::Range(f.fold_expr_range(expr)),
+        Expr::Reference(expr) => Expr::Reference(f.fold_expr_ref(expr)),
+        Expr::RawRef(expr) => Expr::RawRef(f.fold_expr_raw_ref(expr)),
+        Expr::Return(expr) => Expr::Return(f.fold_expr_return(expr)),
+        Expr::Struct(expr) => Expr::Struct(f.fold_expr_struct(expr)),
+        Expr::Try(expr) => Expr::Try(f.fold_expr_try(expr)),
+        Expr::Tuple(expr) => Expr::Tuple(f.fold_expr_tuple(expr)),
+        Expr::Unary(expr) => Expr::Unary(f.fold_expr_unary(expr)),
+        Expr::Verbatim(expr) => Expr::Verbatim(f.fold_verbatim(expr)),
+        Expr::Custom(expr) => Expr::Custom(f.fold_custom(expr)),
+        Expr::While(expr) => Expr::While(f.fold_expr_while(expr)),
+        Expr::Yield(expr) => Expr::Yield(f.fold_expr_yield(expr)),
+    })
+}
+
+/// Folds the elements of an [`ExprArray`].
+pub fn walk_expr_array<F: Fold + ?Sized>(f: &mut F, mut node: ExprArray) -> ExprArray {
+    node.elems = fold_each(node.elems, |elem| f.fold_expr(elem));
+    node
+}
+
+/// Folds both sides of an [`ExprAssign`].
+pub fn walk_expr_assign<F: Fold + ?Sized>(f: &mut F, mut node: ExprAssign) -> ExprAssign {
+    node.left = fold_boxed(node.left, |left| f.fold_expr(left));
+    node.right = fold_boxed(node.right, |right| f.fold_expr(right));
+    node
+}
+
+/// Folds the block of an [`ExprAsync`].
+pub fn walk_expr_async<F: Fold + ?Sized>(f: &mut F, mut node: ExprAsync) -> ExprAsync {
+    node.block = f.fold_block(node.block);
+    node
+}
+
+/// Folds the awaited expression of an [`ExprAwait`].
+pub fn walk_expr_await<F: Fold + ?Sized>(f: &mut F, mut node: ExprAwait) -> ExprAwait {
+    node.expr = fold_boxed(node.expr, |expr| f.fold_expr(expr));
+    node
+}
+
+/// Folds the tail call of an [`ExprBecome`].
+pub fn walk_expr_become<F: Fold + ?Sized>(f: &mut F, mut node: ExprBecome) -> ExprBecome {
+    node.expr = fold_boxed(node.expr, |expr| f.fold_expr(expr));
+    node
+}
+
+/// Folds the operands and operator of an [`ExprBinary`].
+pub fn walk_expr_binary<F: Fold + ?Sized>(f: &mut F, mut node: ExprBinary) -> ExprBinary {
+    node.left = fold_deep(node.left, |left| f.fold_expr(left));
+    node.op = f.fold_bin_op(node.op);
+    node.right = fold_deep(node.right, |right| f.fold_expr(right));
+    node
+}
+
+/// Folds the block of an [`ExprBlock`].
+pub fn walk_expr_block<F: Fold + ?Sized>(f: &mut F, mut node: ExprBlock) -> ExprBlock {
+    node.block = fold_deep(node.block, |block| f.fold_block(block));
+    node
+}
+
+/// Returns an [`ExprBreak`] unchanged, as it has no children.
+pub fn walk_expr_break<F: Fold + ?Sized>(_f: &mut F, node: ExprBreak) -> ExprBreak {
+    node
+}
+
+/// Folds the callee and arguments of an [`ExprCall`].
+pub fn walk_expr_call<F: Fold + ?Sized>(f: &mut F, mut node: ExprCall) -> ExprCall {
+    node.func = fold_boxed(node.func, |func| f.fold_expr(func));
+    node.args = fold_each(node.args, |arg| f.fold_expr(arg));
+    node
+}
+
+/// Folds the expression and target type of an [`ExprCast`].
+pub fn walk_expr_cast<F: Fold + ?Sized>(f: &mut F, mut node: ExprCast) -> ExprCast {
+    node.expr = fold_boxed(node.expr, |expr| f.fold_expr(expr));
+    node.ty = f.fold_type(node.ty);
+    node
+}
+
+/// Folds the parameters and body of an [`ExprClosure`].
+pub fn walk_expr_closure<F: Fold + ?Sized>(f: &mut F, mut node: ExprClosure) -> ExprClosure {
+    node.inputs = fold_each(node.inputs, |input| f.fold_closure_param(input));
+    node.body = fold_boxed(node.body, |body| f.fold_expr(body));
+    node
+}
+
+/// Folds the metadata and pattern of a [`ClosureParam`].
+pub fn walk_closure_param<F: Fold + ?Sized>(f: &mut F, mut node: ClosureParam) -> ClosureParam {
+    node.md = fold_md_opt(f, node.md);
+    node.pat = f.fold_pat(node.pat);
+    node
+}
+
+/// Folds the block of an [`ExprConst`].
+pub fn walk_expr_const<F: Fold + ?Sized>(f: &mut F, mut node: ExprConst) -> ExprConst {
+    node.block = f.fold_block(node.block);
+    node
+}
+
+/// Returns an [`ExprContinue`] unchanged, as it has no children.
+pub fn walk_expr_continue<F: Fold + ?Sized>(_f: &mut F, node: ExprContinue) -> ExprContinue {
+    node
+}
+
+/// Folds the base expression and field name of an [`ExprField`].
+pub fn walk_expr_field<F: Fold + ?Sized>(f: &mut F, mut node: ExprField) -> ExprField {
+    node.expr = fold_boxed(node.expr, |expr| f.fold_expr(expr));
+    node.member = f.fold_ident(node.member);
+    node
+}
+
+/// Folds the pattern, iterator and body of an [`ExprFor`].
+pub fn walk_expr_for<F: Fold + ?Sized>(f: &mut F, mut node: ExprFor) -> ExprFor {
+    node.pat = f.fold_pat(node.pat);
+    node.expr = fold_boxed(node.expr, |expr| f.fold_expr(expr));
+    node.body = f.fold_block(node.body);
+    node
+}
+
+/// Folds the block of an [`ExprGen`].
+pub fn walk_expr_gen<F: Fold + ?Sized>(f: &mut F, mut node: ExprGen) -> ExprGen {
+    node.block = f.fold_block(node.block);
+    node
+}
+
+/// Folds the condition and branches of an [`ExprIf`].
+pub fn walk_expr_if<F: Fold + ?Sized>(f: &mut F, mut node: ExprIf) -> ExprIf {
+    node.cond = fold_boxed(node.cond, |cond| f.fold_expr(cond));
+    node.then_branch = f.fold_block(node.then_branch);
+    node.else_branch = node
+        .else_branch
+        .map(|else_branch| fold_deep(else_branch, |else_branch| f.fold_expr(else_branch)));
+    node
+}
+
+/// Folds the indexed expression and index of an [`ExprIndex`].
+pub fn walk_expr_index<F: Fold + ?Sized>(f: &mut F, mut node: ExprIndex) -> ExprIndex {
+    node.expr = fold_boxed(node.expr, |expr| f.fold_expr(expr));
+    node.index = fold_boxed(node.index, |index| f.fold_expr(index));
+    node
+}
+
+/// Returns an [`ExprInfer`] unchanged, as it has no children.
+pub fn walk_expr_infer<F: Fold + ?Sized>(_f: &mut F, node: ExprInfer) -> ExprInfer {
+    node
+}
+
+/// Folds the body of an [`ExprLoop`].
+pub fn walk_expr_loop<F: Fold + ?Sized>(f: &mut F, mut node: ExprLoop) -> ExprLoop {
+    node.body = f.fold_block(node.body);
+    node
+}
+
+/// Folds the path and tokens of an [`ExprMacroCall`].
+pub fn walk_expr_macro_call<F: Fold + ?Sized>(f: &mut F, mut node: ExprMacroCall) -> ExprMacroCall {
+    node.path = f.fold_path(node.path);
+    node.tokens = f.fold_token_stream(node.tokens);
+    node
+}
+
+/// Folds the scrutinee and arms of an [`ExprMatch`].
+pub fn walk_expr_match<F: Fold + ?Sized>(f: &mut F, mut node: ExprMatch) -> ExprMatch {
+    node.expr = fold_boxed(node.expr, |expr| f.fold_expr(expr));
+    node.arms = fold_each(node.arms, |arm| f.fold_arm(arm));
+    node
+}
+
+/// Folds the metadata, pattern, guard and body of an [`Arm`].
+pub fn walk_arm<F: Fold + ?Sized>(f: &mut F, mut node: Arm) -> Arm {
+    node.md = fold_md_opt(f, node.md);
+    node.pat = f.fold_pat(node.pat);
+    node.guard = node
+        .guard
+        .map(|guard| fold_boxed(guard, |guard| f.fold_expr(guard)));
+    node.body = fold_boxed(node.body, |body| f.fold_expr(body));
+    node
+}
+
+/// Folds the receiver, method name and arguments of an [`ExprMethodCall`].
+pub fn walk_expr_method_call<F: Fold + ?Sized>(
+    f: &mut F,
+    mut node: ExprMethodCall,
+) -> ExprMethodCall {
+    node.receiver = fold_boxed(node.receiver, |receiver| f.fold_expr(receiver));
+    node.method = f.fold_ident(node.method);
+    node.args = fold_each(node.args, |arg| f.fold_expr(arg));
+    node
+}
+
+/// Folds the inner expression of an [`ExprParen`].
+pub fn walk_expr_paren<F: Fold + ?Sized>(f: &mut F, mut node: ExprParen) -> ExprParen {
+    node.expr = fold_boxed(node.expr, |expr| f.fold_expr(expr));
+    node
+}
+
+/// Folds the path of an [`ExprPath`].
+pub fn walk_expr_path<F: Fold + ?Sized>(f: &mut F, mut node: ExprPath) -> ExprPath {
+    node.path = f.fold_path(node.path);
+    node
+}
+
+/// Folds the bounds of an [`ExprRange`].
+pub fn walk_expr_range<F: Fold + ?Sized>(f: &mut F, mut node: ExprRange) -> ExprRange {
+    node.start = node
+        .start
+        .map(|start| fold_boxed(start, |start| f.fold_expr(start)));
+    node.end = node.end.map(|end| fold_boxed(end, |end| f.fold_expr(end)));
+    node
+}
+
+/// Folds the place expression of an [`ExprRawRef`].
+pub fn walk_expr_raw_ref<F: Fold + ?Sized>(f: &mut F, mut node: ExprRawRef) -> ExprRawRef {
+    node.expr = fold_boxed(node.expr, |expr| f.fold_expr(expr));
+    node
+}
+
+/// Folds the referenced expression of an [`ExprRef`].
+pub fn walk_expr_ref<F: Fold + ?Sized>(f: &mut F, mut node: ExprRef) -> ExprRef {
+    node.expr = fold_boxed(node.expr, |expr| f.fold_expr(expr));
+    node
+}
+
+/// Folds the returned value of an [`ExprReturn`].
+pub fn walk_expr_return<F: Fold + ?Sized>(f: &mut F, mut node: ExprReturn) -> ExprReturn {
+    node.expr = node
+        .expr
+        .map(|expr| fold_boxed(expr, |expr| f.fold_expr(expr)));
+    node
+}
+
+/// Folds the path and fields of an [`ExprStruct`].
+pub fn walk_expr_struct<F: Fold + ?Sized>(f: &mut F, mut node: ExprStruct) -> ExprStruct {
+    node.path = f.fold_path(node.path);
+    node.fields = fold_each(node.fields, |field| f.fold_field_value(field));
+    node
+}
+
+/// Folds the metadata, name and value of a [`FieldValue`].
+pub fn walk_field_value<F: Fold + ?Sized>(f: &mut F, mut node: FieldValue) -> FieldValue {
+    node.md = fold_md_opt(f, node.md);
+    node.member = f.fold_ident(node.member);
+    node.value = f.fold_expr(node.value);
+    node
+}
+
+/// Folds the block of an [`ExprTry`].
+pub fn walk_expr_try<F: Fold + ?Sized>(f: &mut F, mut node: ExprTry) -> ExprTry {
+    node.block = f.fold_block(node.block);
+    node
+}
+
+/// Folds the elements of an [`ExprTuple`].
+pub fn walk_expr_tuple<F: Fold + ?Sized>(f: &mut F, mut node: ExprTuple) -> ExprTuple {
+    node.elems = fold_each(node.elems, |elem| f.fold_expr(elem));
+    node
+}
+
+/// Folds the operator and operand of an [`ExprUnary`].
+pub fn walk_expr_unary<F: Fold + ?Sized>(f: &mut F, mut node: ExprUnary) -> ExprUnary {
+    node.op = f.fold_un_op(node.op);
+    node.expr = fold_boxed(node.expr, |expr| f.fold_expr(expr));
+    node
+}
+
+/// Folds the condition and body of an [`ExprWhile`].
+pub fn walk_expr_while<F: Fold + ?Sized>(f: &mut F, mut node: ExprWhile) -> ExprWhile {
+    node.cond = fold_boxed(node.cond, |cond| f.fold_expr(cond));
+    node.body = f.fold_block(node.body);
+    node
+}
+
+/// Folds the yielded value of an [`ExprYield`].
+pub fn walk_expr_yield<F: Fold + ?Sized>(f: &mut F, mut node: ExprYield) -> ExprYield {
+    node.expr = node
+        .expr
+        .map(|expr| fold_boxed(expr, |expr| f.fold_expr(expr)));
+    node
+}
+
+/// Returns a [`BinOp`] unchanged, as it has no children.
+pub fn walk_bin_op<F: Fold + ?Sized>(_f: &mut F, node: BinOp) -> BinOp {
+    node
+}
+
+/// Returns an [`UnOp`] unchanged, as it has no children.
+pub fn walk_un_op<F: Fold + ?Sized>(_f: &mut F, node: UnOp) -> UnOp {
+    node
+}
+
+// Literals.
+
+/// Folds the string literal of a [`Lit::Str`].
+pub fn walk_lit<F: Fold + ?Sized>(f: &mut F, node: Lit) -> Lit {
+    match node {
+        Lit::Str(lit) => Lit::Str(f.fold_lit_str(lit)),
+        node @ (Lit::ByteStr(_)
+        | Lit::CStr(_)
+        | Lit::Byte(_)
+        | Lit::Char(_)
+        | Lit::Int(_)
+        | Lit::Float(_)
+        | Lit::Bool(_)) => node,
+    }
+}
+
+/// Returns a [`LitStr`] unchanged, as it has no children.
+pub fn walk_lit_str<F: Fold + ?Sized>(_f: &mut F, node: LitStr) -> LitStr {
+    node
+}
+
+// Patterns.
+
+/// Folds the node wrapped by a [`Pat`].
+pub fn walk_pat<F: Fold + ?Sized>(f: &mut F, node: Pat) -> Pat {
+    match node {
+        Pat::Const(pat) => Pat::Const(f.fold_pat_const(pat)),
+        Pat::Ident(pat) => Pat::Ident(f.fold_pat_ident(pat)),
+        Pat::Lit(pat) => Pat::Lit(f.fold_pat_lit(pat)),
+        Pat::Macro(pat) => Pat::Macro(f.fold_pat_macro(pat)),
+        Pat::Or(pat) => Pat::Or(f.fold_pat_or(pat)),
+        Pat::Paren(pat) => Pat::Paren(f.fold_pat_paren(pat)),
+        Pat::Path(pat) => Pat::Path(f.fold_pat_path(pat)),
+        Pat::Range(pat) => Pat::Range(f.fold_pat_range(pat)),
+        Pat::Reference(pat) => Pat::Reference(f.fold_pat_reference(pat)),
+        Pat::Rest(pat) => Pat::Rest(f.fold_pat_rest(pat)),
+        Pat::Slice(pat) => Pat::Slice(f.fold_pat_slice(pat)),
+        Pat::Struct(pat) => Pat::Struct(f.fold_pat_struct(pat)),
+        Pat::Tuple(pat) => Pat::Tuple(f.fold_pat_tuple(pat)),
+        Pat::TupleStruct(pat) => Pat::TupleStruct(f.fold_pat_tuple_struct(pat)),
+        Pat::Type(pat) => Pat::Type(f.fold_pat_type(pat)),
+        Pat::Verbatim(pat) => Pat::Verbatim(f.fold_verbatim(pat)),
+        Pat::Wild(pat) => Pat::Wild(f.fold_pat_wild(pat)),
+    }
+}
+
+/// Folds the expression of a [`PatConst`].
+pub fn walk_pat_const<F: Fold + ?Sized>(f: &mut F, mut node: PatConst) -> PatConst {
+    node.expr = fold_boxed(node.expr, |expr| f.fold_expr(expr));
+    node
+}
+
+/// Folds the binding name of a [`PatIdent`].
+pub fn walk_pat_ident<F: Fold + ?Sized>(f: &mut F, mut node: PatIdent) -> PatIdent {
+    node.ident = f.fold_ident(node.ident);
+    node
+}
+
+/// Folds the literal of a [`PatLit`].
+pub fn walk_pat_lit<F: Fold + ?Sized>(f: &mut F, mut node: PatLit) -> PatLit {
+    node.lit = fold_boxed(node.lit, |lit| f.fold_lit(lit));
+    node
+}
+
+/// Folds the macro call of a [`PatMacro`].
+pub fn walk_pat_macro<F: Fold + ?Sized>(f: &mut F, mut node: PatMacro) -> PatMacro {
+    node.mac = f.fold_expr_macro_call(node.mac);
+    node
+}
+
+/// Folds the alternatives of a [`PatOr`].
+pub fn walk_pat_or<F: Fold + ?Sized>(f: &mut F, mut node: PatOr) -> PatOr {
+    node.pats = fold_each(node.pats, |pat| f.fold_pat(pat));
+    node
+}
+
+/// Folds the inner pattern of a [`PatParen`].
+pub fn walk_pat_paren<F: Fold + ?Sized>(f: &mut F, mut node: PatParen) -> PatParen {
+    node.pat = fold_boxed(node.pat, |pat| f.fold_pat(pat));
+    node
+}
+
+/// Folds the path of a [`PatPath`].
+pub fn walk_pat_path<F: Fold + ?Sized>(f: &mut F, mut node: PatPath) -> PatPath {
+    node.path = f.fold_path(node.path);
+    node
+}
+
+/// Folds the bounds of a [`PatRange`].
+pub fn walk_pat_range<F: Fold + ?Sized>(f: &mut F, mut node: PatRange) -> PatRange {
+    node.start = node
+        .start
+        .map(|start| fold_boxed(start, |start| f.fold_expr(start)));
+    node.end = node.end.map(|end| fold_boxed(end, |end| f.fold_expr(end)));
+    node
+}
+
+/// Folds the referenced pattern of a [`PatReference`].
+pub fn walk_pat_reference<F: Fold + ?Sized>(f: &mut F, mut node: PatReference) -> PatReference {
+    node.pat = fold_boxed(node.pat, |pat| f.fold_pat(pat));
+    node
+}
+
+/// Returns a [`PatRest`] unchanged, as it has no children.
+pub fn walk_pat_rest<F: Fold + ?Sized>(_f: &mut F, node: PatRest) -> PatRest {
+    node
+}
+
+/// Folds the elements of a [`PatSlice`].
+pub fn walk_pat_slice<F: Fold + ?Sized>(f: &mut F, mut node: PatSlice) -> PatSlice {
+    node.pats = fold_each(node.pats, |pat| f.fold_pat(pat));
+    node
+}
+
+/// Folds the path and fields of a [`PatStruct`].
+pub fn walk_pat_struct<F: Fold + ?Sized>(f: &mut F, mut node: PatStruct) -> PatStruct {
+    node.path = f.fold_path(node.path);
+    node.fields = fold_each(node.fields, |field| f.fold_field_pat(field));
+    node
+}
+
+/// Folds the name and pattern of a [`FieldPat`].
+pub fn walk_field_pat<F: Fold + ?Sized>(f: &mut F, mut node: FieldPat) -> FieldPat {
+    node.member = f.fold_ident(node.member);
+    node.pat = fold_boxed(node.pat, |pat| f.fold_pat(pat));
+    node
+}
+
+/// Folds the elements of a [`PatTuple`].
+pub fn walk_pat_tuple<F: Fold + ?Sized>(f: &mut F, mut node: PatTuple) -> PatTuple {
+    node.pats = fold_each(node.pats, |pat| f.fold_pat(pat));
+    node
+}
+
+/// Folds the path and elements of a [`PatTupleStruct`].
+pub fn walk_pat_tuple_struct<F: Fold + ?Sized>(
+    f: &mut F,
+    mut node: PatTupleStruct,
+) -> PatTupleStruct {
+    node.path = f.fold_path(node.path);
+    node.pats = fold_each(node.pats, |pat| f.fold_pat(pat));
+    node
+}
+
+/// Folds the pattern and type of a [`PatType`].
+pub fn walk_pat_type<F: Fold + ?Sized>(f: &mut F, mut node: PatType) -> PatType {
+    node.pat = fold_boxed(node.pat, |pat| f.fold_pat(pat));
+    node.ty = fold_boxed(node.ty, |ty| f.fold_type(ty));
+    node
+}
+
+/// Returns a [`PatWild`] unchanged, as it has no children.
+pub fn walk_pat_wild<F: Fold + ?Sized>(_f: &mut F, node: PatWild) -> PatWild {
+    node
+}
+
+// Types.
+
+/// Folds the node wrapped by a [`Type`].
+pub fn walk_type<F: Fold + ?Sized>(f: &mut F, node: Type) -> Type {
+    match node {
+        Type::Array(ty) => Type::Array(f.fold_type_array(ty)),
+        Type::BareFn(ty) => Type::BareFn(f.fold_type_bare_fn(ty)),
+        Type::Group(ty) => Type::Group(fold_boxed(ty, |ty| f.fold_type(ty))),
+        Type::Paren(ty) => Type::Paren(fold_boxed(ty, |ty| f.fold_type(ty))),
+        Type::Slice(ty) => Type::Slice(fold_boxed(ty, |ty| f.fold_type(ty))),
+        Type::Macro(mac) => Type::Macro(f.fold_item_macro(mac)),
+        Type::Path(ty) => Type::Path(f.fold_type_path(ty)),
+        Type::Ptr(ty) => Type::Ptr(f.fold_type_ptr(ty)),
+        Type::Reference(ty) => Type::Reference(f.fold_type_reference(ty)),
+        Type::Tuple(elems) => Type::Tuple(fold_each(elems, |elem| f.fold_type(elem))),
+        Type::Verbatim(ty) => Type::Verbatim(f.fold_verbatim(ty)),
+        node @ (Type::ImplTrait | Type::Infer | Type::Never | Type::TraitObject) => node,
+    }
+}
+
+/// Folds the element type and length of a [`TypeArray`].
+pub fn walk_type_array<F: Fold + ?Sized>(f: &mut F, mut node: TypeArray) -> TypeArray {
+    node.elem = fold_boxed(node.elem, |elem| f.fold_type(elem));
+    node.len = fold_boxed(node.len, |len| f.fold_expr(len));
+    node
+}
+
+/// Folds the lifetimes, ABI, parameters and return type of a
+/// [`TypeBareFn`].
+pub fn walk_type_bare_fn<F: Fold + ?Sized>(f: &mut F, mut node: TypeBareFn) -> TypeBareFn {
+    node.lifetimes = fold_each(node.lifetimes, |lifetime| f.fold_lifetime(lifetime));
+    node.abi = node.abi.map(|abi| f.fold_abi(abi));
+    node.inputs = fold_each(node.inputs, |input| f.fold_bare_fn_arg(input));
+    node.output = node
+        .output
+        .map(|output| fold_boxed(output, |output| f.fold_type(output)));
+    node
+}
+
+/// Folds the name and type of a [`BareFnArg`].
+pub fn walk_bare_fn_arg<F: Fold + ?Sized>(f: &mut F, mut node: BareFnArg) -> BareFnArg {
+    node.name = node.name.map(|name| f.fold_ident(name));
+    node.ty = f.fold_type(node.ty);
+    node
+}
+
+/// Folds the path of a [`TypePath`].
+pub fn walk_type_path<F: Fold + ?Sized>(f: &mut F, mut node: TypePath) -> TypePath {
+    node.path = f.fold_path(node.path);
+    node
+}
+
+/// Folds the pointee of a [`TypePtr`].
+pub fn walk_type_ptr<F: Fold + ?Sized>(f: &mut F, mut node: TypePtr) -> TypePtr {
+    node.elem = fold_boxed(node.elem, |elem| f.fold_type(elem));
+    node
+}
+
+/// Folds the lifetime and referent of a [`TypeReference`].
+pub fn walk_type_reference<F: Fold + ?Sized>(f: &mut F, mut node: TypeReference) -> TypeReference {
+    node.lifetime = node.lifetime.map(|lifetime| f.fold_lifetime(lifetime));
+    node.elem = fold_boxed(node.elem, |elem| f.fold_type(elem));
+    node
+}
+
+// Paths and generics.
+
+/// Folds the segments of a [`Path`].
+pub fn walk_path<F: Fold + ?Sized>(f: &mut F, mut node: Path) -> Path {
+    node.segments = fold_each(node.segments, |segment| f.fold_path_segment(segment));
+    node
+}
+
+/// Folds the name and generic arguments of a [`PathSegment`].
+pub fn walk_path_segment<F: Fold + ?Sized>(f: &mut F, mut node: PathSegment) -> PathSegment {
+    node.ident = f.fold_ident(node.ident);
+    node.args = node.args.map(|args| f.fold_generic_args(args));
+    node
+}
+
+/// Folds the arguments of a [`GenericArgs`].
+pub fn walk_generic_args<F: Fold + ?Sized>(f: &mut F, mut node: GenericArgs) -> GenericArgs {
+    node.args = fold_each(node.args, |arg| f.fold_generic_arg(arg));
+    node
+}
+
+/// Folds the node wrapped by a [`GenericArg`].
+pub fn walk_generic_arg<F: Fold + ?Sized>(f: &mut F, node: GenericArg) -> GenericArg {
+    match node {
+        GenericArg::Lifetime(lifetime) => GenericArg::Lifetime(f.fold_lifetime(lifetime)),
+        GenericArg::Type(ty) => GenericArg::Type(f.fold_type(ty)),
+        GenericArg::Const(expr) => GenericArg::Const(f.fold_expr(expr)),
+    }
+}
+
+/// Folds the parameters of a [`GenericParams`].
+pub fn walk_generic_params<F: Fold + ?Sized>(f: &mut F, mut node: GenericParams) -> GenericParams {
+    node.params = fold_each(node.params, |param| f.fold_generic_param(param));
+    node
+}
+
+/// Folds the node wrapped by a [`GenericParam`].
+pub fn walk_generic_param<F: Fold + ?Sized>(f: &mut F, node: GenericParam) -> GenericParam {
+    match node {
+        GenericParam::Lifetime(param) => GenericParam::Lifetime(f.fold_lifetime_param(param)),
+        GenericParam::Type(param) => GenericParam::Type(f.fold_type_param(param)),
+        GenericParam::Const(param) => GenericParam::Const(f.fold_const_param(param)),
+    }
+}
+
+/// Folds the metadata, lifetime and bounds of a [`LifetimeParam`].
+pub fn walk_lifetime_param<F: Fold + ?Sized>(f: &mut F, mut node: LifetimeParam) -> LifetimeParam {
+    node.md = fold_md_opt(f, node.md);
+    node.lifetime = f.fold_lifetime(node.lifetime);
+    node.bounds = fold_each(node.bounds, |bound| f.fold_lifetime(bound));
+    node
+}
+
+/// Folds the metadata, name and bounds of a [`TypeParam`].
+pub fn walk_type_param<F: Fold + ?Sized>(f: &mut F, mut node: TypeParam) -> TypeParam {
+    node.md = fold_md_opt(f, node.md);
+    node.ident = f.fold_ident(node.ident);
+    node.bounds = fold_each(node.bounds, |bound| f.fold_type(bound));
+    node
+}
+
+/// Folds the metadata, name and type of a [`ConstParam`].
+pub fn walk_const_param<F: Fold + ?Sized>(f: &mut F, mut node: ConstParam) -> ConstParam {
+    node.md = fold_md_opt(f, node.md);
+    node.ident = f.fold_ident(node.ident);
+    node.ty = f.fold_type(node.ty);
+    node
+}
+
+/// Folds the predicates of a [`WhereClause`].
+pub fn walk_where_clause<F: Fold + ?Sized>(f: &mut F, mut node: WhereClause) -> WhereClause {
+    node.predicates = fold_each(node.predicates, |predicate| {
+        f.fold_where_predicate(predicate)
+    });
+    node
+}
+
+/// Folds the node wrapped by a [`WherePredicate`].
+pub fn walk_where_predicate<F: Fold + ?Sized>(f: &mut F, node: WherePredicate) -> WherePredicate {
+    match node {
+        WherePredicate::Lifetime(predicate) => {
+            WherePredicate::Lifetime(f.fold_lifetime_predicate(predicate))
+        }
+        WherePredicate::Type(predicate) => WherePredicate::Type(f.fold_type_predicate(predicate)),
+    }
+}
+
+/// Folds the lifetime and bounds of a [`LifetimePredicate`].
+pub fn walk_lifetime_predicate<F: Fold + ?Sized>(
+    f: &mut F,
+    mut node: LifetimePredicate,
+) -> LifetimePredicate {
+    node.lifetime = f.fold_lifetime(node.lifetime);
+    node.bounds = fold_each(node.bounds, |bound| f.fold_lifetime(bound));
+    node
+}
+
+/// Folds the bounded type and bounds of a [`TypePredicate`].
+pub fn walk_type_predicate<F: Fold + ?Sized>(f: &mut F, mut node: TypePredicate) -> TypePredicate {
+    node.ty = f.fold_type(node.ty);
+    node.bounds = fold_each(node.bounds, |bound| f.fold_type(bound));
+    node
+}
+
+/// Folds the name of a [`Lifetime::Named`].
+pub fn walk_lifetime<F: Fold + ?Sized>(f: &mut F, node: Lifetime) -> Lifetime {
+    match node {
+        Lifetime::Named(ident) => Lifetime::Named(f.fold_ident(ident)),
+        node @ (Lifetime::Static | Lifetime::Elided) => node,
+    }
+}
+
+/// Returns an [`Ident`] unchanged, as it has no children.
+pub fn walk_ident<F: Fold + ?Sized>(_f: &mut F, node: Ident) -> Ident {
+    node
+}
+
+// Metadata.
+
+/// Folds the attributes, leading comments and trailing comments of an
+/// [`Md`].
+pub fn walk_md<F: Fold + ?Sized>(f: &mut F, mut node: Md) -> Md {
+    node.attrs = fold_each(node.attrs, |attr| f.fold_attribute(attr));
+    node.comments = fold_each(node.comments, |comment| f.fold_comment(comment));
+    node.trailing_comments = fold_each(node.trailing_comments, |comment| f.fold_comment(comment));
+    node
+}
+
+/// Folds the meta item of an [`Attribute`].
+pub fn walk_attribute<F: Fold + ?Sized>(f: &mut F, node: Attribute) -> Attribute {
+    match node {
+        Attribute::Inner(meta) => Attribute::Inner(f.fold_meta(meta)),
+        Attribute::Outer(meta) => Attribute::Outer(f.fold_meta(meta)),
+    }
+}
+
+/// Folds the node wrapped by a [`Meta`].
+pub fn walk_meta<F: Fold + ?Sized>(f: &mut F, node: Meta) -> Meta {
+    match node {
+        Meta::List(list) => Meta::List(f.fold_meta_list(list)),
+        Meta::Path(path) => Meta::Path(f.fold_ident(path)),
+        Meta::NameValue(name_value) => Meta::NameValue(f.fold_meta_name_value(name_value)),
+    }
+}
+
+/// Folds the path and nested meta items of a [`MetaList`].
+pub fn walk_meta_list<F: Fold + ?Sized>(f: &mut F, mut node: MetaList) -> MetaList {
+    node.path = f.fold_ident(node.path);
+    node.metas = fold_each(node.metas, |meta| f.fold_meta(meta));
+    node
+}
+
+/// Folds the path and value of a [`MetaNameValue`].
+pub fn walk_meta_name_value<F: Fold + ?Sized>(f: &mut F, mut node: MetaNameValue) -> MetaNameValue {
+    node.path = f.fold_ident(node.path);
+    node.value = f.fold_lit(node.value);
+    node
+}
+
+/// Returns a [`Comment`] unchanged, as it has no children.
+pub fn walk_comment<F: Fold + ?Sized>(_f: &mut F, node: Comment) -> Comment {
+    node
+}
+
+// Tokens.
+
+/// Folds the token trees of a [`TokenStream`].
+pub fn walk_token_stream<F: Fold + ?Sized>(f: &mut F, mut node: TokenStream) -> TokenStream {
+    node.tokens = fold_each(node.tokens, |token| f.fold_token_tree(token));
+    node
+}
+
+/// Folds the node wrapped by a [`TokenTree`].
+///
+/// Identifier tokens are plain strings and are not folded.
+pub fn walk_token_tree<F: Fold + ?Sized>(f: &mut F, node: TokenTree) -> TokenTree {
+    match node {
+        TokenTree::Group(group) => TokenTree::Group(f.fold_group(group)),
+        TokenTree::Ident(ident) => TokenTree::Ident(ident),
+        TokenTree::Punct(punct) => TokenTree::Punct(f.fold_punct(punct)),
+        TokenTree::Literal(lit) => TokenTree::Literal(f.fold_lit(lit)),
+    }
+}
+
+/// Folds the token stream of a [`Group`].
+pub fn walk_group<F: Fold + ?Sized>(f: &mut F, mut node: Group) -> Group {
+    node.stream = f.fold_token_stream(node.stream);
+    node
+}
+
+/// Returns a [`Punct`] unchanged, as it has no children.
+pub fn walk_punct<F: Fold + ?Sized>(_f: &mut F, node: Punct) -> Punct {
+    node
+}
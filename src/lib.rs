@@ -72,5 +72,35 @@ pub mod pretty_printer;
 /// complex AST structures with minimal boilerplate code.
 pub mod builder;
 
+/// The `visit` module provides read-only traversal of the AST.
+///
+/// It defines the `Visit` trait, with one overridable method per node type,
+/// and the `walk_*` functions that visit the children of each node.
+pub mod visit;
+
+/// The `visit_mut` module provides in-place traversal of the AST.
+///
+/// It defines the `VisitMut` trait, with one overridable method per node type,
+/// and the `walk_*_mut` functions that visit the children of each node.
+pub mod visit_mut;
+
+/// The `fold` module provides owning traversal of the AST.
+///
+/// It defines the `Fold` trait, whose methods consume a node and return its
+/// replacement, and the `walk_*` functions that fold the children of each node.
+pub mod fold;
+
+/// The `parser` module turns Rust source text into AST nodes.
+///
+/// It is only available with the `parser` cargo feature, and accepts the
+/// subset of Rust that the AST can represent.
+#[cfg(feature = "parser")]
+pub mod parser;
+
+mod stack;
+
 /// Re-exports the main pretty-printing utilities for convenient access.
-pub use pretty_printer::{PrettyPrinter, Printer, pretty};
+pub use pretty_printer::{
+    PrettyPrinter, Printer, PrinterConfig, pretty, pretty_highlighted, pretty_to_writer,
+    pretty_with, pretty_with_source_map,
+};
@@ -0,0 +1,687 @@
+//! A lexer and recursive-descent parser that turns Rust source text into
+//! rasto ASTs.
+//!
+//! The parser accepts the subset of Rust that the AST can represent. Syntax
+//! that the AST has no node for, such as tuple structs or the `?` operator,
+//! is reported as an error instead of being silently dropped, so that
+//! printing a parsed file never changes its meaning.
+//!
+//! # Comments
+//!
+//! Comments follow the same placement rules as the builders:
+//!
+//! - Comments before an item, field, variant, statement, match arm or
+//!   struct-literal field are attached to its leading comments.
+//! - A `//` comment on the same line after such a node becomes its
+//!   end-of-line comment.
+//! - Comments after the last node of a block or braced list become trailing
+//!   comments of that node. In an empty block, they are attached to the block.
+//! - Inner doc comments (`//!`) at the top of a file are attached to the file.
+//! - Block comments are turned into line comments, one per line.
+//!
+//! Comments anywhere else, e.g. inside an expression or a signature, are
+//! dropped.
+//!
+//! # Example
+//!
+//! ```rust
+//! use rasto::parser::parse_file;
+//! use rasto::pretty;
+//!
+//! let file = parse_file("const ANSWER: u32 = 42; // The answer.").unwrap();
+//! assert_eq!(pretty(&file), "const ANSWER: u32 = 42; // The answer.");
+//!
+//! let error = parse_file("struct Unit;").unwrap_err();
+//! assert_eq!(
+//!     error.to_string(),
+//!     "1:12: tuple and unit structs are not supported by the rasto AST",
+//! );
+//! ```
+
+mod expressions;
+mod items;
+mod lexer;
+mod patterns;
+mod types;
+
+use crate::ast::items::Item;
+use crate::ast::{
+    Attribute, Block, Comment, Delimiter, Expr, File, GenericArgs, Ident, Lifetime, Md, Pat, Path,
+    Type,
+};
+use lexer::{RawComment, Token, TokenKind};
+use std::fmt;
+use std::str::FromStr;
+use thin_vec::ThinVec;
+use types::PathStyle;
+
+/// Parses a whole source file.
+///
+/// # Parameters
+///
+/// - `src`: The source text.
+pub fn parse_file(src: &str) -> Result<File, ParseError> {
+    Parser::new(src)?.parse_all(Parser::parse_file)
+}
+
+/// Parses a single item, such as a function or a struct, with its attributes
+/// and comments.
+///
+/// # Parameters
+///
+/// - `src`: The source text.
+pub fn parse_item(src: &str) -> Result<Item, ParseError> {
+    Parser::new(src)?.parse_all(Parser::parse_item)
+}
+
+/// Parses a single expression.
+///
+/// # Parameters
+///
+/// - `src`: The source text.
+pub fn parse_expr(src: &str) -> Result<Expr, ParseError> {
+    Parser::new(src)?.parse_all(Parser::parse_expr)
+}
+
+/// Parses a single type.
+///
+/// # Parameters
+///
+/// - `src`: The source text.
+pub fn parse_type(src: &str) -> Result<Type, ParseError> {
+    Parser::new(src)?.parse_all(Parser::parse_type)
+}
+
+/// Parses a single pattern, including `|` alternatives.
+///
+/// # Parameters
+///
+/// - `src`: The source text.
+pub fn parse_pat(src: &str) -> Result<Pat, ParseError> {
+    Parser::new(src)?.parse_all(Parser::parse_pat)
+}
+
+/// Parses a path, such as `std::collections::HashMap<K, V>` or
+/// `Vec::<u8>::new`.
+///
+/// # Parameters
+///
+/// - `src`: The source text.
+pub fn parse_path(src: &str) -> Result<Path, ParseError> {
+    Parser::new(src)?.parse_all(|parser| parser.parse_path(PathStyle::Type))
+}
+
+/// Parses generic arguments in angle brackets, such as `<'a, T, 4>`.
+///
+/// # Parameters
+///
+/// - `src`: The source text.
+pub fn parse_generic_args(src: &str) -> Result<GenericArgs, ParseError> {
+    Parser::new(src)?.parse_all(Parser::parse_generic_args)
+}
+
+impl FromStr for Type {
+    type Err = ParseError;
+
+    /// Parses a type with [`parse_type`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_type(s)
+    }
+}
+
+impl FromStr for Path {
+    type Err = ParseError;
+
+    /// Parses a path with [`parse_path`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_path(s)
+    }
+}
+
+impl FromStr for Expr {
+    type Err = ParseError;
+
+    /// Parses an expression with [`parse_expr`].
+    ///
+    /// Unlike `Expr::from("...")`, which creates a string literal, this
+    /// parses the string as code.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_expr(s)
+    }
+}
+
+impl FromStr for Pat {
+    type Err = ParseError;
+
+    /// Parses a pattern with [`parse_pat`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_pat(s)
+    }
+}
+
+impl FromStr for GenericArgs {
+    type Err = ParseError;
+
+    /// Parses generic arguments with [`parse_generic_args`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_generic_args(s)
+    }
+}
+
+/// A range of byte offsets in the source text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Span {
+    /// The offset of the first byte.
+    pub start: usize,
+    /// The offset one past the last byte.
+    pub end: usize,
+}
+
+impl Span {
+    /// Creates a new `Span`.
+    ///
+    /// # Parameters
+    ///
+    /// - `start`: The offset of the first byte.
+    /// - `end`: The offset one past the last byte.
+    pub fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+}
+
+/// An error reported by the parser, pointing at the offending source text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// A description of the problem, e.g. ``expected `;`, found `}` ``.
+    pub message: String,
+    /// The location of the offending source text.
+    pub span: Span,
+    /// The 1-based line of the start of the span.
+    pub line: usize,
+    /// The 1-based column, in characters, of the start of the span.
+    pub column: usize,
+}
+
+impl ParseError {
+    fn new(src: &str, span: Span, message: impl Into<String>) -> Self {
+        let start = span.start.min(src.len());
+        let before = &src[..start];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        Self {
+            message: message.into(),
+            span: Span::new(start, span.end.min(src.len()).max(start)),
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    /// Formats the error as `line:column: message`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+type PResult<T> = Result<T, ParseError>;
+
+/// Keywords that cannot be used as identifiers. `self`, `Self`, `super` and
+/// `crate` are allowed as path segments.
+const KEYWORDS: &[&str] = &[
+    "_", "as", "async", "await", "become", "box", "break", "const", "continue", "crate", "do",
+    "dyn", "else", "enum", "extern", "false", "fn", "for", "gen", "if", "impl", "in", "let",
+    "loop", "macro", "match", "mod", "move", "mut", "pub", "ref", "return", "self", "Self",
+    "static", "struct", "super", "trait", "true", "try", "type", "typeof", "unsafe", "use",
+    "where", "while", "yield",
+];
+
+/// Multi-character operators, longest first, followed by the
+/// single-character ones.
+const OPERATORS: &[&str] = &[
+    "<<=", ">>=", "...", "..=", "::", "->", "=>", "==", "!=", "<=", ">=", "&&", "||", "+=", "-=",
+    "*=", "/=", "%=", "^=", "&=", "|=", "<<", ">>", "..", "+", "-", "*", "/", "%", "^", "!", "&",
+    "|", "=", "<", ">", "@", ".", ",", ";", ":", "#", "$", "?", "~",
+];
+
+/// The parser state: the token stream, a cursor into it, and the comments
+/// that have not been attached to a node yet.
+struct Parser<'a> {
+    src: &'a str,
+    tokens: Vec<Token>,
+    pos: usize,
+    comments: Vec<RawComment>,
+    next_comment: usize,
+    /// Set while parsing the head of an `if`, `match`, `while` or `for`,
+    /// where `x {` starts the body rather than a struct literal.
+    no_struct_literal: bool,
+}
+
+impl<'a> Parser<'a> {
+    fn new(src: &'a str) -> PResult<Self> {
+        let lexed = lexer::lex(src)?;
+        Ok(Self {
+            src,
+            tokens: lexed.tokens,
+            pos: 0,
+            comments: lexed.comments,
+            next_comment: 0,
+            no_struct_literal: false,
+        })
+    }
+
+    /// Runs `parse` and checks that it consumed the whole input.
+    fn parse_all<T>(mut self, parse: impl FnOnce(&mut Self) -> PResult<T>) -> PResult<T> {
+        let node = parse(&mut self)?;
+        if !self.is_eof() {
+            return Err(self.unexpected("end of input"));
+        }
+        Ok(node)
+    }
+
+    // Token access.
+
+    fn peek(&self) -> &Token {
+        self.peek_nth(0)
+    }
+
+    fn peek_nth(&self, n: usize) -> &Token {
+        let last = self.tokens.len() - 1;
+        &self.tokens[(self.pos + n).min(last)]
+    }
+
+    fn bump(&mut self) -> Token {
+        let token = self.peek().clone();
+        if !self.is_eof() {
+            self.pos += 1;
+        }
+        token
+    }
+
+    fn is_eof(&self) -> bool {
+        self.peek().kind == TokenKind::Eof
+    }
+
+    /// The start of the next token.
+    fn start(&self) -> usize {
+        self.peek().span.start
+    }
+
+    /// The end of the last consumed token.
+    fn prev_end(&self) -> usize {
+        match self.pos {
+            0 => 0,
+            pos => self.tokens[pos - 1].span.end,
+        }
+    }
+
+    fn span_from(&self, start: usize) -> Span {
+        Span::new(start, self.prev_end().max(start))
+    }
+
+    // Keywords and identifiers.
+
+    fn is_ident_nth(&self, n: usize, name: &str) -> bool {
+        matches!(&self.peek_nth(n).kind, TokenKind::Ident { name: found, is_raw: false } if found == name)
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        self.is_ident_nth(0, keyword)
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let found = self.is_keyword(keyword);
+        if found {
+            self.bump();
+        }
+        found
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> PResult<()> {
+        if self.eat_keyword(keyword) {
+            Ok(())
+        } else {
+            Err(self.unexpected(&format!("`{keyword}`")))
+        }
+    }
+
+    /// Whether the next token is an identifier that is not a keyword.
+    fn is_ident(&self) -> bool {
+        self.is_ident_at(0)
+    }
+
+    fn is_ident_at(&self, n: usize) -> bool {
+        match &self.peek_nth(n).kind {
+            TokenKind::Ident { is_raw: true, .. } => true,
+            TokenKind::Ident { name, .. } => !KEYWORDS.contains(&name.as_str()),
+            _ => false,
+        }
+    }
+
+    fn ident(&mut self) -> PResult<Ident> {
+        if !self.is_ident() {
+            return Err(self.unexpected("an identifier"));
+        }
+        match self.bump().kind {
+            TokenKind::Ident { name, is_raw } => Ok(Ident::new(name, is_raw)),
+            _ => unreachable!("checked by `is_ident`"),
+        }
+    }
+
+    /// Parses an identifier that may also be `self`, `Self`, `super` or
+    /// `crate`, as allowed in paths.
+    fn path_segment_ident(&mut self) -> PResult<Ident> {
+        if ["self", "Self", "super", "crate"]
+            .iter()
+            .any(|keyword| self.is_keyword(keyword))
+        {
+            let TokenKind::Ident { name, .. } = self.bump().kind else {
+                unreachable!("checked by `is_keyword`")
+            };
+            return Ok(Ident::new(name, false));
+        }
+        self.ident()
+    }
+
+    fn is_path_start(&self) -> bool {
+        self.is_ident()
+            || ["self", "Self", "super", "crate"]
+                .iter()
+                .any(|keyword| self.is_keyword(keyword))
+    }
+
+    fn lifetime(&mut self) -> PResult<Lifetime> {
+        match self.peek().kind.clone() {
+            TokenKind::Lifetime(name) => {
+                self.bump();
+                Ok(Lifetime::new(name))
+            }
+            _ => Err(self.unexpected("a lifetime")),
+        }
+    }
+
+    fn is_lifetime(&self) -> bool {
+        self.is_lifetime_nth(0)
+    }
+
+    fn is_lifetime_nth(&self, n: usize) -> bool {
+        matches!(self.peek_nth(n).kind, TokenKind::Lifetime(_))
+    }
+
+    // Punctuation.
+
+    /// Whether the next tokens spell `op`, with no space inside it.
+    fn is_punct(&self, op: &str) -> bool {
+        self.is_punct_nth(0, op)
+    }
+
+    /// Whether the tokens starting `n` tokens ahead spell `op`.
+    fn is_punct_nth(&self, n: usize, op: &str) -> bool {
+        op.chars().enumerate().all(|(i, expected)| {
+            matches!(self.peek_nth(n + i).kind, TokenKind::Punct { ch, joint }
+                if ch == expected && (joint || i == op.len() - 1))
+        })
+    }
+
+    /// Returns the longest operator at the next token, so that e.g. `=` is
+    /// not mistaken for the start of `==`.
+    fn peek_op(&self) -> Option<&'static str> {
+        OPERATORS.iter().copied().find(|op| self.is_punct(op))
+    }
+
+    fn eat_punct(&mut self, op: &str) -> bool {
+        let found = self.is_punct(op);
+        if found {
+            self.pos += op.len();
+        }
+        found
+    }
+
+    fn expect_punct(&mut self, op: &str) -> PResult<()> {
+        if self.eat_punct(op) {
+            Ok(())
+        } else {
+            Err(self.unexpected(&format!("`{op}`")))
+        }
+    }
+
+    fn is_open(&self, delimiter: Delimiter) -> bool {
+        self.peek().kind == TokenKind::Open(delimiter)
+    }
+
+    fn is_open_nth(&self, n: usize, delimiter: Delimiter) -> bool {
+        self.peek_nth(n).kind == TokenKind::Open(delimiter)
+    }
+
+    fn is_close(&self, delimiter: Delimiter) -> bool {
+        self.peek().kind == TokenKind::Close(delimiter)
+    }
+
+    fn eat_open(&mut self, delimiter: Delimiter) -> bool {
+        let found = self.is_open(delimiter);
+        if found {
+            self.bump();
+        }
+        found
+    }
+
+    fn expect_open(&mut self, delimiter: Delimiter) -> PResult<()> {
+        if self.eat_open(delimiter) {
+            Ok(())
+        } else {
+            let open = lexer::delimiter_chars(delimiter).0;
+            Err(self.unexpected(&format!("`{open}`")))
+        }
+    }
+
+    fn eat_close(&mut self, delimiter: Delimiter) -> bool {
+        let found = self.is_close(delimiter);
+        if found {
+            self.bump();
+        }
+        found
+    }
+
+    fn expect_close(&mut self, delimiter: Delimiter) -> PResult<()> {
+        if self.eat_close(delimiter) {
+            Ok(())
+        } else {
+            let close = lexer::delimiter_chars(delimiter).1;
+            Err(self.unexpected(&format!("`{close}`")))
+        }
+    }
+
+    /// Parses `item (, item)* ,?` up to and including the `close` delimiter.
+    fn comma_list<T>(
+        &mut self,
+        close: Delimiter,
+        mut item: impl FnMut(&mut Self) -> PResult<T>,
+    ) -> PResult<ThinVec<T>> {
+        let mut items = ThinVec::new();
+        while !self.eat_close(close) {
+            items.push(item(self)?);
+            if !self.eat_punct(",") && !self.is_close(close) {
+                let close = lexer::delimiter_chars(close).1;
+                return Err(self.unexpected(&format!("`,` or `{close}`")));
+            }
+        }
+        Ok(items)
+    }
+
+    // Errors.
+
+    fn error(&self, span: Span, message: impl Into<String>) -> ParseError {
+        ParseError::new(self.src, span, message)
+    }
+
+    /// Reports that the next token is not what the parser expected.
+    fn unexpected(&self, expected: &str) -> ParseError {
+        let token = self.peek();
+        self.error(
+            token.span,
+            format!("expected {expected}, found {}", token.kind.describe()),
+        )
+    }
+
+    /// Reports valid Rust syntax that the AST cannot represent.
+    fn unsupported(&self, span: Span, what: &str) -> ParseError {
+        self.error(span, format!("{what} are not supported by the rasto AST"))
+    }
+
+    // Attributes.
+
+    fn is_inner_attr(&self) -> bool {
+        self.is_punct("#!") && matches!(self.peek_nth(2).kind, TokenKind::Open(Delimiter::Bracket))
+    }
+
+    /// Parses the outer attributes before a node.
+    fn outer_attrs(&mut self) -> PResult<ThinVec<Attribute>> {
+        let mut attrs = ThinVec::new();
+        while self.is_punct("#") && !self.is_inner_attr() {
+            self.bump();
+            attrs.push(Attribute::Outer(self.attr_body()?));
+        }
+        Ok(attrs)
+    }
+
+    fn inner_attr(&mut self) -> PResult<Attribute> {
+        self.expect_punct("#!")?;
+        Ok(Attribute::Inner(self.attr_body()?))
+    }
+
+    // Comments.
+
+    /// Takes the comments that start before `pos`.
+    fn take_comments(&mut self, pos: usize) -> ThinVec<RawComment> {
+        let mut taken = ThinVec::new();
+        while let Some(comment) = self.comments.get(self.next_comment)
+            && comment.span.start < pos
+        {
+            taken.push(comment.clone());
+            self.next_comment += 1;
+        }
+        taken
+    }
+
+    /// Takes the comments before the next token.
+    fn leading_comments(&mut self) -> ThinVec<Comment> {
+        let start = self.start();
+        self.take_comments(start)
+            .into_iter()
+            .map(|comment| comment.comment)
+            .collect()
+    }
+
+    /// Takes a `//` comment that follows the last consumed token on the same
+    /// line, and drops the comments inside the node that just ended.
+    fn eol_comment(&mut self) -> Option<String> {
+        let end = self.prev_end();
+        self.take_comments(end);
+        let comment = self.comments.get(self.next_comment)?;
+        let is_same_line = !self.src[end..comment.span.start].contains('\n');
+        if !is_same_line || comment.span.start > self.start() || comment.is_inner {
+            return None;
+        }
+        let Comment::Line(text) = &comment.comment else {
+            return None;
+        };
+        let text = text.clone();
+        // Skip all the lines of a block comment.
+        let span = comment.span;
+        while self
+            .comments
+            .get(self.next_comment)
+            .is_some_and(|comment| comment.span == span)
+        {
+            self.next_comment += 1;
+        }
+        Some(text)
+    }
+
+    /// Takes the comments before the closing delimiter at the next token.
+    fn trailing_comments(&mut self) -> ThinVec<Comment> {
+        self.leading_comments()
+    }
+
+    /// Consumes an opening delimiter and drops the comments before it.
+    fn expect_open_list(&mut self, delimiter: Delimiter) -> PResult<()> {
+        self.expect_open(delimiter)?;
+        self.take_comments(self.prev_end());
+        Ok(())
+    }
+
+    /// Runs `parse` with struct literals allowed or not, restoring the
+    /// previous setting afterwards.
+    fn with_struct_literals<T>(
+        &mut self,
+        allowed: bool,
+        parse: impl FnOnce(&mut Self) -> PResult<T>,
+    ) -> PResult<T> {
+        let saved = std::mem::replace(&mut self.no_struct_literal, !allowed);
+        let result = parse(self);
+        self.no_struct_literal = saved;
+        result
+    }
+
+    /// Parses a block: `{ stmts }`.
+    fn parse_block(&mut self) -> PResult<Block> {
+        self.with_struct_literals(true, Self::parse_block_inner)
+    }
+
+    fn parse_block_inner(&mut self) -> PResult<Block> {
+        self.expect_open_list(Delimiter::Brace)?;
+        let mut md = Md::default();
+        while self.is_inner_attr() {
+            md.comments.extend(self.leading_comments());
+            md.attrs.push(self.inner_attr()?);
+        }
+        let (stmts, has_trailing_semicolon, trailing) = self.parse_stmts()?;
+        let mut block = Block {
+            stmts,
+            has_trailing_semicolon,
+            md: None,
+        };
+        match block.stmts.last_mut() {
+            Some(stmt) => extend_trailing(stmt.md_mut(), trailing),
+            None => md.comments.extend(trailing),
+        }
+        block.md = into_md(md);
+        self.expect_close(Delimiter::Brace)?;
+        Ok(block)
+    }
+}
+
+/// Returns `None` for empty metadata, so that nodes without attributes or
+/// comments compare equal to the ones made by the builders.
+fn into_md(md: Md) -> Option<Box<Md>> {
+    let is_empty = md.attrs.is_empty()
+        && md.comments.is_empty()
+        && md.trailing_comments.is_empty()
+        && md.eol_comment.is_none()
+        && !md.blank_line_before;
+    (!is_empty).then(|| Box::new(md))
+}
+
+/// Builds the metadata of a node from its leading comments and attributes.
+fn leading_md(comments: ThinVec<Comment>, attrs: ThinVec<Attribute>) -> Option<Box<Md>> {
+    into_md(Md {
+        attrs,
+        comments,
+        ..Default::default()
+    })
+}
+
+fn set_eol_comment(md: &mut Option<Box<Md>>, text: Option<String>) {
+    if text.is_some() {
+        md.get_or_insert_default().eol_comment = text;
+    }
+}
+
+fn extend_trailing(md: &mut Option<Box<Md>>, comments: ThinVec<Comment>) {
+    if !comments.is_empty() {
+        md.get_or_insert_default()
+            .trailing_comments
+            .extend(comments);
+    }
+}
@@ -0,0 +1,794 @@
+//! Parsing of expressions, statements and macro invocations.
+
+use crate::ast::{
+    Arm, BinOp, ClosureParam, Comment, Delimiter, Expr, ExprArray, ExprAssign, ExprAsync,
+    ExprAwait, ExprBecome, ExprBinary, ExprBlock, ExprBreak, ExprCall, ExprCast, ExprClosure,
+    ExprConst, ExprContinue, ExprField, ExprFor, ExprGen, ExprIf, ExprIndex, ExprInfer, ExprLoop,
+    ExprMacroCall, ExprMatch, ExprMethodCall, ExprParen, ExprPath, ExprRange, ExprRawRef, ExprRef,
+    ExprReturn, ExprStruct, ExprTry, ExprTuple, ExprUnary, ExprWhile, ExprYield, FieldValue, Group,
+    Ident, Lit, LitBool, Local, Md, Pat, PatType, Path, PathSegment, Punct, RangeLimits, Spacing,
+    Stmt, StmtExpr, TokenStream, TokenTree, UnOp,
+};
+use crate::parser::lexer::{TokenKind, delimiter_chars};
+use crate::parser::types::PathStyle;
+use crate::parser::{PResult, Parser, leading_md, set_eol_comment};
+use thin_vec::ThinVec;
+
+/// The precedence of `..` ranges, which bind looser than `||`.
+const RANGE_PRECEDENCE: u8 = 1;
+
+/// Returns the operator and precedence of a binary operator token, from
+/// `||` (2) up to `*` (10).
+fn binary_op(op: &str) -> Option<(BinOp, u8)> {
+    Some(match op {
+        "||" => (BinOp::Or, 2),
+        "&&" => (BinOp::And, 3),
+        "==" => (BinOp::Eq, 4),
+        "!=" => (BinOp::Ne, 4),
+        "<" => (BinOp::Lt, 4),
+        "<=" => (BinOp::Le, 4),
+        ">" => (BinOp::Gt, 4),
+        ">=" => (BinOp::Ge, 4),
+        "|" => (BinOp::BitOr, 5),
+        "^" => (BinOp::BitXor, 6),
+        "&" => (BinOp::BitAnd, 7),
+        "<<" => (BinOp::Shl, 8),
+        ">>" => (BinOp::Shr, 8),
+        "+" => (BinOp::Add, 9),
+        "-" => (BinOp::Sub, 9),
+        "*" => (BinOp::Mul, 10),
+        "/" => (BinOp::Div, 10),
+        "%" => (BinOp::Rem, 10),
+        _ => return None,
+    })
+}
+
+/// Returns the operator of a compound assignment token such as `+=`.
+fn compound_assign_op(op: &str) -> Option<BinOp> {
+    Some(match op {
+        "+=" => BinOp::AddAssign,
+        "-=" => BinOp::SubAssign,
+        "*=" => BinOp::MulAssign,
+        "/=" => BinOp::DivAssign,
+        "%=" => BinOp::RemAssign,
+        "^=" => BinOp::BitXorAssign,
+        "&=" => BinOp::BitAndAssign,
+        "|=" => BinOp::BitOrAssign,
+        "<<=" => BinOp::ShlAssign,
+        ">>=" => BinOp::ShrAssign,
+        _ => return None,
+    })
+}
+
+/// Whether an expression ends with a block, so that it can be used as a
+/// statement without a `;`.
+fn is_block_like(expr: &Expr) -> bool {
+    match expr {
+        Expr::Async(_)
+        | Expr::Block(_)
+        | Expr::Const(_)
+        | Expr::For(_)
+        | Expr::Gen(_)
+        | Expr::If(_)
+        | Expr::Loop(_)
+        | Expr::Match(_)
+        | Expr::Try(_)
+        | Expr::While(_) => true,
+        Expr::MacroCall(mac) => mac.delimiter == Delimiter::Brace,
+        _ => false,
+    }
+}
+
+fn binary(left: Expr, op: BinOp, right: Expr) -> Expr {
+    Expr::Binary(ExprBinary {
+        left: left.into(),
+        op,
+        right: right.into(),
+    })
+}
+
+impl Parser<'_> {
+    /// Parses an expression.
+    pub(super) fn parse_expr(&mut self) -> PResult<Expr> {
+        if self.is_punct("#") {
+            return Err(self.unsupported(self.peek().span, "attributes on expressions"));
+        }
+        self.parse_assign()
+    }
+
+    /// Parses an expression where `x {` does not start a struct literal.
+    fn parse_expr_no_struct(&mut self) -> PResult<Expr> {
+        self.with_struct_literals(false, Self::parse_expr)
+    }
+
+    /// Parses an expression inside delimiters, where struct literals are
+    /// always allowed.
+    fn parse_expr_nested(&mut self) -> PResult<Expr> {
+        self.with_struct_literals(true, Self::parse_expr)
+    }
+
+    /// Parses an assignment, which is right-associative: `a = b = c`.
+    fn parse_assign(&mut self) -> PResult<Expr> {
+        let left = self.parse_range()?;
+        let Some(op) = self.peek_op() else {
+            return Ok(left);
+        };
+        if op == "=" {
+            self.bump();
+            let right = self.parse_assign()?;
+            return Ok(Expr::Assign(ExprAssign {
+                left: Box::new(left),
+                right: Box::new(right),
+            }));
+        }
+        if let Some(bin_op) = compound_assign_op(op) {
+            self.pos += op.len();
+            let right = self.parse_assign()?;
+            return Ok(binary(left, bin_op, right));
+        }
+        Ok(left)
+    }
+
+    /// Parses `a..b`, `a..=b`, `a..`, `..b` and `..`.
+    fn parse_range(&mut self) -> PResult<Expr> {
+        let start = if self.is_range_op() {
+            None
+        } else {
+            let expr = self.parse_binary(RANGE_PRECEDENCE + 1)?;
+            if !self.is_range_op() {
+                return Ok(expr);
+            }
+            Some(Box::new(expr))
+        };
+        let limits = if self.eat_punct("..=") {
+            RangeLimits::Closed
+        } else {
+            self.expect_punct("..")?;
+            RangeLimits::HalfOpen
+        };
+        let end = if self.can_begin_expr() {
+            Some(Box::new(self.parse_binary(RANGE_PRECEDENCE + 1)?))
+        } else {
+            None
+        };
+        if end.is_none() && limits == RangeLimits::Closed {
+            return Err(self.unexpected("the end of the range"));
+        }
+        Ok(Expr::Range(ExprRange { start, limits, end }))
+    }
+
+    fn is_range_op(&self) -> bool {
+        matches!(self.peek_op(), Some(".." | "..="))
+    }
+
+    /// Whether the next token can start an expression, which decides if
+    /// `return`, `..` and friends have an operand.
+    fn can_begin_expr(&self) -> bool {
+        match &self.peek().kind {
+            TokenKind::Eof | TokenKind::Close(_) => false,
+            TokenKind::Open(Delimiter::Brace) => !self.no_struct_literal,
+            TokenKind::Punct { .. } => {
+                matches!(
+                    self.peek_op(),
+                    Some(
+                        "-" | "!" | "*" | "&" | "&&" | "|" | "||" | "<" | "::" | ".." | "..=" | "#"
+                    )
+                )
+            }
+            TokenKind::Ident {
+                name,
+                is_raw: false,
+            } => name != "as" && name != "else",
+            _ => true,
+        }
+    }
+
+    /// Parses binary operators with a precedence of at least `min`, using
+    /// precedence climbing.
+    fn parse_binary(&mut self, min: u8) -> PResult<Expr> {
+        let mut left = self.parse_cast()?;
+        while let Some(op) = self.peek_op()
+            && let Some((bin_op, precedence)) = binary_op(op)
+            && precedence >= min
+        {
+            self.pos += op.len();
+            let right = self.parse_binary(precedence + 1)?;
+            left = binary(left, bin_op, right);
+        }
+        Ok(left)
+    }
+
+    /// Parses `expr as Type`, which binds tighter than binary operators.
+    fn parse_cast(&mut self) -> PResult<Expr> {
+        let mut expr = self.parse_unary()?;
+        while self.eat_keyword("as") {
+            let ty = self.parse_type()?;
+            expr = Expr::Cast(ExprCast {
+                expr: Box::new(expr),
+                ty,
+            });
+        }
+        Ok(expr)
+    }
+
+    /// Parses `!x`, `-x`, `*x`, `&x`, `&mut x` and `&raw const x`.
+    fn parse_unary(&mut self) -> PResult<Expr> {
+        let op = if self.eat_punct("!") {
+            UnOp::Not
+        } else if self.eat_punct("-") {
+            let expr = self.parse_unary()?;
+            return Ok(match expr {
+                Expr::Lit(Lit::Int(mut lit)) if !lit.is_negative => {
+                    lit.is_negative = true;
+                    Expr::Lit(Lit::Int(lit))
+                }
+                Expr::Lit(Lit::Float(mut lit)) if !lit.value.starts_with('-') => {
+                    lit.value.insert(0, '-');
+                    Expr::Lit(Lit::Float(lit))
+                }
+                expr => Expr::Unary(ExprUnary {
+                    op: UnOp::Neg,
+                    expr: Box::new(expr),
+                }),
+            });
+        } else if self.eat_punct("*") {
+            UnOp::Deref
+        } else if self.eat_punct("&") {
+            let is_raw = self.is_keyword("raw")
+                && (self.is_ident_nth(1, "const") || self.is_ident_nth(1, "mut"));
+            if is_raw {
+                self.bump();
+                let is_mut = self.eat_keyword("mut");
+                if !is_mut {
+                    self.expect_keyword("const")?;
+                }
+                let expr = Box::new(self.parse_unary()?);
+                return Ok(Expr::RawRef(ExprRawRef { is_mut, expr }));
+            }
+            let is_mut = self.eat_keyword("mut");
+            let expr = Box::new(self.parse_unary()?);
+            return Ok(Expr::Reference(ExprRef { is_mut, expr }));
+        } else {
+            return self.parse_postfix();
+        };
+        let expr = Box::new(self.parse_unary()?);
+        Ok(Expr::Unary(ExprUnary { op, expr }))
+    }
+
+    /// Parses a primary expression followed by calls, indexing, field
+    /// accesses, method calls and `.await`.
+    fn parse_postfix(&mut self) -> PResult<Expr> {
+        let expr = self.parse_primary()?;
+        self.parse_postfix_ops(expr, false)
+    }
+
+    /// Parses the postfix operators after `expr`. With `dots_only`, only
+    /// `.` accesses are parsed, as after a block-like statement.
+    fn parse_postfix_ops(&mut self, mut expr: Expr, dots_only: bool) -> PResult<Expr> {
+        loop {
+            if self.is_punct("?") {
+                return Err(self.unsupported(self.peek().span, "`?` operators"));
+            }
+            if self.peek_op() == Some(".") {
+                self.bump();
+                expr = self.parse_dot_access(expr)?;
+            } else if dots_only {
+                return Ok(expr);
+            } else if self.eat_open(Delimiter::Parenthesis) {
+                let args = self.comma_list(Delimiter::Parenthesis, Self::parse_expr_nested)?;
+                expr = Expr::Call(ExprCall {
+                    func: Box::new(expr),
+                    args,
+                });
+            } else if self.eat_open(Delimiter::Bracket) {
+                let index = Box::new(self.parse_expr_nested()?);
+                self.expect_close(Delimiter::Bracket)?;
+                expr = Expr::Index(ExprIndex {
+                    expr: Box::new(expr),
+                    index,
+                });
+            } else {
+                return Ok(expr);
+            }
+        }
+    }
+
+    /// Parses what follows a `.`: `await`, a method call, a field or a tuple
+    /// index.
+    fn parse_dot_access(&mut self, expr: Expr) -> PResult<Expr> {
+        let expr = Box::new(expr);
+        if self.eat_keyword("await") {
+            return Ok(Expr::Await(ExprAwait { expr }));
+        }
+        if let TokenKind::Lit(lit) = self.peek().kind.clone() {
+            let index = match &lit {
+                Lit::Int(int) if int.suffix.is_none() => Some(int.value.to_string()),
+                // `t.0.1` is lexed as `t`, `.` and the float `0.1`.
+                Lit::Float(float) if float.suffix.is_none() => Some(float.value.clone()),
+                _ => None,
+            };
+            let Some(index) =
+                index.filter(|index| index.chars().all(|c| c.is_ascii_digit() || c == '.'))
+            else {
+                return Err(self.unexpected("a field name"));
+            };
+            self.bump();
+            let mut expr = expr;
+            for member in index.split('.') {
+                expr = Box::new(Expr::Field(ExprField {
+                    expr,
+                    member: Ident::new(member, false),
+                }));
+            }
+            return Ok(*expr);
+        }
+        let member = self.ident()?;
+        if self.is_punct("::") {
+            return Err(self.unsupported(self.peek().span, "turbofish on method calls"));
+        }
+        if self.eat_open(Delimiter::Parenthesis) {
+            let args = self.comma_list(Delimiter::Parenthesis, Self::parse_expr_nested)?;
+            return Ok(Expr::MethodCall(ExprMethodCall {
+                receiver: expr,
+                method: member,
+                args,
+            }));
+        }
+        Ok(Expr::Field(ExprField { expr, member }))
+    }
+
+    /// Parses a literal, including a negative number and `true` or `false`.
+    pub(super) fn parse_lit_expr(&mut self) -> PResult<Expr> {
+        if self.is_keyword("true") || self.is_keyword("false") {
+            let value = self.is_keyword("true");
+            self.bump();
+            return Ok(Expr::Lit(Lit::Bool(LitBool { value })));
+        }
+        if self.eat_punct("-") {
+            return match self.parse_lit_expr()? {
+                Expr::Lit(Lit::Int(mut lit)) => {
+                    lit.is_negative = true;
+                    Ok(Expr::Lit(Lit::Int(lit)))
+                }
+                Expr::Lit(Lit::Float(mut lit)) => {
+                    lit.value.insert(0, '-');
+                    Ok(Expr::Lit(Lit::Float(lit)))
+                }
+                _ => Err(self.error(self.span_from(self.prev_end()), "expected a number")),
+            };
+        }
+        match self.peek().kind.clone() {
+            TokenKind::Lit(lit) => {
+                self.bump();
+                Ok(Expr::Lit(lit))
+            }
+            _ => Err(self.unexpected("a literal")),
+        }
+    }
+
+    fn parse_primary(&mut self) -> PResult<Expr> {
+        let start = self.start();
+        if matches!(self.peek().kind, TokenKind::Lit(_))
+            || self.is_keyword("true")
+            || self.is_keyword("false")
+        {
+            return self.parse_lit_expr();
+        }
+        if self.eat_open(Delimiter::Parenthesis) {
+            return self.with_struct_literals(true, |p| {
+                let mut elems = ThinVec::new();
+                let mut has_trailing_comma = false;
+                while !p.eat_close(Delimiter::Parenthesis) {
+                    elems.push(p.parse_expr()?);
+                    has_trailing_comma = p.eat_punct(",");
+                    if !has_trailing_comma && !p.is_close(Delimiter::Parenthesis) {
+                        return Err(p.unexpected("`,` or `)`"));
+                    }
+                }
+                if elems.len() == 1 && !has_trailing_comma {
+                    let expr = Box::new(elems.remove(0));
+                    return Ok(Expr::Paren(ExprParen { expr }));
+                }
+                Ok(Expr::Tuple(ExprTuple { elems }))
+            });
+        }
+        if self.eat_open(Delimiter::Bracket) {
+            return self.with_struct_literals(true, |p| {
+                let mut elems = ThinVec::new();
+                while !p.eat_close(Delimiter::Bracket) {
+                    elems.push(p.parse_expr()?);
+                    if p.is_punct(";") {
+                        return Err(p.unsupported(p.peek().span, "array repeat expressions"));
+                    }
+                    if !p.eat_punct(",") && !p.is_close(Delimiter::Bracket) {
+                        return Err(p.unexpected("`,` or `]`"));
+                    }
+                }
+                Ok(Expr::Array(ExprArray { elems }))
+            });
+        }
+        if self.is_open(Delimiter::Brace) {
+            let block = self.parse_block()?;
+            return Ok(Expr::Block(ExprBlock {
+                block: block.into(),
+            }));
+        }
+        if self.is_lifetime() {
+            return Err(self.unsupported(self.peek().span, "labels"));
+        }
+        if self.peek_op() == Some("|") || self.peek_op() == Some("||") {
+            return self.parse_closure();
+        }
+        if self.eat_keyword("_") {
+            return Ok(Expr::Infer(ExprInfer));
+        }
+        if self.is_open_nth(1, Delimiter::Brace) {
+            let block_kind = ["async", "const", "gen", "try"]
+                .into_iter()
+                .find(|keyword| self.is_keyword(keyword));
+            if let Some(keyword) = block_kind {
+                self.bump();
+                let block = self.parse_block()?;
+                return Ok(match keyword {
+                    "async" => Expr::Async(ExprAsync { block }),
+                    "const" => Expr::Const(ExprConst { block }),
+                    "gen" => Expr::Gen(ExprGen { block }),
+                    _ => Expr::Try(ExprTry { block }),
+                });
+            }
+        }
+        if self.eat_keyword("if") {
+            return self.parse_if();
+        }
+        if self.eat_keyword("match") {
+            return self.parse_match();
+        }
+        if self.eat_keyword("loop") {
+            let body = self.parse_block()?;
+            return Ok(Expr::Loop(ExprLoop { body }));
+        }
+        if self.eat_keyword("while") {
+            if self.is_keyword("let") {
+                return Err(self.unsupported(self.peek().span, "`let` expressions"));
+            }
+            let cond = Box::new(self.parse_expr_no_struct()?);
+            let body = self.parse_block()?;
+            return Ok(Expr::While(ExprWhile { cond, body }));
+        }
+        if self.eat_keyword("for") {
+            let pat = self.parse_pat()?;
+            self.expect_keyword("in")?;
+            let expr = Box::new(self.parse_expr_no_struct()?);
+            let body = self.parse_block()?;
+            return Ok(Expr::For(ExprFor { pat, expr, body }));
+        }
+        if self.eat_keyword("return") {
+            let expr = self.parse_optional_operand()?;
+            return Ok(Expr::Return(ExprReturn { expr }));
+        }
+        if self.eat_keyword("yield") {
+            let expr = self.parse_optional_operand()?;
+            return Ok(Expr::Yield(ExprYield { expr }));
+        }
+        if self.eat_keyword("become") {
+            let expr = Box::new(self.parse_expr()?);
+            return Ok(Expr::Become(ExprBecome { expr }));
+        }
+        if self.is_keyword("break") || self.is_keyword("continue") {
+            let is_break = self.is_keyword("break");
+            self.bump();
+            if self.is_lifetime() {
+                return Err(self.unsupported(self.peek().span, "labels"));
+            }
+            if is_break && self.can_begin_expr() {
+                return Err(self.unsupported(self.peek().span, "`break` values"));
+            }
+            return Ok(if is_break {
+                Expr::Break(ExprBreak)
+            } else {
+                Expr::Continue(ExprContinue)
+            });
+        }
+        let unsupported = [
+            ("let", "`let` expressions"),
+            ("unsafe", "`unsafe` blocks"),
+            ("move", "`move` closures"),
+            ("async", "async closures"),
+            ("static", "static closures"),
+            ("box", "`box` expressions"),
+        ];
+        for (keyword, what) in unsupported {
+            if self.is_keyword(keyword) {
+                self.bump();
+                return Err(self.unsupported(self.span_from(start), what));
+            }
+        }
+        if self.is_punct("<") || self.is_punct("::") {
+            return Err(self.unsupported(self.peek().span, "qualified paths"));
+        }
+        if !self.is_path_start() {
+            return Err(self.unexpected("an expression"));
+        }
+        let path = self.parse_path(PathStyle::Expr)?;
+        if self.is_punct("!") && !self.is_punct("!=") {
+            self.bump();
+            return Ok(Expr::MacroCall(self.parse_macro_call(path)?));
+        }
+        if self.is_open(Delimiter::Brace) && !self.no_struct_literal {
+            return self.parse_struct_expr(path);
+        }
+        Ok(Expr::Path(ExprPath { path }))
+    }
+
+    fn parse_optional_operand(&mut self) -> PResult<Option<Box<Expr>>> {
+        if self.can_begin_expr() {
+            Ok(Some(Box::new(self.parse_expr()?)))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Parses the rest of an `if` expression, after the `if`.
+    fn parse_if(&mut self) -> PResult<Expr> {
+        if self.is_keyword("let") {
+            return Err(self.unsupported(self.peek().span, "`let` expressions"));
+        }
+        let cond = Box::new(self.parse_expr_no_struct()?);
+        let then_branch = self.parse_block()?;
+        let else_branch = if self.eat_keyword("else") {
+            let expr = if self.eat_keyword("if") {
+                self.parse_if()?
+            } else {
+                Expr::Block(ExprBlock {
+                    block: self.parse_block()?.into(),
+                })
+            };
+            Some(expr.into())
+        } else {
+            None
+        };
+        Ok(Expr::If(ExprIf {
+            cond,
+            then_branch,
+            else_branch,
+        }))
+    }
+
+    /// Parses the rest of a `match` expression, after the `match`.
+    fn parse_match(&mut self) -> PResult<Expr> {
+        let expr = Box::new(self.parse_expr_no_struct()?);
+        let arms = self.with_struct_literals(true, |p| {
+            p.parse_md_list(
+                |p, md| {
+                    let pat = p.parse_pat()?;
+                    let guard = if p.eat_keyword("if") {
+                        Some(Box::new(p.parse_expr()?))
+                    } else {
+                        None
+                    };
+                    p.expect_punct("=>")?;
+                    let body = Box::new(p.parse_stmt_expr()?);
+                    Ok(Arm {
+                        pat,
+                        guard,
+                        body,
+                        md,
+                    })
+                },
+                |arm| &mut arm.md,
+                |arm| !is_block_like(&arm.body),
+            )
+        })?;
+        Ok(Expr::Match(ExprMatch { expr, arms }))
+    }
+
+    /// Parses a closure, starting at the `|` or `||`.
+    fn parse_closure(&mut self) -> PResult<Expr> {
+        let mut inputs = ThinVec::new();
+        if !self.eat_punct("||") {
+            self.expect_punct("|")?;
+            while !self.eat_punct("|") {
+                let md = leading_md(ThinVec::new(), self.outer_attrs()?);
+                let mut pat = self.parse_pat_no_alt()?;
+                if self.eat_punct(":") {
+                    let ty = Box::new(self.parse_type()?);
+                    pat = Pat::Type(PatType {
+                        pat: Box::new(pat),
+                        ty,
+                    });
+                }
+                inputs.push(ClosureParam { pat, md });
+                if !self.eat_punct(",") && !self.is_punct("|") {
+                    return Err(self.unexpected("`,` or `|`"));
+                }
+            }
+        }
+        if self.is_punct("->") {
+            return Err(self.unsupported(self.peek().span, "closure return types"));
+        }
+        let body = Box::new(self.parse_expr()?);
+        Ok(Expr::Closure(ExprClosure { inputs, body }))
+    }
+
+    /// Parses `Path { field: value, shorthand }`, starting at the `{`.
+    fn parse_struct_expr(&mut self, path: Path) -> PResult<Expr> {
+        let fields = self.parse_md_list(
+            |p, md| {
+                if p.is_punct("..") {
+                    return Err(p.unsupported(p.peek().span, "struct base expressions"));
+                }
+                let member = p.ident()?;
+                let value = if p.eat_punct(":") {
+                    p.parse_expr()?
+                } else {
+                    Expr::Path(ExprPath {
+                        path: Path {
+                            segments: [PathSegment {
+                                ident: member.clone(),
+                                args: None,
+                            }]
+                            .into_iter()
+                            .collect(),
+                        },
+                    })
+                };
+                Ok(FieldValue { member, value, md })
+            },
+            |field| &mut field.md,
+            |_| true,
+        )?;
+        Ok(Expr::Struct(ExprStruct { path, fields }))
+    }
+
+    /// Parses the delimited tokens of a macro invocation, after the `!`.
+    pub(super) fn parse_macro_call(&mut self, path: Path) -> PResult<ExprMacroCall> {
+        if self.is_ident() {
+            return Err(self.unsupported(self.peek().span, "macro definitions"));
+        }
+        let delimiter = match self.peek().kind {
+            TokenKind::Open(delimiter) => delimiter,
+            _ => return Err(self.unexpected("`(`, `[` or `{`")),
+        };
+        self.bump();
+        let tokens = self.parse_token_stream(delimiter)?;
+        Ok(ExprMacroCall {
+            path,
+            delimiter,
+            tokens,
+        })
+    }
+
+    /// Parses tokens up to and including the `close` delimiter.
+    fn parse_token_stream(&mut self, close: Delimiter) -> PResult<TokenStream> {
+        let mut tokens = ThinVec::new();
+        while !self.eat_close(close) {
+            let token = self.bump();
+            match token.kind {
+                TokenKind::Ident { name, is_raw } => {
+                    let name = if is_raw { format!("r#{name}") } else { name };
+                    tokens.push(TokenTree::Ident(name));
+                }
+                TokenKind::Lifetime(name) => {
+                    tokens.push(TokenTree::Punct(Punct {
+                        ch: '\'',
+                        spacing: Spacing::Joint,
+                    }));
+                    tokens.push(TokenTree::Ident(name));
+                }
+                TokenKind::Lit(lit) => tokens.push(TokenTree::Literal(lit)),
+                TokenKind::Punct { ch, joint } => {
+                    let spacing = if joint {
+                        Spacing::Joint
+                    } else {
+                        Spacing::Alone
+                    };
+                    tokens.push(TokenTree::Punct(Punct { ch, spacing }));
+                }
+                TokenKind::Open(delimiter) => {
+                    let stream = self.parse_token_stream(delimiter)?;
+                    tokens.push(TokenTree::Group(Group { delimiter, stream }));
+                }
+                TokenKind::Close(_) | TokenKind::Eof => {
+                    self.pos -= usize::from(token.kind != TokenKind::Eof);
+                    let close = delimiter_chars(close).1;
+                    return Err(self.unexpected(&format!("`{close}`")));
+                }
+            }
+        }
+        Ok(TokenStream { tokens })
+    }
+
+    /// Parses the statements of a block, up to the closing `}`.
+    ///
+    /// Returns the statements, whether the block has a trailing semicolon,
+    /// and the comments after the last statement.
+    pub(super) fn parse_stmts(&mut self) -> PResult<(ThinVec<Stmt>, bool, ThinVec<Comment>)> {
+        let mut stmts = ThinVec::new();
+        let mut is_tail_expr = false;
+        loop {
+            if self.eat_punct(";") {
+                continue;
+            }
+            if self.is_close(Delimiter::Brace) || self.is_eof() {
+                break;
+            }
+            let mut comments = self.leading_comments();
+            let attrs = self.outer_attrs()?;
+            comments.extend(self.leading_comments());
+            is_tail_expr = false;
+            let mut stmt = if self.eat_keyword("let") {
+                Stmt::Local(self.parse_local(leading_md(comments, attrs))?)
+            } else if self.is_item_start() {
+                Stmt::Item(self.parse_item_kind(comments, attrs)?)
+            } else {
+                let md = leading_md(comments, attrs);
+                let expr = self.parse_stmt_expr()?;
+                if !self.eat_punct(";") {
+                    if !self.is_close(Delimiter::Brace) && !is_block_like(&expr) {
+                        return Err(self.unexpected("`;` or `}`"));
+                    }
+                    is_tail_expr = self.is_close(Delimiter::Brace);
+                }
+                Stmt::Expr(StmtExpr { expr, md })
+            };
+            set_eol_comment(stmt.md_mut(), self.eol_comment());
+            stmts.push(stmt);
+        }
+        let trailing = self.trailing_comments();
+        Ok((stmts, !is_tail_expr, trailing))
+    }
+
+    /// Parses the expression of a statement or match arm, which ends after a
+    /// block-like expression unless it is followed by a `.`.
+    fn parse_stmt_expr(&mut self) -> PResult<Expr> {
+        if self.is_block_like_start() {
+            let expr = self.parse_primary()?;
+            self.parse_postfix_ops(expr, true)
+        } else {
+            self.parse_expr()
+        }
+    }
+
+    /// Whether the next tokens start an expression that ends with a block.
+    fn is_block_like_start(&self) -> bool {
+        if self.is_open(Delimiter::Brace) {
+            return true;
+        }
+        if ["if", "match", "loop", "while", "for"]
+            .iter()
+            .any(|keyword| self.is_keyword(keyword))
+        {
+            return true;
+        }
+        ["async", "const", "gen", "try"]
+            .iter()
+            .any(|keyword| self.is_keyword(keyword))
+            && self.is_open_nth(1, Delimiter::Brace)
+    }
+
+    /// Parses the rest of a `let` statement, after the `let`.
+    fn parse_local(&mut self, md: Option<Box<Md>>) -> PResult<Local> {
+        let pat = self.parse_pat()?;
+        let ty = if self.eat_punct(":") {
+            Some(self.parse_type()?)
+        } else {
+            None
+        };
+        let mut expr = None;
+        let mut else_block = None;
+        if self.eat_punct("=") {
+            expr = Some(self.parse_expr()?);
+            if self.eat_keyword("else") {
+                else_block = Some(Box::new(self.parse_block()?));
+            }
+        }
+        self.expect_punct(";")?;
+        Ok(Local {
+            pat,
+            ty,
+            expr,
+            else_block,
+            md,
+        })
+    }
+}
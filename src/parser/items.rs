@@ -0,0 +1,880 @@
+//! Parsing of files, items and attributes.
+
+use crate::ast::items::{Item, TraitItem, TraitItemFn};
+use crate::ast::{
+    Abi, AssociatedConst, AssociatedType, Attribute, Comment, Delimiter, Expr, ExternalItem,
+    ExternalStatic, Field, File, GenericParams, Ident, ImplItem, ItemConst, ItemEnum,
+    ItemExternBlock, ItemExternCrate, ItemExternType, ItemFn, ItemImpl, ItemMacro, ItemMod,
+    ItemStatic, ItemStruct, ItemTrait, ItemTraitAlias, ItemTypeAlias, ItemUnion, ItemUse, Lit, Md,
+    Meta, MetaList, MetaNameValue, Pat, PatIdent, PatReference, PatType, Signature, Variant,
+    Visibility,
+};
+use crate::parser::lexer::TokenKind;
+use crate::parser::types::PathStyle;
+use crate::parser::{PResult, Parser, extend_trailing, into_md, leading_md, set_eol_comment};
+use crate::pretty;
+use thin_vec::ThinVec;
+
+/// The qualifiers before `fn`, e.g. `const unsafe extern "C"`.
+#[derive(Default)]
+struct FnQualifiers {
+    is_const: bool,
+    is_async: bool,
+    is_gen: bool,
+    is_safe: bool,
+    is_unsafe: bool,
+    abi: Option<Abi>,
+}
+
+fn trait_item_md(item: &mut TraitItem) -> &mut Option<Box<Md>> {
+    match item {
+        TraitItem::Fn(item) => &mut item.md,
+        TraitItem::Const(item) => &mut item.md,
+    }
+}
+
+fn impl_item_md(item: &mut ImplItem) -> &mut Option<Box<Md>> {
+    match item {
+        ImplItem::Fn(item) => &mut item.md,
+        ImplItem::Type(item) => &mut item.md,
+        ImplItem::Const(item) => &mut item.md,
+    }
+}
+
+fn external_item_md(item: &mut ExternalItem) -> &mut Option<Box<Md>> {
+    match item {
+        ExternalItem::Static(item) => &mut item.md,
+        ExternalItem::Fn(item) => &mut item.md,
+        ExternalItem::Macro(item) => &mut item.md,
+        ExternalItem::Type(item) => &mut item.md,
+    }
+}
+
+impl Parser<'_> {
+    /// Parses a whole file: inner attributes and doc comments, then items.
+    pub(super) fn parse_file(&mut self) -> PResult<File> {
+        let mut md = Md::default();
+        while self.is_inner_attr() {
+            md.comments.extend(self.leading_comments());
+            md.attrs.push(self.inner_attr()?);
+        }
+        // Comments up to the last `//!` document the file, the rest belong
+        // to the first item.
+        let next = self.start();
+        let pending = &self.comments[self.next_comment..];
+        let before_next = pending.partition_point(|comment| comment.span.start < next);
+        let header_len = pending[..before_next]
+            .iter()
+            .rposition(|comment| comment.is_inner)
+            .map_or(0, |i| i + 1);
+        let header = self.comments[self.next_comment..][..header_len].to_vec();
+        self.next_comment += header_len;
+        md.comments
+            .extend(header.into_iter().map(|comment| comment.comment));
+
+        let mut items: ThinVec<Item> = ThinVec::new();
+        while !self.is_eof() {
+            items.push(self.parse_item()?);
+        }
+        let trailing = self.trailing_comments();
+        match items.last_mut() {
+            Some(item) => extend_trailing(item.md_mut(), trailing),
+            None => md.comments.extend(trailing),
+        }
+        Ok(File {
+            items,
+            md: into_md(md),
+        })
+    }
+
+    /// Parses an item with its leading comments, attributes and
+    /// end-of-line comment.
+    pub(super) fn parse_item(&mut self) -> PResult<Item> {
+        let mut comments = self.leading_comments();
+        let attrs = self.outer_attrs()?;
+        comments.extend(self.leading_comments());
+        let mut item = self.parse_item_kind(comments, attrs)?;
+        set_eol_comment(item.md_mut(), self.eol_comment());
+        Ok(item)
+    }
+
+    /// Whether the next tokens start an item rather than an expression.
+    pub(super) fn is_item_start(&self) -> bool {
+        let item_keywords = [
+            "fn", "struct", "enum", "trait", "impl", "mod", "use", "type", "pub", "extern",
+        ];
+        if item_keywords.iter().any(|keyword| self.is_keyword(keyword)) {
+            return true;
+        }
+        if self.is_keyword("const") || self.is_keyword("unsafe") {
+            return !self.is_open_nth(1, Delimiter::Brace);
+        }
+        if self.is_keyword("static") {
+            return self.is_ident_at(1) || self.is_ident_nth(1, "mut");
+        }
+        if self.is_keyword("union") {
+            return self.is_ident_at(1);
+        }
+        if self.is_keyword("auto") {
+            return self.is_ident_nth(1, "trait");
+        }
+        if self.is_keyword("macro_rules") {
+            return self.is_punct_nth(1, "!");
+        }
+        self.is_fn_start()
+    }
+
+    /// Parses an item after its leading comments and attributes.
+    pub(super) fn parse_item_kind(
+        &mut self,
+        comments: ThinVec<Comment>,
+        attrs: ThinVec<Attribute>,
+    ) -> PResult<Item> {
+        let md = leading_md(comments, attrs);
+        let start = self.start();
+        let vis = self.parse_vis()?;
+        let has_vis = vis != Visibility::Default;
+        let reject_vis = |p: &Self| {
+            if has_vis {
+                Err(p.unsupported(p.span_from(start), "visibilities on this item"))
+            } else {
+                Ok(())
+            }
+        };
+
+        if self.eat_keyword("use") {
+            let path = self.parse_use_tree()?;
+            self.expect_punct(";")?;
+            return Ok(Item::Use(ItemUse { vis, md, path }));
+        }
+        if self.is_keyword("extern") && self.is_ident_nth(1, "crate") {
+            reject_vis(self)?;
+            self.bump();
+            self.bump();
+            let ident = self.path_segment_ident()?;
+            if self.is_keyword("as") {
+                return Err(self.unsupported(self.peek().span, "renamed extern crates"));
+            }
+            self.expect_punct(";")?;
+            return Ok(Item::ExternCrate(ItemExternCrate { ident, md }));
+        }
+        if self.is_extern_block_start() {
+            let is_unsafe = self.eat_keyword("unsafe");
+            let abi = self
+                .parse_abi()?
+                .expect("checked by `is_extern_block_start`");
+            let items = self.parse_extern_items()?;
+            return Ok(Item::ExternBlock(ItemExternBlock {
+                vis,
+                md,
+                is_unsafe,
+                abi,
+                items,
+            }));
+        }
+        if self.is_fn_start() {
+            let qualifiers = self.parse_fn_qualifiers()?;
+            let sig = self.parse_fn_sig(qualifiers)?;
+            let block = self.parse_block()?;
+            return Ok(Item::Fn(ItemFn {
+                vis,
+                md,
+                sig,
+                block,
+            }));
+        }
+        if self.eat_keyword("const") {
+            let ident = self.ident_or_underscore()?;
+            self.expect_punct(":")?;
+            let ty = self.parse_type()?;
+            self.expect_punct("=")?;
+            let expr = Box::new(self.parse_expr()?);
+            self.expect_punct(";")?;
+            return Ok(Item::Const(ItemConst {
+                vis,
+                ident,
+                md,
+                ty,
+                expr,
+            }));
+        }
+        if self.eat_keyword("static") {
+            let is_mut = self.eat_keyword("mut");
+            let ident = self.ident()?;
+            self.expect_punct(":")?;
+            let ty = self.parse_type()?;
+            self.expect_punct("=")?;
+            let expr = Box::new(self.parse_expr()?);
+            self.expect_punct(";")?;
+            return Ok(Item::Static(ItemStatic {
+                vis,
+                ident,
+                md,
+                is_mut,
+                ty,
+                expr,
+            }));
+        }
+        if self.eat_keyword("struct") {
+            let ident = self.ident()?;
+            let generics = self.parse_generic_params()?;
+            self.reject_where_clause()?;
+            if self.is_punct(";") || self.is_open(Delimiter::Parenthesis) {
+                return Err(self.unsupported(self.peek().span, "tuple and unit structs"));
+            }
+            let fields = self.parse_fields()?;
+            return Ok(Item::Struct(ItemStruct {
+                vis,
+                ident,
+                generics,
+                md,
+                fields,
+            }));
+        }
+        if self.is_keyword("union") && self.is_ident_at(1) {
+            self.bump();
+            let ident = self.ident()?;
+            let generics = self.parse_generic_params()?;
+            self.reject_where_clause()?;
+            let fields = self.parse_fields()?;
+            return Ok(Item::Union(ItemUnion {
+                vis,
+                ident,
+                generics,
+                md,
+                fields,
+            }));
+        }
+        if self.eat_keyword("enum") {
+            let ident = self.ident()?;
+            let generics = self.parse_generic_params()?;
+            self.reject_where_clause()?;
+            let variants = self.parse_variants()?;
+            return Ok(Item::Enum(ItemEnum {
+                vis,
+                ident,
+                generics,
+                md,
+                variants,
+            }));
+        }
+        if self.is_keyword("impl") || self.is_keyword("unsafe") && self.is_ident_nth(1, "impl") {
+            reject_vis(self)?;
+            return self.parse_impl(md).map(Item::Impl);
+        }
+        if self.is_keyword("unsafe") || self.is_keyword("auto") {
+            self.bump();
+            return Err(self.unsupported(self.span_from(start), "`unsafe` and `auto` traits"));
+        }
+        if self.eat_keyword("trait") {
+            return self.parse_trait(vis, md, reject_vis);
+        }
+        if self.eat_keyword("type") {
+            let ident = self.ident()?;
+            let generics = self.parse_generic_params()?;
+            if self.is_punct(":") {
+                return Err(self.unsupported(self.peek().span, "bounds on type aliases"));
+            }
+            self.reject_where_clause()?;
+            self.expect_punct("=")?;
+            let ty = self.parse_type()?;
+            self.expect_punct(";")?;
+            return Ok(Item::TypeAlias(ItemTypeAlias {
+                vis,
+                ident,
+                md,
+                generics,
+                ty,
+            }));
+        }
+        if self.eat_keyword("mod") {
+            let ident = self.ident()?;
+            let content = if self.eat_punct(";") {
+                None
+            } else {
+                self.expect_open_list(Delimiter::Brace)?;
+                if self.is_inner_attr() {
+                    return Err(self.unsupported(self.peek().span, "inner attributes in modules"));
+                }
+                let mut items: ThinVec<Item> = ThinVec::new();
+                while !self.is_close(Delimiter::Brace) {
+                    if self.is_eof() {
+                        return Err(self.unexpected("`}`"));
+                    }
+                    items.push(self.parse_item()?);
+                }
+                let trailing = self.trailing_comments();
+                if let Some(item) = items.last_mut() {
+                    extend_trailing(item.md_mut(), trailing);
+                }
+                self.expect_close(Delimiter::Brace)?;
+                Some(items)
+            };
+            return Ok(Item::Mod(ItemMod {
+                vis,
+                ident,
+                md,
+                content,
+            }));
+        }
+        if self.is_path_start() && !has_vis {
+            let path = self.parse_path(PathStyle::Expr)?;
+            self.expect_punct("!")?;
+            let mac = self.parse_macro_call(path)?;
+            if mac.delimiter != Delimiter::Brace {
+                self.expect_punct(";")?;
+            }
+            return Ok(Item::Macro(ItemMacro {
+                md,
+                expr: Box::new(Expr::MacroCall(mac)),
+            }));
+        }
+        Err(self.unexpected("an item"))
+    }
+
+    /// Parses `pub` or `pub(crate)`.
+    fn parse_vis(&mut self) -> PResult<Visibility> {
+        if !self.eat_keyword("pub") {
+            return Ok(Visibility::Default);
+        }
+        if !self.is_open(Delimiter::Parenthesis) {
+            return Ok(Visibility::Public);
+        }
+        if self.is_ident_nth(1, "crate") && matches!(self.peek_nth(2).kind, TokenKind::Close(_)) {
+            self.bump();
+            self.bump();
+            self.bump();
+            return Ok(Visibility::Crate);
+        }
+        Err(self.unsupported(
+            self.peek().span,
+            "restricted visibilities other than `pub(crate)`",
+        ))
+    }
+
+    /// Parses an identifier, or `_` as used by `const _: () = ...;`.
+    fn ident_or_underscore(&mut self) -> PResult<Ident> {
+        if self.eat_keyword("_") {
+            return Ok(Ident::new("_", false));
+        }
+        self.ident()
+    }
+
+    /// Parses a use tree, such as `std::{fmt, io::Write as _}`, into the
+    /// string stored by [`ItemUse`].
+    fn parse_use_tree(&mut self) -> PResult<String> {
+        let mut tree = String::new();
+        if self.eat_punct("::") {
+            tree.push_str("::");
+        }
+        loop {
+            if self.eat_punct("*") {
+                tree.push('*');
+                return Ok(tree);
+            }
+            if self.eat_open(Delimiter::Brace) {
+                let trees = self.comma_list(Delimiter::Brace, Self::parse_use_tree)?;
+                tree.push('{');
+                tree.push_str(&trees.join(", "));
+                tree.push('}');
+                return Ok(tree);
+            }
+            tree.push_str(&pretty(&self.path_segment_ident()?));
+            if !self.eat_punct("::") {
+                break;
+            }
+            tree.push_str("::");
+        }
+        if self.eat_keyword("as") {
+            tree.push_str(" as ");
+            tree.push_str(&pretty(&self.ident_or_underscore()?));
+        }
+        Ok(tree)
+    }
+
+    /// Parses a braced list of nodes with metadata, such as struct fields or
+    /// match arms, starting at the `{`.
+    ///
+    /// `item` parses one node given its leading metadata. `needs_comma`
+    /// tells whether a node must be followed by a `,` when it is not the
+    /// last one.
+    pub(super) fn parse_md_list<T>(
+        &mut self,
+        mut item: impl FnMut(&mut Self, Option<Box<Md>>) -> PResult<T>,
+        md_of: impl Fn(&mut T) -> &mut Option<Box<Md>>,
+        needs_comma: impl Fn(&T) -> bool,
+    ) -> PResult<ThinVec<T>> {
+        self.expect_open_list(Delimiter::Brace)?;
+        let mut items: ThinVec<T> = ThinVec::new();
+        while !self.is_close(Delimiter::Brace) {
+            let mut comments = self.leading_comments();
+            let attrs = self.outer_attrs()?;
+            comments.extend(self.leading_comments());
+            let mut node = item(self, leading_md(comments, attrs))?;
+            if !self.eat_punct(",") && !self.is_close(Delimiter::Brace) && needs_comma(&node) {
+                return Err(self.unexpected("`,` or `}`"));
+            }
+            set_eol_comment(md_of(&mut node), self.eol_comment());
+            items.push(node);
+        }
+        let trailing = self.trailing_comments();
+        if let Some(node) = items.last_mut() {
+            extend_trailing(md_of(node), trailing);
+        }
+        self.expect_close(Delimiter::Brace)?;
+        Ok(items)
+    }
+
+    /// Parses the named fields of a struct or union.
+    fn parse_fields(&mut self) -> PResult<ThinVec<Field>> {
+        self.parse_md_list(
+            |p, md| {
+                let start = p.start();
+                if p.parse_vis()? != Visibility::Default {
+                    return Err(p.unsupported(p.span_from(start), "field visibilities"));
+                }
+                let ident = p.ident()?;
+                p.expect_punct(":")?;
+                let ty = p.parse_type()?;
+                Ok(Field { ident, ty, md })
+            },
+            |field| &mut field.md,
+            |_| true,
+        )
+    }
+
+    /// Parses the unit variants of an enum.
+    fn parse_variants(&mut self) -> PResult<ThinVec<Variant>> {
+        self.parse_md_list(
+            |p, md| {
+                let ident = p.ident()?;
+                if p.is_open(Delimiter::Parenthesis) || p.is_open(Delimiter::Brace) {
+                    return Err(p.unsupported(p.peek().span, "enum variants with fields"));
+                }
+                if p.is_punct("=") {
+                    return Err(p.unsupported(p.peek().span, "enum discriminants"));
+                }
+                Ok(Variant { ident, md })
+            },
+            |variant| &mut variant.md,
+            |_| true,
+        )
+    }
+
+    /// Whether the next tokens start `unsafe? extern "abi"? {`.
+    fn is_extern_block_start(&self) -> bool {
+        let n = usize::from(self.is_keyword("unsafe"));
+        if !self.is_ident_nth(n, "extern") {
+            return false;
+        }
+        let n = n
+            + 1
+            + usize::from(matches!(
+                self.peek_nth(n + 1).kind,
+                TokenKind::Lit(Lit::Str(_))
+            ));
+        self.is_open_nth(n, Delimiter::Brace)
+    }
+
+    /// Whether the next tokens start a function, possibly with qualifiers.
+    fn is_fn_start(&self) -> bool {
+        let mut n = 0;
+        loop {
+            match &self.peek_nth(n).kind {
+                TokenKind::Ident {
+                    name,
+                    is_raw: false,
+                } => match name.as_str() {
+                    "fn" => return true,
+                    "const" | "async" | "gen" | "safe" | "unsafe" | "extern" => n += 1,
+                    _ => return false,
+                },
+                TokenKind::Lit(Lit::Str(_)) if n > 0 && self.is_ident_nth(n - 1, "extern") => {
+                    n += 1
+                }
+                _ => return false,
+            }
+        }
+    }
+
+    fn parse_fn_qualifiers(&mut self) -> PResult<FnQualifiers> {
+        Ok(FnQualifiers {
+            is_const: self.eat_keyword("const"),
+            is_async: self.eat_keyword("async"),
+            is_gen: self.eat_keyword("gen"),
+            is_safe: self.eat_keyword("safe"),
+            is_unsafe: self.eat_keyword("unsafe"),
+            abi: self.parse_abi()?,
+        })
+    }
+
+    /// Parses a function signature, from the `fn` up to the body.
+    fn parse_fn_sig(&mut self, qualifiers: FnQualifiers) -> PResult<Signature> {
+        self.expect_keyword("fn")?;
+        let ident = self.ident()?;
+        let generics = self.parse_generic_params()?;
+        self.expect_open(Delimiter::Parenthesis)?;
+        let mut inputs = ThinVec::new();
+        let mut is_variadic = false;
+        while !self.eat_close(Delimiter::Parenthesis) {
+            let start = self.start();
+            if !self.outer_attrs()?.is_empty() {
+                return Err(
+                    self.unsupported(self.span_from(start), "attributes on function parameters")
+                );
+            }
+            if self.eat_punct("...") {
+                is_variadic = true;
+                self.eat_punct(",");
+                self.expect_close(Delimiter::Parenthesis)?;
+                break;
+            }
+            inputs.push(self.parse_fn_param()?);
+            if !self.eat_punct(",") && !self.is_close(Delimiter::Parenthesis) {
+                return Err(self.unexpected("`,` or `)`"));
+            }
+        }
+        let output = self.parse_return_type()?;
+        let where_clause = self.parse_where_clause()?;
+        Ok(Signature {
+            is_const: qualifiers.is_const,
+            is_async: qualifiers.is_async,
+            is_gen: qualifiers.is_gen,
+            is_safe: qualifiers.is_safe,
+            is_unsafe: qualifiers.is_unsafe,
+            abi: qualifiers.abi,
+            ident,
+            generics,
+            inputs,
+            is_variadic,
+            output,
+            where_clause,
+        })
+    }
+
+    /// Parses a function parameter: a receiver such as `&mut self`, or
+    /// `pat: Type`.
+    fn parse_fn_param(&mut self) -> PResult<Pat> {
+        let start = self.start();
+        let is_ref = self.is_punct("&");
+        let n = usize::from(is_ref);
+        if is_ref && self.is_lifetime_nth(1) {
+            self.bump();
+            self.bump();
+            return Err(self.unsupported(self.span_from(start), "receivers with lifetimes"));
+        }
+        let is_mut = self.is_ident_nth(n, "mut");
+        if !self.is_ident_nth(n + usize::from(is_mut), "self") {
+            let pat = self.parse_pat_no_alt()?;
+            self.expect_punct(":")?;
+            let ty = self.parse_type()?;
+            return Ok(Pat::Type(PatType {
+                pat: Box::new(pat),
+                ty: Box::new(ty),
+            }));
+        }
+        self.pos += n + usize::from(is_mut) + 1;
+        let receiver = if is_ref {
+            Pat::Reference(PatReference {
+                pat: Box::new(Pat::Ident(PatIdent {
+                    ident: Ident::new("self", false),
+                    is_mut: false,
+                })),
+                is_mut,
+            })
+        } else {
+            Pat::Ident(PatIdent {
+                ident: Ident::new("self", false),
+                is_mut,
+            })
+        };
+        if self.eat_punct(":") {
+            let ty = self.parse_type()?;
+            return Ok(Pat::Type(PatType {
+                pat: Box::new(receiver),
+                ty: Box::new(ty),
+            }));
+        }
+        Ok(receiver)
+    }
+
+    /// Parses an `impl` block, starting at `unsafe` or `impl`.
+    fn parse_impl(&mut self, md: Option<Box<Md>>) -> PResult<ItemImpl> {
+        let is_unsafe = self.eat_keyword("unsafe");
+        self.expect_keyword("impl")?;
+        let generics = if self.is_punct("<") {
+            self.parse_generic_params()?
+        } else {
+            GenericParams::default()
+        };
+        let is_negative = self.eat_punct("!");
+        let first = self.parse_type()?;
+        let (trait_, ty) = if self.eat_keyword("for") {
+            (Some(first), self.parse_type()?)
+        } else {
+            (None, first)
+        };
+        self.reject_where_clause()?;
+        let items = self.parse_md_list(
+            |p, md| {
+                let start = p.start();
+                let vis = p.parse_vis()?;
+                if p.eat_keyword("type") {
+                    if vis != Visibility::Default {
+                        return Err(
+                            p.unsupported(p.span_from(start), "visibilities on associated types")
+                        );
+                    }
+                    return p.parse_associated_type(md).map(ImplItem::Type);
+                }
+                if p.is_keyword("const") && !p.is_fn_start() {
+                    if vis != Visibility::Default {
+                        return Err(p.unsupported(
+                            p.span_from(start),
+                            "visibilities on associated constants",
+                        ));
+                    }
+                    p.bump();
+                    return p.parse_associated_const(md).map(ImplItem::Const);
+                }
+                if !p.is_fn_start() {
+                    return Err(p.unexpected("`fn`, `const` or `type`"));
+                }
+                let qualifiers = p.parse_fn_qualifiers()?;
+                let sig = p.parse_fn_sig(qualifiers)?;
+                let block = p.parse_block()?;
+                Ok(ImplItem::Fn(ItemFn {
+                    vis,
+                    md,
+                    sig,
+                    block,
+                }))
+            },
+            impl_item_md,
+            |_| false,
+        )?;
+        Ok(ItemImpl {
+            is_unsafe,
+            is_negative,
+            trait_,
+            ty,
+            items,
+            generics,
+            md,
+        })
+    }
+
+    /// Parses a trait or trait alias, after the `trait`.
+    fn parse_trait(
+        &mut self,
+        vis: Visibility,
+        md: Option<Box<Md>>,
+        reject_vis: impl Fn(&Self) -> PResult<()>,
+    ) -> PResult<Item> {
+        let ident = self.ident()?;
+        let generics = self.parse_generic_params()?;
+        if self.eat_punct("=") {
+            reject_vis(self)?;
+            if !generics.params.is_empty() {
+                return Err(self.unsupported(self.span_from(self.start()), "generic trait aliases"));
+            }
+            let bounds = self.parse_bounds()?.iter().map(pretty).collect();
+            self.expect_punct(";")?;
+            return Ok(Item::TraitAlias(ItemTraitAlias { ident, md, bounds }));
+        }
+        if self.is_punct(":") {
+            return Err(self.unsupported(self.peek().span, "supertraits"));
+        }
+        self.reject_where_clause()?;
+        let members = self.parse_md_list(
+            |p, md| {
+                if p.eat_keyword("type") {
+                    return p.parse_associated_type(md).map(TraitMember::Type);
+                }
+                if p.is_keyword("const") && !p.is_fn_start() {
+                    p.bump();
+                    let item = p.parse_associated_const(md)?;
+                    return Ok(TraitMember::Item(TraitItem::Const(item)));
+                }
+                if !p.is_fn_start() {
+                    return Err(p.unexpected("`fn`, `const` or `type`"));
+                }
+                let qualifiers = p.parse_fn_qualifiers()?;
+                let sig = p.parse_fn_sig(qualifiers)?;
+                let block = if p.eat_punct(";") {
+                    None
+                } else {
+                    Some(p.parse_block()?)
+                };
+                Ok(TraitMember::Item(TraitItem::Fn(TraitItemFn {
+                    sig,
+                    block,
+                    md,
+                })))
+            },
+            |member| match member {
+                TraitMember::Type(item) => &mut item.md,
+                TraitMember::Item(item) => trait_item_md(item),
+            },
+            |_| false,
+        )?;
+        let mut associated_types = ThinVec::new();
+        let mut items = ThinVec::new();
+        for member in members {
+            match member {
+                TraitMember::Type(item) => associated_types.push(item),
+                TraitMember::Item(item) => items.push(item),
+            }
+        }
+        Ok(Item::Trait(ItemTrait {
+            vis,
+            ident,
+            generics,
+            md,
+            associated_types,
+            items,
+        }))
+    }
+
+    /// Parses an associated type, after the `type`.
+    fn parse_associated_type(&mut self, md: Option<Box<Md>>) -> PResult<AssociatedType> {
+        let ident = self.ident()?;
+        let generics = self.parse_generic_params()?;
+        let bounds = if self.eat_punct(":") {
+            self.parse_bounds()?.into_iter().collect()
+        } else {
+            ThinVec::new()
+        };
+        self.reject_where_clause()?;
+        let default = if self.eat_punct("=") {
+            Some(self.parse_type()?)
+        } else {
+            None
+        };
+        self.expect_punct(";")?;
+        Ok(AssociatedType {
+            ident,
+            generics,
+            bounds,
+            default,
+            md,
+        })
+    }
+
+    /// Parses an associated constant, after the `const`.
+    fn parse_associated_const(&mut self, md: Option<Box<Md>>) -> PResult<AssociatedConst> {
+        let ident = self.ident_or_underscore()?;
+        self.expect_punct(":")?;
+        let ty = self.parse_type()?;
+        let expr = if self.eat_punct("=") {
+            Some(Box::new(self.parse_expr()?))
+        } else {
+            None
+        };
+        self.expect_punct(";")?;
+        Ok(AssociatedConst {
+            ident,
+            ty,
+            expr,
+            md,
+        })
+    }
+
+    /// Parses the items of an extern block, starting at the `{`.
+    fn parse_extern_items(&mut self) -> PResult<ThinVec<ExternalItem>> {
+        self.parse_md_list(
+            |p, md| {
+                let vis = p.parse_vis()?;
+                if p.is_fn_start() {
+                    let qualifiers = p.parse_fn_qualifiers()?;
+                    let sig = p.parse_fn_sig(qualifiers)?;
+                    p.expect_punct(";")?;
+                    return Ok(ExternalItem::Fn(ItemFn {
+                        vis,
+                        md,
+                        sig,
+                        block: Default::default(),
+                    }));
+                }
+                let is_safe = p.eat_keyword("safe");
+                let is_unsafe = p.eat_keyword("unsafe");
+                if p.eat_keyword("static") {
+                    let is_mut = p.eat_keyword("mut");
+                    let ident = p.ident()?;
+                    p.expect_punct(":")?;
+                    let ty = p.parse_type()?;
+                    p.expect_punct(";")?;
+                    return Ok(ExternalItem::Static(ExternalStatic {
+                        vis,
+                        ident,
+                        md,
+                        is_mut,
+                        is_safe,
+                        is_unsafe,
+                        ty,
+                    }));
+                }
+                if p.eat_keyword("type") {
+                    let ident = p.ident()?;
+                    p.expect_punct(";")?;
+                    return Ok(ExternalItem::Type(ItemExternType { vis, ident, md }));
+                }
+                if p.is_path_start() && vis == Visibility::Default && !is_safe && !is_unsafe {
+                    let path = p.parse_path(PathStyle::Expr)?;
+                    p.expect_punct("!")?;
+                    let mac = p.parse_macro_call(path)?;
+                    if mac.delimiter != Delimiter::Brace {
+                        p.expect_punct(";")?;
+                    }
+                    return Ok(ExternalItem::Macro(ItemMacro {
+                        md,
+                        expr: Box::new(Expr::MacroCall(mac)),
+                    }));
+                }
+                Err(p.unexpected("`fn`, `static` or `type`"))
+            },
+            external_item_md,
+            |_| false,
+        )
+    }
+
+    /// Parses the body of an attribute, after the `#` or `#!`.
+    pub(super) fn attr_body(&mut self) -> PResult<Meta> {
+        self.expect_open(Delimiter::Bracket)?;
+        let meta = self.parse_meta()?;
+        self.expect_close(Delimiter::Bracket)?;
+        Ok(meta)
+    }
+
+    /// Parses `path`, `path(metas)` or `path = literal`.
+    fn parse_meta(&mut self) -> PResult<Meta> {
+        let start = self.start();
+        if self.is_keyword("unsafe") {
+            return Err(self.unsupported(self.peek().span, "unsafe attributes"));
+        }
+        let path = self.parse_path(PathStyle::Expr)?;
+        if path.segments.iter().any(|segment| segment.args.is_some()) {
+            return Err(self.unsupported(self.span_from(start), "generic arguments in attributes"));
+        }
+        // Multi-segment paths such as `rustfmt::skip` are stored as a single
+        // identifier.
+        let path = Ident::new(pretty(&path), false);
+        if self.eat_open(Delimiter::Parenthesis) {
+            let metas = self.comma_list(Delimiter::Parenthesis, Self::parse_meta)?;
+            return Ok(Meta::List(MetaList { path, metas }));
+        }
+        if self.eat_punct("=") {
+            let Expr::Lit(value) = self.parse_lit_expr()? else {
+                unreachable!("`parse_lit_expr` returns literals")
+            };
+            return Ok(Meta::NameValue(MetaNameValue { path, value }));
+        }
+        Ok(Meta::Path(path))
+    }
+}
+
+/// A member of a trait, which [`ItemTrait`] stores in two lists.
+enum TraitMember {
+    Type(AssociatedType),
+    Item(TraitItem),
+}
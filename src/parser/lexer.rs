@@ -0,0 +1,603 @@
+//! Splits Rust source text into tokens and comments.
+//!
+//! Punctuation is produced one character at a time, like `proc_macro` does,
+//! with a flag telling whether the next character is also punctuation. The
+//! parser glues characters back into operators such as `::` or `>>=` when it
+//! needs them, which makes splitting `>>` in `Vec<Vec<u8>>` trivial.
+
+use crate::ast::{
+    Comment, Delimiter, FloatSuffix, IntRadix, IntSuffix, Lit, LitByte, LitByteStr, LitCStr,
+    LitChar, LitFloat, LitInt, LitStr,
+};
+use crate::parser::{ParseError, Span};
+
+/// The kind of a token.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum TokenKind {
+    /// An identifier or keyword.
+    Ident { name: String, is_raw: bool },
+    /// A lifetime or label, without the leading `'`.
+    Lifetime(String),
+    /// A literal. Booleans are lexed as identifiers.
+    Lit(Lit),
+    /// A single punctuation character. `joint` is set when the next character
+    /// is also punctuation, e.g. for the first `:` of `::`.
+    Punct { ch: char, joint: bool },
+    /// An opening delimiter.
+    Open(Delimiter),
+    /// A closing delimiter.
+    Close(Delimiter),
+    /// The end of the input.
+    Eof,
+}
+
+/// A token and its location in the source.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Token {
+    pub(crate) kind: TokenKind,
+    pub(crate) span: Span,
+}
+
+/// A comment and its location in the source.
+///
+/// Block comments are split into one `RawComment` per line, all sharing the
+/// span of the whole comment.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct RawComment {
+    pub(crate) comment: Comment,
+    pub(crate) span: Span,
+    /// Whether this is an inner doc comment (`//!` or `/*!`), which documents
+    /// the enclosing file or module.
+    pub(crate) is_inner: bool,
+}
+
+/// The output of the lexer.
+pub(crate) struct Lexed {
+    pub(crate) tokens: Vec<Token>,
+    pub(crate) comments: Vec<RawComment>,
+}
+
+const PUNCT: &str = "+-*/%^!&|=<>@.,;:#$?~\\";
+
+/// Lexes `src` into tokens, ending with a [`TokenKind::Eof`] token, and the
+/// comments found between them.
+pub(crate) fn lex(src: &str) -> Result<Lexed, ParseError> {
+    let mut lexer = Lexer {
+        src,
+        pos: 0,
+        tokens: Vec::new(),
+        comments: Vec::new(),
+    };
+    lexer.skip_shebang();
+    loop {
+        lexer.skip_whitespace_and_comments()?;
+        let start = lexer.pos;
+        let Some(c) = lexer.peek() else {
+            lexer.push(TokenKind::Eof, start);
+            break;
+        };
+        let kind = lexer.token(c)?;
+        lexer.push(kind, start);
+    }
+    Ok(Lexed {
+        tokens: lexer.tokens,
+        comments: lexer.comments,
+    })
+}
+
+struct Lexer<'a> {
+    src: &'a str,
+    pos: usize,
+    tokens: Vec<Token>,
+    comments: Vec<RawComment>,
+}
+
+fn is_ident_start(c: char) -> bool {
+    c == '_' || c.is_alphabetic()
+}
+
+fn is_ident_continue(c: char) -> bool {
+    c == '_' || c.is_alphanumeric()
+}
+
+impl<'a> Lexer<'a> {
+    fn rest(&self) -> &'a str {
+        &self.src[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn peek_nth(&self, n: usize) -> Option<char> {
+        self.rest().chars().nth(n)
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn push(&mut self, kind: TokenKind, start: usize) {
+        self.tokens.push(Token {
+            kind,
+            span: Span::new(start, self.pos),
+        });
+    }
+
+    fn error(&self, start: usize, message: impl Into<String>) -> ParseError {
+        ParseError::new(self.src, Span::new(start, self.pos.max(start + 1)), message)
+    }
+
+    fn skip_shebang(&mut self) {
+        if self.rest().starts_with("#!") && !self.rest()[2..].trim_start().starts_with('[') {
+            self.pos = self
+                .rest()
+                .find('\n')
+                .map_or(self.src.len(), |i| self.pos + i);
+        }
+    }
+
+    fn skip_whitespace_and_comments(&mut self) -> Result<(), ParseError> {
+        loop {
+            let rest = self.rest();
+            let trimmed = rest.trim_start();
+            self.pos += rest.len() - trimmed.len();
+            if trimmed.starts_with("//") {
+                self.line_comment();
+            } else if trimmed.starts_with("/*") {
+                self.block_comment()?;
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn line_comment(&mut self) {
+        let start = self.pos;
+        let end = self
+            .rest()
+            .find('\n')
+            .map_or(self.src.len(), |i| self.pos + i);
+        let text = self.src[start + 2..end].trim_end_matches('\r');
+        self.pos = end;
+        let (comment, is_inner) = if let Some(doc) = text.strip_prefix('/')
+            && !doc.starts_with('/')
+        {
+            (Comment::Doc(doc.to_string()), false)
+        } else {
+            (Comment::Line(text.to_string()), text.starts_with('!'))
+        };
+        self.comments.push(RawComment {
+            comment,
+            span: Span::new(start, end),
+            is_inner,
+        });
+    }
+
+    fn block_comment(&mut self) -> Result<(), ParseError> {
+        let start = self.pos;
+        self.pos += 2;
+        let mut depth = 1;
+        while depth > 0 {
+            if self.rest().starts_with("/*") {
+                depth += 1;
+                self.pos += 2;
+            } else if self.rest().starts_with("*/") {
+                depth -= 1;
+                self.pos += 2;
+            } else if self.bump().is_none() {
+                return Err(self.error(start, "unterminated block comment"));
+            }
+        }
+        let body = &self.src[start + 2..self.pos - 2];
+        let (body, is_doc, is_inner) = if let Some(doc) = body.strip_prefix('*')
+            && !doc.starts_with('*')
+            && !doc.is_empty()
+        {
+            (doc, true, false)
+        } else if let Some(doc) = body.strip_prefix('!') {
+            (doc, false, true)
+        } else {
+            (body, false, false)
+        };
+        let lines: Vec<&str> = body.lines().collect();
+        let span = Span::new(start, self.pos);
+        for (i, line) in lines.iter().enumerate() {
+            let line = line.trim_end();
+            // Drop the decoration of `/*\n * text\n */` style comments.
+            let line = match i {
+                0 => line,
+                _ => line
+                    .trim_start()
+                    .strip_prefix('*')
+                    .unwrap_or(line.trim_start()),
+            };
+            if line.trim().is_empty() && (i == 0 || i == lines.len() - 1) {
+                continue;
+            }
+            let text = if line.starts_with(' ') || line.is_empty() {
+                line.to_string()
+            } else {
+                format!(" {line}")
+            };
+            let comment = match (is_doc, is_inner) {
+                (true, _) => Comment::Doc(text),
+                (_, true) => Comment::Line(format!("!{text}")),
+                _ => Comment::Line(text),
+            };
+            self.comments.push(RawComment {
+                comment,
+                span,
+                is_inner,
+            });
+        }
+        Ok(())
+    }
+
+    fn token(&mut self, c: char) -> Result<TokenKind, ParseError> {
+        let start = self.pos;
+        match c {
+            '(' | '[' | '{' => {
+                self.bump();
+                Ok(TokenKind::Open(delimiter(c)))
+            }
+            ')' | ']' | '}' => {
+                self.bump();
+                Ok(TokenKind::Close(delimiter(c)))
+            }
+            '"' => {
+                self.bump();
+                let value = self.quoted('"', start, false)?;
+                self.no_suffix(start)?;
+                Ok(TokenKind::Lit(Lit::Str(LitStr { value })))
+            }
+            '\'' => self.quote(start),
+            '0'..='9' => self.number(start),
+            'b' | 'c' | 'r' if self.is_prefixed_literal() => self.prefixed_literal(start),
+            c if is_ident_start(c) => {
+                let is_raw =
+                    self.rest().starts_with("r#") && self.peek_nth(2).is_some_and(is_ident_start);
+                if is_raw {
+                    self.pos += 2;
+                }
+                let name = self.ident_chars();
+                Ok(TokenKind::Ident { name, is_raw })
+            }
+            c if PUNCT.contains(c) => {
+                self.bump();
+                let joint = self.peek().is_some_and(|next| PUNCT.contains(next));
+                Ok(TokenKind::Punct { ch: c, joint })
+            }
+            _ => {
+                self.bump();
+                Err(self.error(start, format!("unexpected character `{c}`")))
+            }
+        }
+    }
+
+    fn ident_chars(&mut self) -> String {
+        let start = self.pos;
+        while self.peek().is_some_and(is_ident_continue) {
+            self.bump();
+        }
+        self.src[start..self.pos].to_string()
+    }
+
+    fn is_prefixed_literal(&self) -> bool {
+        let rest = self.rest();
+        [
+            "b\"", "b'", "br\"", "br#", "c\"", "cr\"", "cr#", "r\"", "r##", "r#\"",
+        ]
+        .iter()
+        .any(|prefix| rest.starts_with(prefix))
+    }
+
+    fn prefixed_literal(&mut self, start: usize) -> Result<TokenKind, ParseError> {
+        let prefix = self.bump().unwrap_or_default();
+        if prefix == 'b' && self.eat('\'') {
+            let value = self.char_body(start, true)?;
+            self.no_suffix(start)?;
+            return Ok(TokenKind::Lit(Lit::Byte(LitByte { value: value as u8 })));
+        }
+        let value = if prefix == 'r' || self.eat('r') {
+            self.raw_string(start)?
+        } else {
+            self.bump();
+            self.quoted('"', start, prefix == 'b')?
+        };
+        self.no_suffix(start)?;
+        let lit = match prefix {
+            'b' => Lit::ByteStr(LitByteStr {
+                value: value.chars().map(|c| c as u8).collect(),
+            }),
+            'c' => Lit::CStr(LitCStr {
+                value: value.into_bytes(),
+            }),
+            _ => Lit::Str(LitStr { value }),
+        };
+        Ok(TokenKind::Lit(lit))
+    }
+
+    fn raw_string(&mut self, start: usize) -> Result<String, ParseError> {
+        let mut hashes = 0;
+        while self.eat('#') {
+            hashes += 1;
+        }
+        if !self.eat('"') {
+            return Err(self.error(start, "expected `\"` in raw string literal"));
+        }
+        let terminator = format!("\"{}", "#".repeat(hashes));
+        let Some(len) = self.rest().find(&terminator) else {
+            return Err(self.error(start, "unterminated raw string literal"));
+        };
+        let value = self.rest()[..len].replace("\r\n", "\n");
+        self.pos += len + terminator.len();
+        Ok(value)
+    }
+
+    /// Lexes the rest of a quoted literal after the opening quote. For byte
+    /// literals, every `char` of the result is a byte.
+    fn quoted(&mut self, quote: char, start: usize, is_byte: bool) -> Result<String, ParseError> {
+        let mut value = String::new();
+        loop {
+            match self.peek() {
+                None => return Err(self.error(start, "unterminated string literal")),
+                Some(c) if c == quote => {
+                    self.bump();
+                    return Ok(value);
+                }
+                Some('\\') if self.rest()[1..].starts_with(['\n', '\r']) => {
+                    self.bump();
+                    let rest = self.rest();
+                    self.pos += rest.len() - rest.trim_start().len();
+                }
+                Some('\r') if self.rest().starts_with("\r\n") => {
+                    self.pos += 2;
+                    value.push('\n');
+                }
+                Some(_) => value.push(self.char_or_escape(start, is_byte)?),
+            }
+        }
+    }
+
+    fn char_or_escape(&mut self, start: usize, is_byte: bool) -> Result<char, ParseError> {
+        let escape_start = self.pos;
+        let c = self.bump().unwrap_or_default();
+        if c != '\\' {
+            if is_byte && !c.is_ascii() {
+                return Err(self.error(escape_start, "non-ASCII character in byte literal"));
+            }
+            return Ok(c);
+        }
+        let escaped = match self.bump() {
+            Some('n') => '\n',
+            Some('r') => '\r',
+            Some('t') => '\t',
+            Some('\\') => '\\',
+            Some('0') => '\0',
+            Some('\'') => '\'',
+            Some('"') => '"',
+            Some('x') => {
+                let digits = self.rest().get(..2).unwrap_or_default();
+                let value = u8::from_str_radix(digits, 16)
+                    .ok()
+                    .filter(|value| is_byte || value.is_ascii());
+                let Some(value) = value else {
+                    return Err(self.error(escape_start, "invalid `\\x` escape"));
+                };
+                self.pos += 2;
+                value as char
+            }
+            Some('u') if !is_byte && self.eat('{') => {
+                let Some(len) = self.rest().find('}') else {
+                    return Err(self.error(escape_start, "unterminated `\\u{...}` escape"));
+                };
+                let digits = self.rest()[..len].replace('_', "");
+                self.pos += len + 1;
+                u32::from_str_radix(&digits, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| self.error(escape_start, "invalid unicode escape"))?
+            }
+            None => return Err(self.error(start, "unterminated literal")),
+            Some(other) => {
+                return Err(self.error(escape_start, format!("unknown escape `\\{other}`")));
+            }
+        };
+        Ok(escaped)
+    }
+
+    fn char_body(&mut self, start: usize, is_byte: bool) -> Result<char, ParseError> {
+        if matches!(self.peek(), None | Some('\'')) {
+            return Err(self.error(start, "empty character literal"));
+        }
+        let value = self.char_or_escape(start, is_byte)?;
+        if !self.eat('\'') {
+            return Err(self.error(start, "unterminated character literal"));
+        }
+        Ok(value)
+    }
+
+    /// Lexes a character literal or a lifetime, both of which start with `'`.
+    fn quote(&mut self, start: usize) -> Result<TokenKind, ParseError> {
+        self.bump();
+        let is_lifetime = self.peek().is_some_and(is_ident_start) && self.peek_nth(1) != Some('\'');
+        if is_lifetime {
+            return Ok(TokenKind::Lifetime(self.ident_chars()));
+        }
+        let value = self.char_body(start, false)?;
+        self.no_suffix(start)?;
+        Ok(TokenKind::Lit(Lit::Char(LitChar { value })))
+    }
+
+    fn no_suffix(&mut self, start: usize) -> Result<(), ParseError> {
+        if self.peek().is_some_and(is_ident_start) {
+            let suffix = self.ident_chars();
+            return Err(self.error(start, format!("unexpected literal suffix `{suffix}`")));
+        }
+        Ok(())
+    }
+
+    fn number(&mut self, start: usize) -> Result<TokenKind, ParseError> {
+        let radix = match self.rest().get(..2) {
+            Some("0x") => IntRadix::Hex,
+            Some("0o") => IntRadix::Octal,
+            Some("0b") => IntRadix::Binary,
+            _ => IntRadix::Decimal,
+        };
+        if radix != IntRadix::Decimal {
+            self.pos += 2;
+        }
+        let is_digit = |c: char| match radix {
+            IntRadix::Hex => c.is_ascii_hexdigit(),
+            _ => c.is_ascii_digit(),
+        };
+        let digits_start = self.pos;
+        while self.peek().is_some_and(|c| c == '_' || is_digit(c)) {
+            self.bump();
+        }
+        let mut is_float = false;
+        if radix == IntRadix::Decimal {
+            // `1.0` and `1.` are floats, but `1..2`, `1.foo()` and `t.0.1` are not.
+            if self.peek() == Some('.')
+                && self
+                    .peek_nth(1)
+                    .is_none_or(|c| c != '.' && !is_ident_start(c))
+            {
+                is_float = true;
+                self.bump();
+                while self.peek().is_some_and(|c| c == '_' || c.is_ascii_digit()) {
+                    self.bump();
+                }
+            }
+            if matches!(self.peek(), Some('e' | 'E'))
+                && (self.peek_nth(1).is_some_and(|c| c.is_ascii_digit())
+                    || (matches!(self.peek_nth(1), Some('+' | '-'))
+                        && self.peek_nth(2).is_some_and(|c| c.is_ascii_digit())))
+            {
+                is_float = true;
+                self.pos += 2;
+                while self.peek().is_some_and(|c| c == '_' || c.is_ascii_digit()) {
+                    self.bump();
+                }
+            }
+        }
+        let text = &self.src[digits_start..self.pos];
+        let suffix_start = self.pos;
+        let suffix = if self.peek().is_some_and(is_ident_start) {
+            self.ident_chars()
+        } else {
+            String::new()
+        };
+        let float_suffix = match suffix.as_str() {
+            "f32" => Some(Some(FloatSuffix::F32)),
+            "f64" => Some(Some(FloatSuffix::F64)),
+            "" if is_float => Some(None),
+            _ => None,
+        };
+        if let Some(suffix) = float_suffix {
+            if radix != IntRadix::Decimal {
+                return Err(self.error(start, "float literals must be decimal"));
+            }
+            return Ok(TokenKind::Lit(Lit::Float(LitFloat {
+                value: text.to_string(),
+                suffix,
+            })));
+        }
+        if is_float {
+            return Err(self.error(suffix_start, format!("invalid float suffix `{suffix}`")));
+        }
+        let suffix = match suffix.as_str() {
+            "" => None,
+            "u8" => Some(IntSuffix::U8),
+            "i8" => Some(IntSuffix::I8),
+            "u16" => Some(IntSuffix::U16),
+            "i16" => Some(IntSuffix::I16),
+            "u32" => Some(IntSuffix::U32),
+            "i32" => Some(IntSuffix::I32),
+            "u64" => Some(IntSuffix::U64),
+            "i64" => Some(IntSuffix::I64),
+            "u128" => Some(IntSuffix::U128),
+            "i128" => Some(IntSuffix::I128),
+            "usize" => Some(IntSuffix::Usize),
+            "isize" => Some(IntSuffix::Isize),
+            other => {
+                return Err(self.error(suffix_start, format!("invalid integer suffix `{other}`")));
+            }
+        };
+        let base = match radix {
+            IntRadix::Decimal => 10,
+            IntRadix::Hex => 16,
+            IntRadix::Octal => 8,
+            IntRadix::Binary => 2,
+        };
+        let digits = text.replace('_', "");
+        if digits.is_empty() {
+            return Err(self.error(start, "missing digits after the integer base prefix"));
+        }
+        let value = u128::from_str_radix(&digits, base)
+            .map_err(|_| self.error(start, "integer literal is too large or has invalid digits"))?;
+        Ok(TokenKind::Lit(Lit::Int(LitInt {
+            digit_group: digit_group(text),
+            suffix,
+            ..LitInt::new(value).radix(radix)
+        })))
+    }
+}
+
+/// Returns the group size of digits written with evenly spaced underscores,
+/// such as `1_000_000`.
+fn digit_group(text: &str) -> Option<usize> {
+    let groups: Vec<&str> = text.split('_').collect();
+    let (first, rest) = groups.split_first()?;
+    let size = rest.first()?.len();
+    let is_even = size > 0
+        && !first.is_empty()
+        && first.len() <= size
+        && rest.iter().all(|group| group.len() == size);
+    is_even.then_some(size)
+}
+
+fn delimiter(c: char) -> Delimiter {
+    match c {
+        '(' | ')' => Delimiter::Parenthesis,
+        '[' | ']' => Delimiter::Bracket,
+        _ => Delimiter::Brace,
+    }
+}
+
+impl TokenKind {
+    /// Describes the token for error messages, e.g. `` `fn` `` or `end of input`.
+    pub(crate) fn describe(&self) -> String {
+        match self {
+            TokenKind::Ident { name, is_raw: true } => format!("`r#{name}`"),
+            TokenKind::Ident { name, .. } => format!("`{name}`"),
+            TokenKind::Lifetime(name) => format!("`'{name}`"),
+            TokenKind::Lit(_) => "a literal".to_string(),
+            TokenKind::Punct { ch, .. } => format!("`{ch}`"),
+            TokenKind::Open(delimiter) => format!("`{}`", delimiter_chars(*delimiter).0),
+            TokenKind::Close(delimiter) => format!("`{}`", delimiter_chars(*delimiter).1),
+            TokenKind::Eof => "end of input".to_string(),
+        }
+    }
+}
+
+pub(crate) fn delimiter_chars(delimiter: Delimiter) -> (&'static str, &'static str) {
+    match delimiter {
+        Delimiter::Parenthesis => ("(", ")"),
+        Delimiter::Bracket => ("[", "]"),
+        Delimiter::Brace => ("{", "}"),
+        Delimiter::None => ("", ""),
+    }
+}
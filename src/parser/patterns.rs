@@ -0,0 +1,234 @@
+//! Parsing of patterns.
+
+use crate::ast::{
+    Delimiter, Expr, ExprBlock, ExprPath, FieldPat, Ident, Lit, Pat, PatConst, PatIdent, PatLit,
+    PatMacro, PatOr, PatParen, PatPath, PatRange, PatReference, PatRest, PatSlice, PatStruct,
+    PatTuple, PatTupleStruct, PatWild, Path, RangeLimits,
+};
+use crate::parser::lexer::TokenKind;
+use crate::parser::types::PathStyle;
+use crate::parser::{PResult, Parser};
+use thin_vec::ThinVec;
+
+impl Parser<'_> {
+    /// Parses a pattern, including `|` alternatives and a leading `|`.
+    pub(super) fn parse_pat(&mut self) -> PResult<Pat> {
+        self.eat_alt_bar();
+        let mut pats = ThinVec::new();
+        pats.push(self.parse_pat_no_alt()?);
+        while self.eat_alt_bar() {
+            pats.push(self.parse_pat_no_alt()?);
+        }
+        if pats.len() == 1 {
+            return Ok(pats.remove(0));
+        }
+        Ok(Pat::Or(PatOr { pats }))
+    }
+
+    fn eat_alt_bar(&mut self) -> bool {
+        self.peek_op() == Some("|") && self.eat_punct("|")
+    }
+
+    /// Parses a pattern without top-level `|` alternatives, as in closure
+    /// parameters.
+    pub(super) fn parse_pat_no_alt(&mut self) -> PResult<Pat> {
+        let start = self.start();
+        if self.eat_keyword("_") {
+            return Ok(Pat::Wild(PatWild));
+        }
+        if matches!(self.peek_op(), Some(".." | "..=" | "...")) {
+            if self.peek_op() == Some("..") && !self.is_range_end_start() {
+                self.bump();
+                self.bump();
+                return Ok(Pat::Rest(PatRest));
+            }
+            return self.parse_pat_range(None);
+        }
+        if self.eat_punct("&") {
+            let is_mut = self.eat_keyword("mut");
+            let pat = Box::new(self.parse_pat_no_alt()?);
+            return Ok(Pat::Reference(PatReference { pat, is_mut }));
+        }
+        if self.eat_open(Delimiter::Parenthesis) {
+            let mut pats = ThinVec::new();
+            let mut has_trailing_comma = false;
+            while !self.eat_close(Delimiter::Parenthesis) {
+                pats.push(self.parse_pat()?);
+                has_trailing_comma = self.eat_punct(",");
+                if !has_trailing_comma && !self.is_close(Delimiter::Parenthesis) {
+                    return Err(self.unexpected("`,` or `)`"));
+                }
+            }
+            if pats.len() == 1 && !has_trailing_comma {
+                let pat = Box::new(pats.remove(0));
+                return Ok(Pat::Paren(PatParen { pat }));
+            }
+            return Ok(Pat::Tuple(PatTuple { pats }));
+        }
+        if self.eat_open(Delimiter::Bracket) {
+            let pats = self.comma_list(Delimiter::Bracket, Self::parse_pat)?;
+            return Ok(Pat::Slice(PatSlice { pats }));
+        }
+        if self.is_keyword("ref") || self.is_keyword("box") {
+            self.bump();
+            return Err(self.unsupported(self.span_from(start), "`ref` and `box` patterns"));
+        }
+        if self.eat_keyword("mut") {
+            let ident = self.ident()?;
+            return self.finish_pat_ident(ident, true);
+        }
+        if self.is_keyword("const") && self.is_open_nth(1, Delimiter::Brace) {
+            self.bump();
+            let block = self.parse_block()?;
+            let expr = Box::new(Expr::Block(ExprBlock {
+                block: block.into(),
+            }));
+            return Ok(Pat::Const(PatConst { expr }));
+        }
+        if self.is_lit_start() {
+            let Expr::Lit(lit) = self.parse_lit_expr()? else {
+                unreachable!("`parse_lit_expr` returns literals")
+            };
+            if matches!(self.peek_op(), Some(".." | "..=" | "...")) {
+                return self.parse_pat_range(Some(Expr::Lit(lit)));
+            }
+            return Ok(Pat::Lit(PatLit { lit: Box::new(lit) }));
+        }
+        if self.is_punct("<") || self.is_punct("::") {
+            return Err(self.unsupported(self.peek().span, "qualified paths"));
+        }
+        if !self.is_path_start() {
+            return Err(self.unexpected("a pattern"));
+        }
+        let path = self.parse_path(PathStyle::Expr)?;
+        if self.is_punct("!") && !self.is_punct("!=") {
+            self.bump();
+            let mac = self.parse_macro_call(path)?;
+            return Ok(Pat::Macro(PatMacro { mac }));
+        }
+        if self.eat_open(Delimiter::Parenthesis) {
+            let pats = self.comma_list(Delimiter::Parenthesis, Self::parse_pat)?;
+            return Ok(Pat::TupleStruct(PatTupleStruct { path, pats }));
+        }
+        if self.is_open(Delimiter::Brace) {
+            return self.parse_pat_struct(path);
+        }
+        if matches!(self.peek_op(), Some(".." | "..=" | "...")) {
+            return self.parse_pat_range(Some(Expr::Path(ExprPath { path })));
+        }
+        let is_ident = path.segments.len() == 1 && path.segments[0].args.is_none();
+        if is_ident {
+            let ident = path.segments.into_iter().next().expect("one segment").ident;
+            return self.finish_pat_ident(ident, false);
+        }
+        Ok(Pat::Path(PatPath { path }))
+    }
+
+    fn finish_pat_ident(&mut self, ident: Ident, is_mut: bool) -> PResult<Pat> {
+        if self.is_punct("@") {
+            return Err(self.unsupported(self.peek().span, "`@` bindings"));
+        }
+        Ok(Pat::Ident(PatIdent { ident, is_mut }))
+    }
+
+    /// Whether the next token can end a range pattern, as in `..5`.
+    fn is_range_end_start(&self) -> bool {
+        let after = self.peek_nth(2);
+        match &after.kind {
+            TokenKind::Lit(_) => true,
+            TokenKind::Punct { ch: '-', .. } => true,
+            TokenKind::Ident { name, .. } => name != "if" && name != "in",
+            _ => false,
+        }
+    }
+
+    /// Parses the rest of a range pattern, starting at the `..`, `..=` or
+    /// `...`.
+    fn parse_pat_range(&mut self, start: Option<Expr>) -> PResult<Pat> {
+        let op = self.peek_op().expect("checked by the caller");
+        self.pos += op.len();
+        let limits = if op == ".." {
+            RangeLimits::HalfOpen
+        } else {
+            RangeLimits::Closed
+        };
+        let end = if self.is_lit_start() {
+            Some(self.parse_lit_expr()?)
+        } else if self.is_path_start() {
+            let path = self.parse_path(PathStyle::Expr)?;
+            Some(Expr::Path(ExprPath { path }))
+        } else if self.is_open(Delimiter::Brace) {
+            let block = self.parse_block()?;
+            Some(Expr::Block(ExprBlock {
+                block: block.into(),
+            }))
+        } else {
+            None
+        };
+        if end.is_none() && limits == RangeLimits::Closed {
+            return Err(self.unexpected("the end of the range"));
+        }
+        Ok(Pat::Range(PatRange {
+            start: start.map(Box::new),
+            end: end.map(Box::new),
+            limits,
+        }))
+    }
+
+    /// Parses `Path { field: pat, shorthand, .. }`, starting at the `{`.
+    fn parse_pat_struct(&mut self, path: Path) -> PResult<Pat> {
+        self.expect_open(Delimiter::Brace)?;
+        let mut fields = ThinVec::new();
+        let mut has_rest = false;
+        while !self.eat_close(Delimiter::Brace) {
+            let start = self.start();
+            if !self.outer_attrs()?.is_empty() {
+                return Err(self.unsupported(self.span_from(start), "attributes on field patterns"));
+            }
+            if self.eat_punct("..") {
+                has_rest = true;
+                self.expect_close(Delimiter::Brace)?;
+                break;
+            }
+            let field = if self.is_ident() && self.is_punct_nth(1, ":") {
+                let member = self.ident()?;
+                self.bump();
+                let pat = Box::new(self.parse_pat()?);
+                FieldPat { member, pat }
+            } else {
+                if self.is_keyword("ref") || self.is_keyword("box") {
+                    self.bump();
+                    return Err(self.unsupported(self.span_from(start), "`ref` and `box` patterns"));
+                }
+                let is_mut = self.eat_keyword("mut");
+                let member = self.ident()?;
+                let pat = Box::new(self.finish_pat_ident(member.clone(), is_mut)?);
+                FieldPat { member, pat }
+            };
+            fields.push(field);
+            if !self.eat_punct(",") && !self.is_close(Delimiter::Brace) {
+                return Err(self.unexpected("`,` or `}`"));
+            }
+        }
+        Ok(Pat::Struct(PatStruct {
+            path,
+            fields,
+            has_rest,
+        }))
+    }
+
+    /// Whether the next token starts a literal, including a negative number
+    /// or `true` and `false`.
+    pub(super) fn is_lit_start(&self) -> bool {
+        match &self.peek().kind {
+            TokenKind::Lit(_) => true,
+            TokenKind::Punct { ch: '-', .. } => {
+                matches!(
+                    self.peek_nth(1).kind,
+                    TokenKind::Lit(Lit::Int(_) | Lit::Float(_))
+                )
+            }
+            _ => self.is_keyword("true") || self.is_keyword("false"),
+        }
+    }
+}
@@ -0,0 +1,363 @@
+//! Parsing of types, paths, generics and where clauses.
+
+use crate::ast::{
+    Abi, BareFnArg, ConstParam, Delimiter, Expr, ExprBlock, GenericArg, GenericArgs, GenericParam,
+    GenericParams, Lifetime, LifetimeParam, LifetimePredicate, Lit, Path, PathSegment, Type,
+    TypeArray, TypeBareFn, TypeParam, TypePath, TypePredicate, TypePtr, TypeReference, WhereClause,
+    WherePredicate,
+};
+use crate::parser::lexer::TokenKind;
+use crate::parser::{PResult, Parser, leading_md};
+use thin_vec::ThinVec;
+
+/// Where generic arguments may appear in a path.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(super) enum PathStyle {
+    /// Expression and pattern paths, which need a turbofish: `Vec::<u8>::new`.
+    Expr,
+    /// Type paths, which also accept `Vec<u8>`.
+    Type,
+}
+
+impl Parser<'_> {
+    /// Parses a type.
+    pub(super) fn parse_type(&mut self) -> PResult<Type> {
+        let start = self.start();
+        if self.eat_open(Delimiter::Parenthesis) {
+            let mut elems = ThinVec::new();
+            let mut has_trailing_comma = false;
+            while !self.eat_close(Delimiter::Parenthesis) {
+                elems.push(self.parse_type()?);
+                has_trailing_comma = self.eat_punct(",");
+                if !has_trailing_comma && !self.is_close(Delimiter::Parenthesis) {
+                    return Err(self.unexpected("`,` or `)`"));
+                }
+            }
+            if elems.len() == 1 && !has_trailing_comma {
+                return Ok(Type::Paren(Box::new(elems.remove(0))));
+            }
+            return Ok(Type::Tuple(elems));
+        }
+        if self.eat_open(Delimiter::Bracket) {
+            let elem = Box::new(self.parse_type()?);
+            let ty = if self.eat_punct(";") {
+                let len = Box::new(self.parse_expr()?);
+                Type::Array(TypeArray { elem, len })
+            } else {
+                Type::Slice(elem)
+            };
+            self.expect_close(Delimiter::Bracket)?;
+            return Ok(ty);
+        }
+        if self.eat_punct("!") {
+            return Ok(Type::Never);
+        }
+        if self.eat_keyword("_") {
+            return Ok(Type::Infer);
+        }
+        if self.eat_punct("*") {
+            let mutable = if self.eat_keyword("mut") {
+                true
+            } else {
+                self.expect_keyword("const")?;
+                false
+            };
+            let elem = Box::new(self.parse_type()?);
+            return Ok(Type::Ptr(TypePtr { elem, mutable }));
+        }
+        if self.eat_punct("&") {
+            let lifetime = if self.is_lifetime() {
+                Some(self.lifetime()?)
+            } else {
+                None
+            };
+            let mutable = self.eat_keyword("mut");
+            let elem = Box::new(self.parse_type()?);
+            return Ok(Type::Reference(TypeReference {
+                lifetime,
+                elem,
+                mutable,
+            }));
+        }
+        if self.is_keyword("fn")
+            || self.is_keyword("unsafe")
+            || self.is_keyword("extern")
+            || self.is_keyword("for")
+        {
+            return self.parse_bare_fn();
+        }
+        if self.is_keyword("impl") || self.is_keyword("dyn") {
+            self.bump();
+            return Err(self.unsupported(self.span_from(start), "`impl` and `dyn` trait types"));
+        }
+        if self.is_punct("<") {
+            return Err(self.unsupported(self.peek().span, "qualified paths"));
+        }
+        if !self.is_path_start() {
+            return Err(self.unexpected("a type"));
+        }
+        let path = self.parse_path(PathStyle::Type)?;
+        if self.peek_op() == Some("!") {
+            return Err(self.unsupported(self.peek().span, "macros in type position"));
+        }
+        if self.is_open(Delimiter::Parenthesis) {
+            return Err(self.unsupported(self.peek().span, "parenthesized generic arguments"));
+        }
+        Ok(Type::Path(TypePath { path }))
+    }
+
+    /// Parses `for<'a> unsafe extern "C" fn(args) -> ret`.
+    fn parse_bare_fn(&mut self) -> PResult<Type> {
+        let mut lifetimes = ThinVec::new();
+        if self.eat_keyword("for") {
+            self.expect_punct("<")?;
+            while !self.eat_punct(">") {
+                lifetimes.push(self.lifetime()?);
+                if !self.eat_punct(",") && !self.is_punct(">") {
+                    return Err(self.unexpected("`,` or `>`"));
+                }
+            }
+        }
+        let is_unsafe = self.eat_keyword("unsafe");
+        let abi = self.parse_abi()?;
+        self.expect_keyword("fn")?;
+        self.expect_open(Delimiter::Parenthesis)?;
+        let mut inputs = ThinVec::new();
+        let mut is_variadic = false;
+        while !self.eat_close(Delimiter::Parenthesis) {
+            if self.eat_punct("...") {
+                is_variadic = true;
+            } else {
+                let name = if self.is_ident() && self.is_punct_nth(1, ":") {
+                    let name = self.ident()?;
+                    self.bump();
+                    Some(name)
+                } else {
+                    None
+                };
+                let ty = self.parse_type()?;
+                inputs.push(BareFnArg { name, ty });
+            }
+            if !self.eat_punct(",") && !self.is_close(Delimiter::Parenthesis) {
+                return Err(self.unexpected("`,` or `)`"));
+            }
+        }
+        let output = self.parse_return_type()?.map(Box::new);
+        Ok(Type::BareFn(TypeBareFn {
+            lifetimes,
+            is_unsafe,
+            abi,
+            inputs,
+            is_variadic,
+            output,
+        }))
+    }
+
+    /// Parses `extern` with an optional ABI string, if present.
+    pub(super) fn parse_abi(&mut self) -> PResult<Option<Abi>> {
+        if !self.eat_keyword("extern") {
+            return Ok(None);
+        }
+        if let TokenKind::Lit(Lit::Str(abi)) = &self.peek().kind {
+            let abi = Abi::Named(abi.value.clone());
+            self.bump();
+            return Ok(Some(abi));
+        }
+        Ok(Some(Abi::Implicit))
+    }
+
+    /// Parses an optional `-> Type`.
+    pub(super) fn parse_return_type(&mut self) -> PResult<Option<Type>> {
+        if self.eat_punct("->") {
+            Ok(Some(self.parse_type()?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Parses a path such as `std::vec::Vec<u8>` or `Vec::<u8>::new`.
+    pub(super) fn parse_path(&mut self, style: PathStyle) -> PResult<Path> {
+        if self.is_punct("::") {
+            return Err(self.unsupported(self.peek().span, "paths with a leading `::`"));
+        }
+        let mut segments = ThinVec::new();
+        loop {
+            let ident = self.path_segment_ident()?;
+            let mut args = None;
+            if style == PathStyle::Type
+                && self.is_punct("<")
+                && !matches!(self.peek_op(), Some("<=" | "<<="))
+            {
+                args = Some(self.parse_generic_args()?);
+            }
+            segments.push(PathSegment { ident, args });
+            if !self.is_punct("::") {
+                break;
+            }
+            if self.is_punct_nth(2, "<") {
+                self.bump();
+                self.bump();
+                segments.last_mut().expect("just pushed").args = Some(self.parse_generic_args()?);
+                if !self.is_punct("::") {
+                    break;
+                }
+            }
+            self.bump();
+            self.bump();
+        }
+        Ok(Path { segments })
+    }
+
+    /// Parses `<args>`, starting at the `<`.
+    pub(super) fn parse_generic_args(&mut self) -> PResult<GenericArgs> {
+        self.expect_punct("<")?;
+        let mut args = vec![];
+        while !self.eat_punct(">") {
+            args.push(self.parse_generic_arg()?);
+            if !self.eat_punct(",") && !self.is_punct(">") {
+                return Err(self.unexpected("`,` or `>`"));
+            }
+        }
+        Ok(GenericArgs { args })
+    }
+
+    fn parse_generic_arg(&mut self) -> PResult<GenericArg> {
+        if self.is_lifetime() {
+            return Ok(GenericArg::Lifetime(self.lifetime()?));
+        }
+        if self.is_open(Delimiter::Brace) {
+            let block = self.parse_block()?;
+            return Ok(GenericArg::Const(Expr::Block(ExprBlock {
+                block: block.into(),
+            })));
+        }
+        if matches!(self.peek().kind, TokenKind::Lit(_))
+            || self.is_punct("-")
+            || self.is_keyword("true")
+            || self.is_keyword("false")
+        {
+            return Ok(GenericArg::Const(self.parse_lit_expr()?));
+        }
+        if self.is_ident() && self.is_punct_nth(1, ":") && !self.is_punct_nth(1, "::")
+            || self.is_ident() && self.is_punct_nth(1, "=") && !self.is_punct_nth(1, "==")
+        {
+            let start = self.start();
+            self.bump();
+            return Err(self.unsupported(self.span_from(start), "associated item constraints"));
+        }
+        Ok(GenericArg::Type(self.parse_type()?))
+    }
+
+    /// Parses `A + B + C` trait bounds.
+    pub(super) fn parse_bounds(&mut self) -> PResult<Vec<Type>> {
+        let mut bounds = vec![];
+        loop {
+            if self.is_lifetime() || self.is_punct("?") || self.is_keyword("for") {
+                let start = self.start();
+                self.bump();
+                return Err(self.unsupported(
+                    self.span_from(start),
+                    "lifetime, `?Sized` and higher-ranked trait bounds",
+                ));
+            }
+            bounds.push(self.parse_type()?);
+            if !self.eat_punct("+") {
+                return Ok(bounds);
+            }
+        }
+    }
+
+    /// Parses `'a + 'b` lifetime bounds.
+    fn parse_lifetime_bounds(&mut self) -> PResult<Vec<Lifetime>> {
+        let mut bounds = vec![];
+        loop {
+            bounds.push(self.lifetime()?);
+            if !self.eat_punct("+") {
+                return Ok(bounds);
+            }
+        }
+    }
+
+    /// Parses optional `<params>` after the name of an item.
+    pub(super) fn parse_generic_params(&mut self) -> PResult<GenericParams> {
+        let mut params = vec![];
+        if !self.eat_punct("<") {
+            return Ok(GenericParams { params });
+        }
+        while !self.eat_punct(">") {
+            let attrs = self.outer_attrs()?;
+            let md = leading_md(ThinVec::new(), attrs);
+            let param = if self.is_lifetime() {
+                let lifetime = self.lifetime()?;
+                let bounds = if self.eat_punct(":") {
+                    self.parse_lifetime_bounds()?
+                } else {
+                    vec![]
+                };
+                GenericParam::Lifetime(LifetimeParam {
+                    lifetime,
+                    bounds,
+                    md,
+                })
+            } else if self.eat_keyword("const") {
+                let ident = self.ident()?;
+                self.expect_punct(":")?;
+                let ty = self.parse_type()?;
+                GenericParam::Const(ConstParam { ident, ty, md })
+            } else {
+                let ident = self.ident()?;
+                let bounds = if self.eat_punct(":") {
+                    self.parse_bounds()?
+                } else {
+                    vec![]
+                };
+                GenericParam::Type(TypeParam { ident, bounds, md })
+            };
+            if self.is_punct("=") {
+                return Err(self.unsupported(self.peek().span, "generic parameter defaults"));
+            }
+            params.push(param);
+            if !self.eat_punct(",") && !self.is_punct(">") {
+                return Err(self.unexpected("`,` or `>`"));
+            }
+        }
+        Ok(GenericParams { params })
+    }
+
+    /// Parses an optional where clause, which ends before a `{` or `;`.
+    pub(super) fn parse_where_clause(&mut self) -> PResult<Option<WhereClause>> {
+        if !self.eat_keyword("where") {
+            return Ok(None);
+        }
+        let mut predicates = vec![];
+        while !self.is_open(Delimiter::Brace) && !self.is_punct(";") && !self.is_eof() {
+            let predicate = if self.is_lifetime() {
+                let lifetime = self.lifetime()?;
+                self.expect_punct(":")?;
+                let bounds = self.parse_lifetime_bounds()?;
+                WherePredicate::Lifetime(LifetimePredicate { lifetime, bounds })
+            } else {
+                if self.is_keyword("for") {
+                    return Err(self.unsupported(self.peek().span, "higher-ranked trait bounds"));
+                }
+                let ty = self.parse_type()?;
+                self.expect_punct(":")?;
+                let bounds = self.parse_bounds()?;
+                WherePredicate::Type(TypePredicate { ty, bounds })
+            };
+            predicates.push(predicate);
+            if !self.eat_punct(",") {
+                break;
+            }
+        }
+        Ok(Some(WhereClause { predicates }))
+    }
+
+    /// Reports a where clause on an item that cannot store one.
+    pub(super) fn reject_where_clause(&self) -> PResult<()> {
+        if self.is_keyword("where") {
+            return Err(self.unsupported(self.peek().span, "where clauses on this item"));
+        }
+        Ok(())
+    }
+}
@@ -8,16 +8,20 @@
 //! The core of the pretty-printer is the `Printer` struct, which manages the
 //! printing process. It uses a token-based approach, where the AST is first
 //! converted into a sequence of `Token`s. These tokens represent strings,
-//! potential line breaks, and grouping constructs. The printer then uses a
-//! two-pass algorithm:
+//! potential line breaks, and grouping constructs. The printer lays them out
+//! in two stages, as the tokens are added, in the manner of Oppen's streaming
+//! pretty-printer:
 //!
-//! 1. **Scan Pass**: The printer scans the tokens to determine the best layout
-//!    by calculating the size of each token group. This pass decides whether
-//!    a group should be printed on a single line or broken into multiple lines.
+//! 1. **Scan**: The printer measures each group and potential line break as
+//!    its tokens arrive, to decide whether it should be printed on a single
+//!    line or broken into multiple lines. Tokens wait in a ring buffer until
+//!    their size is known, or until the text after them no longer fits on a
+//!    line.
 //!
-//! 2. **Print Pass**: The printer iterates through the tokens again, this time
-//!    writing the formatted output to a `Write` buffer. It uses the information
-//     from the scan pass to insert line breaks and indentation where necessary.
+//! 2. **Print**: Tokens whose layout is decided leave the buffer and are
+//!    written to the `Write` buffer right away, with line breaks and
+//!    indentation where necessary. Memory use is bounded by the largest
+//!    undecided group, not by the size of the document.
 //!
 //! The `PrettyPrinter` trait is implemented by all AST nodes that can be
 //! pretty-printed. This trait provides a `pretty_print` method that
@@ -27,18 +31,159 @@ use crate::ast::item_const::ItemConst;
 use crate::ast::item_extern_type::ItemExternType;
 use crate::ast::item_type_alias::ItemTypeAlias;
 use crate::ast::items::*;
+use crate::ast::precedence::ExprPosition;
 use crate::ast::*;
+use std::any::Any;
 use std::borrow::Cow;
+use std::collections::VecDeque;
 use std::fmt::{self, Write};
+use std::io;
+use std::mem;
 
-/// The line width to aim for when formatting.
-const LINE_WIDTH: isize = 100;
+mod highlight;
+mod source_map;
+
+use highlight::Highlight;
+
+pub use highlight::{Ansi, Highlighter, Html, TokenKind};
+pub use source_map::{NodeId, NodeSpan, Position, SourceMap};
 
 /// A large integer value used to represent an infinitely long line.
-const INFINITY: isize = 0xffff;
+const INFINITY: isize = isize::MAX / 2;
+
+/// Options that control the layout of the pretty-printed code.
+///
+/// The fields mirror the `rustfmt.toml` options of the same name, so that
+/// generated code can match the formatting of the crate it is written into.
+///
+/// # Examples
+///
+/// ```
+/// use rasto::pretty_printer::{NewlineStyle, PrinterConfig};
+///
+/// let config = PrinterConfig {
+///     max_width: 80,
+///     hard_tabs: true,
+///     newline_style: NewlineStyle::Windows,
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrinterConfig {
+    /// The line width to aim for when formatting. Defaults to `100`.
+    pub max_width: usize,
+    /// The number of columns per indentation level. Defaults to `4`.
+    pub tab_spaces: usize,
+    /// Whether to indent with tabs instead of spaces. Each tab counts as
+    /// `tab_spaces` columns towards `max_width`. Defaults to `false`.
+    pub hard_tabs: bool,
+    /// The line ending to print. Defaults to [`NewlineStyle::Unix`].
+    pub newline_style: NewlineStyle,
+    /// When to print a comma after the last element of a list. Defaults to
+    /// [`TrailingComma::Vertical`].
+    pub trailing_comma: TrailingComma,
+    /// Whether to end non-empty output with a line ending. Defaults to `false`.
+    pub final_newline: bool,
+    /// Whether to print the output on a single line. Line breaks are printed
+    /// as spaces, so that a block prints as `{ a; b }`, `//` comments are
+    /// printed as `/* */` comments, and `max_width` is ignored. A space is
+    /// inserted into each `/*` and `*/` in such comments. Defaults to `false`.
+    pub single_line: bool,
+    /// Where to put empty lines between the items of a file, a module, a
+    /// trait or an `impl` block. Defaults to [`ItemSpacing::Compact`].
+    pub item_spacing: ItemSpacing,
+}
+
+impl PrinterConfig {
+    /// Returns the maximum width of the arguments of a call on one line,
+    /// which is 60% of `max_width` as in rustfmt's default heuristics.
+    pub fn fn_call_width(&self) -> usize {
+        self.max_width * 60 / 100
+    }
+
+    /// Returns the maximum width of an array literal on one line, which is
+    /// 60% of `max_width`.
+    pub fn array_width(&self) -> usize {
+        self.max_width * 60 / 100
+    }
+
+    /// Returns the maximum width of a method chain on one line, which is 60%
+    /// of `max_width`.
+    pub fn chain_width(&self) -> usize {
+        self.max_width * 60 / 100
+    }
+
+    /// Returns the maximum width of the fields of a struct literal on one
+    /// line, which is 18% of `max_width`.
+    pub fn struct_lit_width(&self) -> usize {
+        self.max_width * 18 / 100
+    }
+}
+
+impl Default for PrinterConfig {
+    fn default() -> Self {
+        Self {
+            max_width: 100,
+            tab_spaces: 4,
+            hard_tabs: false,
+            newline_style: NewlineStyle::Unix,
+            trailing_comma: TrailingComma::Vertical,
+            final_newline: false,
+            single_line: false,
+            item_spacing: ItemSpacing::Compact,
+        }
+    }
+}
+
+/// The line ending printed by the pretty-printer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NewlineStyle {
+    /// Lines end with `\n`.
+    #[default]
+    Unix,
+    /// Lines end with `\r\n`.
+    Windows,
+}
+
+impl NewlineStyle {
+    /// Returns the line ending as a string.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NewlineStyle::Unix => "\n",
+            NewlineStyle::Windows => "\r\n",
+        }
+    }
+}
+
+/// When to print a comma after the last element of a list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TrailingComma {
+    /// Always print a trailing comma, even on a single line.
+    Always,
+    /// Never print a trailing comma.
+    Never,
+    /// Print a trailing comma only when the list is broken over several lines.
+    #[default]
+    Vertical,
+}
 
-/// The number of spaces to use for indentation.
-const INDENT_SIZE: usize = 4;
+/// Where to put empty lines between items.
+///
+/// Whatever the policy, an empty line is put before the items whose metadata
+/// sets [`Md::blank_line_before`], and no more than one empty line is printed
+/// in a row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ItemSpacing {
+    /// Only put empty lines where the metadata asks for them.
+    #[default]
+    Compact,
+    /// Put an empty line between items, except between consecutive items of
+    /// the same kind that are declarations without a body, such as `use`
+    /// declarations, `const` and `static` items, type aliases and `mod foo;`.
+    Grouped,
+    /// Put an empty line between every two items.
+    Separated,
+}
 
 /// The style of a break.
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -71,10 +216,33 @@ pub enum Token<'a> {
         /// The number of spaces to print if the break is not taken.
         len: usize,
     },
-    /// A hard line break that will always be printed as a newline.
+    /// A hard line break that will always be printed as a newline, or as a
+    /// space in single-line mode.
     ///
     /// This is used for mandatory line breaks, such as between statements or items.
     HardBreak,
+    /// A hard line break that leaves an empty line before the next line,
+    /// unless the current line is already empty and follows an empty line.
+    ///
+    /// It prints nothing at the start of the output, and a space in
+    /// single-line mode.
+    BlankLine,
+    /// A string whose text depends on whether the enclosing group is broken.
+    ///
+    /// This is used for separators that only appear in one layout, such as a
+    /// trailing comma after the last field of a broken struct literal.
+    /// Single-line mode prints the `flat` string.
+    IfBroken {
+        /// The string to print if the enclosing group is broken.
+        broken: &'a str,
+        /// The string to print if the enclosing group fits on one line.
+        flat: &'a str,
+    },
+    /// A `//` comment that runs to the end of the line.
+    ///
+    /// The enclosing group is always broken, and a newline is printed before
+    /// anything that follows the comment.
+    EolComment(Cow<'a, str>),
     /// The beginning of a group of tokens.
     ///
     /// A group is a sequence of tokens that are treated as a single unit for the
@@ -86,6 +254,10 @@ pub enum Token<'a> {
         style: BreakStyle,
         /// The opening string of the group (e.g., `(`, `[`, `{`).
         open: &'a str,
+        /// The maximum width of the contents of the group on one line. The
+        /// group is broken if its contents are wider, even if they would fit
+        /// in the remaining space.
+        max_width: Option<usize>,
     },
     /// The end of a group of tokens.
     ///
@@ -94,7 +266,33 @@ pub enum Token<'a> {
     End {
         /// The closing string of the group (e.g., `)`, `]`, `}`).
         close: &'a str,
+        /// Whether the closing string goes on a new line if the group is
+        /// broken. Groups without delimiters, such as method chains, keep
+        /// their last line open instead.
+        newline: bool,
+    },
+    /// The beginning of the output of an AST node, recorded in the source map.
+    ///
+    /// This token prints nothing and does not affect the layout.
+    BeginNode {
+        /// The identity of the node.
+        id: NodeId,
+        /// The name of the type of the node.
+        kind: &'static str,
     },
+    /// The end of the output of the innermost node begun by a
+    /// [`Token::BeginNode`].
+    EndNode,
+    /// The beginning of strings of the given kind, for syntax highlighting.
+    ///
+    /// This token prints nothing and does not affect the layout.
+    BeginHighlight {
+        /// The kind of the strings that follow.
+        kind: TokenKind,
+    },
+    /// The end of the strings marked by the innermost
+    /// [`Token::BeginHighlight`].
+    EndHighlight,
 }
 
 /// A trait for types that can be pretty-printed.
@@ -130,27 +328,186 @@ impl PrettyPrinter for ExprTry {
 ///
 /// - `ast`: The AST node to pretty-print.
 pub fn pretty(ast: &impl PrettyPrinter) -> String {
+    pretty_with(ast, &PrinterConfig::default())
+}
+
+/// Pretty-prints an AST node to a string using the given configuration.
+///
+/// # Parameters
+///
+/// - `ast`: The AST node to pretty-print.
+/// - `config`: The layout options to use.
+pub fn pretty_with(ast: &impl PrettyPrinter, config: &PrinterConfig) -> String {
+    let mut buf = String::new();
+    let mut printer = Printer::new(&mut buf, config.clone());
+    ast.pretty_print(&mut printer).unwrap();
+    printer.finish().unwrap();
+    buf
+}
+
+/// Pretty-prints an AST node to an [`io::Write`], such as a file, using the
+/// given configuration.
+///
+/// The output is written while the node is printed, as soon as the layout of
+/// each line is decided, so the whole document is never held in memory.
+/// Writes are buffered, so `writer` does not need to be.
+///
+/// # Parameters
+///
+/// - `ast`: The AST node to pretty-print.
+/// - `config`: The layout options to use.
+/// - `writer`: The writer to write the formatted output to.
+///
+/// # Examples
+///
+/// ```
+/// use rasto::builder::*;
+/// use rasto::pretty_printer::{PrinterConfig, pretty_to_writer};
+///
+/// let ast = fn_def("main").build();
+/// let mut out = Vec::new();
+/// pretty_to_writer(&ast, &PrinterConfig::default(), &mut out).unwrap();
+/// assert_eq!(out, b"fn main() {}");
+/// ```
+pub fn pretty_to_writer(
+    ast: &impl PrettyPrinter,
+    config: &PrinterConfig,
+    writer: impl io::Write,
+) -> io::Result<()> {
+    let mut adapter = IoWriter {
+        inner: io::BufWriter::new(writer),
+        error: None,
+    };
+    let mut printer = Printer::new(&mut adapter, config.clone());
+    let result = ast
+        .pretty_print(&mut printer)
+        .and_then(|()| printer.finish());
+    if result.is_err() {
+        return Err(adapter
+            .error
+            .take()
+            .unwrap_or_else(|| io::Error::other("formatter error")));
+    }
+    io::Write::flush(&mut adapter.inner)
+}
+
+// Writes the output of a printer to an `io::Write`, and keeps the error that
+// stopped it.
+struct IoWriter<W: io::Write> {
+    inner: W,
+    error: Option<io::Error>,
+}
+
+impl<W: io::Write> Write for IoWriter<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|error| {
+            self.error = Some(error);
+            fmt::Error
+        })
+    }
+}
+
+/// Pretty-prints an AST node to a string, and records the range of the
+/// string that each node was printed to.
+///
+/// Every item, associated item, field, variant, block, statement, match arm,
+/// field initializer, expression, pattern and type is recorded.
+///
+/// # Parameters
+///
+/// - `ast`: The AST node to pretty-print.
+/// - `config`: The layout options to use.
+pub fn pretty_with_source_map(
+    ast: &impl PrettyPrinter,
+    config: &PrinterConfig,
+) -> (String, SourceMap) {
+    let mut buf = String::new();
+    let mut printer = Printer::new(&mut buf, config.clone());
+    printer.record_source_map();
+    ast.pretty_print(&mut printer).unwrap();
+    let source_map = printer.finish_with_source_map().unwrap();
+    (buf, source_map)
+}
+
+/// Pretty-prints an AST node to a string, writing each keyword, identifier,
+/// literal and other string with the given highlighter.
+///
+/// # Parameters
+///
+/// - `ast`: The AST node to pretty-print.
+/// - `config`: The layout options to use.
+/// - `highlighter`: The highlighter, such as [`Ansi`] or [`Html`].
+///
+/// # Examples
+///
+/// ```
+/// use rasto::builder::*;
+/// use rasto::pretty_printer::{Html, PrinterConfig, pretty_highlighted};
+///
+/// let ast = expr().lit(1);
+/// let html = pretty_highlighted(&ast, &PrinterConfig::default(), Html);
+/// assert_eq!(html, r#"<span class="literal">1</span>"#);
+/// ```
+pub fn pretty_highlighted(
+    ast: &impl PrettyPrinter,
+    config: &PrinterConfig,
+    highlighter: impl Highlighter,
+) -> String {
     let mut buf = String::new();
-    let mut printer = Printer::new(&mut buf);
+    let mut printer = Printer::new(&mut buf, config.clone());
+    printer.set_highlighter(highlighter);
     ast.pretty_print(&mut printer).unwrap();
     printer.finish().unwrap();
     buf
 }
 
 /// A pretty-printer for the Rust AST.
+///
+/// Nodes add strings, breaks and groups to the printer, which lays them out
+/// within the line width. The same methods are used by the nodes of this
+/// crate and by [`CustomNode`] implementations defined elsewhere.
 pub struct Printer<'a> {
     writer: &'a mut dyn Write,
-    tokens: Vec<Token<'a>>,
-    sizes: Vec<isize>,
-    // Ring buffer for scan
-    scan_buffer: Vec<(usize, isize)>,
-    scan_buffer_head: usize,
+    config: PrinterConfig,
+    // The tokens that have not been printed yet, with their sizes, and the
+    // index in the stream of the first of them
+    tokens: VecDeque<(Token<'a>, isize)>,
+    left: usize,
+    // Scan state: the groups and breaks whose size is not known yet, and the
+    // total width of the text scanned so far
+    scan_buffer: VecDeque<(usize, isize)>,
     right_total: isize,
+    // For each open group: its index, whether it directly contains a hard
+    // break, and the total at the first hard break nested anywhere inside it
+    groups: Vec<(usize, bool, Option<isize>)>,
+    // Groups closed since the last possible break, with the total at their
+    // end
+    trailing: Vec<(usize, isize)>,
+    // The first error returned by the writer
+    result: fmt::Result,
     // Print state
     margin: isize,
     space: isize,
     indent: usize,
     print_stack: Vec<(usize, bool, BreakStyle)>, // (indent, is_broken, style)
+    // Set after an end-of-line comment, until the next newline
+    pending_newline: bool,
+    // Set after a line break in single-line mode, until the next string
+    pending_space: bool,
+    // The indentation of the current line, until it is written
+    pending_indent: Option<usize>,
+    // The number of line breaks since the last string, or `None` before the
+    // first string
+    newlines: Option<usize>,
+    // Source map state, when recording
+    source_map: Option<SourceMap>,
+    position: Position,
+    // The source map indices of the nodes being printed, and how many of
+    // them have written their first character
+    open_nodes: Vec<usize>,
+    started_nodes: usize,
+    // Highlighting state, when highlighting
+    highlight: Option<Highlight<'a>>,
 }
 
 impl<'a> Printer<'a> {
@@ -159,21 +516,147 @@ impl<'a> Printer<'a> {
     /// # Parameters
     ///
     /// - `writer`: The `Write` buffer to write the formatted output to.
-    pub fn new(writer: &'a mut dyn Write) -> Self {
+    /// - `config`: The layout options to use.
+    pub fn new(writer: &'a mut dyn Write, config: PrinterConfig) -> Self {
+        // In single-line mode, only the groups with hard breaks are broken
+        let margin = if config.single_line {
+            INFINITY - 1
+        } else {
+            config.max_width as isize
+        };
         Self {
             writer,
-            tokens: Vec::new(),
-            sizes: Vec::new(),
-            scan_buffer: vec![(0, 0); 3 * LINE_WIDTH as usize],
-            scan_buffer_head: 0,
+            config,
+            tokens: VecDeque::new(),
+            left: 0,
+            scan_buffer: VecDeque::new(),
             right_total: 0,
-            margin: LINE_WIDTH,
-            space: LINE_WIDTH,
+            groups: Vec::new(),
+            trailing: Vec::new(),
+            result: Ok(()),
+            margin,
+            space: margin,
             indent: 0,
             print_stack: Vec::new(),
+            pending_newline: false,
+            pending_space: false,
+            pending_indent: Some(0),
+            newlines: None,
+            source_map: None,
+            position: Position::start(),
+            open_nodes: Vec::new(),
+            started_nodes: 0,
+            highlight: None,
+        }
+    }
+
+    /// Makes the printer record a [`SourceMap`] of the nodes marked with
+    /// [`Printer::begin_node`], to be returned by
+    /// [`Printer::finish_with_source_map`].
+    pub fn record_source_map(&mut self) {
+        self.source_map.get_or_insert_with(SourceMap::default);
+    }
+
+    /// Marks the beginning of the output of `node` in the source map.
+    ///
+    /// Does nothing unless the printer records a source map. Every call must
+    /// be matched by a call to [`Printer::end_node`].
+    ///
+    /// # Parameters
+    ///
+    /// - `node`: The node whose output follows.
+    pub fn begin_node<T: Any>(&mut self, node: &T) {
+        if self.source_map.is_some() {
+            self.push(Token::BeginNode {
+                id: NodeId::of(node),
+                kind: source_map::kind_of::<T>(),
+            });
+        }
+    }
+
+    /// Marks the end of the output of the node begun last.
+    pub fn end_node(&mut self) {
+        if self.source_map.is_some() {
+            self.push(Token::EndNode);
+        }
+    }
+
+    /// Prints `node` with `f`, between [`Printer::begin_node`] and
+    /// [`Printer::end_node`].
+    ///
+    /// # Parameters
+    ///
+    /// - `node`: The node that `f` prints.
+    /// - `f`: A function that adds the tokens of the node to the printer.
+    pub fn node<T: Any>(
+        &mut self,
+        node: &T,
+        f: impl FnOnce(&mut Self) -> fmt::Result,
+    ) -> fmt::Result {
+        self.begin_node(node);
+        f(self)?;
+        self.end_node();
+        Ok(())
+    }
+
+    /// Makes the printer write the strings of the output with `highlighter`,
+    /// classified by the kinds marked with [`Printer::begin_highlight`].
+    ///
+    /// This must be called before any tokens are added.
+    ///
+    /// # Parameters
+    ///
+    /// - `highlighter`: The highlighter, such as [`Ansi`] or [`Html`].
+    pub fn set_highlighter(&mut self, highlighter: impl Highlighter + 'a) {
+        self.highlight = Some(Highlight::new(highlighter));
+    }
+
+    /// Marks the strings that follow as being of the given kind.
+    ///
+    /// Does nothing unless the printer highlights its output. Every call must
+    /// be matched by a call to [`Printer::end_highlight`]. An
+    /// [`TokenKind::Ident`] inside another kind, such as the segments of a
+    /// type path, takes the outer kind.
+    ///
+    /// # Parameters
+    ///
+    /// - `kind`: The kind of the strings that follow.
+    pub fn begin_highlight(&mut self, kind: TokenKind) {
+        if self.highlight.is_some() {
+            self.push(Token::BeginHighlight { kind });
         }
     }
 
+    /// Ends the kind marked last.
+    pub fn end_highlight(&mut self) {
+        if self.highlight.is_some() {
+            self.push(Token::EndHighlight);
+        }
+    }
+
+    /// Prints the strings added by `f` as being of the given kind, between
+    /// [`Printer::begin_highlight`] and [`Printer::end_highlight`].
+    ///
+    /// # Parameters
+    ///
+    /// - `kind`: The kind of the strings.
+    /// - `f`: A function that adds the tokens to the printer.
+    pub fn highlight(
+        &mut self,
+        kind: TokenKind,
+        f: impl FnOnce(&mut Self) -> fmt::Result,
+    ) -> fmt::Result {
+        self.begin_highlight(kind);
+        f(self)?;
+        self.end_highlight();
+        Ok(())
+    }
+
+    /// Returns the layout options of this printer.
+    pub fn config(&self) -> &PrinterConfig {
+        &self.config
+    }
+
     /// Adds a string to the printer's token stream.
     ///
     /// # Parameters
@@ -182,18 +665,71 @@ impl<'a> Printer<'a> {
     pub fn string(&mut self, s: impl Into<Cow<'a, str>>) {
         let s = s.into();
         if !s.is_empty() {
-            self.tokens.push(Token::String(s));
+            self.push(Token::String(s));
         }
     }
 
     /// Adds a potential line break to the token stream.
     pub fn break_(&mut self) {
-        self.tokens.push(Token::Break { len: 1 });
+        self.push(Token::Break { len: 1 });
+    }
+
+    /// Adds a potential line break that prints nothing if it is not taken.
+    pub fn zero_break(&mut self) {
+        self.push(Token::Break { len: 0 });
     }
 
     /// Adds a hard line break to the token stream.
+    ///
+    /// In single-line mode, the break is printed as a space.
     pub fn hard_break(&mut self) {
-        self.tokens.push(Token::HardBreak);
+        self.push(Token::HardBreak);
+    }
+
+    /// Adds a hard line break that leaves an empty line before the next line.
+    ///
+    /// Nothing is added after an empty line, so that the output never has two
+    /// empty lines in a row. In single-line mode, the break is printed as a
+    /// space.
+    pub fn blank_line(&mut self) {
+        self.push(Token::BlankLine);
+    }
+
+    /// Adds a `//` comment at the end of the current line.
+    ///
+    /// # Parameters
+    ///
+    /// - `text`: The content of the comment, without the `//`.
+    pub fn eol_comment(&mut self, text: &str) {
+        if !self.config.single_line {
+            self.push(Token::EolComment(Cow::Owned(format!(" //{text}"))));
+        } else {
+            self.begin_highlight(TokenKind::Comment);
+            self.string(format!(" {}", block_comment("", text)));
+            self.end_highlight();
+        }
+    }
+
+    /// Adds a string whose text depends on the layout of the enclosing group.
+    ///
+    /// # Parameters
+    ///
+    /// - `broken`: The string to print if the group is broken.
+    /// - `flat`: The string to print if the group fits on one line.
+    pub fn if_broken(&mut self, broken: &'a str, flat: &'a str) {
+        self.push(Token::IfBroken { broken, flat });
+    }
+
+    /// Adds a comma after the last element of a list, as required by the
+    /// [`TrailingComma`] option of the printer.
+    ///
+    /// The comma depends on the layout of the enclosing group.
+    pub fn trailing_comma(&mut self) {
+        match self.config.trailing_comma {
+            TrailingComma::Always => self.string(","),
+            TrailingComma::Never => {}
+            TrailingComma::Vertical => self.if_broken(",", ""),
+        }
     }
 
     /// Begins a new group of tokens.
@@ -203,7 +739,27 @@ impl<'a> Printer<'a> {
     /// - `style`: The `BreakStyle` of the group.
     /// - `open`: The opening string of the group (e.g., `(`, `[`, `{`).
     pub fn begin(&mut self, style: BreakStyle, open: &'a str) {
-        self.tokens.push(Token::Begin { style, open });
+        self.push(Token::Begin {
+            style,
+            open,
+            max_width: None,
+        });
+    }
+
+    /// Begins a new group of tokens that is broken if its contents are wider
+    /// than `max_width`.
+    ///
+    /// # Parameters
+    ///
+    /// - `style`: The `BreakStyle` of the group.
+    /// - `open`: The opening string of the group (e.g., `(`, `[`, `{`).
+    /// - `max_width`: The maximum width of the contents on one line.
+    pub fn begin_limited(&mut self, style: BreakStyle, open: &'a str, max_width: usize) {
+        self.push(Token::Begin {
+            style,
+            open,
+            max_width: Some(max_width).filter(|_| !self.config.single_line),
+        });
     }
 
     /// Ends the current group of tokens.
@@ -212,203 +768,455 @@ impl<'a> Printer<'a> {
     ///
     /// - `close`: The closing string of the group (e.g., `)`, `]`, `}`).
     pub fn end(&mut self, close: &'a str) {
-        self.tokens.push(Token::End { close });
+        self.push(Token::End {
+            close,
+            newline: true,
+        });
     }
 
-    /// Scans the token stream to determine the best layout.
+    /// Ends the current group of tokens without starting a new line, even if
+    /// the group is broken.
     ///
-    /// This method implements the first pass of the pretty-printing algorithm.
-    /// It calculates the size of each token group to decide whether it should
-    /// be broken into multiple lines or printed on a single line.
-    pub fn scan(&mut self) {
-        self.sizes = vec![INFINITY; self.tokens.len()];
-        self.scan_buffer_head = 0;
-        self.right_total = 0;
-
-        let mut group_has_hard_break = vec![];
-
-        for i in 0..self.tokens.len() {
-            match &self.tokens[i] {
-                Token::Begin { .. } => {
-                    self.scan_push(i, -self.right_total);
-                    group_has_hard_break.push(false);
-                }
-                Token::End { .. } => {
-                    let has_hard_break = group_has_hard_break.pop().unwrap_or(false);
-                    loop {
-                        if self.scan_buffer_head == 0 {
+    /// # Parameters
+    ///
+    /// - `close`: The closing string of the group.
+    pub fn end_inline(&mut self, close: &'a str) {
+        self.push(Token::End {
+            close,
+            newline: false,
+        });
+    }
+
+    // Adds a token to the buffer, scans it, and prints the tokens whose
+    // layout is decided.
+    fn push(&mut self, token: Token<'a>) {
+        let i = self.left + self.tokens.len();
+        self.tokens.push_back((token, INFINITY));
+        self.scan(i);
+        self.check_stream();
+        self.print_decided();
+    }
+
+    fn token(&self, i: usize) -> &Token<'a> {
+        &self.tokens[i - self.left].0
+    }
+
+    fn size_mut(&mut self, i: usize) -> &mut isize {
+        &mut self.tokens[i - self.left].1
+    }
+
+    /// Scans the token at index `i` of the stream to determine the best
+    /// layout.
+    ///
+    /// This is the first half of the pretty-printing algorithm. It calculates
+    /// the size of each group and break to decide whether it should be broken
+    /// into multiple lines or printed on a single line. A size is known once
+    /// the group ends or the next break is reached.
+    ///
+    /// The size of a group includes the text that follows it up to the next
+    /// possible line break, so that a group is broken if what comes after it
+    /// would overflow the line. A group that contains a hard break only
+    /// counts the text up to its first line.
+    fn scan(&mut self, i: usize) {
+        match self.token(i) {
+            Token::Begin { open, .. } => {
+                let start = self.right_total;
+                self.right_total += open.len() as isize;
+                self.scan_trailing();
+                self.scan_buffer.push_back((i, -start));
+                self.groups.push((i, false, None));
+            }
+            Token::End { close, .. } => {
+                let close = close.len() as isize;
+                self.scan_trailing();
+                let contents_end = self.right_total;
+                self.right_total += close;
+                let (_, has_hard_break, first_line) = self.groups.pop().unwrap_or((i, false, None));
+                while let Some((j, offset)) = self.scan_buffer.pop_back() {
+                    match *self.token(j) {
+                        Token::Begin {
+                            open, max_width, ..
+                        } => {
+                            let contents = contents_end + offset - open.len() as isize;
+                            let size = if has_hard_break
+                                || max_width.is_some_and(|max| contents > max as isize)
+                            {
+                                INFINITY
+                            } else if let Some(total) = first_line {
+                                self.fit(total + offset)
+                            } else {
+                                let size = self.fit(self.right_total + offset);
+                                if size < INFINITY {
+                                    self.trailing.push((j, self.right_total));
+                                }
+                                size
+                            };
+                            *self.size_mut(j) = size;
                             break;
                         }
-                        self.scan_buffer_head -= 1;
-                        let (j, offset) = self.scan_buffer[self.scan_buffer_head];
-                        match self.tokens[j] {
-                            Token::Begin { .. } => {
-                                let len = self.right_total + offset;
-                                self.sizes[j] = if has_hard_break || len > self.margin {
-                                    INFINITY
-                                } else {
-                                    len
-                                };
-                                break;
-                            }
-                            Token::Break { .. } => {
-                                let len = self.right_total + offset;
-                                self.sizes[j] = if len > self.margin { INFINITY } else { len };
-                            }
-                            Token::HardBreak => {
-                                self.sizes[j] = INFINITY;
-                            }
-                            _ => {}
-                        }
-                    }
-                }
-                Token::Break { .. } => {
-                    while self.scan_buffer_head > 0 {
-                        let (j, offset) = self.scan_buffer[self.scan_buffer_head - 1];
-                        match self.tokens[j] {
-                            Token::Begin { .. } => break,
-                            Token::Break { .. } | Token::HardBreak => {
-                                self.scan_buffer_head -= 1;
-                                let len = self.right_total + offset;
-                                self.sizes[j] = if len > self.margin { INFINITY } else { len };
-                            }
-                            _ => {
-                                break;
-                            }
-                        }
-                    }
-                    self.scan_push(i, -self.right_total);
-                }
-                Token::HardBreak => {
-                    if let Some(last) = group_has_hard_break.last_mut() {
-                        *last = true;
-                    }
-                    while self.scan_buffer_head > 0 {
-                        let (j, offset) = self.scan_buffer[self.scan_buffer_head - 1];
-                        match self.tokens[j] {
-                            Token::Begin { .. } => break,
-                            Token::Break { .. } | Token::HardBreak => {
-                                self.scan_buffer_head -= 1;
-                                let len = self.right_total + offset;
-                                self.sizes[j] = if len > self.margin { INFINITY } else { len };
-                            }
-                            _ => {
-                                break;
-                            }
+                        Token::Break { .. } => {
+                            *self.size_mut(j) = self.fit(self.right_total + offset);
                         }
+                        _ => {}
                     }
-                    self.scan_push(i, -self.right_total);
-                }
-                Token::String(s) => {
-                    self.right_total += s.len() as isize;
                 }
             }
+            Token::Break { len } => {
+                let len = *len as isize;
+                self.scan_trailing();
+                self.scan_pop_breaks();
+                self.scan_buffer.push_back((i, -self.right_total));
+                self.right_total += len;
+            }
+            Token::HardBreak | Token::BlankLine => {
+                self.scan_trailing();
+                self.scan_hard_break();
+                self.scan_pop_breaks();
+            }
+            Token::String(s) => {
+                self.right_total += s.len() as isize;
+            }
+            Token::IfBroken { flat, .. } => {
+                self.right_total += flat.len() as isize;
+            }
+            Token::EolComment(s) => {
+                self.right_total += s.len() as isize;
+                self.scan_trailing();
+                self.scan_hard_break();
+            }
+            Token::BeginNode { .. }
+            | Token::EndNode
+            | Token::BeginHighlight { .. }
+            | Token::EndHighlight => {}
         }
+    }
 
-        while self.scan_buffer_head > 0 {
-            self.scan_buffer_head -= 1;
-            let (j, offset) = self.scan_buffer[self.scan_buffer_head];
-            if let Token::Break { .. } | Token::HardBreak = self.tokens[j] {
-                let len = self.right_total + offset;
-                self.sizes[j] = if len > self.margin { INFINITY } else { len };
+    fn fit(&self, len: isize) -> isize {
+        if len > self.margin { INFINITY } else { len }
+    }
+
+    // Sets the sizes of the breaks since the start of the innermost group.
+    fn scan_pop_breaks(&mut self) {
+        while let Some(&(j, offset)) = self.scan_buffer.back() {
+            if !matches!(self.token(j), Token::Break { .. }) {
+                break;
             }
+            self.scan_buffer.pop_back();
+            *self.size_mut(j) = self.fit(self.right_total + offset);
         }
     }
 
-    fn scan_push(&mut self, i: usize, offset: isize) {
-        self.scan_buffer[self.scan_buffer_head] = (i, offset);
-        self.scan_buffer_head += 1;
+    fn scan_hard_break(&mut self) {
+        if let Some((_, has_hard_break, _)) = self.groups.last_mut() {
+            *has_hard_break = true;
+        }
+        // The groups whose first line has ended are the outermost ones, so
+        // only the innermost groups up to the first such group are updated.
+        for (_, _, first_line) in self.groups.iter_mut().rev() {
+            if first_line.is_some() {
+                break;
+            }
+            *first_line = Some(self.right_total);
+        }
     }
 
-    /// Prints the token stream to the writer.
-    ///
-    /// This method implements the second pass of the pretty-printing algorithm.
-    /// It iterates through the tokens and writes the formatted output to the
-    /// `Write` buffer, using the layout information from the `scan` pass.
-    pub fn print(&mut self) -> fmt::Result {
-        for i in 0..self.tokens.len() {
-            match &self.tokens[i] {
-                Token::Begin { style, open } => {
-                    let size = self.sizes[i];
-                    let is_broken = size > self.space;
-                    self.print_stack.push((self.indent, is_broken, *style));
-                    self.writer.write_str(open)?;
-                    self.space -= open.len() as isize;
-                    if is_broken {
-                        self.indent += INDENT_SIZE;
+    // Adds the text since the end of recently closed groups to their sizes.
+    fn scan_trailing(&mut self) {
+        for (j, total) in std::mem::take(&mut self.trailing) {
+            *self.size_mut(j) = self.fit(self.tokens[j - self.left].1 + self.right_total - total);
+        }
+    }
+
+    // Breaks the oldest groups and breaks whose size is not known yet, but
+    // which can no longer fit on a line, so that they can be printed.
+    fn check_stream(&mut self) {
+        while let Some(&(j, offset)) = self.scan_buffer.front() {
+            let min_size = match *self.token(j) {
+                Token::Begin {
+                    open, max_width, ..
+                } => {
+                    let group = self.groups.partition_point(|&(k, _, _)| k < j);
+                    let (_, has_hard_break, first_line) = self.groups[group];
+                    let contents = self.right_total + offset - open.len() as isize;
+                    if has_hard_break || max_width.is_some_and(|max| contents > max as isize) {
+                        INFINITY
+                    } else {
+                        first_line.unwrap_or(self.right_total) + offset
                     }
                 }
-                Token::End { close } => {
-                    let (indent, is_broken, _) = self.print_stack.pop().unwrap();
-                    self.indent = indent;
-                    if is_broken {
-                        self.writer.write_char('\n')?;
-                        for _ in 0..self.indent {
-                            self.writer.write_char(' ')?;
-                        }
-                        self.space = self.margin - self.indent as isize;
+                _ => self.right_total + offset,
+            };
+            if min_size <= self.margin {
+                break;
+            }
+            self.scan_buffer.pop_front();
+            *self.size_mut(j) = INFINITY;
+        }
+        let mut k = 0;
+        while k < self.trailing.len() {
+            let (j, total) = self.trailing[k];
+            if self.tokens[j - self.left].1 + self.right_total - total > self.margin {
+                self.trailing.swap_remove(k);
+                *self.size_mut(j) = INFINITY;
+            } else {
+                k += 1;
+            }
+        }
+    }
+
+    // Prints the tokens before the oldest one whose size is not known yet.
+    fn print_decided(&mut self) {
+        let undecided = self.scan_buffer.front().map(|&(j, _)| j);
+        let decided = (self.trailing.iter().map(|&(j, _)| j))
+            .chain(undecided)
+            .min()
+            .unwrap_or(self.left + self.tokens.len());
+        while self.left < decided {
+            let (token, size) = self.tokens.pop_front().unwrap();
+            self.left += 1;
+            if self.result.is_ok() {
+                self.result = self.print_token(&token, size);
+            }
+        }
+    }
+
+    /// Prints a token whose size is known to the writer.
+    ///
+    /// This is the second half of the pretty-printing algorithm. It writes
+    /// the formatted output to the `Write` buffer, using the layout decided
+    /// by the scan.
+    fn print_token(&mut self, token: &Token<'a>, size: isize) -> fmt::Result {
+        match token {
+            Token::Begin { style, open, .. } => {
+                let is_broken = size > self.space;
+                self.print_stack.push((self.indent, is_broken, *style));
+                self.write_str(open)?;
+                if is_broken {
+                    self.indent += self.config.tab_spaces;
+                }
+            }
+            Token::End { close, newline } => {
+                let (indent, is_broken, _) = self.print_stack.pop().unwrap();
+                self.indent = indent;
+                if is_broken && *newline {
+                    self.newline()?;
+                }
+                self.write_str(close)?;
+            }
+            Token::Break { len } => {
+                let (_, is_broken, style) =
+                    self.print_stack
+                        .last()
+                        .copied()
+                        .unwrap_or((0, false, BreakStyle::Consistent));
+
+                let break_decision = if style == BreakStyle::Consistent {
+                    is_broken
+                } else {
+                    size > self.space
+                };
+
+                if break_decision || self.pending_newline {
+                    self.newline()?;
+                } else if self.config.single_line {
+                    self.pending_space |= *len > 0;
+                } else {
+                    self.write_indent()?;
+                    self.flush_highlight()?;
+                    let spaces = &" ".repeat(*len);
+                    self.writer.write_str(spaces)?;
+                    self.advance(spaces);
+                    self.space -= *len as isize;
+                }
+            }
+            Token::HardBreak => self.newline()?,
+            Token::BlankLine => match self.newlines {
+                Some(newlines) if !self.config.single_line => {
+                    for _ in newlines..2 {
+                        self.newline()?;
                     }
-                    self.writer.write_str(close)?;
-                    self.space -= close.len() as isize;
                 }
-                Token::Break { len } => {
-                    let (_, is_broken, style) = self.print_stack.last().copied().unwrap_or((
-                        0,
-                        false,
-                        BreakStyle::Consistent,
-                    ));
-
-                    let break_decision = if style == BreakStyle::Consistent {
-                        is_broken
-                    } else {
-                        self.sizes[i] > self.space
-                    };
-
-                    if break_decision {
-                        self.writer.write_char('\n')?;
-                        for _ in 0..self.indent {
-                            self.writer.write_char(' ')?;
-                        }
-                        self.space = self.margin - self.indent as isize;
+                Some(_) => self.newline()?,
+                None => {}
+            },
+            Token::String(s) => self.write_str(s)?,
+            Token::IfBroken { broken, flat } => {
+                let is_broken =
+                    !self.config.single_line && self.print_stack.last().is_some_and(|&(_, b, _)| b);
+                self.write_str(if is_broken { broken } else { flat })?;
+            }
+            Token::EolComment(s) => {
+                if let Some(highlight) = &mut self.highlight {
+                    highlight.begin(TokenKind::Comment);
+                }
+                self.write_str(s)?;
+                if let Some(highlight) = &mut self.highlight {
+                    highlight.end();
+                }
+                self.pending_newline = true;
+            }
+            Token::BeginNode { id, kind } => {
+                if let Some(source_map) = &mut self.source_map {
+                    let index = source_map.push(NodeSpan::new(*id, kind, self.position));
+                    self.open_nodes.push(index);
+                }
+            }
+            Token::EndNode => {
+                if let (Some(source_map), Some(index)) =
+                    (&mut self.source_map, self.open_nodes.pop())
+                {
+                    let span = source_map.span_mut(index);
+                    if self.started_nodes > self.open_nodes.len() {
+                        self.started_nodes = self.open_nodes.len();
                     } else {
-                        for _ in 0..*len {
-                            self.writer.write_char(' ')?;
-                        }
-                        self.space -= *len as isize;
+                        span.start = self.position;
                     }
+                    span.end = self.position;
                 }
-                Token::HardBreak => {
-                    self.writer.write_char('\n')?;
-                    for _ in 0..self.indent {
-                        self.writer.write_char(' ')?;
-                    }
-                    self.space = self.margin - self.indent as isize;
+            }
+            Token::BeginHighlight { kind } => {
+                if let Some(highlight) = &mut self.highlight {
+                    highlight.begin(*kind);
                 }
-                Token::String(s) => {
-                    self.writer.write_str(s)?;
-                    self.space -= s.len() as isize;
+            }
+            Token::EndHighlight => {
+                if let Some(highlight) = &mut self.highlight {
+                    highlight.end();
                 }
             }
         }
         Ok(())
     }
 
-    /// Scans and prints the token stream to the writer.
+    // Moves the source map position past text that was written.
+    fn advance(&mut self, s: &str) {
+        if self.source_map.is_some() {
+            self.position.advance(s);
+        }
+    }
+
+    // Starts the nodes that have not written anything yet at the current
+    // position, before their first character is written.
+    fn start_nodes(&mut self) {
+        if let Some(source_map) = &mut self.source_map {
+            for &index in &self.open_nodes[self.started_nodes..] {
+                source_map.span_mut(index).start = self.position;
+            }
+            self.started_nodes = self.open_nodes.len();
+        }
+    }
+
+    // Writes the last highlighted token, before whitespace is written.
+    fn flush_highlight(&mut self) -> fmt::Result {
+        match &mut self.highlight {
+            Some(highlight) => highlight.flush(self.writer),
+            None => Ok(()),
+        }
+    }
+
+    // Indentation is written lazily, so that blank lines carry no trailing
+    // whitespace.
+    fn newline(&mut self) -> fmt::Result {
+        if self.config.single_line {
+            self.pending_space |= self.newlines.is_some() && self.pending_indent.is_none();
+            return Ok(());
+        }
+        // Nothing is printed before the first string, and at most one empty
+        // line is printed in a row
+        if self.newlines.is_some_and(|newlines| newlines < 2) {
+            self.flush_highlight()?;
+            let newline = self.config.newline_style.as_str();
+            self.writer.write_str(newline)?;
+            self.advance(newline);
+            self.newlines = self.newlines.map(|newlines| newlines + 1);
+        }
+        self.pending_indent = Some(self.indent);
+        self.space = self.margin - self.indent as isize;
+        self.pending_newline = false;
+        Ok(())
+    }
+
+    fn write_indent(&mut self) -> fmt::Result {
+        if let Some(indent) = self.pending_indent.take() {
+            let (tabs, spaces) = if self.config.hard_tabs && self.config.tab_spaces > 0 {
+                (
+                    indent / self.config.tab_spaces,
+                    indent % self.config.tab_spaces,
+                )
+            } else {
+                (0, indent)
+            };
+            let indent = "\t".repeat(tabs) + &" ".repeat(spaces);
+            self.writer.write_str(&indent)?;
+            self.advance(&indent);
+        }
+        Ok(())
+    }
+
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if s.is_empty() {
+            return Ok(());
+        }
+        if self.pending_newline {
+            self.newline()?;
+        }
+        self.write_indent()?;
+        if self.pending_space {
+            self.pending_space = false;
+            self.flush_highlight()?;
+            self.writer.write_str(" ")?;
+            self.advance(" ");
+        }
+        self.start_nodes();
+        match &mut self.highlight {
+            Some(highlight) => highlight.write(self.writer, s)?,
+            None => self.writer.write_str(s)?,
+        }
+        self.advance(s);
+        self.space -= s.len() as isize;
+        self.newlines = Some(0);
+        Ok(())
+    }
+
+    /// Prints the rest of the token stream to the writer, and ends the
+    /// output with a line ending if `final_newline` is set.
     ///
-    /// This is a convenience method that calls `scan` and then `print`.
+    /// Returns the first error returned by the writer, if any.
     pub fn finish(mut self) -> fmt::Result {
-        self.scan();
-        self.print()
+        self.flush()
+    }
+
+    /// Prints the rest of the token stream like [`Printer::finish`], and
+    /// returns the source map recorded since [`Printer::record_source_map`]
+    /// was called.
+    ///
+    /// The source map is empty if it was not recorded.
+    pub fn finish_with_source_map(mut self) -> Result<SourceMap, fmt::Error> {
+        self.flush()?;
+        Ok(self.source_map.take().unwrap_or_default())
+    }
+
+    fn flush(&mut self) -> fmt::Result {
+        self.scan_trailing();
+        while let Some((j, offset)) = self.scan_buffer.pop_back() {
+            if let Token::Break { .. } = self.token(j) {
+                *self.size_mut(j) = self.fit(self.right_total + offset);
+            }
+        }
+        self.print_decided();
+        self.result?;
+        self.flush_highlight()?;
+        if self.config.final_newline && self.pending_indent.is_none() {
+            self.writer.write_str(self.config.newline_style.as_str())?;
+        }
+        Ok(())
     }
 }
 
 impl PrettyPrinter for Comment {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
         printer.hard_break();
-        match self {
-            Comment::Line(s) => printer.string(format!("//{s}")),
-            Comment::Doc(s) => printer.string(format!("///{s}")),
-        }
+        pp_comment_text(self, printer);
         printer.hard_break();
         Ok(())
     }
@@ -420,7 +1228,7 @@ impl PrettyPrinter for ItemTypeAlias {
         pp_begin(&self.md, printer)?;
         self.vis.pretty_print(printer)?;
         printer.string("type ");
-        self.ident.pretty_print(printer)?;
+        pp_type_name(&self.ident, printer)?;
         self.generics.pretty_print(printer)?;
         printer.string(" = ");
         self.ty.pretty_print(printer)?;
@@ -471,7 +1279,7 @@ impl PrettyPrinter for ItemExternType {
         pp_begin(&self.md, printer)?;
         self.vis.pretty_print(printer)?;
         printer.string("extern type ");
-        self.ident.pretty_print(printer)?;
+        pp_type_name(&self.ident, printer)?;
         printer.string(";");
         pp_end(&self.md, printer)?;
         Ok(())
@@ -485,15 +1293,12 @@ impl PrettyPrinter for ItemExternBlock {
         if self.is_unsafe {
             printer.string("unsafe ");
         }
-        printer.string("extern ");
-        if let Some(abi) = &self.abi {
-            printer.string(format!("\"{abi}\""));
-        }
+        self.abi.pretty_print(printer)?;
         printer.string(" ");
         printer.begin(BreakStyle::Consistent, "{");
         if !self.items.is_empty() {
             printer.hard_break();
-            pp_with_breaks(&self.items, printer)?;
+            pp_items(&self.items, printer)?;
         }
         printer.end("}");
         pp_end(&self.md, printer)?;
@@ -504,26 +1309,50 @@ impl PrettyPrinter for ItemExternBlock {
 impl PrettyPrinter for ExternalItem {
     /// Pretty-prints the `ExternalItem` to the given printer.
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
-        match self {
-            ExternalItem::Static(ident, ty) => {
-                printer.string("static ");
-                ident.pretty_print(printer)?;
-                printer.string(": ");
-                ty.pretty_print(printer)?;
-                printer.string(";");
-            }
-            ExternalItem::Fn(item_fn) => {
-                printer.string("fn ");
-                item_fn.sig.pretty_print(printer)?;
-                printer.string(";");
-            }
-            ExternalItem::Macro(item_macro) => {
-                item_macro.pretty_print(printer)?;
-            }
-            ExternalItem::Type(item_extern_type) => {
-                item_extern_type.pretty_print(printer)?;
+        printer.node(self, |printer| {
+            match self {
+                ExternalItem::Static(item_static) => {
+                    item_static.pretty_print(printer)?;
+                }
+                ExternalItem::Fn(item_fn) => {
+                    pp_begin(&item_fn.md, printer)?;
+                    item_fn.vis.pretty_print(printer)?;
+                    item_fn.sig.pretty_print(printer)?;
+                    printer.string(";");
+                    pp_end(&item_fn.md, printer)?;
+                }
+                ExternalItem::Macro(item_macro) => {
+                    item_macro.pretty_print(printer)?;
+                }
+                ExternalItem::Type(item_extern_type) => {
+                    item_extern_type.pretty_print(printer)?;
+                }
             }
+            Ok(())
+        })
+    }
+}
+
+impl PrettyPrinter for ExternalStatic {
+    /// Pretty-prints the `ExternalStatic` to the given printer.
+    fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
+        pp_begin(&self.md, printer)?;
+        self.vis.pretty_print(printer)?;
+        if self.is_safe {
+            printer.string("safe ");
+        }
+        if self.is_unsafe {
+            printer.string("unsafe ");
+        }
+        printer.string("static ");
+        if self.is_mut {
+            printer.string("mut ");
         }
+        self.ident.pretty_print(printer)?;
+        printer.string(": ");
+        self.ty.pretty_print(printer)?;
+        printer.string(";");
+        pp_end(&self.md, printer)?;
         Ok(())
     }
 }
@@ -540,7 +1369,7 @@ impl PrettyPrinter for AssociatedConst {
             expr.pretty_print(printer)?;
         }
         printer.string(";");
-        Ok(())
+        pp_end(&self.md, printer)
     }
 }
 
@@ -548,7 +1377,7 @@ impl PrettyPrinter for AssociatedType {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
         pp_begin(&self.md, printer)?;
         printer.string("type ");
-        self.ident.pretty_print(printer)?;
+        pp_type_name(&self.ident, printer)?;
         self.generics.pretty_print(printer)?;
         if !self.bounds.is_empty() {
             printer.string(": ");
@@ -564,13 +1393,13 @@ impl PrettyPrinter for AssociatedType {
             default.pretty_print(printer)?;
         }
         printer.string(";");
-        Ok(())
+        pp_end(&self.md, printer)
     }
 }
 
 impl PrettyPrinter for Pat {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
-        match self {
+        printer.node(self, |printer| match self {
             Pat::Const(pat) => pat.pretty_print(printer),
             Pat::Ident(pat) => pat.pretty_print(printer),
             Pat::Lit(pat) => pat.pretty_print(printer),
@@ -586,8 +1415,9 @@ impl PrettyPrinter for Pat {
             Pat::Tuple(pat) => pat.pretty_print(printer),
             Pat::TupleStruct(pat) => pat.pretty_print(printer),
             Pat::Type(pat) => pat.pretty_print(printer),
+            Pat::Verbatim(pat) => pp_verbatim_text(pat, printer),
             Pat::Wild(pat) => pat.pretty_print(printer),
-        }
+        })
     }
 }
 
@@ -656,10 +1486,7 @@ impl PrettyPrinter for PatRange {
         // compile, such as `start..end` or `start..=`. A more robust
         // implementation would involve refining the `PatRange` AST to prevent
         // these invalid states from being represented.
-        match self.limits {
-            RangeLimits::HalfOpen => printer.string(".."),
-            RangeLimits::Closed => printer.string("..="),
-        }
+        self.limits.pretty_print(printer)?;
         if let Some(end) = &self.end {
             end.pretty_print(printer)?;
         }
@@ -701,6 +1528,10 @@ impl PrettyPrinter for PatSlice {
 impl PrettyPrinter for PatStruct {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
         self.path.pretty_print(printer)?;
+        if self.fields.is_empty() && !self.has_rest {
+            printer.string(" {}");
+            return Ok(());
+        }
         printer.begin(BreakStyle::Consistent, " {");
         printer.break_();
         for (i, field) in self.fields.iter().enumerate() {
@@ -725,6 +1556,12 @@ impl PrettyPrinter for PatStruct {
 
 impl PrettyPrinter for FieldPat {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
+        // `x: x` and `x: mut x` are printed in their shorthand forms.
+        if let Pat::Ident(pat) = &*self.pat
+            && pat.ident == self.member
+        {
+            return pat.pretty_print(printer);
+        }
         self.member.pretty_print(printer)?;
         printer.string(": ");
         self.pat.pretty_print(printer)
@@ -780,6 +1617,7 @@ impl PrettyPrinter for UnOp {
         match self {
             UnOp::Not => printer.string("!"),
             UnOp::Neg => printer.string("-"),
+            UnOp::Deref => printer.string("*"),
         }
         Ok(())
     }
@@ -797,6 +1635,24 @@ impl PrettyPrinter for Path {
     }
 }
 
+/// Pretty-prints a path in expression position, where generic arguments need
+/// a turbofish, e.g., `size_of::<u8>`.
+fn pp_path_turbofish<'a>(path: &'a Path, printer: &mut Printer<'a>) -> fmt::Result {
+    for (i, segment) in path.segments.iter().enumerate() {
+        if i > 0 {
+            printer.string("::");
+        }
+        segment.ident.pretty_print(printer)?;
+        if let Some(args) = &segment.args
+            && !args.args.is_empty()
+        {
+            printer.string("::");
+            args.pretty_print(printer)?;
+        }
+    }
+    Ok(())
+}
+
 impl PrettyPrinter for PathSegment {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
         self.ident.pretty_print(printer)?;
@@ -809,7 +1665,7 @@ impl PrettyPrinter for PathSegment {
 
 impl PrettyPrinter for Lit {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
-        match self {
+        printer.highlight(TokenKind::Literal, |printer| match self {
             Lit::Str(lit) => lit.pretty_print(printer),
             Lit::ByteStr(lit) => lit.pretty_print(printer),
             Lit::CStr(lit) => lit.pretty_print(printer),
@@ -818,48 +1674,68 @@ impl PrettyPrinter for Lit {
             Lit::Int(lit) => lit.pretty_print(printer),
             Lit::Float(lit) => lit.pretty_print(printer),
             Lit::Bool(lit) => lit.pretty_print(printer),
-        }
+        })
     }
 }
 
 impl PrettyPrinter for LitStr {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
-        printer.string(format!("\"{}\"", self.value));
-        Ok(())
+        let keep_newlines = !printer.config().single_line;
+        printer.highlight(TokenKind::Literal, |printer| {
+            printer.string(format!("\"{}\"", escape_str(&self.value, keep_newlines)));
+            Ok(())
+        })
     }
 }
 
+/// Escapes the contents of a string literal. Newlines are kept as they are if
+/// `keep_newlines` is set, so that multi-line strings stay readable.
+fn escape_str(value: &str, keep_newlines: bool) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\n' if keep_newlines => escaped.push(c),
+            '\'' => escaped.push(c),
+            _ => escaped.extend(c.escape_debug()),
+        }
+    }
+    escaped
+}
+
 impl PrettyPrinter for LitByteStr {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
-        printer.string(format!("b\"{}\"", String::from_utf8_lossy(&self.value)));
+        printer.string(format!("b\"{}\"", self.value.escape_ascii()));
         Ok(())
     }
 }
 
 impl PrettyPrinter for LitCStr {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
-        printer.string(format!("c\"{}\"", String::from_utf8_lossy(&self.value)));
+        printer.string(format!("c\"{}\"", self.value.escape_ascii()));
         Ok(())
     }
 }
 
 impl PrettyPrinter for LitByte {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
-        printer.string(format!("b'{}'", self.value as char));
+        printer.string(format!("b'{}'", self.value.escape_ascii()));
         Ok(())
     }
 }
 
 impl PrettyPrinter for LitChar {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
-        printer.string(format!("'{}'", self.value));
+        printer.string(format!("{:?}", self.value));
         Ok(())
     }
 }
 
 impl PrettyPrinter for LitInt {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
-        printer.string(self.value.to_string());
+        if self.is_negative {
+            printer.string("-");
+        }
+        printer.string(self.digits());
         if let Some(suffix) = &self.suffix {
             let s = match suffix {
                 IntSuffix::U8 => "u8",
@@ -883,6 +1759,10 @@ impl PrettyPrinter for LitInt {
 
 impl PrettyPrinter for LitFloat {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
+        if let Some(path) = self.non_finite_path() {
+            printer.string(path);
+            return Ok(());
+        }
         printer.string(&self.value);
         if let Some(suffix) = &self.suffix {
             let s = match suffix {
@@ -915,6 +1795,24 @@ impl PrettyPrinter for BinOp {
             BinOp::Ne => printer.string("!="),
             BinOp::Ge => printer.string(">="),
             BinOp::Gt => printer.string(">"),
+            BinOp::Rem => printer.string("%"),
+            BinOp::And => printer.string("&&"),
+            BinOp::Or => printer.string("||"),
+            BinOp::BitXor => printer.string("^"),
+            BinOp::BitAnd => printer.string("&"),
+            BinOp::BitOr => printer.string("|"),
+            BinOp::Shl => printer.string("<<"),
+            BinOp::Shr => printer.string(">>"),
+            BinOp::AddAssign => printer.string("+="),
+            BinOp::SubAssign => printer.string("-="),
+            BinOp::MulAssign => printer.string("*="),
+            BinOp::DivAssign => printer.string("/="),
+            BinOp::RemAssign => printer.string("%="),
+            BinOp::BitXorAssign => printer.string("^="),
+            BinOp::BitAndAssign => printer.string("&="),
+            BinOp::BitOrAssign => printer.string("|="),
+            BinOp::ShlAssign => printer.string("<<="),
+            BinOp::ShrAssign => printer.string(">>="),
         }
         Ok(())
     }
@@ -922,90 +1820,161 @@ impl PrettyPrinter for BinOp {
 
 impl PrettyPrinter for ExprBinary {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
-        let precedence = self.op.precedence();
-        printer.begin(BreakStyle::Inconsistent, "");
-        pretty_print_expr(&self.left, printer, precedence, true)?;
-        printer.break_();
-        self.op.pretty_print(printer)?;
-        printer.string(" ");
-        pretty_print_expr(&self.right, printer, precedence, false)?;
-        printer.end("");
-        Ok(())
+        pp_binary(self, false, printer)
+    }
+}
+
+/// Pretty-prints a binary operation. `followed` is whether tokens of the
+/// enclosing expression follow it.
+fn pp_binary<'a>(expr: &'a ExprBinary, followed: bool, printer: &mut Printer<'a>) -> fmt::Result {
+    // A chain of the same operator, such as `a + b + c`, is broken before
+    // every operator if it does not fit on one line. Left operands that are
+    // chains of another operator are printed by this loop rather than by
+    // recursion, so that long chains do not overflow the stack.
+    let mut chains = vec![];
+    let mut binary = expr;
+    let mut nested = None;
+    let mut followed = followed;
+    loop {
+        let left_position = binary.op.left_position();
+        let mut operands = vec![binary];
+        while let left = &operands[operands.len() - 1].left
+            && let Expr::Binary(inner) = &**left
+            && inner.op == binary.op
+            && !left_position.needs_parens(left)
+        {
+            operands.push(inner);
+        }
+        printer.begin(BreakStyle::Consistent, "");
+        let left = &operands[operands.len() - 1].left;
+        chains.push((operands, nested, followed));
+        match &**left {
+            Expr::Binary(inner) => {
+                let needs_parens = left_position.needs_parens(left);
+                printer.begin_node(&**left);
+                if needs_parens {
+                    printer.string("(");
+                }
+                binary = inner;
+                nested = Some(needs_parens);
+                followed = !needs_parens;
+            }
+            _ => {
+                pretty_print_expr(left, printer, left_position)?;
+                break;
+            }
+        }
+    }
+    for (operands, nested, followed) in chains.into_iter().rev() {
+        let min = operands[0].op.right_precedence();
+        for (i, binary) in operands.into_iter().enumerate().rev() {
+            printer.break_();
+            binary.op.pretty_print(printer)?;
+            printer.string(" ");
+            let position = ExprPosition::Operand {
+                min,
+                followed: i > 0 || followed,
+            };
+            pretty_print_expr(&binary.right, printer, position)?;
+        }
+        printer.end_inline("");
+        if let Some(needs_parens) = nested {
+            if needs_parens {
+                printer.string(")");
+            }
+            printer.end_node();
+        }
     }
+    Ok(())
 }
 
 impl PrettyPrinter for ExprUnary {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
-        self.op.pretty_print(printer)?;
-        self.expr.pretty_print(printer)?;
-        Ok(())
+        pp_unary(self, false, printer)
     }
 }
 
+fn pp_unary<'a>(expr: &'a ExprUnary, followed: bool, printer: &mut Printer<'a>) -> fmt::Result {
+    expr.op.pretty_print(printer)?;
+    pp_operand(&expr.expr, Precedence::Prefix, followed, printer)
+}
+
+/// Pretty-prints an operand that must bind at least at `min`, in parentheses
+/// if it does not.
+fn pp_operand<'a>(
+    expr: &'a Expr,
+    min: Precedence,
+    followed: bool,
+    printer: &mut Printer<'a>,
+) -> fmt::Result {
+    pretty_print_expr(expr, printer, ExprPosition::Operand { min, followed })
+}
+
 impl PrettyPrinter for Expr {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
-        pretty_print_expr(self, printer, 0, false)
+        pretty_print_expr(self, printer, ExprPosition::Top)
     }
 }
 
+/// Pretty-prints `expr` at `position`, in parentheses if it would otherwise
+/// be parsed differently there.
 fn pretty_print_expr<'a>(
     expr: &'a Expr,
     printer: &mut Printer<'a>,
-    parent_precedence: u8,
-    is_left: bool,
+    position: ExprPosition,
 ) -> fmt::Result {
-    match expr {
-        Expr::Binary(binary) => {
-            let precedence = binary.op.precedence();
-            let needs_paren = if is_left {
-                precedence < parent_precedence
-            } else {
-                precedence <= parent_precedence
-            };
-
-            if needs_paren {
-                printer.string("(");
-                binary.pretty_print(printer)?;
-                printer.string(")");
-            } else {
-                binary.pretty_print(printer)?;
-            }
-        }
-        Expr::Lit(lit) => lit.pretty_print(printer)?,
-        Expr::If(expr) => expr.pretty_print(printer)?,
-        Expr::Block(expr) => expr.pretty_print(printer)?,
-        Expr::Loop(expr) => expr.pretty_print(printer)?,
-        Expr::While(expr) => expr.pretty_print(printer)?,
-        Expr::For(expr) => expr.pretty_print(printer)?,
-        Expr::Assign(expr) => expr.pretty_print(printer)?,
-        Expr::MacroCall(expr) => expr.pretty_print(printer)?,
-        Expr::Array(expr) => expr.pretty_print(printer)?,
-        Expr::Async(expr) => expr.pretty_print(printer)?,
-        Expr::Await(expr) => expr.pretty_print(printer)?,
-        Expr::Break(expr) => expr.pretty_print(printer)?,
-        Expr::Call(expr) => expr.pretty_print(printer)?,
-        Expr::Cast(expr) => expr.pretty_print(printer)?,
-        Expr::Closure(expr) => expr.pretty_print(printer)?,
-        Expr::Const(expr) => expr.pretty_print(printer)?,
-        Expr::Continue(expr) => expr.pretty_print(printer)?,
-        Expr::Field(expr) => expr.pretty_print(printer)?,
-        Expr::Gen(expr) => expr.pretty_print(printer)?,
-        Expr::Index(expr) => expr.pretty_print(printer)?,
-        Expr::Match(expr) => expr.pretty_print(printer)?,
-        Expr::MethodCall(expr) => expr.pretty_print(printer)?,
-        Expr::Paren(expr) => expr.pretty_print(printer)?,
-        Expr::Path(expr) => expr.pretty_print(printer)?,
-        Expr::Range(expr) => expr.pretty_print(printer)?,
-        Expr::Reference(expr) => expr.pretty_print(printer)?,
-        Expr::RawRef(expr) => expr.pretty_print(printer)?,
-        Expr::Return(expr) => expr.pretty_print(printer)?,
-        Expr::Struct(expr) => expr.pretty_print(printer)?,
-        Expr::Try(expr) => expr.pretty_print(printer)?,
-        Expr::Tuple(expr) => expr.pretty_print(printer)?,
-        Expr::Infer(expr) => expr.pretty_print(printer)?,
-        Expr::Unary(expr) => expr.pretty_print(printer)?,
-    }
-    Ok(())
+    printer.node(expr, |printer| {
+        let needs_parens = position.needs_parens(expr);
+        if needs_parens {
+            printer.string("(");
+        }
+        let followed = !needs_parens && position.is_followed();
+        match expr {
+            Expr::Binary(expr) => pp_binary(expr, followed, printer)?,
+            Expr::Unary(expr) => pp_unary(expr, followed, printer)?,
+            Expr::Reference(expr) => pp_ref(expr, followed, printer)?,
+            Expr::RawRef(expr) => pp_raw_ref(expr, followed, printer)?,
+            Expr::Range(expr) => pp_range(expr, followed, printer)?,
+            Expr::Assign(expr) => pp_assign(expr, followed, printer)?,
+            Expr::Closure(expr) => pp_closure(expr, followed, printer)?,
+            Expr::Return(expr) => pp_return(expr, followed, printer)?,
+            Expr::Become(expr) => pp_become(expr, followed, printer)?,
+            Expr::Yield(expr) => pp_yield(expr, followed, printer)?,
+            Expr::Lit(lit) => lit.pretty_print(printer)?,
+            Expr::If(expr) => expr.pretty_print(printer)?,
+            Expr::Block(expr) => expr.pretty_print(printer)?,
+            Expr::Loop(expr) => expr.pretty_print(printer)?,
+            Expr::While(expr) => expr.pretty_print(printer)?,
+            Expr::For(expr) => expr.pretty_print(printer)?,
+            Expr::MacroCall(expr) => expr.pretty_print(printer)?,
+            Expr::Array(expr) => expr.pretty_print(printer)?,
+            Expr::Asm(expr) => expr.pretty_print(printer)?,
+            Expr::Async(expr) => expr.pretty_print(printer)?,
+            Expr::Await(expr) => expr.pretty_print(printer)?,
+            Expr::Break(expr) => expr.pretty_print(printer)?,
+            Expr::Call(expr) => expr.pretty_print(printer)?,
+            Expr::Cast(expr) => expr.pretty_print(printer)?,
+            Expr::Const(expr) => expr.pretty_print(printer)?,
+            Expr::Continue(expr) => expr.pretty_print(printer)?,
+            Expr::Field(expr) => expr.pretty_print(printer)?,
+            Expr::Gen(expr) => expr.pretty_print(printer)?,
+            Expr::Index(expr) => expr.pretty_print(printer)?,
+            Expr::Match(expr) => expr.pretty_print(printer)?,
+            Expr::MethodCall(expr) => expr.pretty_print(printer)?,
+            Expr::Paren(expr) => expr.pretty_print(printer)?,
+            Expr::Path(expr) => expr.pretty_print(printer)?,
+            Expr::Struct(expr) => expr.pretty_print(printer)?,
+            Expr::Try(expr) => expr.pretty_print(printer)?,
+            Expr::Tuple(expr) => expr.pretty_print(printer)?,
+            Expr::Infer(expr) => expr.pretty_print(printer)?,
+            Expr::Verbatim(expr) => pp_verbatim_text(expr, printer)?,
+            Expr::Custom(expr) => expr.node.pretty_print(printer)?,
+        }
+        if needs_parens {
+            printer.string(")");
+        }
+        Ok(())
+    })
 }
 
 impl PrettyPrinter for ExprInfer {
@@ -1017,17 +1986,8 @@ impl PrettyPrinter for ExprInfer {
 
 impl PrettyPrinter for ExprArray {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
-        printer.begin(BreakStyle::Consistent, "[");
-        printer.break_();
-        for (i, elem) in self.elems.iter().enumerate() {
-            if i > 0 {
-                printer.string(", ");
-                printer.break_();
-            }
-            elem.pretty_print(printer)?;
-        }
-        printer.end("]");
-        Ok(())
+        let max_width = printer.config().array_width();
+        pp_comma_list(&self.elems, "[", "]", Some(max_width), printer)
     }
 }
 
@@ -1047,9 +2007,7 @@ impl PrettyPrinter for ExprGen {
 
 impl PrettyPrinter for ExprAwait {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
-        self.expr.pretty_print(printer)?;
-        printer.string(".await");
-        Ok(())
+        pp_chain(ChainLink::Await(self), printer)
     }
 }
 
@@ -1062,40 +2020,44 @@ impl PrettyPrinter for ExprBreak {
 
 impl PrettyPrinter for ExprCall {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
-        self.func.pretty_print(printer)?;
-        printer.begin(BreakStyle::Consistent, "(");
-        for (i, arg) in self.args.iter().enumerate() {
-            if i > 0 {
-                printer.string(", ");
-            }
-            arg.pretty_print(printer)?;
-        }
-        printer.end(")");
-        Ok(())
+        pretty_print_expr(&self.func, printer, ExprPosition::Callee)?;
+        let max_width = printer.config().fn_call_width();
+        pp_comma_list(&self.args, "(", ")", Some(max_width), printer)
     }
 }
 
 impl PrettyPrinter for ExprCast {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
-        self.expr.pretty_print(printer)?;
+        pp_operand(&self.expr, Precedence::Cast, true, printer)?;
         printer.string(" as ");
         self.ty.pretty_print(printer)?;
         Ok(())
     }
 }
 
+impl PrettyPrinter for ClosureParam {
+    fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
+        pp_inline_md(&self.md, printer)?;
+        self.pat.pretty_print(printer)
+    }
+}
+
 impl PrettyPrinter for ExprClosure {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
-        printer.string("|");
-        for (i, input) in self.inputs.iter().enumerate() {
-            if i > 0 {
-                printer.string(", ");
-            }
-            input.pretty_print(printer)?;
+        pp_closure(self, false, printer)
+    }
+}
+
+fn pp_closure<'a>(expr: &'a ExprClosure, followed: bool, printer: &mut Printer<'a>) -> fmt::Result {
+    printer.string("|");
+    for (i, input) in expr.inputs.iter().enumerate() {
+        if i > 0 {
+            printer.string(", ");
         }
-        printer.string("| ");
-        self.body.pretty_print(printer)
+        input.pretty_print(printer)?;
     }
+    printer.string("| ");
+    pp_operand(&expr.body, Precedence::Jump, followed, printer)
 }
 
 impl PrettyPrinter for ExprConst {
@@ -1114,16 +2076,13 @@ impl PrettyPrinter for ExprContinue {
 
 impl PrettyPrinter for ExprField {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
-        self.expr.pretty_print(printer)?;
-        printer.string(".");
-        self.member.pretty_print(printer)?;
-        Ok(())
+        pp_chain(ChainLink::Field(self), printer)
     }
 }
 
 impl PrettyPrinter for ExprIndex {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
-        self.expr.pretty_print(printer)?;
+        pp_operand(&self.expr, Precedence::Postfix, true, printer)?;
         printer.string("[");
         self.index.pretty_print(printer)?;
         printer.string("]");
@@ -1134,13 +2093,14 @@ impl PrettyPrinter for ExprIndex {
 impl PrettyPrinter for ExprMatch {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
         printer.string("match ");
-        self.expr.pretty_print(printer)?;
+        pretty_print_expr(&self.expr, printer, ExprPosition::Condition)?;
         printer.begin(BreakStyle::Consistent, " {");
         printer.hard_break();
         let num_arms = self.arms.len();
         for (i, arm) in self.arms.iter().enumerate() {
             arm.pretty_print(printer)?;
             printer.string(",");
+            pp_end(&arm.md, printer)?;
             if i < num_arms - 1 {
                 printer.hard_break();
             }
@@ -1152,31 +2112,127 @@ impl PrettyPrinter for ExprMatch {
 
 impl PrettyPrinter for Arm {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
-        self.pat.pretty_print(printer)?;
-        if let Some(guard) = &self.guard {
-            printer.string(" if ");
-            guard.pretty_print(printer)?;
-        }
-        printer.string(" => ");
-        self.body.pretty_print(printer)
+        printer.node(self, |printer| {
+            pp_begin_nested(&self.md, printer)?;
+            self.pat.pretty_print(printer)?;
+            if let Some(guard) = &self.guard {
+                printer.string(" if ");
+                guard.pretty_print(printer)?;
+            }
+            printer.string(" => ");
+            pretty_print_expr(&self.body, printer, ExprPosition::Statement)
+        })
     }
 }
 
 impl PrettyPrinter for ExprMethodCall {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
-        self.receiver.pretty_print(printer)?;
+        pp_chain(ChainLink::MethodCall(self), printer)
+    }
+}
+
+/// An element of a method chain, such as `.iter()`, `.len` or `.await`.
+#[derive(Clone, Copy)]
+enum ChainLink<'a> {
+    MethodCall(&'a ExprMethodCall),
+    Field(&'a ExprField),
+    Await(&'a ExprAwait),
+}
+
+impl<'a> ChainLink<'a> {
+    fn receiver(self) -> &'a Expr {
+        match self {
+            ChainLink::MethodCall(expr) => &expr.receiver,
+            ChainLink::Field(expr) => &expr.expr,
+            ChainLink::Await(expr) => &expr.expr,
+        }
+    }
+
+    fn pretty_print(self, printer: &mut Printer<'a>) -> fmt::Result {
         printer.string(".");
-        self.method.pretty_print(printer)?;
-        printer.begin(BreakStyle::Consistent, "(");
-        for (i, arg) in self.args.iter().enumerate() {
+        match self {
+            ChainLink::MethodCall(expr) => {
+                expr.method.pretty_print(printer)?;
+                let max_width = printer.config().fn_call_width();
+                pp_comma_list(&expr.args, "(", ")", Some(max_width), printer)
+            }
+            ChainLink::Field(expr) => expr.member.pretty_print(printer),
+            ChainLink::Await(_) => {
+                printer.string("await");
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Pretty-prints a chain of method calls, field accesses and `.await`s.
+///
+/// A chain with three or more method calls that does not fit on one line, or
+/// is wider than the chain width, is broken before each `.`.
+fn pp_chain<'a>(last: ChainLink<'a>, printer: &mut Printer<'a>) -> fmt::Result {
+    let mut links = vec![last];
+    let root = loop {
+        match links[links.len() - 1].receiver() {
+            Expr::MethodCall(expr) => links.push(ChainLink::MethodCall(expr)),
+            Expr::Field(expr) => links.push(ChainLink::Field(expr)),
+            Expr::Await(expr) => links.push(ChainLink::Await(expr)),
+            root => break root,
+        }
+    };
+    links.reverse();
+
+    let calls = links
+        .iter()
+        .filter(|link| matches!(link, ChainLink::MethodCall(_)))
+        .count();
+    if calls < 3 {
+        pretty_print_expr(root, printer, ExprPosition::Receiver)?;
+        for link in links {
+            link.pretty_print(printer)?;
+        }
+        return Ok(());
+    }
+
+    let max_width = printer.config().chain_width();
+    printer.begin_limited(BreakStyle::Consistent, "", max_width);
+    pretty_print_expr(root, printer, ExprPosition::Receiver)?;
+    for link in links {
+        printer.zero_break();
+        link.pretty_print(printer)?;
+    }
+    printer.end_inline("");
+    Ok(())
+}
+
+/// Pretty-prints a delimited, comma-separated list, such as call arguments or
+/// array elements.
+///
+/// The list is printed on one line if it fits in the line and in `max_width`,
+/// and otherwise with one element per line, followed by a trailing comma.
+fn pp_comma_list<'a, T: PrettyPrinter>(
+    items: &'a [T],
+    open: &'a str,
+    close: &'a str,
+    max_width: Option<usize>,
+    printer: &mut Printer<'a>,
+) -> fmt::Result {
+    match max_width {
+        Some(max_width) => printer.begin_limited(BreakStyle::Consistent, open, max_width),
+        None => printer.begin(BreakStyle::Consistent, open),
+    }
+    if !items.is_empty() {
+        printer.zero_break();
+        for (i, item) in items.iter().enumerate() {
             if i > 0 {
-                printer.string(", ");
+                printer.string(",");
+                printer.break_();
             }
-            arg.pretty_print(printer)?;
+            item.pretty_print(printer)?;
         }
-        printer.end(")");
-        Ok(())
+        printer.trailing_comma();
     }
+    printer.end(close);
+    Ok(())
 }
 
 impl PrettyPrinter for ExprParen {
@@ -1190,73 +2246,131 @@ impl PrettyPrinter for ExprParen {
 
 impl PrettyPrinter for ExprPath {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
-        self.path.pretty_print(printer)
+        pp_path_turbofish(&self.path, printer)
     }
 }
 
 impl PrettyPrinter for ExprRange {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
-        if let Some(start) = &self.start {
-            start.pretty_print(printer)?;
-        }
-        match self.limits {
+        pp_range(self, false, printer)
+    }
+}
+
+impl PrettyPrinter for RangeLimits {
+    fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
+        match self {
             RangeLimits::HalfOpen => printer.string(".."),
             RangeLimits::Closed => printer.string("..="),
         }
-        if let Some(end) = &self.end {
-            end.pretty_print(printer)?;
-        }
         Ok(())
     }
 }
 
+fn pp_range<'a>(expr: &'a ExprRange, followed: bool, printer: &mut Printer<'a>) -> fmt::Result {
+    if let Some(start) = &expr.start {
+        pp_operand(start, Precedence::Or, true, printer)?;
+    }
+    expr.limits.pretty_print(printer)?;
+    if let Some(end) = &expr.end {
+        pp_operand(end, Precedence::Or, followed, printer)?;
+    }
+    Ok(())
+}
+
 impl PrettyPrinter for ExprRef {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
-        printer.string("&");
-        if self.is_mut {
-            printer.string("mut ");
-        }
-        self.expr.pretty_print(printer)
+        pp_ref(self, false, printer)
+    }
+}
+
+fn pp_ref<'a>(expr: &'a ExprRef, followed: bool, printer: &mut Printer<'a>) -> fmt::Result {
+    printer.string("&");
+    if expr.is_mut {
+        printer.string("mut ");
     }
+    pp_operand(&expr.expr, Precedence::Prefix, followed, printer)
 }
 
 impl PrettyPrinter for ExprRawRef {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
-        printer.string("&raw ");
-        if self.is_mut {
-            printer.string("mut ");
-        } else {
-            printer.string("const ");
-        }
-        self.expr.pretty_print(printer)
+        pp_raw_ref(self, false, printer)
+    }
+}
+
+fn pp_raw_ref<'a>(expr: &'a ExprRawRef, followed: bool, printer: &mut Printer<'a>) -> fmt::Result {
+    printer.string("&raw ");
+    if expr.is_mut {
+        printer.string("mut ");
+    } else {
+        printer.string("const ");
     }
+    pp_operand(&expr.expr, Precedence::Prefix, followed, printer)
 }
 
 impl PrettyPrinter for ExprReturn {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
-        printer.string("return");
-        if let Some(expr) = &self.expr {
-            printer.string(" ");
-            expr.pretty_print(printer)?;
-        }
-        Ok(())
+        pp_return(self, false, printer)
     }
 }
 
+fn pp_return<'a>(expr: &'a ExprReturn, followed: bool, printer: &mut Printer<'a>) -> fmt::Result {
+    printer.string("return");
+    if let Some(expr) = &expr.expr {
+        printer.string(" ");
+        pp_operand(expr, Precedence::Jump, followed, printer)?;
+    }
+    Ok(())
+}
+
+impl PrettyPrinter for ExprBecome {
+    fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
+        pp_become(self, false, printer)
+    }
+}
+
+fn pp_become<'a>(expr: &'a ExprBecome, followed: bool, printer: &mut Printer<'a>) -> fmt::Result {
+    printer.string("become ");
+    pp_operand(&expr.expr, Precedence::Jump, followed, printer)
+}
+
+impl PrettyPrinter for ExprYield {
+    fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
+        pp_yield(self, false, printer)
+    }
+}
+
+fn pp_yield<'a>(expr: &'a ExprYield, followed: bool, printer: &mut Printer<'a>) -> fmt::Result {
+    printer.string("yield");
+    if let Some(expr) = &expr.expr {
+        printer.string(" ");
+        pp_operand(expr, Precedence::Jump, followed, printer)?;
+    }
+    Ok(())
+}
+
 impl PrettyPrinter for ExprStruct {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
-        self.path.pretty_print(printer)?;
-        if !self.fields.is_empty() {
-            printer.begin(BreakStyle::Consistent, " {");
+        pp_path_turbofish(&self.path, printer)?;
+        if self.fields.is_empty() {
+            printer.string(" {}");
+        } else {
+            // The contents include the spaces inside the braces.
+            let max_width = printer.config().struct_lit_width() + 2;
+            printer.begin_limited(BreakStyle::Consistent, " {", max_width);
             printer.break_();
+            let num_fields = self.fields.len();
             for (i, field) in self.fields.iter().enumerate() {
-                if i > 0 {
+                field.pretty_print(printer)?;
+                if i < num_fields - 1 {
                     printer.string(",");
+                    pp_end(&field.md, printer)?;
                     printer.break_();
+                } else {
+                    printer.trailing_comma();
+                    printer.if_broken("", " ");
+                    pp_end(&field.md, printer)?;
                 }
-                field.pretty_print(printer)?;
             }
-            printer.break_();
             printer.end("}");
         }
         Ok(())
@@ -1265,23 +2379,35 @@ impl PrettyPrinter for ExprStruct {
 
 impl PrettyPrinter for FieldValue {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
-        self.member.pretty_print(printer)?;
-        printer.string(": ");
-        self.value.pretty_print(printer)
+        printer.node(self, |printer| {
+            pp_begin_nested(&self.md, printer)?;
+            self.member.pretty_print(printer)?;
+            // `x: x` is printed in its shorthand form.
+            if let Expr::Path(ExprPath { path }) = &self.value
+                && let [segment] = path.segments.as_slice()
+                && segment.args.is_none()
+                && segment.ident == self.member
+            {
+                return Ok(());
+            }
+            printer.string(": ");
+            self.value.pretty_print(printer)
+        })
     }
 }
 
 impl PrettyPrinter for ExprTuple {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
-        printer.begin(BreakStyle::Consistent, "(");
-        for (i, elem) in self.elems.iter().enumerate() {
-            if i > 0 {
-                printer.string(", ");
-            }
+        if let [elem] = self.elems.as_slice() {
+            printer.begin(BreakStyle::Consistent, "(");
+            printer.zero_break();
             elem.pretty_print(printer)?;
+            printer.string(",");
+            printer.end(")");
+            Ok(())
+        } else {
+            pp_comma_list(&self.elems, "(", ")", None, printer)
         }
-        printer.end(")");
-        Ok(())
     }
 }
 
@@ -1289,10 +2415,8 @@ impl PrettyPrinter for ItemFn {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
         pp_begin(&self.md, printer)?;
         self.vis.pretty_print(printer)?;
-        printer.string("fn ");
         self.sig.pretty_print(printer)?;
-        printer.string(" ");
-        self.block.pretty_print(printer)?;
+        pp_fn_body(&self.sig, &self.block, printer)?;
         pp_end(&self.md, printer)?;
         Ok(())
     }
@@ -1306,30 +2430,35 @@ impl PrettyPrinter for Signature {
         if self.is_async {
             printer.string("async ");
         }
+        if self.is_gen {
+            printer.string("gen ");
+        }
+        if self.is_safe {
+            printer.string("safe ");
+        }
         if self.is_unsafe {
             printer.string("unsafe ");
         }
         if let Some(abi) = &self.abi {
-            printer.string("extern ");
             abi.pretty_print(printer)?;
             printer.string(" ");
         }
+        printer.string("fn ");
         self.ident.pretty_print(printer)?;
         self.generics.pretty_print(printer)?;
-        printer.begin(BreakStyle::Consistent, "(");
-        for (i, input) in self.inputs.iter().enumerate() {
-            if i > 0 {
-                printer.string(", ");
-            }
-            input.pretty_print(printer)?;
-        }
         if self.is_variadic {
-            if !self.inputs.is_empty() {
-                printer.string(", ");
+            printer.begin(BreakStyle::Consistent, "(");
+            printer.zero_break();
+            for input in &self.inputs {
+                input.pretty_print(printer)?;
+                printer.string(",");
+                printer.break_();
             }
             printer.string("...");
+            printer.end(")");
+        } else {
+            pp_comma_list(&self.inputs, "(", ")", None, printer)?;
         }
-        printer.end(")");
         if let Some(output) = &self.output {
             printer.string(" -> ");
             output.pretty_print(printer)?;
@@ -1341,58 +2470,145 @@ impl PrettyPrinter for Signature {
     }
 }
 
+/// Pretty-prints the body of a function, which starts on a new line after a
+/// `where` clause.
+fn pp_fn_body<'a>(sig: &'a Signature, block: &'a Block, printer: &mut Printer<'a>) -> fmt::Result {
+    if sig
+        .where_clause
+        .as_ref()
+        .is_some_and(|where_clause| !where_clause.predicates.is_empty())
+    {
+        if printer.config().trailing_comma != TrailingComma::Never {
+            printer.string(",");
+        }
+        printer.hard_break();
+        if block.stmts.is_empty() && block.md.is_none() {
+            printer.string("{");
+            printer.hard_break();
+            printer.string("}");
+            return Ok(());
+        }
+    } else {
+        printer.string(" ");
+    }
+    block.pretty_print(printer)
+}
+
 impl PrettyPrinter for Block {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
-        printer.begin(BreakStyle::Consistent, "{");
-
-        let is_empty = self.stmts.is_empty() && self.md.is_none();
+        // Blocks that are statements of blocks, as in `{ { 0 } }`, are
+        // printed by this loop rather than by recursion, so that deep nesting
+        // does not overflow the stack. Each entry of the stack holds a block
+        // being printed, the index of its next statement, and the statement
+        // that holds the block with whether it ends with a semicolon.
+        let mut stack = vec![(self, 0, None)];
+        pp_block_open(self, printer)?;
+        while let Some((block, next, _)) = stack.last_mut() {
+            let (block, i) = (*block, *next);
+            *next += 1;
+            let Some(stmt) = block.stmts.get(i) else {
+                pp_block_close(block, printer)?;
+                if let Some((_, _, Some((expr_stmt, semicolon)))) = stack.pop() {
+                    printer.end_node();
+                    pp_stmt_expr_end(expr_stmt, semicolon, printer)?;
+                }
+                continue;
+            };
+            if i > 0 {
+                pp_line_break(false, stmt.md(), printer);
+            }
+            let Stmt::Expr(expr_stmt) = stmt else {
+                stmt.pretty_print(printer)?;
+                continue;
+            };
+            let semicolon = i + 1 < block.stmts.len() || block.has_trailing_semicolon;
+            printer.begin_node(stmt);
+            pp_begin_nested(&expr_stmt.md, printer)?;
+            if let Expr::Block(inner) = &expr_stmt.expr {
+                printer.begin_node(&expr_stmt.expr);
+                pp_block_open(&inner.block, printer)?;
+                stack.push((&inner.block, 0, Some((expr_stmt, semicolon))));
+            } else {
+                pretty_print_expr(&expr_stmt.expr, printer, ExprPosition::Statement)?;
+                pp_stmt_expr_end(expr_stmt, semicolon, printer)?;
+            }
+        }
+        Ok(())
+    }
+}
 
-        if !is_empty {
-            printer.hard_break();
-            pp_begin(&self.md, printer)?;
+/// Prints the opening brace and the leading metadata of `block`, for
+/// [`Block::pretty_print`].
+fn pp_block_open<'a>(block: &'a Block, printer: &mut Printer<'a>) -> fmt::Result {
+    printer.begin_node(block);
+    printer.begin(BreakStyle::Consistent, "{");
+    if !block.stmts.is_empty() || block.md.is_some() {
+        printer.hard_break();
+        pp_begin(&block.md, printer)?;
+    }
+    Ok(())
+}
 
-            let num_stmts = self.stmts.len();
-            for (i, stmt) in self.stmts.iter().enumerate() {
-                stmt.pretty_print(printer)?;
+/// Prints the trailing metadata and the closing brace of `block`, for
+/// [`Block::pretty_print`].
+fn pp_block_close<'a>(block: &'a Block, printer: &mut Printer<'a>) -> fmt::Result {
+    if !block.stmts.is_empty() || block.md.is_some() {
+        pp_end(&block.md, printer)?;
+    }
+    printer.end("}");
+    printer.end_node();
+    Ok(())
+}
 
-                let is_last = i == num_stmts - 1;
+/// Ends an expression statement of a block, after its expression.
+fn pp_stmt_expr_end<'a>(
+    expr_stmt: &'a StmtExpr,
+    semicolon: bool,
+    printer: &mut Printer<'a>,
+) -> fmt::Result {
+    if semicolon {
+        printer.string(";");
+    }
+    pp_end(&expr_stmt.md, printer)?;
+    printer.end_node();
+    Ok(())
+}
 
-                if matches!(stmt, Stmt::Expr(_)) && (!is_last || self.has_trailing_semicolon) {
-                    printer.string(";");
+impl PrettyPrinter for Stmt {
+    fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
+        printer.node(self, |printer| {
+            match self {
+                Stmt::Local(local) => {
+                    local.pretty_print(printer)?;
                 }
-
-                if !is_last {
-                    printer.hard_break();
+                Stmt::Item(item) => {
+                    item.pretty_print(printer)?;
+                }
+                Stmt::Expr(stmt) => {
+                    stmt.pretty_print(printer)?;
+                }
+                Stmt::Verbatim(verbatim) => {
+                    pp_begin_nested(&verbatim.md, printer)?;
+                    pp_verbatim_text(verbatim, printer)?;
+                    pp_end(&verbatim.md, printer)?;
                 }
             }
-
-            pp_end(&self.md, printer)?;
-        }
-
-        printer.end("}");
-        Ok(())
+            Ok(())
+        })
     }
 }
 
-impl PrettyPrinter for Stmt {
+impl PrettyPrinter for StmtExpr {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
-        match self {
-            Stmt::Local(local) => {
-                local.pretty_print(printer)?;
-            }
-            Stmt::Item(item) => {
-                item.pretty_print(printer)?;
-            }
-            Stmt::Expr(expr) => {
-                expr.pretty_print(printer)?;
-            }
-        }
-        Ok(())
+        pp_begin_nested(&self.md, printer)?;
+        pretty_print_expr(&self.expr, printer, ExprPosition::Statement)?;
+        pp_end(&self.md, printer)
     }
 }
 
 impl PrettyPrinter for Local {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
+        pp_begin_nested(&self.md, printer)?;
         printer.string("let ");
         self.pat.pretty_print(printer)?;
         if let Some(ty) = &self.ty {
@@ -1401,7 +2617,11 @@ impl PrettyPrinter for Local {
         }
         if let Some(expr) = &self.expr {
             printer.string(" = ");
-            expr.pretty_print(printer)?;
+            let position = match self.else_block {
+                Some(_) => ExprPosition::LetElse,
+                None => ExprPosition::Top,
+            };
+            pretty_print_expr(expr, printer, position)?;
         }
         if let Some(else_block) = &self.else_block {
             printer.string(" else ");
@@ -1409,38 +2629,187 @@ impl PrettyPrinter for Local {
         } else {
             printer.string(";");
         }
-        Ok(())
+        pp_end(&self.md, printer)
+    }
+}
+
+impl PrettyPrinter for Item {
+    fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
+        printer.node(self, |printer| match self {
+            Item::Fn(item_fn) => item_fn.pretty_print(printer),
+            Item::Const(item_const) => item_const.pretty_print(printer),
+            Item::Struct(item_struct) => item_struct.pretty_print(printer),
+            Item::Static(item_static) => item_static.pretty_print(printer),
+            Item::Enum(item_enum) => item_enum.pretty_print(printer),
+            Item::Impl(item_impl) => item_impl.pretty_print(printer),
+            Item::Trait(item_trait) => item_trait.pretty_print(printer),
+            Item::ExternCrate(item_extern_crate) => item_extern_crate.pretty_print(printer),
+            Item::ForeignMod(item_foreign_mod) => item_foreign_mod.pretty_print(printer),
+            Item::Macro(item_macro) => item_macro.pretty_print(printer),
+            Item::Mod(item_mod) => item_mod.pretty_print(printer),
+            Item::TraitAlias(item_trait_alias) => item_trait_alias.pretty_print(printer),
+            Item::TypeAlias(item_type_alias) => item_type_alias.pretty_print(printer),
+            Item::Union(item_union) => item_union.pretty_print(printer),
+            Item::Use(item_use) => item_use.pretty_print(printer),
+            Item::Asm(item_asm) => item_asm.pretty_print(printer),
+            Item::ExternBlock(item_extern_block) => item_extern_block.pretty_print(printer),
+            Item::ExternType(item_extern_type) => item_extern_type.pretty_print(printer),
+            Item::Verbatim(verbatim) => verbatim.pretty_print(printer),
+            Item::Custom(custom) => custom.pretty_print(printer),
+        })
+    }
+}
+
+impl PrettyPrinter for Verbatim {
+    fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
+        pp_begin(&self.md, printer)?;
+        pp_verbatim_text(self, printer)?;
+        pp_end(&self.md, printer)
+    }
+}
+
+impl PrettyPrinter for Custom {
+    fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
+        pp_begin(&self.md, printer)?;
+        self.node.pretty_print(printer)?;
+        pp_end(&self.md, printer)
+    }
+}
+
+/// Pretty-prints the text of a [`Verbatim`], without its metadata.
+///
+/// Blank lines at the start and end are dropped, the indentation shared by
+/// the other lines is removed, and each line after the first starts on a new
+/// line at the current indentation. In single-line mode, all the indentation
+/// is removed, and `//` comments, which would swallow the rest of the
+/// output, are printed as `/* */` comments.
+fn pp_verbatim_text<'a>(verbatim: &'a Verbatim, printer: &mut Printer<'a>) -> fmt::Result {
+    let lines: Vec<&str> = verbatim.text.lines().collect();
+    let first = lines.iter().position(|line| !line.trim().is_empty());
+    let last = lines.iter().rposition(|line| !line.trim().is_empty());
+    let (Some(first), Some(last)) = (first, last) else {
+        return Ok(());
+    };
+    let lines = &lines[first..=last];
+    let indent = if printer.config().single_line {
+        usize::MAX
+    } else {
+        lines
+            .iter()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| line.len() - line.trim_start().len())
+            .min()
+            .unwrap_or(0)
+    };
+    for (i, line) in lines.iter().enumerate() {
+        if i > 0 {
+            printer.hard_break();
+        }
+        let start = indent.min(line.len() - line.trim_start().len());
+        let line = line[start..].trim_end();
+        if !printer.config().single_line {
+            printer.string(line);
+            continue;
+        }
+        // A `//` comment would swallow the code joined after it.
+        let (code, text) = split_line_comment(line);
+        let code = code.trim_end();
+        if !code.is_empty() {
+            printer.string(code);
+        }
+        let Some(text) = text else {
+            continue;
+        };
+        let comment = match text.strip_prefix(['/', '!']) {
+            Some(doc) if code.is_empty() && text.starts_with('!') => block_comment("!", doc),
+            Some(doc) if code.is_empty() && !doc.starts_with('/') => block_comment("*", doc),
+            _ => block_comment("", text),
+        };
+        if !code.is_empty() {
+            printer.string(" ");
+        }
+        printer.string(comment);
     }
+    Ok(())
 }
 
-impl PrettyPrinter for Item {
-    fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
-        match self {
-            Item::Fn(item_fn) => item_fn.pretty_print(printer),
-            Item::Const(item_const) => item_const.pretty_print(printer),
-            Item::Struct(item_struct) => item_struct.pretty_print(printer),
-            Item::Static(item_static) => item_static.pretty_print(printer),
-            Item::Enum(item_enum) => item_enum.pretty_print(printer),
-            Item::Impl(item_impl) => item_impl.pretty_print(printer),
-            Item::Trait(item_trait) => item_trait.pretty_print(printer),
-            Item::ExternCrate(item_extern_crate) => item_extern_crate.pretty_print(printer),
-            Item::ForeignMod(item_foreign_mod) => item_foreign_mod.pretty_print(printer),
-            Item::Macro(item_macro) => item_macro.pretty_print(printer),
-            Item::Mod(item_mod) => item_mod.pretty_print(printer),
-            Item::TraitAlias(item_trait_alias) => item_trait_alias.pretty_print(printer),
-            Item::TypeAlias(item_type_alias) => item_type_alias.pretty_print(printer),
-            Item::Union(item_union) => item_union.pretty_print(printer),
-            Item::Use(item_use) => item_use.pretty_print(printer),
-            Item::Asm(item_asm) => item_asm.pretty_print(printer),
-            Item::ExternBlock(item_extern_block) => item_extern_block.pretty_print(printer),
-            Item::ExternType(item_extern_type) => item_extern_type.pretty_print(printer),
+/// Splits a line of source text into its code and the text of the `//`
+/// comment that ends it, skipping string and character literals and block
+/// comments.
+fn split_line_comment(line: &str) -> (&str, Option<&str>) {
+    let bytes = line.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        let rest = &line[i..];
+        if let Some(text) = rest.strip_prefix("//") {
+            return (&line[..i], Some(text));
+        } else if let Some(comment) = rest.strip_prefix("/*") {
+            i += comment.find("*/").map_or(rest.len(), |end| end + 4);
+        } else if rest.starts_with('"') || rest.starts_with("r\"") || rest.starts_with("r#") {
+            let hashes = rest.bytes().skip(1).take_while(|&b| b == b'#').count();
+            let raw = rest.starts_with('r');
+            if raw && rest.as_bytes().get(1 + hashes) != Some(&b'"') {
+                i += 1;
+                continue;
+            }
+            let body = if raw { 2 + hashes } else { 1 };
+            let close = format!("\"{}", "#".repeat(hashes));
+            let mut end = body;
+            while end < rest.len() {
+                if !raw && rest.as_bytes()[end] == b'\\' {
+                    end += 2;
+                } else if rest.as_bytes()[end..].starts_with(close.as_bytes()) {
+                    end += close.len();
+                    break;
+                } else {
+                    end += 1;
+                }
+            }
+            i += end.min(rest.len());
+        } else if let Some(literal) = rest.strip_prefix('\'') {
+            // A character literal, unlike a lifetime, is closed by a quote.
+            let mut chars = literal.chars();
+            i += match chars.next() {
+                Some('\\') => literal
+                    .get(2..)
+                    .and_then(|escape| escape.find('\''))
+                    .map_or(rest.len(), |end| end + 4),
+                Some(c) if chars.as_str().starts_with('\'') => 2 + c.len_utf8(),
+                _ => 1,
+            };
+        } else {
+            i += rest.chars().next().map_or(1, char::len_utf8);
         }
     }
+    (line, None)
 }
 
 impl PrettyPrinter for ItemAsm {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
-        printer.string("asm!");
+        pp_begin(&self.md, printer)?;
+        pp_macro_name("global_asm!", printer)?;
+        self.args.pretty_print(printer)?;
+        printer.string(";");
+        pp_end(&self.md, printer)
+    }
+}
+
+impl PrettyPrinter for ExprAsm {
+    fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
+        pp_macro_name(self.mac.name(), printer)?;
+        self.args.pretty_print(printer)
+    }
+}
+
+fn pp_macro_name(name: &'static str, printer: &mut Printer<'_>) -> fmt::Result {
+    printer.highlight(TokenKind::Macro, |printer| {
+        printer.string(name);
+        Ok(())
+    })
+}
+
+impl PrettyPrinter for AsmArgs {
+    fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
         printer.begin(BreakStyle::Consistent, "(");
 
         let mut args: Vec<&dyn PrettyPrinter> = Vec::new();
@@ -1477,13 +2846,17 @@ impl PrettyPrinter for AsmOperand {
             AsmOperand::Reg(reg) => reg.pretty_print(printer),
             AsmOperand::Sym(path) => {
                 printer.string("sym ");
-                path.pretty_print(printer)
+                pp_path_turbofish(path, printer)
             }
             AsmOperand::Const(expr) => {
                 printer.string("const ");
                 expr.pretty_print(printer)
             }
             AsmOperand::ClobberAbi(clobber) => clobber.pretty_print(printer),
+            AsmOperand::Label(block) => {
+                printer.string("label ");
+                block.pretty_print(printer)
+            }
         }
     }
 }
@@ -1577,35 +2950,145 @@ impl PrettyPrinter for ClobberAbi {
 fn pp_separated_with_trailing<'a, T: PrettyPrinter>(
     items: &'a [T],
     separator: &'a str,
+    md: impl Fn(&'a T) -> &'a Option<Box<Md>>,
     printer: &mut Printer<'a>,
 ) -> fmt::Result {
     let num_items = items.len();
     for (i, item) in items.iter().enumerate() {
         item.pretty_print(printer)?;
-        printer.string(separator);
         if i < num_items - 1 {
+            printer.string(separator);
+            pp_end(md(item), printer)?;
             printer.hard_break();
+        } else {
+            printer.trailing_comma();
+            pp_end(md(item), printer)?;
         }
     }
     Ok(())
 }
 
-fn pp_with_breaks<'a, T: PrettyPrinter>(items: &'a [T], printer: &mut Printer<'a>) -> fmt::Result {
-    let num_items = items.len();
+/// An element of a list of items, such as the items of a file or the
+/// members of a trait, which are separated by [`PrinterConfig::item_spacing`].
+pub(crate) trait ListItem: PrettyPrinter {
+    /// Returns the metadata of the element.
+    fn md(&self) -> &Option<Box<Md>>;
+
+    /// Returns `true` if `self` and `next` are declarations of the same kind
+    /// without a body, which [`ItemSpacing::Grouped`] keeps together.
+    fn groups_with(&self, next: &Self) -> bool;
+}
+
+impl ListItem for Item {
+    fn md(&self) -> &Option<Box<Md>> {
+        Item::md(self)
+    }
+
+    fn groups_with(&self, next: &Self) -> bool {
+        let is_declaration = |item: &Item| match item {
+            Item::Const(_)
+            | Item::ExternCrate(_)
+            | Item::ExternType(_)
+            | Item::Static(_)
+            | Item::TraitAlias(_)
+            | Item::TypeAlias(_)
+            | Item::Use(_) => true,
+            Item::Mod(item_mod) => item_mod.content.is_none(),
+            _ => false,
+        };
+        is_declaration(self) && is_declaration(next) && mem::discriminant(self) == mem::discriminant(next)
+    }
+}
+
+impl ListItem for ImplItem {
+    fn md(&self) -> &Option<Box<Md>> {
+        ImplItem::md(self)
+    }
+
+    fn groups_with(&self, next: &Self) -> bool {
+        matches!(
+            (self, next),
+            (ImplItem::Const(_), ImplItem::Const(_)) | (ImplItem::Type(_), ImplItem::Type(_))
+        )
+    }
+}
+
+impl ListItem for TraitItem {
+    fn md(&self) -> &Option<Box<Md>> {
+        match self {
+            TraitItem::Fn(item) => &item.md,
+            TraitItem::Const(item) => &item.md,
+        }
+    }
+
+    fn groups_with(&self, next: &Self) -> bool {
+        match (self, next) {
+            (TraitItem::Fn(a), TraitItem::Fn(b)) => a.block.is_none() && b.block.is_none(),
+            (TraitItem::Const(_), TraitItem::Const(_)) => true,
+            _ => false,
+        }
+    }
+}
+
+impl ListItem for AssociatedType {
+    fn md(&self) -> &Option<Box<Md>> {
+        &self.md
+    }
+
+    fn groups_with(&self, _next: &Self) -> bool {
+        true
+    }
+}
+
+impl ListItem for ExternalItem {
+    fn md(&self) -> &Option<Box<Md>> {
+        match self {
+            ExternalItem::Static(item) => &item.md,
+            ExternalItem::Fn(item) => &item.md,
+            ExternalItem::Macro(item) => &item.md,
+            ExternalItem::Type(item) => &item.md,
+        }
+    }
+
+    fn groups_with(&self, next: &Self) -> bool {
+        !matches!(self, ExternalItem::Macro(_)) && mem::discriminant(self) == mem::discriminant(next)
+    }
+}
+
+/// Pretty-prints a list of items on their own lines, with the empty lines
+/// asked for by [`PrinterConfig::item_spacing`] and [`Md::blank_line_before`].
+pub(crate) fn pp_items<'a, T: ListItem>(items: &'a [T], printer: &mut Printer<'a>) -> fmt::Result {
     for (i, item) in items.iter().enumerate() {
-        item.pretty_print(printer)?;
-        if i < num_items - 1 {
-            printer.hard_break();
+        if i > 0 {
+            let blank_line = match printer.config().item_spacing {
+                ItemSpacing::Compact => false,
+                ItemSpacing::Grouped => !items[i - 1].groups_with(item),
+                ItemSpacing::Separated => true,
+            };
+            pp_line_break(blank_line, item.md(), printer);
         }
+        item.pretty_print(printer)?;
     }
     Ok(())
 }
 
+/// Starts a new line before a node, leaving an empty line if `blank_line` is
+/// set or if the metadata of the node asks for one.
+fn pp_line_break(blank_line: bool, md: &Option<Box<Md>>, printer: &mut Printer<'_>) {
+    if blank_line || md.as_ref().is_some_and(|md| md.blank_line_before) {
+        printer.blank_line();
+    } else {
+        printer.hard_break();
+    }
+}
+
 impl PrettyPrinter for File {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
-        pp_begin(&self.md, printer)?;
-        pp_with_breaks(&self.items, printer)?;
-        pp_end(&self.md, printer)
+        printer.node(self, |printer| {
+            pp_begin(&self.md, printer)?;
+            pp_items(&self.items, printer)?;
+            pp_end(&self.md, printer)
+        })
     }
 }
 
@@ -1614,13 +3097,13 @@ impl PrettyPrinter for ItemStruct {
         pp_begin(&self.md, printer)?;
         self.vis.pretty_print(printer)?;
         printer.string("struct ");
-        self.ident.pretty_print(printer)?;
+        pp_type_name(&self.ident, printer)?;
         self.generics.pretty_print(printer)?;
         printer.string(" ");
         printer.begin(BreakStyle::Consistent, "{");
         if !self.fields.is_empty() {
             printer.hard_break();
-            pp_separated_with_trailing(&self.fields, ",", printer)?;
+            pp_separated_with_trailing(&self.fields, ",", |field| &field.md, printer)?;
         }
         printer.end("}");
         pp_end(&self.md, printer)?;
@@ -1630,11 +3113,13 @@ impl PrettyPrinter for ItemStruct {
 
 impl PrettyPrinter for Field {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
-        pp_begin(&self.md, printer)?;
-        self.ident.pretty_print(printer)?;
-        printer.string(": ");
-        self.ty.pretty_print(printer)?;
-        Ok(())
+        printer.node(self, |printer| {
+            pp_begin_nested(&self.md, printer)?;
+            self.ident.pretty_print(printer)?;
+            printer.string(": ");
+            self.ty.pretty_print(printer)?;
+            Ok(())
+        })
     }
 }
 
@@ -1643,13 +3128,13 @@ impl PrettyPrinter for ItemEnum {
         pp_begin(&self.md, printer)?;
         self.vis.pretty_print(printer)?;
         printer.string("enum ");
-        self.ident.pretty_print(printer)?;
+        pp_type_name(&self.ident, printer)?;
         self.generics.pretty_print(printer)?;
         printer.string(" ");
         printer.begin(BreakStyle::Consistent, "{");
         if !self.variants.is_empty() {
             printer.hard_break();
-            pp_separated_with_trailing(&self.variants, ",", printer)?;
+            pp_separated_with_trailing(&self.variants, ",", |variant| &variant.md, printer)?;
         }
         printer.end("}");
         pp_end(&self.md, printer)?;
@@ -1659,31 +3144,39 @@ impl PrettyPrinter for ItemEnum {
 
 impl PrettyPrinter for Variant {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
-        pp_begin(&self.md, printer)?;
-        self.ident.pretty_print(printer)?;
-        Ok(())
+        printer.node(self, |printer| {
+            pp_begin_nested(&self.md, printer)?;
+            self.ident.pretty_print(printer)?;
+            Ok(())
+        })
     }
 }
 
-
 impl PrettyPrinter for ItemTrait {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
         pp_begin(&self.md, printer)?;
         self.vis.pretty_print(printer)?;
         printer.string("trait ");
-        self.ident.pretty_print(printer)?;
+        pp_type_name(&self.ident, printer)?;
         self.generics.pretty_print(printer)?;
         printer.string(" ");
         printer.begin(BreakStyle::Consistent, "{");
 
         if !self.associated_types.is_empty() {
             printer.hard_break();
-            pp_with_breaks(&self.associated_types, printer)?;
+            pp_items(&self.associated_types, printer)?;
         }
 
-        if !self.items.is_empty() {
-            printer.hard_break();
-            pp_with_breaks(&self.items, printer)?;
+        if let Some(first) = self.items.first() {
+            if self.associated_types.is_empty() {
+                printer.hard_break();
+            } else {
+                // The items are separated from the associated types like
+                // items of different kinds
+                let blank_line = printer.config().item_spacing != ItemSpacing::Compact;
+                pp_line_break(blank_line, ListItem::md(first), printer);
+            }
+            pp_items(&self.items, printer)?;
         }
 
         printer.end("}");
@@ -1694,21 +3187,19 @@ impl PrettyPrinter for ItemTrait {
 
 impl PrettyPrinter for TraitItem {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
-        match self {
+        printer.node(self, |printer| match self {
             TraitItem::Fn(item_fn) => item_fn.pretty_print(printer),
             TraitItem::Const(associated_const) => associated_const.pretty_print(printer),
-        }
+        })
     }
 }
 
 impl PrettyPrinter for TraitItemFn {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
         pp_begin(&self.md, printer)?;
-        printer.string("fn ");
         self.sig.pretty_print(printer)?;
         if let Some(block) = &self.block {
-            printer.string(" ");
-            block.pretty_print(printer)?;
+            pp_fn_body(&self.sig, block, printer)?;
         } else {
             printer.string(";");
         }
@@ -1719,13 +3210,33 @@ impl PrettyPrinter for TraitItemFn {
 
 impl PrettyPrinter for ExprIf {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
-        printer.string("if ");
-        self.cond.pretty_print(printer)?;
-        printer.string(" ");
-        self.then_branch.pretty_print(printer)?;
-        if let Some(else_branch) = &self.else_branch {
+        // An `else if` chain is printed by this loop rather than by
+        // recursion, so that long chains do not overflow the stack.
+        let mut expr_if = self;
+        let mut nested = 0;
+        loop {
+            printer.string("if ");
+            pretty_print_expr(&expr_if.cond, printer, ExprPosition::Condition)?;
+            printer.string(" ");
+            expr_if.then_branch.pretty_print(printer)?;
+            let Some(else_branch) = &expr_if.else_branch else {
+                break;
+            };
             printer.string(" else ");
-            else_branch.pretty_print(printer)?;
+            match &**else_branch {
+                Expr::If(inner) => {
+                    printer.begin_node(&**else_branch);
+                    expr_if = inner;
+                    nested += 1;
+                }
+                _ => {
+                    else_branch.pretty_print(printer)?;
+                    break;
+                }
+            }
+        }
+        for _ in 0..nested {
+            printer.end_node();
         }
         Ok(())
     }
@@ -1747,7 +3258,7 @@ impl PrettyPrinter for ExprLoop {
 impl PrettyPrinter for ExprWhile {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
         printer.string("while ");
-        self.cond.pretty_print(printer)?;
+        pretty_print_expr(&self.cond, printer, ExprPosition::Condition)?;
         printer.string(" ");
         self.body.pretty_print(printer)
     }
@@ -1758,7 +3269,7 @@ impl PrettyPrinter for ExprFor {
         printer.string("for ");
         self.pat.pretty_print(printer)?;
         printer.string(" in ");
-        self.expr.pretty_print(printer)?;
+        pretty_print_expr(&self.expr, printer, ExprPosition::Condition)?;
         printer.string(" ");
         self.body.pretty_print(printer)
     }
@@ -1766,31 +3277,25 @@ impl PrettyPrinter for ExprFor {
 
 impl PrettyPrinter for ExprAssign {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
-        self.left.pretty_print(printer)?;
-        printer.string(" = ");
-        let needs_paren = matches!(&*self.right, Expr::Assign(_));
-        if needs_paren {
-            printer.string("(");
-            self.right.pretty_print(printer)?;
-            printer.string(")");
-        } else {
-            self.right.pretty_print(printer)?;
-        }
-        Ok(())
+        pp_assign(self, false, printer)
     }
 }
 
+fn pp_assign<'a>(expr: &'a ExprAssign, followed: bool, printer: &mut Printer<'a>) -> fmt::Result {
+    pp_operand(&expr.left, Precedence::Range, true, printer)?;
+    printer.string(" = ");
+    pp_operand(&expr.right, Precedence::Assign, followed, printer)
+}
+
 impl PrettyPrinter for ExprMacroCall {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
-        self.path.pretty_print(printer)?;
-        printer.string("!");
+        printer.highlight(TokenKind::Macro, |printer| {
+            self.path.pretty_print(printer)?;
+            printer.string("!");
+            Ok(())
+        })?;
 
-        let (open, close) = match self.delimiter {
-            Delimiter::Parenthesis => ("(", ")"),
-            Delimiter::Brace => ("{", "}"),
-            Delimiter::Bracket => ("[", "]"),
-            Delimiter::None => ("", ""),
-        };
+        let (open, close) = self.delimiter.tokens();
 
         printer.begin(BreakStyle::Consistent, open);
         self.tokens.pretty_print(printer)?;
@@ -1803,7 +3308,7 @@ impl PrettyPrinter for ExprMacroCall {
 impl PrettyPrinter for TokenStream {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
         for (i, token) in self.tokens.iter().enumerate() {
-            if i > 0 {
+            if i > 0 && spaced(&self.tokens[i - 1], token) {
                 printer.break_();
             }
             token.pretty_print(printer)?;
@@ -1812,6 +3317,27 @@ impl PrettyPrinter for TokenStream {
     }
 }
 
+/// Returns whether a space separates the `next` token from the `prev` one.
+///
+/// Joint punctuation such as the `+` of `+=` or the `'` of a lifetime is
+/// never followed by a space, and neither are `!`, `#`, `$` and `.`, which
+/// are attached to the following token. `,`, `;` and `.` attach to the
+/// preceding token, and so does the `!` of a nested macro call.
+fn spaced(prev: &TokenTree, next: &TokenTree) -> bool {
+    match (prev, next) {
+        (TokenTree::Punct(prev), _)
+            if prev.spacing == Spacing::Joint || matches!(prev.ch, '!' | '#' | '$' | '.') =>
+        {
+            false
+        }
+        (_, TokenTree::Punct(next)) if matches!(next.ch, ',' | ';' | '.') => false,
+        (TokenTree::Ident(_), TokenTree::Punct(next)) => {
+            !(next.ch == '!' && next.spacing == Spacing::Alone)
+        }
+        _ => true,
+    }
+}
+
 impl PrettyPrinter for TokenTree {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
         match self {
@@ -1826,14 +3352,32 @@ impl PrettyPrinter for TokenTree {
     }
 }
 
-impl PrettyPrinter for Group {
-    fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
-        let (open, close) = match self.delimiter {
+impl Delimiter {
+    /// Returns the opening and closing tokens of the delimiter.
+    fn tokens(self) -> (&'static str, &'static str) {
+        match self {
             Delimiter::Parenthesis => ("(", ")"),
             Delimiter::Brace => ("{", "}"),
             Delimiter::Bracket => ("[", "]"),
             Delimiter::None => ("", ""),
-        };
+        }
+    }
+}
+
+impl PrettyPrinter for Delimiter {
+    /// Pretty-prints the opening and closing tokens of the delimiter, such as
+    /// `()`.
+    fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
+        let (open, close) = self.tokens();
+        printer.string(open);
+        printer.string(close);
+        Ok(())
+    }
+}
+
+impl PrettyPrinter for Group {
+    fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
+        let (open, close) = self.delimiter.tokens();
         printer.begin(BreakStyle::Consistent, open);
         self.stream.pretty_print(printer)?;
         printer.end(close);
@@ -1844,9 +3388,6 @@ impl PrettyPrinter for Group {
 impl PrettyPrinter for Punct {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
         printer.string(self.ch.to_string());
-        if self.spacing == Spacing::Alone {
-            printer.break_();
-        }
         Ok(())
     }
 }
@@ -1854,19 +3395,21 @@ impl PrettyPrinter for Punct {
 impl PrettyPrinter for Attribute {
     /// Pretty-prints the `Attribute` to the given printer.
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
-        match self {
-            Attribute::Inner(meta) => {
-                printer.string("#![");
-                meta.pretty_print(printer)?;
-                printer.string("]");
-            }
-            Attribute::Outer(meta) => {
-                printer.string("#[");
-                meta.pretty_print(printer)?;
-                printer.string("]");
+        printer.highlight(TokenKind::Attribute, |printer| {
+            match self {
+                Attribute::Inner(meta) => {
+                    printer.string("#![");
+                    meta.pretty_print(printer)?;
+                    printer.string("]");
+                }
+                Attribute::Outer(meta) => {
+                    printer.string("#[");
+                    meta.pretty_print(printer)?;
+                    printer.string("]");
+                }
             }
-        }
-        Ok(())
+            Ok(())
+        })
     }
 }
 
@@ -1927,10 +3470,7 @@ impl PrettyPrinter for GenericArg {
     /// Pretty-prints the `GenericArg` to the given printer.
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
         match self {
-            GenericArg::Lifetime(lt) => {
-                printer.string("'");
-                lt.pretty_print(printer)
-            }
+            GenericArg::Lifetime(lt) => lt.pretty_print(printer),
             GenericArg::Type(t) => t.pretty_print(printer),
             GenericArg::Const(c) => c.pretty_print(printer),
         }
@@ -1954,12 +3494,15 @@ impl PrettyPrinter for ItemForeignMod {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
         pp_begin(&self.md, printer)?;
         printer.string("extern ");
-        printer.string(format!("\"{}\"", self.abi));
+        printer.highlight(TokenKind::Literal, |printer| {
+            printer.string(format!("\"{}\"", self.abi));
+            Ok(())
+        })?;
         printer.string(" ");
         printer.begin(BreakStyle::Consistent, "{");
         if !self.items.is_empty() {
             printer.hard_break();
-            pp_with_breaks(&self.items, printer)?;
+            pp_items(&self.items, printer)?;
         }
         printer.end("}");
         pp_end(&self.md, printer)?;
@@ -1990,7 +3533,7 @@ impl PrettyPrinter for ItemMod {
             printer.begin(BreakStyle::Consistent, "{");
             if !content.is_empty() {
                 printer.hard_break();
-                pp_with_breaks(content, printer)?;
+                pp_items(content, printer)?;
             }
             printer.end("}");
         } else {
@@ -2006,13 +3549,16 @@ impl PrettyPrinter for ItemTraitAlias {
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
         pp_begin(&self.md, printer)?;
         printer.string("trait ");
-        self.ident.pretty_print(printer)?;
+        pp_type_name(&self.ident, printer)?;
         printer.string(" = ");
         for (i, bound) in self.bounds.iter().enumerate() {
             if i > 0 {
                 printer.string(" + ");
             }
-            printer.string(bound);
+            printer.highlight(TokenKind::Type, |printer| {
+                printer.string(bound);
+                Ok(())
+            })?;
         }
         printer.string(";");
         pp_end(&self.md, printer)?;
@@ -2026,13 +3572,13 @@ impl PrettyPrinter for ItemUnion {
         pp_begin(&self.md, printer)?;
         self.vis.pretty_print(printer)?;
         printer.string("union ");
-        self.ident.pretty_print(printer)?;
+        pp_type_name(&self.ident, printer)?;
         self.generics.pretty_print(printer)?;
         printer.string(" ");
         printer.begin(BreakStyle::Consistent, "{");
         if !self.fields.is_empty() {
             printer.hard_break();
-            pp_separated_with_trailing(&self.fields, ",", printer)?;
+            pp_separated_with_trailing(&self.fields, ",", |field| &field.md, printer)?;
         }
         printer.end("}");
 
@@ -2070,11 +3616,118 @@ pub fn pp_begin<'a>(md: &'a Option<Box<Md>>, printer: &mut Printer<'a>) -> fmt::
     Ok(())
 }
 
+impl PrettyPrinter for Md {
+    /// Pretty-prints the comments and attributes that go before a node,
+    /// followed by the comments that go after it, one per line.
+    fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
+        let mut is_empty = true;
+        for comment in &self.comments {
+            if !mem::replace(&mut is_empty, false) {
+                printer.hard_break();
+            }
+            pp_comment_text(comment, printer);
+        }
+        for attr in &self.attrs {
+            if !mem::replace(&mut is_empty, false) {
+                printer.hard_break();
+            }
+            attr.pretty_print(printer)?;
+        }
+        if let Some(text) = &self.eol_comment {
+            printer.eol_comment(text);
+        }
+        for comment in &self.trailing_comments {
+            if !mem::replace(&mut is_empty, false) {
+                printer.hard_break();
+            }
+            pp_comment_text(comment, printer);
+        }
+        Ok(())
+    }
+}
+
+/// Pretty-prints the leading metadata of a statement, match arm, struct or
+/// enum member, or field value.
+///
+/// Unlike [`pp_begin`], this expects the caller to have already started a
+/// new line, so comments are printed first without a blank line before them,
+/// followed by the attributes.
+pub(crate) fn pp_begin_nested<'a>(
+    md: &'a Option<Box<Md>>,
+    printer: &mut Printer<'a>,
+) -> fmt::Result {
+    if let Some(md) = &md {
+        for comment in &md.comments {
+            pp_comment_text(comment, printer);
+            printer.hard_break();
+        }
+        for attr in &md.attrs {
+            attr.pretty_print(printer)?;
+            printer.hard_break();
+        }
+    }
+    Ok(())
+}
+
+/// Pretty-prints the metadata of a node that sits inside a single line, such
+/// as a generic or closure parameter.
+///
+/// Attributes are followed by a space and comments are printed as block
+/// comments so that the surrounding line is not broken.
+pub(crate) fn pp_inline_md<'a>(md: &'a Option<Box<Md>>, printer: &mut Printer<'a>) -> fmt::Result {
+    if let Some(md) = &md {
+        for comment in &md.comments {
+            let text = match comment {
+                Comment::Line(s) => block_comment("", s),
+                Comment::Doc(s) => block_comment("*", s),
+            };
+            printer.begin_highlight(TokenKind::Comment);
+            printer.string(text);
+            printer.end_highlight();
+            printer.string(" ");
+        }
+        for attr in &md.attrs {
+            attr.pretty_print(printer)?;
+            printer.string(" ");
+        }
+    }
+    Ok(())
+}
+
+/// Pretty-prints the name of a type or trait that an item declares.
+fn pp_type_name<'a>(ident: &'a Ident, printer: &mut Printer<'a>) -> fmt::Result {
+    printer.highlight(TokenKind::Type, |printer| ident.pretty_print(printer))
+}
+
+fn pp_comment_text(comment: &Comment, printer: &mut Printer<'_>) {
+    let text = match (comment, printer.config().single_line) {
+        (Comment::Line(s), false) => format!("//{s}"),
+        (Comment::Doc(s), false) => format!("///{s}"),
+        (Comment::Line(s), true) => block_comment("", s),
+        (Comment::Doc(s), true) => block_comment("*", s),
+    };
+    printer.begin_highlight(TokenKind::Comment);
+    printer.string(text);
+    printer.end_highlight();
+}
+
+/// Returns the text of a `//` comment as a `/* */` comment, with a space
+/// inserted into each `*/` and `/*` of the text, so that it neither ends the
+/// comment early nor opens a nested one.
+fn block_comment(prefix: &str, text: &str) -> String {
+    let text = format!("{prefix}{text}").replace("*/", "* /").replace("/*", "/ *");
+    format!("/*{text} */")
+}
+
 /// Pretty-prints the trailing metadata of an AST node.
 ///
-/// This includes trailing comments.
+/// This includes the end-of-line comment, followed by trailing comments on
+/// their own lines.
 pub fn pp_end<'a>(md: &'a Option<Box<Md>>, printer: &mut Printer<'a>) -> fmt::Result {
     if let Some(md) = &md {
+        if let Some(text) = &md.eol_comment {
+            printer.eol_comment(text);
+        }
         for comment in &md.trailing_comments {
             comment.pretty_print(printer)?;
         }
@@ -2085,7 +3738,8 @@ pub fn pp_end<'a>(md: &'a Option<Box<Md>>, printer: &mut Printer<'a>) -> fmt::Re
 impl PrettyPrinter for Type {
     /// Pretty-prints the `Type` to the given printer.
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
-        match self {
+        printer.begin_highlight(TokenKind::Type);
+        printer.node(self, |printer| match self {
             Type::Array(array) => array.pretty_print(printer),
             Type::BareFn(bare_fn) => bare_fn.pretty_print(printer),
             Type::Group(group) => group.pretty_print(printer),
@@ -2135,7 +3789,10 @@ impl PrettyPrinter for Type {
                 printer.string(")");
                 Ok(())
             }
-        }
+            Type::Verbatim(verbatim) => pp_verbatim_text(verbatim, printer),
+        })?;
+        printer.end_highlight();
+        Ok(())
     }
 }
 
@@ -2154,12 +3811,35 @@ impl PrettyPrinter for TypeArray {
 impl PrettyPrinter for TypeBareFn {
     /// Pretty-prints the `TypeBareFn` to the given printer.
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
+        if !self.lifetimes.is_empty() {
+            printer.string("for<");
+            for (i, lifetime) in self.lifetimes.iter().enumerate() {
+                if i > 0 {
+                    printer.string(", ");
+                }
+                lifetime.pretty_print(printer)?;
+            }
+            printer.string("> ");
+        }
+        if self.is_unsafe {
+            printer.string("unsafe ");
+        }
+        if let Some(abi) = &self.abi {
+            abi.pretty_print(printer)?;
+            printer.string(" ");
+        }
         printer.string("fn(");
-        for (i, ty) in self.inputs.iter().enumerate() {
+        for (i, arg) in self.inputs.iter().enumerate() {
             if i > 0 {
                 printer.string(", ");
             }
-            ty.pretty_print(printer)?;
+            arg.pretty_print(printer)?;
+        }
+        if self.is_variadic {
+            if !self.inputs.is_empty() {
+                printer.string(", ");
+            }
+            printer.string("...");
         }
         printer.string(")");
         if let Some(output) = &self.output {
@@ -2170,6 +3850,17 @@ impl PrettyPrinter for TypeBareFn {
     }
 }
 
+impl PrettyPrinter for BareFnArg {
+    /// Pretty-prints the `BareFnArg` to the given printer.
+    fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
+        if let Some(name) = &self.name {
+            name.pretty_print(printer)?;
+            printer.string(": ");
+        }
+        self.ty.pretty_print(printer)
+    }
+}
+
 impl PrettyPrinter for TypePath {
     /// Pretty-prints the `TypePath` to the given printer.
     fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
@@ -0,0 +1,339 @@
+//! Classifies the printed text for syntax highlighting.
+//!
+//! A [`Highlighter`] set with [`Printer::set_highlighter`] receives every
+//! string the printer writes together with its [`TokenKind`], and decides how
+//! to write it. [`Ansi`] and [`Html`] highlight code for terminals and web
+//! pages, and [`pretty_highlighted`] prints any node with a highlighter.
+//!
+//! Printers mark the strings of identifiers, types, literals and other
+//! context-dependent tokens with [`Printer::begin_highlight`] and
+//! [`Printer::end_highlight`]. Keywords and punctuation are recognized from
+//! the text that the printer writes for the syntax of each node.
+//!
+//! [`Printer::set_highlighter`]: crate::pretty_printer::Printer::set_highlighter
+//! [`Printer::begin_highlight`]: crate::pretty_printer::Printer::begin_highlight
+//! [`Printer::end_highlight`]: crate::pretty_printer::Printer::end_highlight
+//! [`pretty_highlighted`]: crate::pretty_printer::pretty_highlighted
+
+use std::fmt::{self, Write};
+
+/// The kind of a string in the printed output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TokenKind {
+    /// A keyword, such as `fn`, `let` or `Self`.
+    Keyword,
+    /// The name of a variable, function, field, module or other item.
+    Ident,
+    /// The name of a type or trait, including primitive types.
+    Type,
+    /// A string, character, number or boolean literal.
+    Literal,
+    /// A lifetime, such as `'a` or `'static`.
+    Lifetime,
+    /// A `//` or `/* */` comment, including doc comments.
+    Comment,
+    /// An operator or delimiter, such as `+`, `::` or `{`.
+    Punct,
+    /// The path and `!` of a macro invocation.
+    Macro,
+    /// An attribute, such as `#[derive(Debug)]`.
+    Attribute,
+}
+
+impl TokenKind {
+    /// Returns the name of the kind, such as `keyword`, which [`Html`] uses
+    /// as the class of its elements.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TokenKind::Keyword => "keyword",
+            TokenKind::Ident => "ident",
+            TokenKind::Type => "type",
+            TokenKind::Literal => "literal",
+            TokenKind::Lifetime => "lifetime",
+            TokenKind::Comment => "comment",
+            TokenKind::Punct => "punct",
+            TokenKind::Macro => "macro",
+            TokenKind::Attribute => "attribute",
+        }
+    }
+}
+
+/// Writes the classified strings of the printed output.
+///
+/// Consecutive strings of the same kind are joined into one token, and
+/// whitespace between tokens is written to the output directly. A closure that takes the same arguments as [`Highlighter::write_token`] is
+/// also a highlighter.
+///
+/// # Examples
+///
+/// ```
+/// use rasto::builder::*;
+/// use rasto::pretty_printer::{PrinterConfig, TokenKind, pretty_highlighted};
+/// use std::fmt::Write;
+///
+/// let ast = expr().call(expr().path("f"), [expr().lit(1)]);
+/// let mut kinds = vec![];
+/// let output = pretty_highlighted(
+///     &ast,
+///     &PrinterConfig::default(),
+///     |out: &mut dyn Write, kind: TokenKind, text: &str| {
+///         kinds.push((kind, text.to_string()));
+///         out.write_str(text)
+///     },
+/// );
+///
+/// assert_eq!(output, "f(1)");
+/// assert_eq!(kinds[0], (TokenKind::Ident, "f".to_string()));
+/// assert_eq!(kinds[1], (TokenKind::Punct, "(".to_string()));
+/// assert_eq!(kinds[2], (TokenKind::Literal, "1".to_string()));
+/// ```
+pub trait Highlighter {
+    /// Writes `text`, which is of the given kind, to `out`.
+    ///
+    /// # Parameters
+    ///
+    /// - `out`: The writer of the printer.
+    /// - `kind`: The kind of `text`.
+    /// - `text`: The string to write. It only contains whitespace if it is a
+    ///   comment, literal or attribute.
+    fn write_token(&mut self, out: &mut dyn Write, kind: TokenKind, text: &str) -> fmt::Result;
+}
+
+impl<F: FnMut(&mut dyn Write, TokenKind, &str) -> fmt::Result> Highlighter for F {
+    fn write_token(&mut self, out: &mut dyn Write, kind: TokenKind, text: &str) -> fmt::Result {
+        self(out, kind, text)
+    }
+}
+
+/// Highlights code with ANSI escape codes for terminals.
+///
+/// Keywords are magenta, types yellow, literals green, lifetimes cyan,
+/// comments gray, macros blue and attributes bright blue. Identifiers and
+/// punctuation use the default color.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Ansi;
+
+impl Ansi {
+    /// Returns the SGR parameters of the escape code for `kind`, or `None` if
+    /// `kind` is written without one.
+    pub fn style(kind: TokenKind) -> Option<&'static str> {
+        match kind {
+            TokenKind::Keyword => Some("35"),
+            TokenKind::Type => Some("33"),
+            TokenKind::Literal => Some("32"),
+            TokenKind::Lifetime => Some("36"),
+            TokenKind::Comment => Some("90"),
+            TokenKind::Macro => Some("34"),
+            TokenKind::Attribute => Some("94"),
+            TokenKind::Ident | TokenKind::Punct => None,
+        }
+    }
+}
+
+impl Highlighter for Ansi {
+    fn write_token(&mut self, out: &mut dyn Write, kind: TokenKind, text: &str) -> fmt::Result {
+        match Ansi::style(kind) {
+            Some(style) => write!(out, "\x1b[{style}m{text}\x1b[0m"),
+            None => out.write_str(text),
+        }
+    }
+}
+
+/// Highlights code with HTML `<span>` elements.
+///
+/// Each string is escaped and wrapped in a `<span>` whose class is the
+/// [`TokenKind::as_str`] of its kind, such as `<span class="keyword">fn</span>`.
+/// Whitespace is kept as is, so the output belongs in a `<pre>` element.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Html;
+
+impl Highlighter for Html {
+    fn write_token(&mut self, out: &mut dyn Write, kind: TokenKind, text: &str) -> fmt::Result {
+        write!(out, "<span class=\"{}\">", kind.as_str())?;
+        for ch in text.chars() {
+            match ch {
+                '&' => out.write_str("&amp;")?,
+                '<' => out.write_str("&lt;")?,
+                '>' => out.write_str("&gt;")?,
+                '"' => out.write_str("&quot;")?,
+                _ => out.write_char(ch)?,
+            }
+        }
+        out.write_str("</span>")
+    }
+}
+
+/// Keywords of the language, including the reserved and contextual ones that
+/// the printer writes.
+const KEYWORDS: &[&str] = &[
+    "abstract",
+    "as",
+    "async",
+    "auto",
+    "await",
+    "become",
+    "box",
+    "break",
+    "const",
+    "continue",
+    "crate",
+    "default",
+    "do",
+    "dyn",
+    "else",
+    "enum",
+    "extern",
+    "false",
+    "final",
+    "fn",
+    "for",
+    "gen",
+    "if",
+    "impl",
+    "in",
+    "let",
+    "loop",
+    "macro",
+    "macro_rules",
+    "match",
+    "mod",
+    "move",
+    "mut",
+    "override",
+    "priv",
+    "pub",
+    "raw",
+    "ref",
+    "return",
+    "safe",
+    "self",
+    "Self",
+    "static",
+    "struct",
+    "super",
+    "trait",
+    "true",
+    "try",
+    "type",
+    "typeof",
+    "union",
+    "unsafe",
+    "unsized",
+    "use",
+    "virtual",
+    "where",
+    "while",
+    "yield",
+];
+
+/// Keywords that are path segments, and so may be printed as identifiers.
+const PATH_KEYWORDS: &[&str] = &["crate", "self", "Self", "super"];
+
+/// The state of a printer that highlights its output.
+///
+/// Consecutive strings of the same kind are joined, so that a lifetime or a
+/// macro name is written as one token.
+pub(crate) struct Highlight<'a> {
+    highlighter: Box<dyn Highlighter + 'a>,
+    // The kinds begun around the strings being written
+    kinds: Vec<TokenKind>,
+    // The token written last, until a token of another kind or whitespace
+    pending: Option<(TokenKind, String)>,
+}
+
+impl<'a> Highlight<'a> {
+    pub(crate) fn new(highlighter: impl Highlighter + 'a) -> Self {
+        Self {
+            highlighter: Box::new(highlighter),
+            kinds: Vec::new(),
+            pending: None,
+        }
+    }
+
+    /// Begins strings of the given kind. An identifier inside another kind
+    /// takes the outer kind.
+    pub(crate) fn begin(&mut self, kind: TokenKind) {
+        let kind = match self.kinds.last() {
+            Some(&outer) if kind == TokenKind::Ident => outer,
+            _ => kind,
+        };
+        self.kinds.push(kind);
+    }
+
+    pub(crate) fn end(&mut self) {
+        self.kinds.pop();
+    }
+
+    /// Writes `text`, classified by the innermost kind begun around it.
+    ///
+    /// Identifiers are never keywords, other than the path keywords. Outside
+    /// of any kind, or in a type, the text is split into whitespace, words,
+    /// which are keywords, identifiers or types, and punctuation. Text of any
+    /// other kind is one token.
+    pub(crate) fn write(&mut self, out: &mut dyn Write, text: &str) -> fmt::Result {
+        match self.kinds.last().copied() {
+            marked @ (None | Some(TokenKind::Type)) => {
+                let word_kind = marked.unwrap_or(TokenKind::Ident);
+                let is_word = |c: char| c.is_alphanumeric() || c == '_';
+                let mut rest = text;
+                while let Some(ch) = rest.chars().next() {
+                    let len = if ch.is_whitespace() {
+                        rest.find(|c: char| !c.is_whitespace())
+                    } else if is_word(ch) {
+                        // A raw identifier is one word.
+                        let prefix = match rest.strip_prefix("r#") {
+                            Some(raw) if raw.starts_with(is_word) => 2,
+                            _ => 0,
+                        };
+                        rest[prefix..].find(|c| !is_word(c)).map(|len| prefix + len)
+                    } else {
+                        rest.find(|c: char| c.is_whitespace() || is_word(c))
+                    }
+                    .unwrap_or(rest.len());
+                    let (run, tail) = rest.split_at(len);
+                    if ch.is_whitespace() {
+                        self.flush(out)?;
+                        out.write_str(run)?;
+                    } else {
+                        let kind = if !is_word(ch) || run == "_" {
+                            TokenKind::Punct
+                        } else if ch.is_ascii_digit() {
+                            TokenKind::Literal
+                        } else if KEYWORDS.contains(&run) {
+                            TokenKind::Keyword
+                        } else {
+                            word_kind
+                        };
+                        self.push(out, kind, run)?;
+                    }
+                    rest = tail;
+                }
+                Ok(())
+            }
+            Some(TokenKind::Ident) if PATH_KEYWORDS.contains(&text) => {
+                self.push(out, TokenKind::Keyword, text)
+            }
+            Some(kind) => self.push(out, kind, text),
+        }
+    }
+
+    fn push(&mut self, out: &mut dyn Write, kind: TokenKind, text: &str) -> fmt::Result {
+        match &mut self.pending {
+            Some((pending_kind, pending)) if *pending_kind == kind => pending.push_str(text),
+            _ => {
+                self.flush(out)?;
+                self.pending = Some((kind, text.to_string()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the token written last, before whitespace is written to `out`
+    /// directly.
+    pub(crate) fn flush(&mut self, out: &mut dyn Write) -> fmt::Result {
+        match self.pending.take() {
+            Some((kind, text)) => self.highlighter.write_token(out, kind, &text),
+            None => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,195 @@
+//! Maps AST nodes to the ranges of the output they were printed to.
+//!
+//! A [`SourceMap`] is recorded by [`pretty_with_source_map`] or by a
+//! [`Printer`] on which [`Printer::record_source_map`] was called. Printers
+//! mark the nodes they print with [`Printer::begin_node`] and
+//! [`Printer::end_node`], and the source map stores the range between the
+//! first and the last character written for each node.
+//!
+//! [`pretty_with_source_map`]: crate::pretty_printer::pretty_with_source_map
+//! [`Printer`]: crate::pretty_printer::Printer
+//! [`Printer::record_source_map`]: crate::pretty_printer::Printer::record_source_map
+//! [`Printer::begin_node`]: crate::pretty_printer::Printer::begin_node
+//! [`Printer::end_node`]: crate::pretty_printer::Printer::end_node
+
+use std::any::{Any, TypeId, type_name};
+use std::ops::Range;
+
+/// Identifies an AST node by its address and type.
+///
+/// Two `NodeId`s are equal if they were created from the same node, so a
+/// node can be looked up in a [`SourceMap`] as long as it has not been moved
+/// since it was printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NodeId {
+    addr: usize,
+    type_id: TypeId,
+}
+
+impl NodeId {
+    /// Returns the identity of `node`.
+    pub fn of<T: Any>(node: &T) -> Self {
+        Self {
+            addr: node as *const T as usize,
+            type_id: TypeId::of::<T>(),
+        }
+    }
+}
+
+/// Returns the name of `T` without its module path, such as `Expr`.
+pub(crate) fn kind_of<T: Any>() -> &'static str {
+    let name = type_name::<T>();
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+/// A position in the printed output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Position {
+    /// The byte offset from the start of the output.
+    pub offset: usize,
+    /// The 1-based line.
+    pub line: usize,
+    /// The 1-based column, in characters.
+    pub column: usize,
+}
+
+impl Position {
+    pub(crate) fn start() -> Self {
+        Self {
+            offset: 0,
+            line: 1,
+            column: 1,
+        }
+    }
+
+    /// Moves the position past `s`.
+    pub(crate) fn advance(&mut self, s: &str) {
+        self.offset += s.len();
+        match s.rfind('\n') {
+            Some(last) => {
+                self.line += s.matches('\n').count();
+                self.column = s[last + 1..].chars().count() + 1;
+            }
+            None => self.column += s.chars().count(),
+        }
+    }
+}
+
+/// The range of the output that a node was printed to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeSpan {
+    /// The name of the type of the node, such as `Expr` or `Item`.
+    pub kind: &'static str,
+    /// The position of the first character of the node.
+    pub start: Position,
+    /// The position just past the last character of the node.
+    pub end: Position,
+    id: NodeId,
+}
+
+impl NodeSpan {
+    pub(crate) fn new(id: NodeId, kind: &'static str, position: Position) -> Self {
+        Self {
+            kind,
+            start: position,
+            end: position,
+            id,
+        }
+    }
+
+    /// Returns the identity of the node.
+    pub fn id(&self) -> NodeId {
+        self.id
+    }
+
+    /// Returns whether this span was recorded for `node`.
+    pub fn is<T: Any>(&self, node: &T) -> bool {
+        self.id == NodeId::of(node)
+    }
+
+    /// Returns the byte range of the node in the output.
+    pub fn range(&self) -> Range<usize> {
+        self.start.offset..self.end.offset
+    }
+
+    fn contains(&self, line: usize, column: usize) -> bool {
+        (self.start.line, self.start.column) <= (line, column)
+            && (line, column) < (self.end.line, self.end.column)
+    }
+}
+
+/// The printed ranges of the nodes of a tree, in the order they were begun.
+///
+/// Outer nodes come before the nodes nested in them.
+///
+/// # Examples
+///
+/// ```
+/// use rasto::builder::*;
+/// use rasto::pretty_printer::{PrinterConfig, pretty_with_source_map};
+///
+/// let call = expr().call(expr().path("f"), [expr().lit(1)]);
+/// let item = fn_def("main").statement(call).build();
+/// let (output, source_map) = pretty_with_source_map(&item, &PrinterConfig::default());
+///
+/// let innermost = source_map.nodes_at(2, 7).next().unwrap();
+/// assert_eq!(innermost.kind, "Expr");
+/// assert_eq!(&output[innermost.range()], "1");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SourceMap {
+    spans: Vec<NodeSpan>,
+}
+
+impl SourceMap {
+    pub(crate) fn push(&mut self, span: NodeSpan) -> usize {
+        self.spans.push(span);
+        self.spans.len() - 1
+    }
+
+    pub(crate) fn span_mut(&mut self, index: usize) -> &mut NodeSpan {
+        &mut self.spans[index]
+    }
+
+    /// Returns the span of `node`, if it was printed.
+    pub fn span_of<T: Any>(&self, node: &T) -> Option<&NodeSpan> {
+        let id = NodeId::of(node);
+        self.spans.iter().find(|span| span.id == id)
+    }
+
+    /// Returns the spans that contain the character at the given 1-based
+    /// line and column, innermost first.
+    ///
+    /// This is the position a compiler diagnostic on the printed code points
+    /// at.
+    pub fn nodes_at(&self, line: usize, column: usize) -> impl Iterator<Item = &NodeSpan> {
+        self.spans
+            .iter()
+            .rev()
+            .filter(move |span| span.contains(line, column))
+    }
+
+    /// Returns the spans in the order the nodes were begun.
+    pub fn iter(&self) -> std::slice::Iter<'_, NodeSpan> {
+        self.spans.iter()
+    }
+
+    /// Returns the number of recorded spans.
+    pub fn len(&self) -> usize {
+        self.spans.len()
+    }
+
+    /// Returns whether no spans were recorded.
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+}
+
+impl<'a> IntoIterator for &'a SourceMap {
+    type Item = &'a NodeSpan;
+    type IntoIter = std::slice::Iter<'a, NodeSpan>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.spans.iter()
+    }
+}
@@ -0,0 +1,15 @@
+//! Stack growth for the recursive traversals of the AST.
+
+/// The stack space that must be left when a recursive call is made, below
+/// which a new stack segment is allocated.
+const RED_ZONE: usize = 64 * 1024;
+
+/// The size of the stack segments allocated when the stack runs low.
+const STACK_SEGMENT: usize = 1024 * 1024;
+
+/// Runs `f`, on a new stack segment if the current stack is nearly
+/// exhausted, so that recursion through deeply nested trees does not
+/// overflow the stack.
+pub(crate) fn ensure_sufficient_stack<R>(f: impl FnOnce() -> R) -> R {
+    stacker::maybe_grow(RED_ZONE, STACK_SEGMENT, f)
+}
@@ -0,0 +1,1334 @@
+//! Read-only traversal of the AST.
+//!
+//! The [`Visit`](crate::visit::Visit) trait has one method per node type. Each
+//! method defaults to the matching `walk_*` function, which visits the children
+//! of the node in source order. Override the methods for the nodes you are
+//! interested in, and call the `walk_*` function from the override to keep
+//! descending.
+//!
+//! # Example
+//!
+//! ```rust
+//! use rasto::ast::ExprCall;
+//! use rasto::builder::*;
+//! use rasto::visit::{self, Visit};
+//!
+//! #[derive(Default)]
+//! struct CallCounter {
+//!     calls: usize,
+//! }
+//!
+//! impl<'ast> Visit<'ast> for CallCounter {
+//!     fn visit_expr_call(&mut self, call: &'ast ExprCall) {
+//!         self.calls += 1;
+//!         visit::walk_expr_call(self, call);
+//!     }
+//! }
+//!
+//! let item = fn_def("main")
+//!     .statement(expr().call(expr().path("f"), [expr().call(expr().path("g"), [])]))
+//!     .build();
+//!
+//! let mut counter = CallCounter::default();
+//! counter.visit_item_fn(&item);
+//! assert_eq!(counter.calls, 2);
+//! ```
+
+use crate::ast::items::{Item, TraitItem, TraitItemFn};
+use crate::ast::*;
+
+macro_rules! visit_methods {
+    (
+        $(#[$outer:meta])*
+        pub trait $trait:ident {
+            $($method:ident($ty:ident) => $walk:ident,)*
+        }
+    ) => {
+        $(#[$outer])*
+        pub trait $trait<'ast> {
+            $(
+                #[doc = concat!("Visits a [`", stringify!($ty), "`].")]
+                #[doc = ""]
+                #[doc = concat!("Defaults to [`", stringify!($walk), "`], which visits its children.")]
+                fn $method(&mut self, node: &'ast $ty) {
+                    $walk(self, node);
+                }
+            )*
+        }
+    };
+}
+
+visit_methods! {
+    /// A read-only visitor over the AST, with one method per node type.
+    ///
+    /// See the [module documentation](self) for an example.
+    pub trait Visit {
+        visit_abi(Abi) => walk_abi,
+        visit_arm(Arm) => walk_arm,
+        visit_asm_args(AsmArgs) => walk_asm_args,
+        visit_asm_operand(AsmOperand) => walk_asm_operand,
+        visit_asm_options(AsmOptions) => walk_asm_options,
+        visit_associated_const(AssociatedConst) => walk_associated_const,
+        visit_associated_type(AssociatedType) => walk_associated_type,
+        visit_attribute(Attribute) => walk_attribute,
+        visit_bare_fn_arg(BareFnArg) => walk_bare_fn_arg,
+        visit_bin_op(BinOp) => walk_bin_op,
+        visit_block(Block) => walk_block,
+        visit_clobber_abi(ClobberAbi) => walk_clobber_abi,
+        visit_closure_param(ClosureParam) => walk_closure_param,
+        visit_comment(Comment) => walk_comment,
+        visit_const_param(ConstParam) => walk_const_param,
+        visit_custom(Custom) => walk_custom,
+        visit_expr(Expr) => walk_expr,
+        visit_expr_array(ExprArray) => walk_expr_array,
+        visit_expr_asm(ExprAsm) => walk_expr_asm,
+        visit_expr_assign(ExprAssign) => walk_expr_assign,
+        visit_expr_async(ExprAsync) => walk_expr_async,
+        visit_expr_await(ExprAwait) => walk_expr_await,
+        visit_expr_become(ExprBecome) => walk_expr_become,
+        visit_expr_binary(ExprBinary) => walk_expr_binary,
+        visit_expr_block(ExprBlock) => walk_expr_block,
+        visit_expr_break(ExprBreak) => walk_expr_break,
+        visit_expr_call(ExprCall) => walk_expr_call,
+        visit_expr_cast(ExprCast) => walk_expr_cast,
+        visit_expr_closure(ExprClosure) => walk_expr_closure,
+        visit_expr_const(ExprConst) => walk_expr_const,
+        visit_expr_continue(ExprContinue) => walk_expr_continue,
+        visit_expr_field(ExprField) => walk_expr_field,
+        visit_expr_for(ExprFor) => walk_expr_for,
+        visit_expr_gen(ExprGen) => walk_expr_gen,
+        visit_expr_if(ExprIf) => walk_expr_if,
+        visit_expr_index(ExprIndex) => walk_expr_index,
+        visit_expr_infer(ExprInfer) => walk_expr_infer,
+        visit_expr_loop(ExprLoop) => walk_expr_loop,
+        visit_expr_macro_call(ExprMacroCall) => walk_expr_macro_call,
+        visit_expr_match(ExprMatch) => walk_expr_match,
+        visit_expr_method_call(ExprMethodCall) => walk_expr_method_call,
+        visit_expr_paren(ExprParen) => walk_expr_paren,
+        visit_expr_path(ExprPath) => walk_expr_path,
+        visit_expr_range(ExprRange) => walk_expr_range,
+        visit_expr_raw_ref(ExprRawRef) => walk_expr_raw_ref,
+        visit_expr_ref(ExprRef) => walk_expr_ref,
+        visit_expr_return(ExprReturn) => walk_expr_return,
+        visit_expr_struct(ExprStruct) => walk_expr_struct,
+        visit_expr_try(ExprTry) => walk_expr_try,
+        visit_expr_tuple(ExprTuple) => walk_expr_tuple,
+        visit_expr_unary(ExprUnary) => walk_expr_unary,
+        visit_expr_while(ExprWhile) => walk_expr_while,
+        visit_expr_yield(ExprYield) => walk_expr_yield,
+        visit_external_item(ExternalItem) => walk_external_item,
+        visit_external_static(ExternalStatic) => walk_external_static,
+        visit_field(Field) => walk_field,
+        visit_field_pat(FieldPat) => walk_field_pat,
+        visit_field_value(FieldValue) => walk_field_value,
+        visit_file(File) => walk_file,
+        visit_generic_arg(GenericArg) => walk_generic_arg,
+        visit_generic_args(GenericArgs) => walk_generic_args,
+        visit_generic_param(GenericParam) => walk_generic_param,
+        visit_generic_params(GenericParams) => walk_generic_params,
+        visit_group(Group) => walk_group,
+        visit_ident(Ident) => walk_ident,
+        visit_impl_item(ImplItem) => walk_impl_item,
+        visit_item(Item) => walk_item,
+        visit_item_asm(ItemAsm) => walk_item_asm,
+        visit_item_const(ItemConst) => walk_item_const,
+        visit_item_enum(ItemEnum) => walk_item_enum,
+        visit_item_extern_block(ItemExternBlock) => walk_item_extern_block,
+        visit_item_extern_crate(ItemExternCrate) => walk_item_extern_crate,
+        visit_item_extern_type(ItemExternType) => walk_item_extern_type,
+        visit_item_fn(ItemFn) => walk_item_fn,
+        visit_item_foreign_mod(ItemForeignMod) => walk_item_foreign_mod,
+        visit_item_impl(ItemImpl) => walk_item_impl,
+        visit_item_macro(ItemMacro) => walk_item_macro,
+        visit_item_mod(ItemMod) => walk_item_mod,
+        visit_item_static(ItemStatic) => walk_item_static,
+        visit_item_struct(ItemStruct) => walk_item_struct,
+        visit_item_trait(ItemTrait) => walk_item_trait,
+        visit_item_trait_alias(ItemTraitAlias) => walk_item_trait_alias,
+        visit_item_type_alias(ItemTypeAlias) => walk_item_type_alias,
+        visit_item_union(ItemUnion) => walk_item_union,
+        visit_item_use(ItemUse) => walk_item_use,
+        visit_lifetime(Lifetime) => walk_lifetime,
+        visit_lifetime_param(LifetimeParam) => walk_lifetime_param,
+        visit_lifetime_predicate(LifetimePredicate) => walk_lifetime_predicate,
+        visit_lit(Lit) => walk_lit,
+        visit_lit_str(LitStr) => walk_lit_str,
+        visit_local(Local) => walk_local,
+        visit_md(Md) => walk_md,
+        visit_meta(Meta) => walk_meta,
+        visit_meta_list(MetaList) => walk_meta_list,
+        visit_meta_name_value(MetaNameValue) => walk_meta_name_value,
+        visit_pat(Pat) => walk_pat,
+        visit_pat_const(PatConst) => walk_pat_const,
+        visit_pat_ident(PatIdent) => walk_pat_ident,
+        visit_pat_lit(PatLit) => walk_pat_lit,
+        visit_pat_macro(PatMacro) => walk_pat_macro,
+        visit_pat_or(PatOr) => walk_pat_or,
+        visit_pat_paren(PatParen) => walk_pat_paren,
+        visit_pat_path(PatPath) => walk_pat_path,
+        visit_pat_range(PatRange) => walk_pat_range,
+        visit_pat_reference(PatReference) => walk_pat_reference,
+        visit_pat_rest(PatRest) => walk_pat_rest,
+        visit_pat_slice(PatSlice) => walk_pat_slice,
+        visit_pat_struct(PatStruct) => walk_pat_struct,
+        visit_pat_tuple(PatTuple) => walk_pat_tuple,
+        visit_pat_tuple_struct(PatTupleStruct) => walk_pat_tuple_struct,
+        visit_pat_type(PatType) => walk_pat_type,
+        visit_pat_wild(PatWild) => walk_pat_wild,
+        visit_path(Path) => walk_path,
+        visit_path_segment(PathSegment) => walk_path_segment,
+        visit_punct(Punct) => walk_punct,
+        visit_reg_operand(RegOperand) => walk_reg_operand,
+        visit_signature(Signature) => walk_signature,
+        visit_stmt(Stmt) => walk_stmt,
+        visit_stmt_expr(StmtExpr) => walk_stmt_expr,
+        visit_token_stream(TokenStream) => walk_token_stream,
+        visit_token_tree(TokenTree) => walk_token_tree,
+        visit_trait_item(TraitItem) => walk_trait_item,
+        visit_trait_item_fn(TraitItemFn) => walk_trait_item_fn,
+        visit_type(Type) => walk_type,
+        visit_type_array(TypeArray) => walk_type_array,
+        visit_type_bare_fn(TypeBareFn) => walk_type_bare_fn,
+        visit_type_param(TypeParam) => walk_type_param,
+        visit_type_path(TypePath) => walk_type_path,
+        visit_type_predicate(TypePredicate) => walk_type_predicate,
+        visit_type_ptr(TypePtr) => walk_type_ptr,
+        visit_type_reference(TypeReference) => walk_type_reference,
+        visit_un_op(UnOp) => walk_un_op,
+        visit_variant(Variant) => walk_variant,
+        visit_verbatim(Verbatim) => walk_verbatim,
+        visit_visibility(Visibility) => walk_visibility,
+        visit_where_clause(WhereClause) => walk_where_clause,
+        visit_where_predicate(WherePredicate) => walk_where_predicate,
+    }
+}
+
+fn visit_md_opt<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, md: &'ast Option<Box<Md>>) {
+    if let Some(md) = md {
+        v.visit_md(md);
+    }
+}
+
+// Items.
+
+/// Visits the items of a [`File`].
+pub fn walk_file<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast File) {
+    visit_md_opt(v, &node.md);
+    for item in &node.items {
+        v.visit_item(item);
+    }
+}
+
+/// Visits the node wrapped by an [`Item`].
+pub fn walk_item<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast Item) {
+    match node {
+        Item::Asm(item) => v.visit_item_asm(item),
+        Item::Const(item) => v.visit_item_const(item),
+        Item::Fn(item) => v.visit_item_fn(item),
+        Item::Struct(item) => v.visit_item_struct(item),
+        Item::Static(item) => v.visit_item_static(item),
+        Item::Enum(item) => v.visit_item_enum(item),
+        Item::Impl(item) => v.visit_item_impl(item),
+        Item::Trait(item) => v.visit_item_trait(item),
+        Item::ExternCrate(item) => v.visit_item_extern_crate(item),
+        Item::ExternType(item) => v.visit_item_extern_type(item),
+        Item::ForeignMod(item) => v.visit_item_foreign_mod(item),
+        Item::ExternBlock(item) => v.visit_item_extern_block(item),
+        Item::Macro(item) => v.visit_item_macro(item),
+        Item::Mod(item) => v.visit_item_mod(item),
+        Item::TraitAlias(item) => v.visit_item_trait_alias(item),
+        Item::TypeAlias(item) => v.visit_item_type_alias(item),
+        Item::Union(item) => v.visit_item_union(item),
+        Item::Use(item) => v.visit_item_use(item),
+        Item::Verbatim(item) => v.visit_verbatim(item),
+        Item::Custom(item) => v.visit_custom(item),
+    }
+}
+
+/// Visits the metadata and arguments of an [`ItemAsm`].
+pub fn walk_item_asm<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ItemAsm) {
+    visit_md_opt(v, &node.md);
+    v.visit_asm_args(&node.args);
+}
+
+/// Visits the metadata, name, type and value of an [`ItemConst`].
+pub fn walk_item_const<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ItemConst) {
+    visit_md_opt(v, &node.md);
+    v.visit_visibility(&node.vis);
+    v.visit_ident(&node.ident);
+    v.visit_type(&node.ty);
+    v.visit_expr(&node.expr);
+}
+
+/// Visits the metadata, name, generics and variants of an [`ItemEnum`].
+pub fn walk_item_enum<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ItemEnum) {
+    visit_md_opt(v, &node.md);
+    v.visit_visibility(&node.vis);
+    v.visit_ident(&node.ident);
+    v.visit_generic_params(&node.generics);
+    for variant in &node.variants {
+        v.visit_variant(variant);
+    }
+}
+
+/// Visits the metadata and name of a [`Variant`].
+pub fn walk_variant<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast Variant) {
+    visit_md_opt(v, &node.md);
+    v.visit_ident(&node.ident);
+}
+
+/// Visits the metadata, ABI and items of an [`ItemExternBlock`].
+pub fn walk_item_extern_block<'ast, V: Visit<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast ItemExternBlock,
+) {
+    visit_md_opt(v, &node.md);
+    v.visit_visibility(&node.vis);
+    v.visit_abi(&node.abi);
+    for item in &node.items {
+        v.visit_external_item(item);
+    }
+}
+
+/// Visits the node wrapped by an [`ExternalItem`].
+pub fn walk_external_item<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ExternalItem) {
+    match node {
+        ExternalItem::Static(item) => v.visit_external_static(item),
+        ExternalItem::Fn(item) => v.visit_item_fn(item),
+        ExternalItem::Macro(item) => v.visit_item_macro(item),
+        ExternalItem::Type(item) => v.visit_item_extern_type(item),
+    }
+}
+
+/// Visits the metadata, name and type of an [`ExternalStatic`].
+pub fn walk_external_static<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ExternalStatic) {
+    visit_md_opt(v, &node.md);
+    v.visit_visibility(&node.vis);
+    v.visit_ident(&node.ident);
+    v.visit_type(&node.ty);
+}
+
+/// Visits the metadata and crate name of an [`ItemExternCrate`].
+pub fn walk_item_extern_crate<'ast, V: Visit<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast ItemExternCrate,
+) {
+    visit_md_opt(v, &node.md);
+    v.visit_ident(&node.ident);
+}
+
+/// Visits the metadata and name of an [`ItemExternType`].
+pub fn walk_item_extern_type<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ItemExternType) {
+    visit_md_opt(v, &node.md);
+    v.visit_visibility(&node.vis);
+    v.visit_ident(&node.ident);
+}
+
+/// Visits the metadata, signature and body of an [`ItemFn`].
+pub fn walk_item_fn<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ItemFn) {
+    visit_md_opt(v, &node.md);
+    v.visit_visibility(&node.vis);
+    v.visit_signature(&node.sig);
+    v.visit_block(&node.block);
+}
+
+/// Visits the ABI, name, generics, parameters, return type and where clause
+/// of a [`Signature`].
+pub fn walk_signature<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast Signature) {
+    if let Some(abi) = &node.abi {
+        v.visit_abi(abi);
+    }
+    v.visit_ident(&node.ident);
+    v.visit_generic_params(&node.generics);
+    for input in &node.inputs {
+        v.visit_pat(input);
+    }
+    if let Some(output) = &node.output {
+        v.visit_type(output);
+    }
+    if let Some(where_clause) = &node.where_clause {
+        v.visit_where_clause(where_clause);
+    }
+}
+
+/// Visits the metadata and items of an [`ItemForeignMod`].
+pub fn walk_item_foreign_mod<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ItemForeignMod) {
+    visit_md_opt(v, &node.md);
+    for item in &node.items {
+        v.visit_item(item);
+    }
+}
+
+/// Visits the metadata, generics, trait, self type and items of an
+/// [`ItemImpl`].
+pub fn walk_item_impl<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ItemImpl) {
+    visit_md_opt(v, &node.md);
+    v.visit_generic_params(&node.generics);
+    if let Some(trait_) = &node.trait_ {
+        v.visit_type(trait_);
+    }
+    v.visit_type(&node.ty);
+    for item in &node.items {
+        v.visit_impl_item(item);
+    }
+}
+
+/// Visits the node wrapped by an [`ImplItem`].
+pub fn walk_impl_item<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ImplItem) {
+    match node {
+        ImplItem::Fn(item) => v.visit_item_fn(item),
+        ImplItem::Type(item) => v.visit_associated_type(item),
+        ImplItem::Const(item) => v.visit_associated_const(item),
+    }
+}
+
+/// Visits the metadata, name, type and value of an [`AssociatedConst`].
+pub fn walk_associated_const<'ast, V: Visit<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast AssociatedConst,
+) {
+    visit_md_opt(v, &node.md);
+    v.visit_ident(&node.ident);
+    v.visit_type(&node.ty);
+    if let Some(expr) = &node.expr {
+        v.visit_expr(expr);
+    }
+}
+
+/// Visits the metadata, name, generics, bounds and default of an
+/// [`AssociatedType`].
+pub fn walk_associated_type<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast AssociatedType) {
+    visit_md_opt(v, &node.md);
+    v.visit_ident(&node.ident);
+    v.visit_generic_params(&node.generics);
+    for bound in &node.bounds {
+        v.visit_type(bound);
+    }
+    if let Some(default) = &node.default {
+        v.visit_type(default);
+    }
+}
+
+/// Visits the metadata and macro call of an [`ItemMacro`].
+pub fn walk_item_macro<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ItemMacro) {
+    visit_md_opt(v, &node.md);
+    v.visit_expr(&node.expr);
+}
+
+/// Visits the metadata, name and items of an [`ItemMod`].
+pub fn walk_item_mod<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ItemMod) {
+    visit_md_opt(v, &node.md);
+    v.visit_visibility(&node.vis);
+    v.visit_ident(&node.ident);
+    for item in node.content.iter().flatten() {
+        v.visit_item(item);
+    }
+}
+
+/// Visits the metadata, name, type and value of an [`ItemStatic`].
+pub fn walk_item_static<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ItemStatic) {
+    visit_md_opt(v, &node.md);
+    v.visit_visibility(&node.vis);
+    v.visit_ident(&node.ident);
+    v.visit_type(&node.ty);
+    v.visit_expr(&node.expr);
+}
+
+/// Visits the metadata, name, generics and fields of an [`ItemStruct`].
+pub fn walk_item_struct<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ItemStruct) {
+    visit_md_opt(v, &node.md);
+    v.visit_visibility(&node.vis);
+    v.visit_ident(&node.ident);
+    v.visit_generic_params(&node.generics);
+    for field in &node.fields {
+        v.visit_field(field);
+    }
+}
+
+/// Visits the metadata, name and type of a [`Field`].
+pub fn walk_field<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast Field) {
+    visit_md_opt(v, &node.md);
+    v.visit_ident(&node.ident);
+    v.visit_type(&node.ty);
+}
+
+/// Visits the metadata, name, generics, associated types and items of an
+/// [`ItemTrait`].
+pub fn walk_item_trait<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ItemTrait) {
+    visit_md_opt(v, &node.md);
+    v.visit_visibility(&node.vis);
+    v.visit_ident(&node.ident);
+    v.visit_generic_params(&node.generics);
+    for associated_type in &node.associated_types {
+        v.visit_associated_type(associated_type);
+    }
+    for item in &node.items {
+        v.visit_trait_item(item);
+    }
+}
+
+/// Visits the node wrapped by a [`TraitItem`].
+pub fn walk_trait_item<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast TraitItem) {
+    match node {
+        TraitItem::Fn(item) => v.visit_trait_item_fn(item),
+        TraitItem::Const(item) => v.visit_associated_const(item),
+    }
+}
+
+/// Visits the metadata, signature and default body of a [`TraitItemFn`].
+pub fn walk_trait_item_fn<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast TraitItemFn) {
+    visit_md_opt(v, &node.md);
+    v.visit_signature(&node.sig);
+    if let Some(block) = &node.block {
+        v.visit_block(block);
+    }
+}
+
+/// Visits the metadata and name of an [`ItemTraitAlias`].
+///
+/// The bounds are plain strings and are not visited.
+pub fn walk_item_trait_alias<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ItemTraitAlias) {
+    visit_md_opt(v, &node.md);
+    v.visit_ident(&node.ident);
+}
+
+/// Visits the metadata, name, generics and aliased type of an
+/// [`ItemTypeAlias`].
+pub fn walk_item_type_alias<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ItemTypeAlias) {
+    visit_md_opt(v, &node.md);
+    v.visit_visibility(&node.vis);
+    v.visit_ident(&node.ident);
+    v.visit_generic_params(&node.generics);
+    v.visit_type(&node.ty);
+}
+
+/// Visits the metadata, name, generics and fields of an [`ItemUnion`].
+pub fn walk_item_union<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ItemUnion) {
+    visit_md_opt(v, &node.md);
+    v.visit_visibility(&node.vis);
+    v.visit_ident(&node.ident);
+    v.visit_generic_params(&node.generics);
+    for field in &node.fields {
+        v.visit_field(field);
+    }
+}
+
+/// Visits the metadata and visibility of an [`ItemUse`].
+///
+/// The use tree is a plain string and is not visited.
+pub fn walk_item_use<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ItemUse) {
+    visit_md_opt(v, &node.md);
+    v.visit_visibility(&node.vis);
+}
+
+/// Does nothing, as a [`Visibility`] has no children.
+pub fn walk_visibility<'ast, V: Visit<'ast> + ?Sized>(_v: &mut V, _node: &'ast Visibility) {}
+
+/// Visits the metadata of a [`Verbatim`].
+pub fn walk_verbatim<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast Verbatim) {
+    visit_md_opt(v, &node.md);
+}
+
+/// Visits the metadata of a [`Custom`] node, but not the node itself, whose
+/// type is unknown.
+pub fn walk_custom<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast Custom) {
+    visit_md_opt(v, &node.md);
+}
+
+/// Does nothing, as an [`Abi`] has no children.
+pub fn walk_abi<'ast, V: Visit<'ast> + ?Sized>(_v: &mut V, _node: &'ast Abi) {}
+
+// Inline assembly.
+
+/// Visits the arguments of an [`ExprAsm`].
+pub fn walk_expr_asm<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ExprAsm) {
+    v.visit_asm_args(&node.args);
+}
+
+/// Visits the template, operands and options of an [`AsmArgs`].
+pub fn walk_asm_args<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast AsmArgs) {
+    for template in &node.template {
+        v.visit_lit_str(template);
+    }
+    for operand in &node.operands {
+        v.visit_asm_operand(operand);
+    }
+    if let Some(options) = &node.options {
+        v.visit_asm_options(options);
+    }
+}
+
+/// Visits the node wrapped by an [`AsmOperand`].
+pub fn walk_asm_operand<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast AsmOperand) {
+    match node {
+        AsmOperand::Reg(reg) => v.visit_reg_operand(reg),
+        AsmOperand::Sym(path) => v.visit_path(path),
+        AsmOperand::Const(expr) => v.visit_expr(expr),
+        AsmOperand::ClobberAbi(clobber_abi) => v.visit_clobber_abi(clobber_abi),
+        AsmOperand::Label(block) => v.visit_block(block),
+    }
+}
+
+/// Visits the explicit register and expressions of a [`RegOperand`].
+pub fn walk_reg_operand<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast RegOperand) {
+    if let RegSpec::Explicit(reg) = &node.reg {
+        v.visit_lit_str(reg);
+    }
+    v.visit_expr(&node.expr);
+    if let Some(out_expr) = &node.out_expr {
+        v.visit_expr(out_expr);
+    }
+}
+
+/// Visits the ABI strings of a [`ClobberAbi`].
+pub fn walk_clobber_abi<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ClobberAbi) {
+    for abi in &node.abis {
+        v.visit_lit_str(abi);
+    }
+}
+
+/// Does nothing, as [`AsmOptions`] only hold flags.
+pub fn walk_asm_options<'ast, V: Visit<'ast> + ?Sized>(_v: &mut V, _node: &'ast AsmOptions) {}
+
+// Statements.
+
+/// Visits the metadata and statements of a [`Block`].
+pub fn walk_block<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast Block) {
+    visit_md_opt(v, &node.md);
+    for stmt in &node.stmts {
+        v.visit_stmt(stmt);
+    }
+}
+
+/// Visits the node wrapped by a [`Stmt`].
+pub fn walk_stmt<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast Stmt) {
+    match node {
+        Stmt::Local(local) => v.visit_local(local),
+        Stmt::Item(item) => v.visit_item(item),
+        Stmt::Expr(stmt) => v.visit_stmt_expr(stmt),
+        Stmt::Verbatim(stmt) => v.visit_verbatim(stmt),
+    }
+}
+
+/// Visits the metadata and expression of a [`StmtExpr`].
+pub fn walk_stmt_expr<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast StmtExpr) {
+    visit_md_opt(v, &node.md);
+    v.visit_expr(&node.expr);
+}
+
+/// Visits the metadata, pattern, type, initializer and `else` block of a
+/// [`Local`].
+pub fn walk_local<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast Local) {
+    visit_md_opt(v, &node.md);
+    v.visit_pat(&node.pat);
+    if let Some(ty) = &node.ty {
+        v.visit_type(ty);
+    }
+    if let Some(expr) = &node.expr {
+        v.visit_expr(expr);
+    }
+    if let Some(else_block) = &node.else_block {
+        v.visit_block(else_block);
+    }
+}
+
+// Expressions.
+
+/// Visits the node wrapped by an [`Expr`].
+pub fn walk_expr<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast Expr) {
+    crate::stack::ensure_sufficient_stack(|| match node {
+        Expr::Array(expr) => v.visit_expr_array(expr),
+        Expr::Asm(expr) => v.visit_expr_asm(expr),
+        Expr::Assign(expr) => v.visit_expr_assign(expr),
+        Expr::Async(expr) => v.visit_expr_async(expr),
+        Expr::Await(expr) => v.visit_expr_await(expr),
+        Expr::Become(expr) => v.visit_expr_become(expr),
+        Expr::Binary(expr) => v.visit_expr_binary(expr),
+        Expr::Block(expr) => v.visit_expr_block(expr),
+        Expr::Break(expr) => v.visit_expr_break(expr),
+        Expr::Call(expr) => v.visit_expr_call(expr),
+        Expr::Cast(expr) => v.visit_expr_cast(expr),
+        Expr::Closure(expr) => v.visit_expr_closure(expr),
+        Expr::Const(expr) => v.visit_expr_const(expr),
+        Expr::Continue(expr) => v.visit_expr_continue(expr),
+        Expr::Field(expr) => v.visit_expr_field(expr),
+        Expr::For(expr) => v.visit_expr_for(expr),
+        Expr::Gen(expr) => v.visit_expr_gen(expr),
+        Expr::If(expr) => v.visit_expr_if(expr),
+        Expr::Index(expr) => v.visit_expr_index(expr),
+        Expr::Infer(expr) => v.visit_expr_infer(expr),
+        Expr::Lit(lit) => v.visit_lit(lit),
+        Expr::Loop(expr) => v.visit_expr_loop(expr),
+        Expr::MacroCall(expr) => v.visit_expr_macro_call(expr),
+        Expr::Match(expr) => v.visit_expr_match(expr),
+        Expr::MethodCall(expr) => v.visit_expr_method_call(expr),
+        Expr::Paren(expr) => v.visit_expr_paren(expr),
+        Expr::Path(expr) => v.visit_expr_path(expr),
+        Expr::Range(expr) => v.visit_expr_range(expr),
+        Expr::Reference(expr) => v.visit_expr_ref(expr),
+        Expr::RawRef(expr) => v.visit_expr_raw_ref(expr),
+        Expr::Return(expr) => v.visit_expr_return(expr),
+        Expr::Struct(expr) => v.visit_expr_struct(expr),
+        Expr::Try(expr) => v.visit_expr_try(expr),
+        Expr::Tuple(expr) => v.visit_expr_tuple(expr),
+        Expr::Unary(expr) => v.visit_expr_unary(expr),
+        Expr::Verbatim(expr) => v.visit_verbatim(expr),
+        Expr::Custom(expr) => v.visit_custom(expr),
+        Expr::While(expr) => v.visit_expr_while(expr),
+        Expr::Yield(expr) => v.visit_expr_yield(expr),
+    })
+}
+
+/// Visits the elements of an [`ExprArray`].
+pub fn walk_expr_array<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ExprArray) {
+    for elem in &node.elems {
+        v.visit_expr(elem);
+    }
+}
+
+/// Visits both sides of an [`ExprAssign`].
+pub fn walk_expr_assign<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ExprAssign) {
+    v.visit_expr(&node.left);
+    v.visit_expr(&node.right);
+}
+
+/// Visits the block of an [`ExprAsync`].
+pub fn walk_expr_async<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ExprAsync) {
+    v.visit_block(&node.block);
+}
+
+/// Visits the awaited expression of an [`ExprAwait`].
+pub fn walk_expr_await<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ExprAwait) {
+    v.visit_expr(&node.expr);
+}
+
+/// Visits the tail call of an [`ExprBecome`].
+pub fn walk_expr_become<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ExprBecome) {
+    v.visit_expr(&node.expr);
+}
+
+/// Visits the operands and operator of an [`ExprBinary`].
+pub fn walk_expr_binary<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ExprBinary) {
+    v.visit_expr(&node.left);
+    v.visit_bin_op(&node.op);
+    v.visit_expr(&node.right);
+}
+
+/// Visits the block of an [`ExprBlock`].
+pub fn walk_expr_block<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ExprBlock) {
+    v.visit_block(&node.block);
+}
+
+/// Does nothing, as an [`ExprBreak`] has no children.
+pub fn walk_expr_break<'ast, V: Visit<'ast> + ?Sized>(_v: &mut V, _node: &'ast ExprBreak) {}
+
+/// Visits the callee and arguments of an [`ExprCall`].
+pub fn walk_expr_call<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ExprCall) {
+    v.visit_expr(&node.func);
+    for arg in &node.args {
+        v.visit_expr(arg);
+    }
+}
+
+/// Visits the expression and target type of an [`ExprCast`].
+pub fn walk_expr_cast<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ExprCast) {
+    v.visit_expr(&node.expr);
+    v.visit_type(&node.ty);
+}
+
+/// Visits the parameters and body of an [`ExprClosure`].
+pub fn walk_expr_closure<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ExprClosure) {
+    for input in &node.inputs {
+        v.visit_closure_param(input);
+    }
+    v.visit_expr(&node.body);
+}
+
+/// Visits the metadata and pattern of a [`ClosureParam`].
+pub fn walk_closure_param<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ClosureParam) {
+    visit_md_opt(v, &node.md);
+    v.visit_pat(&node.pat);
+}
+
+/// Visits the block of an [`ExprConst`].
+pub fn walk_expr_const<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ExprConst) {
+    v.visit_block(&node.block);
+}
+
+/// Does nothing, as an [`ExprContinue`] has no children.
+pub fn walk_expr_continue<'ast, V: Visit<'ast> + ?Sized>(_v: &mut V, _node: &'ast ExprContinue) {}
+
+/// Visits the base expression and field name of an [`ExprField`].
+pub fn walk_expr_field<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ExprField) {
+    v.visit_expr(&node.expr);
+    v.visit_ident(&node.member);
+}
+
+/// Visits the pattern, iterator and body of an [`ExprFor`].
+pub fn walk_expr_for<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ExprFor) {
+    v.visit_pat(&node.pat);
+    v.visit_expr(&node.expr);
+    v.visit_block(&node.body);
+}
+
+/// Visits the block of an [`ExprGen`].
+pub fn walk_expr_gen<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ExprGen) {
+    v.visit_block(&node.block);
+}
+
+/// Visits the condition and branches of an [`ExprIf`].
+pub fn walk_expr_if<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ExprIf) {
+    v.visit_expr(&node.cond);
+    v.visit_block(&node.then_branch);
+    if let Some(else_branch) = &node.else_branch {
+        v.visit_expr(else_branch);
+    }
+}
+
+/// Visits the indexed expression and index of an [`ExprIndex`].
+pub fn walk_expr_index<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ExprIndex) {
+    v.visit_expr(&node.expr);
+    v.visit_expr(&node.index);
+}
+
+/// Does nothing, as an [`ExprInfer`] has no children.
+pub fn walk_expr_infer<'ast, V: Visit<'ast> + ?Sized>(_v: &mut V, _node: &'ast ExprInfer) {}
+
+/// Visits the body of an [`ExprLoop`].
+pub fn walk_expr_loop<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ExprLoop) {
+    v.visit_block(&node.body);
+}
+
+/// Visits the path and tokens of an [`ExprMacroCall`].
+pub fn walk_expr_macro_call<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ExprMacroCall) {
+    v.visit_path(&node.path);
+    v.visit_token_stream(&node.tokens);
+}
+
+/// Visits the scrutinee and arms of an [`ExprMatch`].
+pub fn walk_expr_match<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ExprMatch) {
+    v.visit_expr(&node.expr);
+    for arm in &node.arms {
+        v.visit_arm(arm);
+    }
+}
+
+/// Visits the metadata, pattern, guard and body of an [`Arm`].
+pub fn walk_arm<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast Arm) {
+    visit_md_opt(v, &node.md);
+    v.visit_pat(&node.pat);
+    if let Some(guard) = &node.guard {
+        v.visit_expr(guard);
+    }
+    v.visit_expr(&node.body);
+}
+
+/// Visits the receiver, method name and arguments of an [`ExprMethodCall`].
+pub fn walk_expr_method_call<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ExprMethodCall) {
+    v.visit_expr(&node.receiver);
+    v.visit_ident(&node.method);
+    for arg in &node.args {
+        v.visit_expr(arg);
+    }
+}
+
+/// Visits the inner expression of an [`ExprParen`].
+pub fn walk_expr_paren<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ExprParen) {
+    v.visit_expr(&node.expr);
+}
+
+/// Visits the path of an [`ExprPath`].
+pub fn walk_expr_path<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ExprPath) {
+    v.visit_path(&node.path);
+}
+
+/// Visits the bounds of an [`ExprRange`].
+pub fn walk_expr_range<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ExprRange) {
+    if let Some(start) = &node.start {
+        v.visit_expr(start);
+    }
+    if let Some(end) = &node.end {
+        v.visit_expr(end);
+    }
+}
+
+/// Visits the place expression of an [`ExprRawRef`].
+pub fn walk_expr_raw_ref<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ExprRawRef) {
+    v.visit_expr(&node.expr);
+}
+
+/// Visits the referenced expression of an [`ExprRef`].
+pub fn walk_expr_ref<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ExprRef) {
+    v.visit_expr(&node.expr);
+}
+
+/// Visits the returned value of an [`ExprReturn`].
+pub fn walk_expr_return<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ExprReturn) {
+    if let Some(expr) = &node.expr {
+        v.visit_expr(expr);
+    }
+}
+
+/// Visits the path and fields of an [`ExprStruct`].
+pub fn walk_expr_struct<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ExprStruct) {
+    v.visit_path(&node.path);
+    for field in &node.fields {
+        v.visit_field_value(field);
+    }
+}
+
+/// Visits the metadata, name and value of a [`FieldValue`].
+pub fn walk_field_value<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast FieldValue) {
+    visit_md_opt(v, &node.md);
+    v.visit_ident(&node.member);
+    v.visit_expr(&node.value);
+}
+
+/// Visits the block of an [`ExprTry`].
+pub fn walk_expr_try<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ExprTry) {
+    v.visit_block(&node.block);
+}
+
+/// Visits the elements of an [`ExprTuple`].
+pub fn walk_expr_tuple<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ExprTuple) {
+    for elem in &node.elems {
+        v.visit_expr(elem);
+    }
+}
+
+/// Visits the operator and operand of an [`ExprUnary`].
+pub fn walk_expr_unary<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ExprUnary) {
+    v.visit_un_op(&node.op);
+    v.visit_expr(&node.expr);
+}
+
+/// Visits the condition and body of an [`ExprWhile`].
+pub fn walk_expr_while<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ExprWhile) {
+    v.visit_expr(&node.cond);
+    v.visit_block(&node.body);
+}
+
+/// Visits the yielded value of an [`ExprYield`].
+pub fn walk_expr_yield<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ExprYield) {
+    if let Some(expr) = &node.expr {
+        v.visit_expr(expr);
+    }
+}
+
+/// Does nothing, as a [`BinOp`] has no children.
+pub fn walk_bin_op<'ast, V: Visit<'ast> + ?Sized>(_v: &mut V, _node: &'ast BinOp) {}
+
+/// Does nothing, as an [`UnOp`] has no children.
+pub fn walk_un_op<'ast, V: Visit<'ast> + ?Sized>(_v: &mut V, _node: &'ast UnOp) {}
+
+// Literals.
+
+/// Visits the string literal of a [`Lit::Str`].
+pub fn walk_lit<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast Lit) {
+    match node {
+        Lit::Str(lit) => v.visit_lit_str(lit),
+        Lit::ByteStr(_)
+        | Lit::CStr(_)
+        | Lit::Byte(_)
+        | Lit::Char(_)
+        | Lit::Int(_)
+        | Lit::Float(_)
+        | Lit::Bool(_) => {}
+    }
+}
+
+/// Does nothing, as a [`LitStr`] has no children.
+pub fn walk_lit_str<'ast, V: Visit<'ast> + ?Sized>(_v: &mut V, _node: &'ast LitStr) {}
+
+// Patterns.
+
+/// Visits the node wrapped by a [`Pat`].
+pub fn walk_pat<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast Pat) {
+    match node {
+        Pat::Const(pat) => v.visit_pat_const(pat),
+        Pat::Ident(pat) => v.visit_pat_ident(pat),
+        Pat::Lit(pat) => v.visit_pat_lit(pat),
+        Pat::Macro(pat) => v.visit_pat_macro(pat),
+        Pat::Or(pat) => v.visit_pat_or(pat),
+        Pat::Paren(pat) => v.visit_pat_paren(pat),
+        Pat::Path(pat) => v.visit_pat_path(pat),
+        Pat::Range(pat) => v.visit_pat_range(pat),
+        Pat::Reference(pat) => v.visit_pat_reference(pat),
+        Pat::Rest(pat) => v.visit_pat_rest(pat),
+        Pat::Slice(pat) => v.visit_pat_slice(pat),
+        Pat::Struct(pat) => v.visit_pat_struct(pat),
+        Pat::Tuple(pat) => v.visit_pat_tuple(pat),
+        Pat::TupleStruct(pat) => v.visit_pat_tuple_struct(pat),
+        Pat::Type(pat) => v.visit_pat_type(pat),
+        Pat::Verbatim(pat) => v.visit_verbatim(pat),
+        Pat::Wild(pat) => v.visit_pat_wild(pat),
+    }
+}
+
+/// Visits the expression of a [`PatConst`].
+pub fn walk_pat_const<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast PatConst) {
+    v.visit_expr(&node.expr);
+}
+
+/// Visits the binding name of a [`PatIdent`].
+pub fn walk_pat_ident<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast PatIdent) {
+    v.visit_ident(&node.ident);
+}
+
+/// Visits the literal of a [`PatLit`].
+pub fn walk_pat_lit<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast PatLit) {
+    v.visit_lit(&node.lit);
+}
+
+/// Visits the macro call of a [`PatMacro`].
+pub fn walk_pat_macro<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast PatMacro) {
+    v.visit_expr_macro_call(&node.mac);
+}
+
+/// Visits the alternatives of a [`PatOr`].
+pub fn walk_pat_or<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast PatOr) {
+    for pat in &node.pats {
+        v.visit_pat(pat);
+    }
+}
+
+/// Visits the inner pattern of a [`PatParen`].
+pub fn walk_pat_paren<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast PatParen) {
+    v.visit_pat(&node.pat);
+}
+
+/// Visits the path of a [`PatPath`].
+pub fn walk_pat_path<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast PatPath) {
+    v.visit_path(&node.path);
+}
+
+/// Visits the bounds of a [`PatRange`].
+pub fn walk_pat_range<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast PatRange) {
+    if let Some(start) = &node.start {
+        v.visit_expr(start);
+    }
+    if let Some(end) = &node.end {
+        v.visit_expr(end);
+    }
+}
+
+/// Visits the referenced pattern of a [`PatReference`].
+pub fn walk_pat_reference<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast PatReference) {
+    v.visit_pat(&node.pat);
+}
+
+/// Does nothing, as a [`PatRest`] has no children.
+pub fn walk_pat_rest<'ast, V: Visit<'ast> + ?Sized>(_v: &mut V, _node: &'ast PatRest) {}
+
+/// Visits the elements of a [`PatSlice`].
+pub fn walk_pat_slice<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast PatSlice) {
+    for pat in &node.pats {
+        v.visit_pat(pat);
+    }
+}
+
+/// Visits the path and fields of a [`PatStruct`].
+pub fn walk_pat_struct<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast PatStruct) {
+    v.visit_path(&node.path);
+    for field in &node.fields {
+        v.visit_field_pat(field);
+    }
+}
+
+/// Visits the name and pattern of a [`FieldPat`].
+pub fn walk_field_pat<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast FieldPat) {
+    v.visit_ident(&node.member);
+    v.visit_pat(&node.pat);
+}
+
+/// Visits the elements of a [`PatTuple`].
+pub fn walk_pat_tuple<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast PatTuple) {
+    for pat in &node.pats {
+        v.visit_pat(pat);
+    }
+}
+
+/// Visits the path and elements of a [`PatTupleStruct`].
+pub fn walk_pat_tuple_struct<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast PatTupleStruct) {
+    v.visit_path(&node.path);
+    for pat in &node.pats {
+        v.visit_pat(pat);
+    }
+}
+
+/// Visits the pattern and type of a [`PatType`].
+pub fn walk_pat_type<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast PatType) {
+    v.visit_pat(&node.pat);
+    v.visit_type(&node.ty);
+}
+
+/// Does nothing, as a [`PatWild`] has no children.
+pub fn walk_pat_wild<'ast, V: Visit<'ast> + ?Sized>(_v: &mut V, _node: &'ast PatWild) {}
+
+// Types.
+
+/// Visits the node wrapped by a [`Type`].
+pub fn walk_type<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast Type) {
+    match node {
+        Type::Array(ty) => v.visit_type_array(ty),
+        Type::BareFn(ty) => v.visit_type_bare_fn(ty),
+        Type::Group(ty) | Type::Paren(ty) | Type::Slice(ty) => v.visit_type(ty),
+        Type::Macro(mac) => v.visit_item_macro(mac),
+        Type::Path(ty) => v.visit_type_path(ty),
+        Type::Ptr(ty) => v.visit_type_ptr(ty),
+        Type::Reference(ty) => v.visit_type_reference(ty),
+        Type::Tuple(elems) => {
+            for elem in elems {
+                v.visit_type(elem);
+            }
+        }
+        Type::Verbatim(ty) => v.visit_verbatim(ty),
+        Type::ImplTrait | Type::Infer | Type::Never | Type::TraitObject => {}
+    }
+}
+
+/// Visits the element type and length of a [`TypeArray`].
+pub fn walk_type_array<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast TypeArray) {
+    v.visit_type(&node.elem);
+    v.visit_expr(&node.len);
+}
+
+/// Visits the lifetimes, ABI, parameters and return type of a
+/// [`TypeBareFn`].
+pub fn walk_type_bare_fn<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast TypeBareFn) {
+    for lifetime in &node.lifetimes {
+        v.visit_lifetime(lifetime);
+    }
+    if let Some(abi) = &node.abi {
+        v.visit_abi(abi);
+    }
+    for input in &node.inputs {
+        v.visit_bare_fn_arg(input);
+    }
+    if let Some(output) = &node.output {
+        v.visit_type(output);
+    }
+}
+
+/// Visits the name and type of a [`BareFnArg`].
+pub fn walk_bare_fn_arg<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast BareFnArg) {
+    if let Some(name) = &node.name {
+        v.visit_ident(name);
+    }
+    v.visit_type(&node.ty);
+}
+
+/// Visits the path of a [`TypePath`].
+pub fn walk_type_path<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast TypePath) {
+    v.visit_path(&node.path);
+}
+
+/// Visits the pointee of a [`TypePtr`].
+pub fn walk_type_ptr<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast TypePtr) {
+    v.visit_type(&node.elem);
+}
+
+/// Visits the lifetime and referent of a [`TypeReference`].
+pub fn walk_type_reference<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast TypeReference) {
+    if let Some(lifetime) = &node.lifetime {
+        v.visit_lifetime(lifetime);
+    }
+    v.visit_type(&node.elem);
+}
+
+// Paths and generics.
+
+/// Visits the segments of a [`Path`].
+pub fn walk_path<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast Path) {
+    for segment in &node.segments {
+        v.visit_path_segment(segment);
+    }
+}
+
+/// Visits the name and generic arguments of a [`PathSegment`].
+pub fn walk_path_segment<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast PathSegment) {
+    v.visit_ident(&node.ident);
+    if let Some(args) = &node.args {
+        v.visit_generic_args(args);
+    }
+}
+
+/// Visits the arguments of a [`GenericArgs`].
+pub fn walk_generic_args<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast GenericArgs) {
+    for arg in &node.args {
+        v.visit_generic_arg(arg);
+    }
+}
+
+/// Visits the node wrapped by a [`GenericArg`].
+pub fn walk_generic_arg<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast GenericArg) {
+    match node {
+        GenericArg::Lifetime(lifetime) => v.visit_lifetime(lifetime),
+        GenericArg::Type(ty) => v.visit_type(ty),
+        GenericArg::Const(expr) => v.visit_expr(expr),
+    }
+}
+
+/// Visits the parameters of a [`GenericParams`].
+pub fn walk_generic_params<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast GenericParams) {
+    for param in &node.params {
+        v.visit_generic_param(param);
+    }
+}
+
+/// Visits the node wrapped by a [`GenericParam`].
+pub fn walk_generic_param<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast GenericParam) {
+    match node {
+        GenericParam::Lifetime(param) => v.visit_lifetime_param(param),
+        GenericParam::Type(param) => v.visit_type_param(param),
+        GenericParam::Const(param) => v.visit_const_param(param),
+    }
+}
+
+/// Visits the metadata, lifetime and bounds of a [`LifetimeParam`].
+pub fn walk_lifetime_param<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast LifetimeParam) {
+    visit_md_opt(v, &node.md);
+    v.visit_lifetime(&node.lifetime);
+    for bound in &node.bounds {
+        v.visit_lifetime(bound);
+    }
+}
+
+/// Visits the metadata, name and bounds of a [`TypeParam`].
+pub fn walk_type_param<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast TypeParam) {
+    visit_md_opt(v, &node.md);
+    v.visit_ident(&node.ident);
+    for bound in &node.bounds {
+        v.visit_type(bound);
+    }
+}
+
+/// Visits the metadata, name and type of a [`ConstParam`].
+pub fn walk_const_param<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ConstParam) {
+    visit_md_opt(v, &node.md);
+    v.visit_ident(&node.ident);
+    v.visit_type(&node.ty);
+}
+
+/// Visits the predicates of a [`WhereClause`].
+pub fn walk_where_clause<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast WhereClause) {
+    for predicate in &node.predicates {
+        v.visit_where_predicate(predicate);
+    }
+}
+
+/// Visits the node wrapped by a [`WherePredicate`].
+pub fn walk_where_predicate<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast WherePredicate) {
+    match node {
+        WherePredicate::Lifetime(predicate) => v.visit_lifetime_predicate(predicate),
+        WherePredicate::Type(predicate) => v.visit_type_predicate(predicate),
+    }
+}
+
+/// Visits the lifetime and bounds of a [`LifetimePredicate`].
+pub fn walk_lifetime_predicate<'ast, V: Visit<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast LifetimePredicate,
+) {
+    v.visit_lifetime(&node.lifetime);
+    for bound in &node.bounds {
+        v.visit_lifetime(bound);
+    }
+}
+
+/// Visits the bounded type and bounds of a [`TypePredicate`].
+pub fn walk_type_predicate<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast TypePredicate) {
+    v.visit_type(&node.ty);
+    for bound in &node.bounds {
+        v.visit_type(bound);
+    }
+}
+
+/// Visits the name of a [`Lifetime::Named`].
+pub fn walk_lifetime<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast Lifetime) {
+    match node {
+        Lifetime::Named(ident) => v.visit_ident(ident),
+        Lifetime::Static | Lifetime::Elided => {}
+    }
+}
+
+/// Does nothing, as an [`Ident`] has no children.
+pub fn walk_ident<'ast, V: Visit<'ast> + ?Sized>(_v: &mut V, _node: &'ast Ident) {}
+
+// Metadata.
+
+/// Visits the attributes, leading comments and trailing comments of an
+/// [`Md`].
+pub fn walk_md<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast Md) {
+    for attr in &node.attrs {
+        v.visit_attribute(attr);
+    }
+    for comment in &node.comments {
+        v.visit_comment(comment);
+    }
+    for comment in &node.trailing_comments {
+        v.visit_comment(comment);
+    }
+}
+
+/// Visits the meta item of an [`Attribute`].
+pub fn walk_attribute<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast Attribute) {
+    match node {
+        Attribute::Inner(meta) | Attribute::Outer(meta) => v.visit_meta(meta),
+    }
+}
+
+/// Visits the node wrapped by a [`Meta`].
+pub fn walk_meta<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast Meta) {
+    match node {
+        Meta::List(list) => v.visit_meta_list(list),
+        Meta::Path(path) => v.visit_ident(path),
+        Meta::NameValue(name_value) => v.visit_meta_name_value(name_value),
+    }
+}
+
+/// Visits the path and nested meta items of a [`MetaList`].
+pub fn walk_meta_list<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast MetaList) {
+    v.visit_ident(&node.path);
+    for meta in &node.metas {
+        v.visit_meta(meta);
+    }
+}
+
+/// Visits the path and value of a [`MetaNameValue`].
+pub fn walk_meta_name_value<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast MetaNameValue) {
+    v.visit_ident(&node.path);
+    v.visit_lit(&node.value);
+}
+
+/// Does nothing, as a [`Comment`] has no children.
+pub fn walk_comment<'ast, V: Visit<'ast> + ?Sized>(_v: &mut V, _node: &'ast Comment) {}
+
+// Tokens.
+
+/// Visits the token trees of a [`TokenStream`].
+pub fn walk_token_stream<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast TokenStream) {
+    for token in &node.tokens {
+        v.visit_token_tree(token);
+    }
+}
+
+/// Visits the node wrapped by a [`TokenTree`].
+///
+/// Identifier tokens are plain strings and are not visited.
+pub fn walk_token_tree<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast TokenTree) {
+    match node {
+        TokenTree::Group(group) => v.visit_group(group),
+        TokenTree::Ident(_) => {}
+        TokenTree::Punct(punct) => v.visit_punct(punct),
+        TokenTree::Literal(lit) => v.visit_lit(lit),
+    }
+}
+
+/// Visits the token stream of a [`Group`].
+pub fn walk_group<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast Group) {
+    v.visit_token_stream(&node.stream);
+}
+
+/// Does nothing, as a [`Punct`] has no children.
+pub fn walk_punct<'ast, V: Visit<'ast> + ?Sized>(_v: &mut V, _node: &'ast Punct) {}
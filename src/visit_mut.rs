@@ -0,0 +1,1318 @@
+//! In-place traversal of the AST.
+//!
+//! The [`VisitMut`](crate::visit_mut::VisitMut) trait has one method per node
+//! type. Each method defaults to the matching `walk_*_mut` function, which
+//! visits the children of the node in source order. Override the methods for
+//! the nodes you want to edit, and call the `walk_*_mut` function from the
+//! override to keep descending.
+//!
+//! # Example
+//!
+//! ```rust
+//! use rasto::ast::{BinOp, Lit};
+//! use rasto::builder::*;
+//! use rasto::pretty;
+//! use rasto::visit_mut::{self, VisitMut};
+//!
+//! struct DoubleInts;
+//!
+//! impl VisitMut for DoubleInts {
+//!     fn visit_lit_mut(&mut self, lit: &mut Lit) {
+//!         if let Lit::Int(int) = lit {
+//!             int.value *= 2;
+//!         }
+//!         visit_mut::walk_lit_mut(self, lit);
+//!     }
+//! }
+//!
+//! let mut expr = expr().binary(expr().lit(1), BinOp::Add, expr().lit(2));
+//! DoubleInts.visit_expr_mut(&mut expr);
+//! assert_eq!(pretty(&expr), "2 + 4");
+//! ```
+
+use crate::ast::items::{Item, TraitItem, TraitItemFn};
+use crate::ast::*;
+
+macro_rules! visit_methods {
+    (
+        $(#[$outer:meta])*
+        pub trait $trait:ident {
+            $($method:ident($ty:ident) => $walk:ident,)*
+        }
+    ) => {
+        $(#[$outer])*
+        pub trait $trait {
+            $(
+                #[doc = concat!("Visits a [`", stringify!($ty), "`] mutably.")]
+                #[doc = ""]
+                #[doc = concat!("Defaults to [`", stringify!($walk), "`], which visits its children.")]
+                fn $method(&mut self, node: &mut $ty) {
+                    $walk(self, node);
+                }
+            )*
+        }
+    };
+}
+
+visit_methods! {
+    /// A visitor that edits the AST in place, with one method per node type.
+    ///
+    /// See the [module documentation](self) for an example.
+    pub trait VisitMut {
+        visit_abi_mut(Abi) => walk_abi_mut,
+        visit_arm_mut(Arm) => walk_arm_mut,
+        visit_asm_args_mut(AsmArgs) => walk_asm_args_mut,
+        visit_asm_operand_mut(AsmOperand) => walk_asm_operand_mut,
+        visit_asm_options_mut(AsmOptions) => walk_asm_options_mut,
+        visit_associated_const_mut(AssociatedConst) => walk_associated_const_mut,
+        visit_associated_type_mut(AssociatedType) => walk_associated_type_mut,
+        visit_attribute_mut(Attribute) => walk_attribute_mut,
+        visit_bare_fn_arg_mut(BareFnArg) => walk_bare_fn_arg_mut,
+        visit_bin_op_mut(BinOp) => walk_bin_op_mut,
+        visit_block_mut(Block) => walk_block_mut,
+        visit_clobber_abi_mut(ClobberAbi) => walk_clobber_abi_mut,
+        visit_closure_param_mut(ClosureParam) => walk_closure_param_mut,
+        visit_comment_mut(Comment) => walk_comment_mut,
+        visit_const_param_mut(ConstParam) => walk_const_param_mut,
+        visit_custom_mut(Custom) => walk_custom_mut,
+        visit_expr_mut(Expr) => walk_expr_mut,
+        visit_expr_array_mut(ExprArray) => walk_expr_array_mut,
+        visit_expr_asm_mut(ExprAsm) => walk_expr_asm_mut,
+        visit_expr_assign_mut(ExprAssign) => walk_expr_assign_mut,
+        visit_expr_async_mut(ExprAsync) => walk_expr_async_mut,
+        visit_expr_await_mut(ExprAwait) => walk_expr_await_mut,
+        visit_expr_become_mut(ExprBecome) => walk_expr_become_mut,
+        visit_expr_binary_mut(ExprBinary) => walk_expr_binary_mut,
+        visit_expr_block_mut(ExprBlock) => walk_expr_block_mut,
+        visit_expr_break_mut(ExprBreak) => walk_expr_break_mut,
+        visit_expr_call_mut(ExprCall) => walk_expr_call_mut,
+        visit_expr_cast_mut(ExprCast) => walk_expr_cast_mut,
+        visit_expr_closure_mut(ExprClosure) => walk_expr_closure_mut,
+        visit_expr_const_mut(ExprConst) => walk_expr_const_mut,
+        visit_expr_continue_mut(ExprContinue) => walk_expr_continue_mut,
+        visit_expr_field_mut(ExprField) => walk_expr_field_mut,
+        visit_expr_for_mut(ExprFor) => walk_expr_for_mut,
+        visit_expr_gen_mut(ExprGen) => walk_expr_gen_mut,
+        visit_expr_if_mut(ExprIf) => walk_expr_if_mut,
+        visit_expr_index_mut(ExprIndex) => walk_expr_index_mut,
+        visit_expr_infer_mut(ExprInfer) => walk_expr_infer_mut,
+        visit_expr_loop_mut(ExprLoop) => walk_expr_loop_mut,
+        visit_expr_macro_call_mut(ExprMacroCall) => walk_expr_macro_call_mut,
+        visit_expr_match_mut(ExprMatch) => walk_expr_match_mut,
+        visit_expr_method_call_mut(ExprMethodCall) => walk_expr_method_call_mut,
+        visit_expr_paren_mut(ExprParen) => walk_expr_paren_mut,
+        visit_expr_path_mut(ExprPath) => walk_expr_path_mut,
+        visit_expr_range_mut(ExprRange) => walk_expr_range_mut,
+        visit_expr_raw_ref_mut(ExprRawRef) => walk_expr_raw_ref_mut,
+        visit_expr_ref_mut(ExprRef) => walk_expr_ref_mut,
+        visit_expr_return_mut(ExprReturn) => walk_expr_return_mut,
+        visit_expr_struct_mut(ExprStruct) => walk_expr_struct_mut,
+        visit_expr_try_mut(ExprTry) => walk_expr_try_mut,
+        visit_expr_tuple_mut(ExprTuple) => walk_expr_tuple_mut,
+        visit_expr_unary_mut(ExprUnary) => walk_expr_unary_mut,
+        visit_expr_while_mut(ExprWhile) => walk_expr_while_mut,
+        visit_expr_yield_mut(ExprYield) => walk_expr_yield_mut,
+        visit_external_item_mut(ExternalItem) => walk_external_item_mut,
+        visit_external_static_mut(ExternalStatic) => walk_external_static_mut,
+        visit_field_mut(Field) => walk_field_mut,
+        visit_field_pat_mut(FieldPat) => walk_field_pat_mut,
+        visit_field_value_mut(FieldValue) => walk_field_value_mut,
+        visit_file_mut(File) => walk_file_mut,
+        visit_generic_arg_mut(GenericArg) => walk_generic_arg_mut,
+        visit_generic_args_mut(GenericArgs) => walk_generic_args_mut,
+        visit_generic_param_mut(GenericParam) => walk_generic_param_mut,
+        visit_generic_params_mut(GenericParams) => walk_generic_params_mut,
+        visit_group_mut(Group) => walk_group_mut,
+        visit_ident_mut(Ident) => walk_ident_mut,
+        visit_impl_item_mut(ImplItem) => walk_impl_item_mut,
+        visit_item_mut(Item) => walk_item_mut,
+        visit_item_asm_mut(ItemAsm) => walk_item_asm_mut,
+        visit_item_const_mut(ItemConst) => walk_item_const_mut,
+        visit_item_enum_mut(ItemEnum) => walk_item_enum_mut,
+        visit_item_extern_block_mut(ItemExternBlock) => walk_item_extern_block_mut,
+        visit_item_extern_crate_mut(ItemExternCrate) => walk_item_extern_crate_mut,
+        visit_item_extern_type_mut(ItemExternType) => walk_item_extern_type_mut,
+        visit_item_fn_mut(ItemFn) => walk_item_fn_mut,
+        visit_item_foreign_mod_mut(ItemForeignMod) => walk_item_foreign_mod_mut,
+        visit_item_impl_mut(ItemImpl) => walk_item_impl_mut,
+        visit_item_macro_mut(ItemMacro) => walk_item_macro_mut,
+        visit_item_mod_mut(ItemMod) => walk_item_mod_mut,
+        visit_item_static_mut(ItemStatic) => walk_item_static_mut,
+        visit_item_struct_mut(ItemStruct) => walk_item_struct_mut,
+        visit_item_trait_mut(ItemTrait) => walk_item_trait_mut,
+        visit_item_trait_alias_mut(ItemTraitAlias) => walk_item_trait_alias_mut,
+        visit_item_type_alias_mut(ItemTypeAlias) => walk_item_type_alias_mut,
+        visit_item_union_mut(ItemUnion) => walk_item_union_mut,
+        visit_item_use_mut(ItemUse) => walk_item_use_mut,
+        visit_lifetime_mut(Lifetime) => walk_lifetime_mut,
+        visit_lifetime_param_mut(LifetimeParam) => walk_lifetime_param_mut,
+        visit_lifetime_predicate_mut(LifetimePredicate) => walk_lifetime_predicate_mut,
+        visit_lit_mut(Lit) => walk_lit_mut,
+        visit_lit_str_mut(LitStr) => walk_lit_str_mut,
+        visit_local_mut(Local) => walk_local_mut,
+        visit_md_mut(Md) => walk_md_mut,
+        visit_meta_mut(Meta) => walk_meta_mut,
+        visit_meta_list_mut(MetaList) => walk_meta_list_mut,
+        visit_meta_name_value_mut(MetaNameValue) => walk_meta_name_value_mut,
+        visit_pat_mut(Pat) => walk_pat_mut,
+        visit_pat_const_mut(PatConst) => walk_pat_const_mut,
+        visit_pat_ident_mut(PatIdent) => walk_pat_ident_mut,
+        visit_pat_lit_mut(PatLit) => walk_pat_lit_mut,
+        visit_pat_macro_mut(PatMacro) => walk_pat_macro_mut,
+        visit_pat_or_mut(PatOr) => walk_pat_or_mut,
+        visit_pat_paren_mut(PatParen) => walk_pat_paren_mut,
+        visit_pat_path_mut(PatPath) => walk_pat_path_mut,
+        visit_pat_range_mut(PatRange) => walk_pat_range_mut,
+        visit_pat_reference_mut(PatReference) => walk_pat_reference_mut,
+        visit_pat_rest_mut(PatRest) => walk_pat_rest_mut,
+        visit_pat_slice_mut(PatSlice) => walk_pat_slice_mut,
+        visit_pat_struct_mut(PatStruct) => walk_pat_struct_mut,
+        visit_pat_tuple_mut(PatTuple) => walk_pat_tuple_mut,
+        visit_pat_tuple_struct_mut(PatTupleStruct) => walk_pat_tuple_struct_mut,
+        visit_pat_type_mut(PatType) => walk_pat_type_mut,
+        visit_pat_wild_mut(PatWild) => walk_pat_wild_mut,
+        visit_path_mut(Path) => walk_path_mut,
+        visit_path_segment_mut(PathSegment) => walk_path_segment_mut,
+        visit_punct_mut(Punct) => walk_punct_mut,
+        visit_reg_operand_mut(RegOperand) => walk_reg_operand_mut,
+        visit_signature_mut(Signature) => walk_signature_mut,
+        visit_stmt_mut(Stmt) => walk_stmt_mut,
+        visit_stmt_expr_mut(StmtExpr) => walk_stmt_expr_mut,
+        visit_token_stream_mut(TokenStream) => walk_token_stream_mut,
+        visit_token_tree_mut(TokenTree) => walk_token_tree_mut,
+        visit_trait_item_mut(TraitItem) => walk_trait_item_mut,
+        visit_trait_item_fn_mut(TraitItemFn) => walk_trait_item_fn_mut,
+        visit_type_mut(Type) => walk_type_mut,
+        visit_type_array_mut(TypeArray) => walk_type_array_mut,
+        visit_type_bare_fn_mut(TypeBareFn) => walk_type_bare_fn_mut,
+        visit_type_param_mut(TypeParam) => walk_type_param_mut,
+        visit_type_path_mut(TypePath) => walk_type_path_mut,
+        visit_type_predicate_mut(TypePredicate) => walk_type_predicate_mut,
+        visit_type_ptr_mut(TypePtr) => walk_type_ptr_mut,
+        visit_type_reference_mut(TypeReference) => walk_type_reference_mut,
+        visit_un_op_mut(UnOp) => walk_un_op_mut,
+        visit_variant_mut(Variant) => walk_variant_mut,
+        visit_verbatim_mut(Verbatim) => walk_verbatim_mut,
+        visit_visibility_mut(Visibility) => walk_visibility_mut,
+        visit_where_clause_mut(WhereClause) => walk_where_clause_mut,
+        visit_where_predicate_mut(WherePredicate) => walk_where_predicate_mut,
+    }
+}
+
+fn visit_md_opt<V: VisitMut + ?Sized>(v: &mut V, md: &mut Option<Box<Md>>) {
+    if let Some(md) = md {
+        v.visit_md_mut(md);
+    }
+}
+
+// Items.
+
+/// Visits the items of a [`File`].
+pub fn walk_file_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut File) {
+    visit_md_opt(v, &mut node.md);
+    for item in &mut node.items {
+        v.visit_item_mut(item);
+    }
+}
+
+/// Visits the node wrapped by an [`Item`].
+pub fn walk_item_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Item) {
+    match node {
+        Item::Asm(item) => v.visit_item_asm_mut(item),
+        Item::Const(item) => v.visit_item_const_mut(item),
+        Item::Fn(item) => v.visit_item_fn_mut(item),
+        Item::Struct(item) => v.visit_item_struct_mut(item),
+        Item::Static(item) => v.visit_item_static_mut(item),
+        Item::Enum(item) => v.visit_item_enum_mut(item),
+        Item::Impl(item) => v.visit_item_impl_mut(item),
+        Item::Trait(item) => v.visit_item_trait_mut(item),
+        Item::ExternCrate(item) => v.visit_item_extern_crate_mut(item),
+        Item::ExternType(item) => v.visit_item_extern_type_mut(item),
+        Item::ForeignMod(item) => v.visit_item_foreign_mod_mut(item),
+        Item::ExternBlock(item) => v.visit_item_extern_block_mut(item),
+        Item::Macro(item) => v.visit_item_macro_mut(item),
+        Item::Mod(item) => v.visit_item_mod_mut(item),
+        Item::TraitAlias(item) => v.visit_item_trait_alias_mut(item),
+        Item::TypeAlias(item) => v.visit_item_type_alias_mut(item),
+        Item::Union(item) => v.visit_item_union_mut(item),
+        Item::Use(item) => v.visit_item_use_mut(item),
+        Item::Verbatim(item) => v.visit_verbatim_mut(item),
+        Item::Custom(item) => v.visit_custom_mut(item),
+    }
+}
+
+/// Visits the metadata and arguments of an [`ItemAsm`].
+pub fn walk_item_asm_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ItemAsm) {
+    visit_md_opt(v, &mut node.md);
+    v.visit_asm_args_mut(&mut node.args);
+}
+
+/// Visits the metadata, name, type and value of an [`ItemConst`].
+pub fn walk_item_const_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ItemConst) {
+    visit_md_opt(v, &mut node.md);
+    v.visit_visibility_mut(&mut node.vis);
+    v.visit_ident_mut(&mut node.ident);
+    v.visit_type_mut(&mut node.ty);
+    v.visit_expr_mut(&mut node.expr);
+}
+
+/// Visits the metadata, name, generics and variants of an [`ItemEnum`].
+pub fn walk_item_enum_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ItemEnum) {
+    visit_md_opt(v, &mut node.md);
+    v.visit_visibility_mut(&mut node.vis);
+    v.visit_ident_mut(&mut node.ident);
+    v.visit_generic_params_mut(&mut node.generics);
+    for variant in &mut node.variants {
+        v.visit_variant_mut(variant);
+    }
+}
+
+/// Visits the metadata and name of a [`Variant`].
+pub fn walk_variant_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Variant) {
+    visit_md_opt(v, &mut node.md);
+    v.visit_ident_mut(&mut node.ident);
+}
+
+/// Visits the metadata, ABI and items of an [`ItemExternBlock`].
+pub fn walk_item_extern_block_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ItemExternBlock) {
+    visit_md_opt(v, &mut node.md);
+    v.visit_visibility_mut(&mut node.vis);
+    v.visit_abi_mut(&mut node.abi);
+    for item in &mut node.items {
+        v.visit_external_item_mut(item);
+    }
+}
+
+/// Visits the node wrapped by an [`ExternalItem`].
+pub fn walk_external_item_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ExternalItem) {
+    match node {
+        ExternalItem::Static(item) => v.visit_external_static_mut(item),
+        ExternalItem::Fn(item) => v.visit_item_fn_mut(item),
+        ExternalItem::Macro(item) => v.visit_item_macro_mut(item),
+        ExternalItem::Type(item) => v.visit_item_extern_type_mut(item),
+    }
+}
+
+/// Visits the metadata, name and type of an [`ExternalStatic`].
+pub fn walk_external_static_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ExternalStatic) {
+    visit_md_opt(v, &mut node.md);
+    v.visit_visibility_mut(&mut node.vis);
+    v.visit_ident_mut(&mut node.ident);
+    v.visit_type_mut(&mut node.ty);
+}
+
+/// Visits the metadata and crate name of an [`ItemExternCrate`].
+pub fn walk_item_extern_crate_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ItemExternCrate) {
+    visit_md_opt(v, &mut node.md);
+    v.visit_ident_mut(&mut node.ident);
+}
+
+/// Visits the metadata and name of an [`ItemExternType`].
+pub fn walk_item_extern_type_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ItemExternType) {
+    visit_md_opt(v, &mut node.md);
+    v.visit_visibility_mut(&mut node.vis);
+    v.visit_ident_mut(&mut node.ident);
+}
+
+/// Visits the metadata, signature and body of an [`ItemFn`].
+pub fn walk_item_fn_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ItemFn) {
+    visit_md_opt(v, &mut node.md);
+    v.visit_visibility_mut(&mut node.vis);
+    v.visit_signature_mut(&mut node.sig);
+    v.visit_block_mut(&mut node.block);
+}
+
+/// Visits the ABI, name, generics, parameters, return type and where clause
+/// of a [`Signature`].
+pub fn walk_signature_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Signature) {
+    if let Some(abi) = &mut node.abi {
+        v.visit_abi_mut(abi);
+    }
+    v.visit_ident_mut(&mut node.ident);
+    v.visit_generic_params_mut(&mut node.generics);
+    for input in &mut node.inputs {
+        v.visit_pat_mut(input);
+    }
+    if let Some(output) = &mut node.output {
+        v.visit_type_mut(output);
+    }
+    if let Some(where_clause) = &mut node.where_clause {
+        v.visit_where_clause_mut(where_clause);
+    }
+}
+
+/// Visits the metadata and items of an [`ItemForeignMod`].
+pub fn walk_item_foreign_mod_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ItemForeignMod) {
+    visit_md_opt(v, &mut node.md);
+    for item in &mut node.items {
+        v.visit_item_mut(item);
+    }
+}
+
+/// Visits the metadata, generics, trait, self type and items of an
+/// [`ItemImpl`].
+pub fn walk_item_impl_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ItemImpl) {
+    visit_md_opt(v, &mut node.md);
+    v.visit_generic_params_mut(&mut node.generics);
+    if let Some(trait_) = &mut node.trait_ {
+        v.visit_type_mut(trait_);
+    }
+    v.visit_type_mut(&mut node.ty);
+    for item in &mut node.items {
+        v.visit_impl_item_mut(item);
+    }
+}
+
+/// Visits the node wrapped by an [`ImplItem`].
+pub fn walk_impl_item_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ImplItem) {
+    match node {
+        ImplItem::Fn(item) => v.visit_item_fn_mut(item),
+        ImplItem::Type(item) => v.visit_associated_type_mut(item),
+        ImplItem::Const(item) => v.visit_associated_const_mut(item),
+    }
+}
+
+/// Visits the metadata, name, type and value of an [`AssociatedConst`].
+pub fn walk_associated_const_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut AssociatedConst) {
+    visit_md_opt(v, &mut node.md);
+    v.visit_ident_mut(&mut node.ident);
+    v.visit_type_mut(&mut node.ty);
+    if let Some(expr) = &mut node.expr {
+        v.visit_expr_mut(expr);
+    }
+}
+
+/// Visits the metadata, name, generics, bounds and default of an
+/// [`AssociatedType`].
+pub fn walk_associated_type_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut AssociatedType) {
+    visit_md_opt(v, &mut node.md);
+    v.visit_ident_mut(&mut node.ident);
+    v.visit_generic_params_mut(&mut node.generics);
+    for bound in &mut node.bounds {
+        v.visit_type_mut(bound);
+    }
+    if let Some(default) = &mut node.default {
+        v.visit_type_mut(default);
+    }
+}
+
+/// Visits the metadata and macro call of an [`ItemMacro`].
+pub fn walk_item_macro_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ItemMacro) {
+    visit_md_opt(v, &mut node.md);
+    v.visit_expr_mut(&mut node.expr);
+}
+
+/// Visits the metadata, name and items of an [`ItemMod`].
+pub fn walk_item_mod_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ItemMod) {
+    visit_md_opt(v, &mut node.md);
+    v.visit_visibility_mut(&mut node.vis);
+    v.visit_ident_mut(&mut node.ident);
+    for item in node.content.iter_mut().flatten() {
+        v.visit_item_mut(item);
+    }
+}
+
+/// Visits the metadata, name, type and value of an [`ItemStatic`].
+pub fn walk_item_static_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ItemStatic) {
+    visit_md_opt(v, &mut node.md);
+    v.visit_visibility_mut(&mut node.vis);
+    v.visit_ident_mut(&mut node.ident);
+    v.visit_type_mut(&mut node.ty);
+    v.visit_expr_mut(&mut node.expr);
+}
+
+/// Visits the metadata, name, generics and fields of an [`ItemStruct`].
+pub fn walk_item_struct_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ItemStruct) {
+    visit_md_opt(v, &mut node.md);
+    v.visit_visibility_mut(&mut node.vis);
+    v.visit_ident_mut(&mut node.ident);
+    v.visit_generic_params_mut(&mut node.generics);
+    for field in &mut node.fields {
+        v.visit_field_mut(field);
+    }
+}
+
+/// Visits the metadata, name and type of a [`Field`].
+pub fn walk_field_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Field) {
+    visit_md_opt(v, &mut node.md);
+    v.visit_ident_mut(&mut node.ident);
+    v.visit_type_mut(&mut node.ty);
+}
+
+/// Visits the metadata, name, generics, associated types and items of an
+/// [`ItemTrait`].
+pub fn walk_item_trait_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ItemTrait) {
+    visit_md_opt(v, &mut node.md);
+    v.visit_visibility_mut(&mut node.vis);
+    v.visit_ident_mut(&mut node.ident);
+    v.visit_generic_params_mut(&mut node.generics);
+    for associated_type in &mut node.associated_types {
+        v.visit_associated_type_mut(associated_type);
+    }
+    for item in &mut node.items {
+        v.visit_trait_item_mut(item);
+    }
+}
+
+/// Visits the node wrapped by a [`TraitItem`].
+pub fn walk_trait_item_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut TraitItem) {
+    match node {
+        TraitItem::Fn(item) => v.visit_trait_item_fn_mut(item),
+        TraitItem::Const(item) => v.visit_associated_const_mut(item),
+    }
+}
+
+/// Visits the metadata, signature and default body of a [`TraitItemFn`].
+pub fn walk_trait_item_fn_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut TraitItemFn) {
+    visit_md_opt(v, &mut node.md);
+    v.visit_signature_mut(&mut node.sig);
+    if let Some(block) = &mut node.block {
+        v.visit_block_mut(block);
+    }
+}
+
+/// Visits the metadata and name of an [`ItemTraitAlias`].
+///
+/// The bounds are plain strings and are not visited.
+pub fn walk_item_trait_alias_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ItemTraitAlias) {
+    visit_md_opt(v, &mut node.md);
+    v.visit_ident_mut(&mut node.ident);
+}
+
+/// Visits the metadata, name, generics and aliased type of an
+/// [`ItemTypeAlias`].
+pub fn walk_item_type_alias_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ItemTypeAlias) {
+    visit_md_opt(v, &mut node.md);
+    v.visit_visibility_mut(&mut node.vis);
+    v.visit_ident_mut(&mut node.ident);
+    v.visit_generic_params_mut(&mut node.generics);
+    v.visit_type_mut(&mut node.ty);
+}
+
+/// Visits the metadata, name, generics and fields of an [`ItemUnion`].
+pub fn walk_item_union_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ItemUnion) {
+    visit_md_opt(v, &mut node.md);
+    v.visit_visibility_mut(&mut node.vis);
+    v.visit_ident_mut(&mut node.ident);
+    v.visit_generic_params_mut(&mut node.generics);
+    for field in &mut node.fields {
+        v.visit_field_mut(field);
+    }
+}
+
+/// Visits the metadata and visibility of an [`ItemUse`].
+///
+/// The use tree is a plain string and is not visited.
+pub fn walk_item_use_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ItemUse) {
+    visit_md_opt(v, &mut node.md);
+    v.visit_visibility_mut(&mut node.vis);
+}
+
+/// Does nothing, as a [`Visibility`] has no children.
+pub fn walk_visibility_mut<V: VisitMut + ?Sized>(_v: &mut V, _node: &mut Visibility) {}
+
+/// Visits the metadata of a [`Verbatim`].
+pub fn walk_verbatim_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Verbatim) {
+    visit_md_opt(v, &mut node.md);
+}
+
+/// Visits the metadata of a [`Custom`] node, but not the node itself, whose
+/// type is unknown.
+pub fn walk_custom_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Custom) {
+    visit_md_opt(v, &mut node.md);
+}
+
+/// Does nothing, as an [`Abi`] has no children.
+pub fn walk_abi_mut<V: VisitMut + ?Sized>(_v: &mut V, _node: &mut Abi) {}
+
+// Inline assembly.
+
+/// Visits the arguments of an [`ExprAsm`].
+pub fn walk_expr_asm_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ExprAsm) {
+    v.visit_asm_args_mut(&mut node.args);
+}
+
+/// Visits the template, operands and options of an [`AsmArgs`].
+pub fn walk_asm_args_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut AsmArgs) {
+    for template in &mut node.template {
+        v.visit_lit_str_mut(template);
+    }
+    for operand in &mut node.operands {
+        v.visit_asm_operand_mut(operand);
+    }
+    if let Some(options) = &mut node.options {
+        v.visit_asm_options_mut(options);
+    }
+}
+
+/// Visits the node wrapped by an [`AsmOperand`].
+pub fn walk_asm_operand_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut AsmOperand) {
+    match node {
+        AsmOperand::Reg(reg) => v.visit_reg_operand_mut(reg),
+        AsmOperand::Sym(path) => v.visit_path_mut(path),
+        AsmOperand::Const(expr) => v.visit_expr_mut(expr),
+        AsmOperand::ClobberAbi(clobber_abi) => v.visit_clobber_abi_mut(clobber_abi),
+        AsmOperand::Label(block) => v.visit_block_mut(block),
+    }
+}
+
+/// Visits the explicit register and expressions of a [`RegOperand`].
+pub fn walk_reg_operand_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut RegOperand) {
+    if let RegSpec::Explicit(reg) = &mut node.reg {
+        v.visit_lit_str_mut(reg);
+    }
+    v.visit_expr_mut(&mut node.expr);
+    if let Some(out_expr) = &mut node.out_expr {
+        v.visit_expr_mut(out_expr);
+    }
+}
+
+/// Visits the ABI strings of a [`ClobberAbi`].
+pub fn walk_clobber_abi_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ClobberAbi) {
+    for abi in &mut node.abis {
+        v.visit_lit_str_mut(abi);
+    }
+}
+
+/// Does nothing, as [`AsmOptions`] only hold flags.
+pub fn walk_asm_options_mut<V: VisitMut + ?Sized>(_v: &mut V, _node: &mut AsmOptions) {}
+
+// Statements.
+
+/// Visits the metadata and statements of a [`Block`].
+pub fn walk_block_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Block) {
+    visit_md_opt(v, &mut node.md);
+    for stmt in &mut node.stmts {
+        v.visit_stmt_mut(stmt);
+    }
+}
+
+/// Visits the node wrapped by a [`Stmt`].
+pub fn walk_stmt_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Stmt) {
+    match node {
+        Stmt::Local(local) => v.visit_local_mut(local),
+        Stmt::Item(item) => v.visit_item_mut(item),
+        Stmt::Expr(stmt) => v.visit_stmt_expr_mut(stmt),
+        Stmt::Verbatim(stmt) => v.visit_verbatim_mut(stmt),
+    }
+}
+
+/// Visits the metadata and expression of a [`StmtExpr`].
+pub fn walk_stmt_expr_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut StmtExpr) {
+    visit_md_opt(v, &mut node.md);
+    v.visit_expr_mut(&mut node.expr);
+}
+
+/// Visits the metadata, pattern, type, initializer and `else` block of a
+/// [`Local`].
+pub fn walk_local_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Local) {
+    visit_md_opt(v, &mut node.md);
+    v.visit_pat_mut(&mut node.pat);
+    if let Some(ty) = &mut node.ty {
+        v.visit_type_mut(ty);
+    }
+    if let Some(expr) = &mut node.expr {
+        v.visit_expr_mut(expr);
+    }
+    if let Some(else_block) = &mut node.else_block {
+        v.visit_block_mut(else_block);
+    }
+}
+
+// Expressions.
+
+/// Visits the node wrapped by an [`Expr`].
+pub fn walk_expr_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Expr) {
+    crate::stack::ensure_sufficient_stack(|| match node {
+        Expr::Array(expr) => v.visit_expr_array_mut(expr),
+        Expr::Asm(expr) => v.visit_expr_asm_mut(expr),
+        Expr::Assign(expr) => v.visit_expr_assign_mut(expr),
+        Expr::Async(expr) => v.visit_expr_async_mut(expr),
+        Expr::Await(expr) => v.visit_expr_await_mut(expr),
+        Expr::Become(expr) => v.visit_expr_become_mut(expr),
+        Expr::Binary(expr) => v.visit_expr_binary_mut(expr),
+        Expr::Block(expr) => v.visit_expr_block_mut(expr),
+        Expr::Break(expr) => v.visit_expr_break_mut(expr),
+        Expr::Call(expr) => v.visit_expr_call_mut(expr),
+        Expr::Cast(expr) => v.visit_expr_cast_mut(expr),
+        Expr::Closure(expr) => v.visit_expr_closure_mut(expr),
+        Expr::Const(expr) => v.visit_expr_const_mut(expr),
+        Expr::Continue(expr) => v.visit_expr_continue_mut(expr),
+        Expr::Field(expr) => v.visit_expr_field_mut(expr),
+        Expr::For(expr) => v.visit_expr_for_mut(expr),
+        Expr::Gen(expr) => v.visit_expr_gen_mut(expr),
+        Expr::If(expr) => v.visit_expr_if_mut(expr),
+        Expr::Index(expr) => v.visit_expr_index_mut(expr),
+        Expr::Infer(expr) => v.visit_expr_infer_mut(expr),
+        Expr::Lit(lit) => v.visit_lit_mut(lit),
+        Expr::Loop(expr) => v.visit_expr_loop_mut(expr),
+        Expr::MacroCall(expr) => v.visit_expr_macro_call_mut(expr),
+        Expr::Match(expr) => v.visit_expr_match_mut(expr),
+        Expr::MethodCall(expr) => v.visit_expr_method_call_mut(expr),
+        Expr::Paren(expr) => v.visit_expr_paren_mut(expr),
+        Expr::Path(expr) => v.visit_expr_path_mut(expr),
+        Expr::Range(expr) => v.visit_expr_range_mut(expr),
+        Expr::Reference(expr) => v.visit_expr_ref_mut(expr),
+        Expr::RawRef(expr) => v.visit_expr_raw_ref_mut(expr),
+        Expr::Return(expr) => v.visit_expr_return_mut(expr),
+        Expr::Struct(expr) => v.visit_expr_struct_mut(expr),
+        Expr::Try(expr) => v.visit_expr_try_mut(expr),
+        Expr::Tuple(expr) => v.visit_expr_tuple_mut(expr),
+        Expr::Unary(expr) => v.visit_expr_unary_mut(expr),
+        Expr::Verbatim(expr) => v.visit_verbatim_mut(expr),
+        Expr::Custom(expr) => v.visit_custom_mut(expr),
+        Expr::While(expr) => v.visit_expr_while_mut(expr),
+        Expr::Yield(expr) => v.visit_expr_yield_mut(expr),
+    })
+}
+
+/// Visits the elements of an [`ExprArray`].
+pub fn walk_expr_array_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ExprArray) {
+    for elem in &mut node.elems {
+        v.visit_expr_mut(elem);
+    }
+}
+
+/// Visits both sides of an [`ExprAssign`].
+pub fn walk_expr_assign_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ExprAssign) {
+    v.visit_expr_mut(&mut node.left);
+    v.visit_expr_mut(&mut node.right);
+}
+
+/// Visits the block of an [`ExprAsync`].
+pub fn walk_expr_async_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ExprAsync) {
+    v.visit_block_mut(&mut node.block);
+}
+
+/// Visits the awaited expression of an [`ExprAwait`].
+pub fn walk_expr_await_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ExprAwait) {
+    v.visit_expr_mut(&mut node.expr);
+}
+
+/// Visits the tail call of an [`ExprBecome`].
+pub fn walk_expr_become_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ExprBecome) {
+    v.visit_expr_mut(&mut node.expr);
+}
+
+/// Visits the operands and operator of an [`ExprBinary`].
+pub fn walk_expr_binary_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ExprBinary) {
+    v.visit_expr_mut(&mut node.left);
+    v.visit_bin_op_mut(&mut node.op);
+    v.visit_expr_mut(&mut node.right);
+}
+
+/// Visits the block of an [`ExprBlock`].
+pub fn walk_expr_block_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ExprBlock) {
+    v.visit_block_mut(&mut node.block);
+}
+
+/// Does nothing, as an [`ExprBreak`] has no children.
+pub fn walk_expr_break_mut<V: VisitMut + ?Sized>(_v: &mut V, _node: &mut ExprBreak) {}
+
+/// Visits the callee and arguments of an [`ExprCall`].
+pub fn walk_expr_call_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ExprCall) {
+    v.visit_expr_mut(&mut node.func);
+    for arg in &mut node.args {
+        v.visit_expr_mut(arg);
+    }
+}
+
+/// Visits the expression and target type of an [`ExprCast`].
+pub fn walk_expr_cast_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ExprCast) {
+    v.visit_expr_mut(&mut node.expr);
+    v.visit_type_mut(&mut node.ty);
+}
+
+/// Visits the parameters and body of an [`ExprClosure`].
+pub fn walk_expr_closure_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ExprClosure) {
+    for input in &mut node.inputs {
+        v.visit_closure_param_mut(input);
+    }
+    v.visit_expr_mut(&mut node.body);
+}
+
+/// Visits the metadata and pattern of a [`ClosureParam`].
+pub fn walk_closure_param_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ClosureParam) {
+    visit_md_opt(v, &mut node.md);
+    v.visit_pat_mut(&mut node.pat);
+}
+
+/// Visits the block of an [`ExprConst`].
+pub fn walk_expr_const_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ExprConst) {
+    v.visit_block_mut(&mut node.block);
+}
+
+/// Does nothing, as an [`ExprContinue`] has no children.
+pub fn walk_expr_continue_mut<V: VisitMut + ?Sized>(_v: &mut V, _node: &mut ExprContinue) {}
+
+/// Visits the base expression and field name of an [`ExprField`].
+pub fn walk_expr_field_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ExprField) {
+    v.visit_expr_mut(&mut node.expr);
+    v.visit_ident_mut(&mut node.member);
+}
+
+/// Visits the pattern, iterator and body of an [`ExprFor`].
+pub fn walk_expr_for_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ExprFor) {
+    v.visit_pat_mut(&mut node.pat);
+    v.visit_expr_mut(&mut node.expr);
+    v.visit_block_mut(&mut node.body);
+}
+
+/// Visits the block of an [`ExprGen`].
+pub fn walk_expr_gen_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ExprGen) {
+    v.visit_block_mut(&mut node.block);
+}
+
+/// Visits the condition and branches of an [`ExprIf`].
+pub fn walk_expr_if_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ExprIf) {
+    v.visit_expr_mut(&mut node.cond);
+    v.visit_block_mut(&mut node.then_branch);
+    if let Some(else_branch) = &mut node.else_branch {
+        v.visit_expr_mut(else_branch);
+    }
+}
+
+/// Visits the indexed expression and index of an [`ExprIndex`].
+pub fn walk_expr_index_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ExprIndex) {
+    v.visit_expr_mut(&mut node.expr);
+    v.visit_expr_mut(&mut node.index);
+}
+
+/// Does nothing, as an [`ExprInfer`] has no children.
+pub fn walk_expr_infer_mut<V: VisitMut + ?Sized>(_v: &mut V, _node: &mut ExprInfer) {}
+
+/// Visits the body of an [`ExprLoop`].
+pub fn walk_expr_loop_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ExprLoop) {
+    v.visit_block_mut(&mut node.body);
+}
+
+/// Visits the path and tokens of an [`ExprMacroCall`].
+pub fn walk_expr_macro_call_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ExprMacroCall) {
+    v.visit_path_mut(&mut node.path);
+    v.visit_token_stream_mut(&mut node.tokens);
+}
+
+/// Visits the scrutinee and arms of an [`ExprMatch`].
+pub fn walk_expr_match_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ExprMatch) {
+    v.visit_expr_mut(&mut node.expr);
+    for arm in &mut node.arms {
+        v.visit_arm_mut(arm);
+    }
+}
+
+/// Visits the metadata, pattern, guard and body of an [`Arm`].
+pub fn walk_arm_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Arm) {
+    visit_md_opt(v, &mut node.md);
+    v.visit_pat_mut(&mut node.pat);
+    if let Some(guard) = &mut node.guard {
+        v.visit_expr_mut(guard);
+    }
+    v.visit_expr_mut(&mut node.body);
+}
+
+/// Visits the receiver, method name and arguments of an [`ExprMethodCall`].
+pub fn walk_expr_method_call_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ExprMethodCall) {
+    v.visit_expr_mut(&mut node.receiver);
+    v.visit_ident_mut(&mut node.method);
+    for arg in &mut node.args {
+        v.visit_expr_mut(arg);
+    }
+}
+
+/// Visits the inner expression of an [`ExprParen`].
+pub fn walk_expr_paren_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ExprParen) {
+    v.visit_expr_mut(&mut node.expr);
+}
+
+/// Visits the path of an [`ExprPath`].
+pub fn walk_expr_path_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ExprPath) {
+    v.visit_path_mut(&mut node.path);
+}
+
+/// Visits the bounds of an [`ExprRange`].
+pub fn walk_expr_range_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ExprRange) {
+    if let Some(start) = &mut node.start {
+        v.visit_expr_mut(start);
+    }
+    if let Some(end) = &mut node.end {
+        v.visit_expr_mut(end);
+    }
+}
+
+/// Visits the place expression of an [`ExprRawRef`].
+pub fn walk_expr_raw_ref_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ExprRawRef) {
+    v.visit_expr_mut(&mut node.expr);
+}
+
+/// Visits the referenced expression of an [`ExprRef`].
+pub fn walk_expr_ref_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ExprRef) {
+    v.visit_expr_mut(&mut node.expr);
+}
+
+/// Visits the returned value of an [`ExprReturn`].
+pub fn walk_expr_return_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ExprReturn) {
+    if let Some(expr) = &mut node.expr {
+        v.visit_expr_mut(expr);
+    }
+}
+
+/// Visits the path and fields of an [`ExprStruct`].
+pub fn walk_expr_struct_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ExprStruct) {
+    v.visit_path_mut(&mut node.path);
+    for field in &mut node.fields {
+        v.visit_field_value_mut(field);
+    }
+}
+
+/// Visits the metadata, name and value of a [`FieldValue`].
+pub fn walk_field_value_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut FieldValue) {
+    visit_md_opt(v, &mut node.md);
+    v.visit_ident_mut(&mut node.member);
+    v.visit_expr_mut(&mut node.value);
+}
+
+/// Visits the block of an [`ExprTry`].
+pub fn walk_expr_try_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ExprTry) {
+    v.visit_block_mut(&mut node.block);
+}
+
+/// Visits the elements of an [`ExprTuple`].
+pub fn walk_expr_tuple_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ExprTuple) {
+    for elem in &mut node.elems {
+        v.visit_expr_mut(elem);
+    }
+}
+
+/// Visits the operator and operand of an [`ExprUnary`].
+pub fn walk_expr_unary_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ExprUnary) {
+    v.visit_un_op_mut(&mut node.op);
+    v.visit_expr_mut(&mut node.expr);
+}
+
+/// Visits the condition and body of an [`ExprWhile`].
+pub fn walk_expr_while_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ExprWhile) {
+    v.visit_expr_mut(&mut node.cond);
+    v.visit_block_mut(&mut node.body);
+}
+
+/// Visits the yielded value of an [`ExprYield`].
+pub fn walk_expr_yield_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ExprYield) {
+    if let Some(expr) = &mut node.expr {
+        v.visit_expr_mut(expr);
+    }
+}
+
+/// Does nothing, as a [`BinOp`] has no children.
+pub fn walk_bin_op_mut<V: VisitMut + ?Sized>(_v: &mut V, _node: &mut BinOp) {}
+
+/// Does nothing, as an [`UnOp`] has no children.
+pub fn walk_un_op_mut<V: VisitMut + ?Sized>(_v: &mut V, _node: &mut UnOp) {}
+
+// Literals.
+
+/// Visits the string literal of a [`Lit::Str`].
+pub fn walk_lit_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Lit) {
+    match node {
+        Lit::Str(lit) => v.visit_lit_str_mut(lit),
+        Lit::ByteStr(_)
+        | Lit::CStr(_)
+        | Lit::Byte(_)
+        | Lit::Char(_)
+        | Lit::Int(_)
+        | Lit::Float(_)
+        | Lit::Bool(_) => {}
+    }
+}
+
+/// Does nothing, as a [`LitStr`] has no children.
+pub fn walk_lit_str_mut<V: VisitMut + ?Sized>(_v: &mut V, _node: &mut LitStr) {}
+
+// Patterns.
+
+/// Visits the node wrapped by a [`Pat`].
+pub fn walk_pat_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Pat) {
+    match node {
+        Pat::Const(pat) => v.visit_pat_const_mut(pat),
+        Pat::Ident(pat) => v.visit_pat_ident_mut(pat),
+        Pat::Lit(pat) => v.visit_pat_lit_mut(pat),
+        Pat::Macro(pat) => v.visit_pat_macro_mut(pat),
+        Pat::Or(pat) => v.visit_pat_or_mut(pat),
+        Pat::Paren(pat) => v.visit_pat_paren_mut(pat),
+        Pat::Path(pat) => v.visit_pat_path_mut(pat),
+        Pat::Range(pat) => v.visit_pat_range_mut(pat),
+        Pat::Reference(pat) => v.visit_pat_reference_mut(pat),
+        Pat::Rest(pat) => v.visit_pat_rest_mut(pat),
+        Pat::Slice(pat) => v.visit_pat_slice_mut(pat),
+        Pat::Struct(pat) => v.visit_pat_struct_mut(pat),
+        Pat::Tuple(pat) => v.visit_pat_tuple_mut(pat),
+        Pat::TupleStruct(pat) => v.visit_pat_tuple_struct_mut(pat),
+        Pat::Type(pat) => v.visit_pat_type_mut(pat),
+        Pat::Verbatim(pat) => v.visit_verbatim_mut(pat),
+        Pat::Wild(pat) => v.visit_pat_wild_mut(pat),
+    }
+}
+
+/// Visits the expression of a [`PatConst`].
+pub fn walk_pat_const_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut PatConst) {
+    v.visit_expr_mut(&mut node.expr);
+}
+
+/// Visits the binding name of a [`PatIdent`].
+pub fn walk_pat_ident_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut PatIdent) {
+    v.visit_ident_mut(&mut node.ident);
+}
+
+/// Visits the literal of a [`PatLit`].
+pub fn walk_pat_lit_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut PatLit) {
+    v.visit_lit_mut(&mut node.lit);
+}
+
+/// Visits the macro call of a [`PatMacro`].
+pub fn walk_pat_macro_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut PatMacro) {
+    v.visit_expr_macro_call_mut(&mut node.mac);
+}
+
+/// Visits the alternatives of a [`PatOr`].
+pub fn walk_pat_or_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut PatOr) {
+    for pat in &mut node.pats {
+        v.visit_pat_mut(pat);
+    }
+}
+
+/// Visits the inner pattern of a [`PatParen`].
+pub fn walk_pat_paren_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut PatParen) {
+    v.visit_pat_mut(&mut node.pat);
+}
+
+/// Visits the path of a [`PatPath`].
+pub fn walk_pat_path_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut PatPath) {
+    v.visit_path_mut(&mut node.path);
+}
+
+/// Visits the bounds of a [`PatRange`].
+pub fn walk_pat_range_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut PatRange) {
+    if let Some(start) = &mut node.start {
+        v.visit_expr_mut(start);
+    }
+    if let Some(end) = &mut node.end {
+        v.visit_expr_mut(end);
+    }
+}
+
+/// Visits the referenced pattern of a [`PatReference`].
+pub fn walk_pat_reference_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut PatReference) {
+    v.visit_pat_mut(&mut node.pat);
+}
+
+/// Does nothing, as a [`PatRest`] has no children.
+pub fn walk_pat_rest_mut<V: VisitMut + ?Sized>(_v: &mut V, _node: &mut PatRest) {}
+
+/// Visits the elements of a [`PatSlice`].
+pub fn walk_pat_slice_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut PatSlice) {
+    for pat in &mut node.pats {
+        v.visit_pat_mut(pat);
+    }
+}
+
+/// Visits the path and fields of a [`PatStruct`].
+pub fn walk_pat_struct_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut PatStruct) {
+    v.visit_path_mut(&mut node.path);
+    for field in &mut node.fields {
+        v.visit_field_pat_mut(field);
+    }
+}
+
+/// Visits the name and pattern of a [`FieldPat`].
+pub fn walk_field_pat_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut FieldPat) {
+    v.visit_ident_mut(&mut node.member);
+    v.visit_pat_mut(&mut node.pat);
+}
+
+/// Visits the elements of a [`PatTuple`].
+pub fn walk_pat_tuple_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut PatTuple) {
+    for pat in &mut node.pats {
+        v.visit_pat_mut(pat);
+    }
+}
+
+/// Visits the path and elements of a [`PatTupleStruct`].
+pub fn walk_pat_tuple_struct_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut PatTupleStruct) {
+    v.visit_path_mut(&mut node.path);
+    for pat in &mut node.pats {
+        v.visit_pat_mut(pat);
+    }
+}
+
+/// Visits the pattern and type of a [`PatType`].
+pub fn walk_pat_type_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut PatType) {
+    v.visit_pat_mut(&mut node.pat);
+    v.visit_type_mut(&mut node.ty);
+}
+
+/// Does nothing, as a [`PatWild`] has no children.
+pub fn walk_pat_wild_mut<V: VisitMut + ?Sized>(_v: &mut V, _node: &mut PatWild) {}
+
+// Types.
+
+/// Visits the node wrapped by a [`Type`].
+pub fn walk_type_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Type) {
+    match node {
+        Type::Array(ty) => v.visit_type_array_mut(ty),
+        Type::BareFn(ty) => v.visit_type_bare_fn_mut(ty),
+        Type::Group(ty) | Type::Paren(ty) | Type::Slice(ty) => v.visit_type_mut(ty),
+        Type::Macro(mac) => v.visit_item_macro_mut(mac),
+        Type::Path(ty) => v.visit_type_path_mut(ty),
+        Type::Ptr(ty) => v.visit_type_ptr_mut(ty),
+        Type::Reference(ty) => v.visit_type_reference_mut(ty),
+        Type::Tuple(elems) => {
+            for elem in elems {
+                v.visit_type_mut(elem);
+            }
+        }
+        Type::Verbatim(ty) => v.visit_verbatim_mut(ty),
+        Type::ImplTrait | Type::Infer | Type::Never | Type::TraitObject => {}
+    }
+}
+
+/// Visits the element type and length of a [`TypeArray`].
+pub fn walk_type_array_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut TypeArray) {
+    v.visit_type_mut(&mut node.elem);
+    v.visit_expr_mut(&mut node.len);
+}
+
+/// Visits the lifetimes, ABI, parameters and return type of a
+/// [`TypeBareFn`].
+pub fn walk_type_bare_fn_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut TypeBareFn) {
+    for lifetime in &mut node.lifetimes {
+        v.visit_lifetime_mut(lifetime);
+    }
+    if let Some(abi) = &mut node.abi {
+        v.visit_abi_mut(abi);
+    }
+    for input in &mut node.inputs {
+        v.visit_bare_fn_arg_mut(input);
+    }
+    if let Some(output) = &mut node.output {
+        v.visit_type_mut(output);
+    }
+}
+
+/// Visits the name and type of a [`BareFnArg`].
+pub fn walk_bare_fn_arg_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut BareFnArg) {
+    if let Some(name) = &mut node.name {
+        v.visit_ident_mut(name);
+    }
+    v.visit_type_mut(&mut node.ty);
+}
+
+/// Visits the path of a [`TypePath`].
+pub fn walk_type_path_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut TypePath) {
+    v.visit_path_mut(&mut node.path);
+}
+
+/// Visits the pointee of a [`TypePtr`].
+pub fn walk_type_ptr_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut TypePtr) {
+    v.visit_type_mut(&mut node.elem);
+}
+
+/// Visits the lifetime and referent of a [`TypeReference`].
+pub fn walk_type_reference_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut TypeReference) {
+    if let Some(lifetime) = &mut node.lifetime {
+        v.visit_lifetime_mut(lifetime);
+    }
+    v.visit_type_mut(&mut node.elem);
+}
+
+// Paths and generics.
+
+/// Visits the segments of a [`Path`].
+pub fn walk_path_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Path) {
+    for segment in &mut node.segments {
+        v.visit_path_segment_mut(segment);
+    }
+}
+
+/// Visits the name and generic arguments of a [`PathSegment`].
+pub fn walk_path_segment_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut PathSegment) {
+    v.visit_ident_mut(&mut node.ident);
+    if let Some(args) = &mut node.args {
+        v.visit_generic_args_mut(args);
+    }
+}
+
+/// Visits the arguments of a [`GenericArgs`].
+pub fn walk_generic_args_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut GenericArgs) {
+    for arg in &mut node.args {
+        v.visit_generic_arg_mut(arg);
+    }
+}
+
+/// Visits the node wrapped by a [`GenericArg`].
+pub fn walk_generic_arg_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut GenericArg) {
+    match node {
+        GenericArg::Lifetime(lifetime) => v.visit_lifetime_mut(lifetime),
+        GenericArg::Type(ty) => v.visit_type_mut(ty),
+        GenericArg::Const(expr) => v.visit_expr_mut(expr),
+    }
+}
+
+/// Visits the parameters of a [`GenericParams`].
+pub fn walk_generic_params_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut GenericParams) {
+    for param in &mut node.params {
+        v.visit_generic_param_mut(param);
+    }
+}
+
+/// Visits the node wrapped by a [`GenericParam`].
+pub fn walk_generic_param_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut GenericParam) {
+    match node {
+        GenericParam::Lifetime(param) => v.visit_lifetime_param_mut(param),
+        GenericParam::Type(param) => v.visit_type_param_mut(param),
+        GenericParam::Const(param) => v.visit_const_param_mut(param),
+    }
+}
+
+/// Visits the metadata, lifetime and bounds of a [`LifetimeParam`].
+pub fn walk_lifetime_param_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut LifetimeParam) {
+    visit_md_opt(v, &mut node.md);
+    v.visit_lifetime_mut(&mut node.lifetime);
+    for bound in &mut node.bounds {
+        v.visit_lifetime_mut(bound);
+    }
+}
+
+/// Visits the metadata, name and bounds of a [`TypeParam`].
+pub fn walk_type_param_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut TypeParam) {
+    visit_md_opt(v, &mut node.md);
+    v.visit_ident_mut(&mut node.ident);
+    for bound in &mut node.bounds {
+        v.visit_type_mut(bound);
+    }
+}
+
+/// Visits the metadata, name and type of a [`ConstParam`].
+pub fn walk_const_param_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ConstParam) {
+    visit_md_opt(v, &mut node.md);
+    v.visit_ident_mut(&mut node.ident);
+    v.visit_type_mut(&mut node.ty);
+}
+
+/// Visits the predicates of a [`WhereClause`].
+pub fn walk_where_clause_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut WhereClause) {
+    for predicate in &mut node.predicates {
+        v.visit_where_predicate_mut(predicate);
+    }
+}
+
+/// Visits the node wrapped by a [`WherePredicate`].
+pub fn walk_where_predicate_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut WherePredicate) {
+    match node {
+        WherePredicate::Lifetime(predicate) => v.visit_lifetime_predicate_mut(predicate),
+        WherePredicate::Type(predicate) => v.visit_type_predicate_mut(predicate),
+    }
+}
+
+/// Visits the lifetime and bounds of a [`LifetimePredicate`].
+pub fn walk_lifetime_predicate_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut LifetimePredicate) {
+    v.visit_lifetime_mut(&mut node.lifetime);
+    for bound in &mut node.bounds {
+        v.visit_lifetime_mut(bound);
+    }
+}
+
+/// Visits the bounded type and bounds of a [`TypePredicate`].
+pub fn walk_type_predicate_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut TypePredicate) {
+    v.visit_type_mut(&mut node.ty);
+    for bound in &mut node.bounds {
+        v.visit_type_mut(bound);
+    }
+}
+
+/// Visits the name of a [`Lifetime::Named`].
+pub fn walk_lifetime_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Lifetime) {
+    match node {
+        Lifetime::Named(ident) => v.visit_ident_mut(ident),
+        Lifetime::Static | Lifetime::Elided => {}
+    }
+}
+
+/// Does nothing, as an [`Ident`] has no children.
+pub fn walk_ident_mut<V: VisitMut + ?Sized>(_v: &mut V, _node: &mut Ident) {}
+
+// Metadata.
+
+/// Visits the attributes, leading comments and trailing comments of an
+/// [`Md`].
+pub fn walk_md_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Md) {
+    for attr in &mut node.attrs {
+        v.visit_attribute_mut(attr);
+    }
+    for comment in &mut node.comments {
+        v.visit_comment_mut(comment);
+    }
+    for comment in &mut node.trailing_comments {
+        v.visit_comment_mut(comment);
+    }
+}
+
+/// Visits the meta item of an [`Attribute`].
+pub fn walk_attribute_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Attribute) {
+    match node {
+        Attribute::Inner(meta) | Attribute::Outer(meta) => v.visit_meta_mut(meta),
+    }
+}
+
+/// Visits the node wrapped by a [`Meta`].
+pub fn walk_meta_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Meta) {
+    match node {
+        Meta::List(list) => v.visit_meta_list_mut(list),
+        Meta::Path(path) => v.visit_ident_mut(path),
+        Meta::NameValue(name_value) => v.visit_meta_name_value_mut(name_value),
+    }
+}
+
+/// Visits the path and nested meta items of a [`MetaList`].
+pub fn walk_meta_list_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut MetaList) {
+    v.visit_ident_mut(&mut node.path);
+    for meta in &mut node.metas {
+        v.visit_meta_mut(meta);
+    }
+}
+
+/// Visits the path and value of a [`MetaNameValue`].
+pub fn walk_meta_name_value_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut MetaNameValue) {
+    v.visit_ident_mut(&mut node.path);
+    v.visit_lit_mut(&mut node.value);
+}
+
+/// Does nothing, as a [`Comment`] has no children.
+pub fn walk_comment_mut<V: VisitMut + ?Sized>(_v: &mut V, _node: &mut Comment) {}
+
+// Tokens.
+
+/// Visits the token trees of a [`TokenStream`].
+pub fn walk_token_stream_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut TokenStream) {
+    for token in &mut node.tokens {
+        v.visit_token_tree_mut(token);
+    }
+}
+
+/// Visits the node wrapped by a [`TokenTree`].
+///
+/// Identifier tokens are plain strings and are not visited.
+pub fn walk_token_tree_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut TokenTree) {
+    match node {
+        TokenTree::Group(group) => v.visit_group_mut(group),
+        TokenTree::Ident(_) => {}
+        TokenTree::Punct(punct) => v.visit_punct_mut(punct),
+        TokenTree::Literal(lit) => v.visit_lit_mut(lit),
+    }
+}
+
+/// Visits the token stream of a [`Group`].
+pub fn walk_group_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Group) {
+    v.visit_token_stream_mut(&mut node.stream);
+}
+
+/// Does nothing, as a [`Punct`] has no children.
+pub fn walk_punct_mut<V: VisitMut + ?Sized>(_v: &mut V, _node: &mut Punct) {}
@@ -1,5 +1,5 @@
-use rasto::ast::{AsmDirection, LitStr, RegSpec};
-use rasto::builder::{asm_item, asm_operand, expr, fn_def, stmt};
+use rasto::ast::{AsmDirection, GenericArg, LitStr, RegSpec};
+use rasto::builder::{asm_expr, asm_operand, expr, fn_def, global_asm_item, naked_asm_expr};
 use rasto::pretty;
 
 #[test]
@@ -7,32 +7,104 @@ fn test_asm_macro() {
     let ast = fn_def("test")
         .unsafe_()
         .statement(
-            stmt().item(
-                asm_item(LitStr::new("jmp 3f"))
-                    .template(LitStr::new("2: .ascii \\\"Hello World!\\\""))
-                    .template(LitStr::new("3: lea {bytes}, [2b+rip]"))
-                    .template(LitStr::new("mov {len}, 12"))
-                    .operand(
-                        asm_operand()
-                            .reg(
-                                AsmDirection::Out,
-                                RegSpec::Class("reg".to_string()),
-                                expr().path("bytes"),
-                            )
-                            .name("bytes"),
-                    )
-                    .operand(
-                        asm_operand()
-                            .reg(
-                                AsmDirection::Out,
-                                RegSpec::Class("reg".to_string()),
-                                expr().path("len"),
-                            )
-                            .name("len"),
-                    )
+            asm_expr(LitStr::new("jmp 3f"))
+                .template(LitStr::new("2: .ascii \"Hello World!\""))
+                .template(LitStr::new("3: lea {bytes}, [2b+rip]"))
+                .template(LitStr::new("mov {len}, 12"))
+                .operand(
+                    asm_operand()
+                        .reg(
+                            AsmDirection::Out,
+                            RegSpec::Class("reg".to_string()),
+                            expr().path("bytes"),
+                        )
+                        .name("bytes"),
+                )
+                .operand(
+                    asm_operand()
+                        .reg(
+                            AsmDirection::Out,
+                            RegSpec::Class("reg".to_string()),
+                            expr().path("len"),
+                        )
+                        .name("len"),
+                )
+                .build_expr(),
+        )
+        .build();
+    insta::assert_snapshot!(pretty(&ast));
+}
+
+#[test]
+fn test_asm_expr() {
+    let ast = fn_def("set_five")
+        .unsafe_()
+        .statement(
+            asm_expr("mov {0}, 5")
+                .operand(asm_operand().reg(
+                    AsmDirection::Out,
+                    RegSpec::Class("reg".to_string()),
+                    expr().path("x"),
+                ))
+                .build_expr(),
+        )
+        .build();
+    insta::assert_snapshot!(pretty(&ast));
+}
+
+#[test]
+fn test_global_asm() {
+    let ast = rasto::builder::file()
+        .item(
+            global_asm_item(".globl my_symbol")
+                .template("my_symbol:")
+                .template("ret")
+                .build(),
+        )
+        .build();
+    insta::assert_snapshot!(pretty(&ast), @r#"
+    global_asm!(
+        ".globl my_symbol",
+        "my_symbol:",
+        "ret"
+    );
+    "#);
+}
+
+#[test]
+fn test_naked_asm() {
+    let ast = fn_def("entry")
+        .attr(rasto::builder::attr().meta("unsafe(naked)"))
+        .abi("C")
+        .statement(naked_asm_expr("mov eax, 42").template("ret").build_expr())
+        .build();
+    insta::assert_snapshot!(pretty(&ast));
+}
+
+#[test]
+fn test_asm_label_and_generic_sym() {
+    let ast = asm_expr("jmp {}")
+        .operand(
+            asm_operand().label(
+                rasto::builder::block()
+                    .statement(expr().call(expr().path("handle"), []))
                     .build(),
             ),
         )
-        .build();
+        .operand(
+            asm_operand().sym(
+                rasto::builder::path("callback")
+                    .generic(GenericArg::Type(rasto::builder::type_().path("u32")))
+                    .build(),
+            ),
+        )
+        .build_expr();
     insta::assert_snapshot!(pretty(&ast));
 }
+
+#[test]
+#[allow(deprecated)]
+fn test_deprecated_asm_item() {
+    let item = rasto::builder::asm_item("nop").build();
+    assert_eq!(item, global_asm_item("nop").build());
+}
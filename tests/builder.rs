@@ -1,4 +1,7 @@
-use rasto::ast::{Block, Expr, ExprTry, ExprUnary, Lit, LitInt, PatIdent, Stmt, UnOp};
+mod common;
+
+use common::assert_item_compiles;
+use rasto::ast::{BinOp, Block, Expr, ExprTry, ExprUnary, Lit, LitInt, PatIdent, Stmt, UnOp};
 use rasto::{builder::*, pretty};
 use thin_vec::thin_vec;
 
@@ -18,6 +21,25 @@ fn test_fn_builder() {
     "#);
 }
 
+#[test]
+fn test_fn_builder_compiles() {
+    let ast = fn_def("foo")
+        .input_typed("a", "u8")
+        .input_typed("b", "u8")
+        .output("bool")
+        .statement(expr().lit("Hello, world!"))
+        .statement(expr().binary(expr().path("a"), BinOp::Eq, expr().path("b")))
+        .has_trailing_semicolon(false)
+        .build();
+
+    insta::assert_snapshot!(assert_item_compiles(ast, ""), @r#"
+    fn foo(a: u8, b: u8) -> bool {
+        "Hello, world!";
+        a == b
+    }
+    "#);
+}
+
 #[test]
 fn test_fn_builder_with_metadata() {
     let ast = fn_def("foo")
@@ -39,6 +61,24 @@ fn test_fn_builder_with_metadata() {
     "#);
 }
 
+#[test]
+fn test_fn_builder_with_metadata_compiles() {
+    let ast = fn_def("foo")
+        .attr(attr().meta("test"))
+        .comment(comment().line(" a leading comment"))
+        .statement(expr().lit("Hello, world!"))
+        .build();
+
+    insta::assert_snapshot!(assert_item_compiles(ast, ""), @r#"
+    #[test]
+
+    // a leading comment
+    fn foo() {
+        "Hello, world!";
+    }
+    "#);
+}
+
 #[test]
 fn test_stmt_builder() {
     let local_stmt = stmt().local("x").ty("i32").expr(expr().lit(42)).build();
@@ -53,12 +93,13 @@ fn test_stmt_builder() {
             ty: Some("i32".into()),
             expr: Some(Expr::Lit(Lit::Int(LitInt::new(42)))),
             else_block: None,
+            md: Some(Default::default()),
         })
     );
 
     let expr_stmt = stmt().expr(expr().lit(42));
 
-    assert_eq!(expr_stmt, Stmt::Expr(Expr::Lit(Lit::Int(LitInt::new(42)))));
+    assert_eq!(expr_stmt, Stmt::Expr(Expr::Lit(Lit::Int(LitInt::new(42))).into()));
 }
 
 #[test]
@@ -97,7 +138,7 @@ fn test_try_block_builder() {
         expr,
         Expr::Try(ExprTry {
             block: Block {
-                stmts: thin_vec![Stmt::Expr(Expr::Lit(Lit::Int(LitInt::new(42))))],
+                stmts: thin_vec![Stmt::Expr(Expr::Lit(Lit::Int(LitInt::new(42))).into())],
                 has_trailing_semicolon: true,
                 md: None,
             }
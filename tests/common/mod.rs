@@ -0,0 +1,109 @@
+//! Helpers shared by the integration tests.
+
+// Each test binary uses only some of the helpers.
+#![allow(dead_code)]
+
+use rasto::ast::items::Item;
+use rasto::ast::{Expr, File};
+use rasto::builder::{file, fn_def, verbatim};
+use rasto::pretty;
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::process::Command;
+use std::{env, fs, thread};
+
+/// Pretty-prints `file`, checks that the local `rustc` accepts it as a
+/// 2024-edition library, and returns the printed code for snapshotting.
+///
+/// The code is written to `<test binary>__<test name>.rs`, named after the
+/// snapshot of the calling test, so that the line numbers in the reported
+/// diagnostics match the snapshot. Nightly features reported by
+/// [`File::required_features`] are enabled with `RUSTC_BOOTSTRAP`. The check
+/// is skipped if `rustc` cannot be run.
+///
+/// # Panics
+///
+/// Panics with the numbered code and the diagnostics of `rustc` if the code
+/// does not compile.
+pub fn assert_compiles(file: &File) -> String {
+    let code = pretty(file);
+    check(&code, file.required_features());
+    code
+}
+
+/// Like [`assert_compiles`], for an item that is checked after the items in
+/// `context`, such as the types and macros it refers to.
+///
+/// Returns the printed item alone.
+pub fn assert_item_compiles(item: impl Into<Item>, context: &str) -> String {
+    let item = item.into();
+    let file = file().item(verbatim(context)).item(item.clone()).build();
+    check(&pretty(&file), file.required_features());
+    pretty(&item)
+}
+
+/// Like [`assert_compiles`], for an expression that is checked in place of
+/// the `$0` in `context`, a list of items such as a function whose body
+/// declares the names the expression refers to.
+///
+/// Returns the printed expression alone.
+pub fn assert_expr_compiles(expr: &Expr, context: &str) -> String {
+    let printed = pretty(expr);
+    let features = file()
+        .item(fn_def("check").statement(expr.clone()))
+        .build()
+        .required_features();
+    check(&context.replace("$0", &printed), features);
+    printed
+}
+
+/// Checks that `rustc` accepts `code`, with the given nightly `features`.
+fn check(code: &str, features: BTreeSet<&str>) {
+    let test = thread::current()
+        .name()
+        .unwrap_or("main")
+        .replace("::", "__");
+    let name = format!(
+        "{}__{}",
+        env!("CARGO_CRATE_NAME"),
+        test.strip_prefix("test_").unwrap_or(&test)
+    );
+    let dir = env::temp_dir().join(format!("rasto-{}-{name}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join(format!("{name}.rs")), code).unwrap();
+
+    let result = rustc(&dir, &name, features);
+    fs::remove_dir_all(&dir).ok();
+    match result {
+        Ok(None) => {}
+        Ok(Some(diagnostics)) => {
+            let listing: String = code
+                .lines()
+                .enumerate()
+                .map(|(i, line)| format!("{:>4} | {line}\n", i + 1))
+                .collect();
+            panic!(
+                "the printed code of `tests/snapshots/{name}.snap` does not compile:\n\n\
+                 {listing}\n{diagnostics}"
+            );
+        }
+        Err(err) => eprintln!("skipping compile check of `{name}`: cannot run rustc: {err}"),
+    }
+}
+
+// Returns the diagnostics of `rustc` if the code does not compile.
+fn rustc(dir: &Path, name: &str, features: BTreeSet<&str>) -> std::io::Result<Option<String>> {
+    let mut cmd = Command::new(env::var_os("RUSTC").unwrap_or_else(|| "rustc".into()));
+    cmd.current_dir(dir)
+        .args(["--emit=metadata", "--edition=2024", "--crate-type=lib"])
+        .args(["--crate-name=compile_check", "--color=never", "-Awarnings"])
+        .arg(format!("{name}.rs"));
+    if !features.is_empty() {
+        cmd.env("RUSTC_BOOTSTRAP", "1");
+        for feature in features {
+            cmd.arg(format!("-Zcrate-attr=feature({feature})"));
+        }
+    }
+    let output = cmd.output()?;
+    Ok((!output.status.success()).then(|| String::from_utf8_lossy(&output.stderr).into_owned()))
+}
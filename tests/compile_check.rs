@@ -0,0 +1,279 @@
+mod common;
+
+use common::assert_compiles;
+use rasto::ast::*;
+use rasto::builder::*;
+
+#[test]
+fn test_struct_with_impl() {
+    let ast = file()
+        .item(
+            struct_def("Point")
+                .vis(Visibility::Public)
+                .field("x", "i32")
+                .field("y", "i32")
+                .build(),
+        )
+        .item(
+            impl_block("Point")
+                .item(
+                    fn_def("new")
+                        .vis(Visibility::Public)
+                        .input_typed("x", "i32")
+                        .input_typed("y", "i32")
+                        .output("Self")
+                        .statement(expr().struct_expr(
+                            "Self",
+                            [
+                                field_value("x", expr().path("x")),
+                                field_value("y", expr().path("y")),
+                            ],
+                        ))
+                        .has_trailing_semicolon(false)
+                        .build(),
+                )
+                .item(
+                    fn_def("sum")
+                        .input(pat().ident("self"))
+                        .output("i32")
+                        .statement(expr().binary(
+                            expr().field(expr().path("self"), "x"),
+                            BinOp::Add,
+                            expr().field(expr().path("self"), "y"),
+                        ))
+                        .has_trailing_semicolon(false)
+                        .build(),
+                )
+                .build(),
+        )
+        .build();
+
+    insta::assert_snapshot!(assert_compiles(&ast));
+}
+
+#[test]
+fn test_enum_with_match() {
+    let ast = file()
+        .item(
+            enum_def("Color")
+                .variant("Red")
+                .variant("Green")
+                .variant("Blue")
+                .build(),
+        )
+        .item(
+            fn_def("name")
+                .input_typed("color", "Color")
+                .output(type_().reference(false, "str").lifetime("static"))
+                .statement(
+                    expr().match_expr(
+                        expr().path("color"),
+                        [
+                            expr()
+                                .arm(pat().path(path("Color").segment("Red")))
+                                .body(expr().lit("red"))
+                                .build(),
+                            expr()
+                                .arm(pat().or([
+                                    pat().path(path("Color").segment("Green")),
+                                    pat().path(path("Color").segment("Blue")),
+                                ]))
+                                .body(expr().lit("other"))
+                                .build(),
+                        ],
+                    ),
+                )
+                .has_trailing_semicolon(false)
+                .build(),
+        )
+        .build();
+
+    insta::assert_snapshot!(assert_compiles(&ast));
+}
+
+#[test]
+fn test_trait_with_generic_impl() {
+    let ast = file()
+        .item(
+            trait_def("Describe")
+                .item(trait_item_fn("describe").input(pat().ident("self")))
+                .build(),
+        )
+        .item(
+            impl_block("T")
+                .generic(generic_param().ty("T"))
+                .trait_("Describe")
+                .item(fn_def("describe").input(pat().ident("self")).build())
+                .build(),
+        )
+        .item(
+            fn_def("describe_all")
+                .generic(generic_param().ty("T"))
+                .input_typed("items", type_().reference(false, type_().slice("T")))
+                .where_clause(WhereClause {
+                    predicates: vec![WherePredicate::Type(TypePredicate {
+                        ty: "T".into(),
+                        bounds: vec!["Describe".into()],
+                    })],
+                })
+                .statement(expr().for_loop(
+                    pat().ident("item"),
+                    expr().path("items"),
+                    vec![expr().method_call(expr().path("item"), "describe", [])],
+                ))
+                .has_trailing_semicolon(false)
+                .build(),
+        )
+        .build();
+
+    insta::assert_snapshot!(assert_compiles(&ast));
+}
+
+#[test]
+fn test_literals() {
+    let ast = file()
+        .item(
+            fn_def("literals")
+                .statement(
+                    stmt()
+                        .local("s")
+                        .expr(expr().lit("quote \" backslash \\ newline \n")),
+                )
+                .statement(stmt().local("c").expr(expr().lit('\'')))
+                .statement(stmt().local("b").expr(expr().lit(b'\\')))
+                .statement(
+                    stmt()
+                        .local("i")
+                        .expr(expr().int_lit_with_suffix(-42, IntSuffix::I64)),
+                )
+                .statement(stmt().local("u").expr(expr().lit(u128::MAX)))
+                .statement(stmt().local("f").expr(expr().lit(1.5)))
+                .statement(stmt().local("t").expr(expr().lit(true)))
+                .build(),
+        )
+        .build();
+
+    insta::assert_snapshot!(assert_compiles(&ast));
+}
+
+#[test]
+fn test_turbofish_and_closures() {
+    let new_vec = expr().call(
+        expr().path(
+            path("Vec")
+                .generic(GenericArg::Type("u8".into()))
+                .segment("new"),
+        ),
+        [],
+    );
+    let ast = file()
+        .item(
+            fn_def("doubled")
+                .output(
+                    path("Vec")
+                        .generic(GenericArg::Type("u8".into()))
+                        .build_type(),
+                )
+                .statement(stmt().local(pat().mutable().ident("v")).expr(new_vec))
+                .statement(expr().method_call(expr().path("v"), "push", [expr().lit(1)]))
+                .statement(expr().method_call(
+                    expr().method_call(
+                        expr().method_call(
+                            expr().method_call(expr().path("v"), "into_iter", []),
+                            "map",
+                            [expr().closure(
+                                [pat().ident("x")],
+                                expr().binary(expr().path("x"), BinOp::Mul, expr().lit(2)),
+                            )],
+                        ),
+                        "filter",
+                        [expr().closure(
+                            [pat().ident("x")],
+                            expr().binary(
+                                expr().path("x"),
+                                BinOp::Gt,
+                                expr().reference(false, expr().lit(0)),
+                            ),
+                        )],
+                    ),
+                    "collect",
+                    [],
+                ))
+                .has_trailing_semicolon(false)
+                .build(),
+        )
+        .build();
+
+    insta::assert_snapshot!(assert_compiles(&ast));
+}
+
+#[test]
+fn test_control_flow() {
+    let ast = file()
+        .item(
+            fn_def("count")
+                .input_typed("limit", "u32")
+                .output("u32")
+                .statement(
+                    stmt()
+                        .local(pat().mutable().ident("total"))
+                        .expr(expr().lit(0)),
+                )
+                .statement(expr().for_loop(
+                    pat().ident("i"),
+                    expr().range(
+                        Some(expr().lit(0)),
+                        RangeLimits::HalfOpen,
+                        Some(expr().path("limit")),
+                    ),
+                    vec![expr().if_expr(
+                        expr().binary(expr().path("i"), BinOp::Eq, expr().lit(3)),
+                        vec![expr().continue_expr()],
+                        None,
+                    )],
+                ))
+                .statement(expr().assign(
+                    expr().path("total"),
+                    expr().binary(expr().path("total"), BinOp::Add, expr().lit(1)),
+                ))
+                .statement(expr().path("total"))
+                .has_trailing_semicolon(false)
+                .build(),
+        )
+        .build();
+
+    insta::assert_snapshot!(assert_compiles(&ast));
+}
+
+#[test]
+fn test_nightly_features() {
+    let ast = file()
+        .item(
+            fn_def("checked")
+                .output(
+                    path("Option")
+                        .generic(GenericArg::Type("u8".into()))
+                        .build_type(),
+                )
+                .statement(
+                    stmt()
+                        .local("value")
+                        .ty(path("Option")
+                            .generic(GenericArg::Type("u8".into()))
+                            .build_type())
+                        .expr(
+                            expr().try_block(
+                                block()
+                                    .statement(expr().lit(1))
+                                    .has_trailing_semicolon(false),
+                            ),
+                        ),
+                )
+                .statement(expr().path("value"))
+                .has_trailing_semicolon(false)
+                .build(),
+        )
+        .build();
+
+    insta::assert_snapshot!(assert_compiles(&ast));
+}
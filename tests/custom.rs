@@ -0,0 +1,135 @@
+use rasto::ast::items::Item;
+use rasto::ast::{BinOp, Custom, CustomNode, Expr, Precedence};
+use rasto::builder::*;
+use rasto::pretty;
+use rasto::pretty_printer::{BreakStyle, PrettyPrinter, Printer, PrinterConfig, pretty_with};
+use std::fmt;
+
+/// A `query! { ... }` block, with one clause per line if it does not fit.
+#[derive(Debug, Clone, PartialEq)]
+struct Query {
+    clauses: Vec<String>,
+}
+
+impl PrettyPrinter for Query {
+    fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
+        printer.string("query! ");
+        printer.begin(BreakStyle::Consistent, "{");
+        for clause in &self.clauses {
+            printer.break_();
+            printer.string(clause.as_str());
+        }
+        printer.if_broken("", " ");
+        printer.end("}");
+        Ok(())
+    }
+}
+
+impl CustomNode for Query {}
+
+/// An `a ~ b` operator, which binds like a comparison.
+#[derive(Debug, Clone, PartialEq)]
+struct Similar(String, String);
+
+impl PrettyPrinter for Similar {
+    fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> fmt::Result {
+        printer.string(format!("{} ~ {}", self.0, self.1));
+        Ok(())
+    }
+}
+
+impl CustomNode for Similar {
+    fn precedence(&self) -> Precedence {
+        Precedence::Compare
+    }
+}
+
+fn query(clauses: &[&str]) -> Query {
+    Query {
+        clauses: clauses.iter().map(|clause| clause.to_string()).collect(),
+    }
+}
+
+#[test]
+fn test_custom_expr_layout() {
+    let short = query(&["from users", "select name"]);
+    let long = query(&[
+        "from users",
+        "where age > 18 and country = 'NL'",
+        "order by last_name, first_name",
+        "select id, name",
+    ]);
+    let item = fn_def("f")
+        .statement(stmt().local(pat().ident("a")).expr(expr().custom(short)))
+        .statement(stmt().local(pat().ident("b")).expr(expr().custom(long)))
+        .build();
+    insta::assert_snapshot!(pretty(&item), @r"
+    fn f() {
+        let a = query! { from users select name };
+        let b = query! {
+            from users
+            where age > 18 and country = 'NL'
+            order by last_name, first_name
+            select id, name
+        };
+    }
+    ");
+
+    let config = PrinterConfig {
+        single_line: true,
+        ..PrinterConfig::default()
+    };
+    let compact = pretty_with(&item, &config);
+    assert!(compact.contains("let b = query! { from users where age"));
+}
+
+#[test]
+fn test_custom_expr_precedence() {
+    let similar = || expr().custom(Similar("a".into(), "b".into()));
+    let and = expr().binary(similar(), BinOp::And, expr().path("c"));
+    assert_eq!(and.to_string(), "a ~ b && c");
+    let add = expr().binary(similar(), BinOp::Add, expr().path("c"));
+    assert_eq!(add.to_string(), "(a ~ b) + c");
+    let call = expr().method_call(similar(), "f", []);
+    assert_eq!(call.to_string(), "(a ~ b).f()");
+}
+
+#[test]
+fn test_custom_item() {
+    let ast = file()
+        .item(custom(query(&["from users"])).comment(comment().doc(" Users.")))
+        .item(fn_def("f").build())
+        .build();
+    insta::assert_snapshot!(pretty(&ast), @r"
+    /// Users.
+    query! { from users }
+    fn f() {}
+    ");
+}
+
+#[test]
+fn test_custom_clone_eq_downcast() {
+    let mut item: Item = custom(query(&["from users"])).into();
+    assert_eq!(item.clone(), item);
+    assert_ne!(item, custom(query(&["from orders"])).into());
+    assert_ne!(
+        Expr::from(custom(query(&["a ~ b"]))),
+        expr().custom(Similar("a".into(), "b".into()))
+    );
+
+    let Item::Custom(node) = &mut item else {
+        unreachable!("the item is a custom node");
+    };
+    assert!(node.downcast_ref::<Similar>().is_none());
+    node.downcast_mut::<Query>()
+        .unwrap()
+        .clauses
+        .push("select name".into());
+    assert_eq!(item.to_string(), "query! { from users select name }");
+
+    let custom: Custom = custom(query(&[]));
+    assert_eq!(
+        format!("{custom:?}"),
+        "Custom { node: Query { clauses: [] }, md: None }"
+    );
+}
@@ -0,0 +1,142 @@
+use rasto::ast::*;
+use rasto::builder::*;
+use rasto::pretty_printer::PrinterConfig;
+use rasto::visit::{self, Visit};
+use rasto::{pretty, pretty_with};
+
+const DEPTH: usize = 50_000;
+
+/// Builds `a0 op a1 op a2 ...`, nested to the left.
+fn binary_chain(terms: usize, op: impl Fn(usize) -> BinOp) -> Expr {
+    let mut chain = expr().path("a0");
+    for i in 1..terms {
+        chain = expr().binary(chain, op(i), expr().path(format!("a{i}").as_str()));
+    }
+    chain
+}
+
+/// Builds `if c0 { 1 } else if c1 { 1 } ... else { 0 }`.
+fn else_if_chain(depth: usize) -> Expr {
+    let mut chain = expr().block([expr().lit(0)]);
+    for i in (0..depth).rev() {
+        chain = expr().if_expr(
+            expr().path(format!("c{i}").as_str()),
+            [expr().lit(1)],
+            Some(chain),
+        );
+    }
+    chain
+}
+
+/// Builds `{ { ... { 0 } ... } }`.
+fn nested_blocks(depth: usize) -> Expr {
+    let mut block = expr().lit(0);
+    for _ in 0..depth {
+        block = expr().block([block]);
+    }
+    block
+}
+
+#[test]
+fn test_long_binary_chain() {
+    let chain = binary_chain(DEPTH, |_| BinOp::Add);
+    let output = pretty(&chain);
+    assert!(output.starts_with("a0\n    + a1\n    + a2\n"));
+    assert_eq!(output.lines().count(), DEPTH);
+    let clone = chain.clone();
+    assert_eq!(clone, chain);
+    assert_eq!(pretty(&clone), output);
+}
+
+#[test]
+fn test_long_mixed_binary_chain() {
+    let chain = binary_chain(DEPTH, |i| match i % 3 {
+        0 => BinOp::Add,
+        1 => BinOp::Sub,
+        _ => BinOp::Mul,
+    });
+    let config = PrinterConfig {
+        max_width: 1 << 30,
+        ..Default::default()
+    };
+    let output = pretty_with(&chain, &config);
+    assert!(output.ends_with(") * a49997 + a49998 - a49999"));
+    // Every multiplication has a sum or difference on the left.
+    assert_eq!(output.matches('(').count(), DEPTH / 3);
+    let clone = chain.clone();
+    assert_eq!(clone, chain);
+    assert_eq!(pretty_with(&clone, &config), output);
+}
+
+#[test]
+fn test_long_else_if_chain() {
+    let chain = else_if_chain(DEPTH);
+    let output = pretty(&chain);
+    assert!(output.starts_with("if c0 {\n    1;\n} else if c1 {\n    1;\n} else if c2 {"));
+    assert!(output.ends_with("} else {\n    0;\n}"));
+    let clone = chain.clone();
+    assert_eq!(clone, chain);
+    assert_eq!(pretty(&clone), output);
+}
+
+#[test]
+fn test_deeply_nested_blocks() {
+    let blocks = nested_blocks(DEPTH);
+    // Without indentation, the output grows linearly with the depth.
+    let config = PrinterConfig {
+        tab_spaces: 0,
+        ..Default::default()
+    };
+    let output = pretty_with(&blocks, &config);
+    let expected = format!("{}0;\n{}}}", "{\n".repeat(DEPTH), "};\n".repeat(DEPTH - 1));
+    assert_eq!(output, expected);
+    let clone = blocks.clone();
+    assert_eq!(clone, blocks);
+    assert_eq!(pretty_with(&clone, &config), output);
+}
+
+#[test]
+fn test_drop_deeply_nested_mixed_tree() {
+    let mut tree = expr().lit(0);
+    for i in 0..DEPTH {
+        tree = match i % 3 {
+            0 => expr().binary(tree, BinOp::Add, expr().lit(1)),
+            1 => expr().if_expr(expr().path("c"), [tree], None),
+            _ => expr().block([tree]),
+        };
+    }
+    // The tree is dropped without recursing through its levels.
+}
+
+#[test]
+fn test_drop_long_binary_chain_implicitly() {
+    {
+        let _chain = binary_chain(DEPTH, |_| BinOp::Add);
+    }
+    // The operands stay deep after the chain is destructured.
+    let Expr::Binary(ExprBinary { left, right, .. }) = binary_chain(DEPTH, |_| BinOp::Add) else {
+        unreachable!();
+    };
+    assert_eq!(*right, expr().path("a49999"));
+    assert!(matches!(*left, Expr::Binary(_)));
+}
+
+#[test]
+fn test_visit_long_binary_chain() {
+    /// Counts the paths in an expression.
+    struct PathCounter(usize);
+
+    impl<'ast> Visit<'ast> for PathCounter {
+        fn visit_expr(&mut self, node: &'ast Expr) {
+            if let Expr::Path(_) = node {
+                self.0 += 1;
+            }
+            visit::walk_expr(self, node);
+        }
+    }
+
+    let chain = binary_chain(DEPTH, |_| BinOp::Add);
+    let mut counter = PathCounter(0);
+    counter.visit_expr(&chain);
+    assert_eq!(counter.0, DEPTH);
+}
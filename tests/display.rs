@@ -0,0 +1,83 @@
+use rasto::ast::{Attribute, BinOp, Delimiter, GenericArg, RangeLimits, Stmt, StmtExpr};
+#[cfg(feature = "parser")]
+use rasto::ast::{Expr, GenericArgs, Pat, Path, Type};
+use rasto::builder::*;
+#[cfg(feature = "parser")]
+use rasto::parser::parse_file;
+
+#[test]
+fn test_display_nodes() {
+    let ty = path("Vec")
+        .generic(GenericArg::Type(path("Option").generic("u8").build_type()))
+        .build_type();
+    assert_eq!(ty.to_string(), "Vec<Option<u8>>");
+    let path = path("std").segment("fmt").segment("Display").build();
+    assert_eq!(path.to_string(), "std::fmt::Display");
+
+    let sum = expr().binary(expr().path("a"), BinOp::Add, expr().lit(1));
+    assert_eq!(sum.to_string(), "a + 1");
+    let stmt: Stmt = stmt().local(pat().ident("x")).expr(sum).into();
+    assert_eq!(stmt.to_string(), "let x = a + 1;");
+    let attr: Attribute = attr().meta(meta().list("derive", ["Debug"])).into();
+    assert_eq!(attr.to_string(), "#[derive(Debug)]");
+
+    let message = format!("expected `{ty}`, found `{}`", BinOp::Add);
+    assert_eq!(message, "expected `Vec<Option<u8>>`, found `+`");
+}
+
+#[test]
+fn test_display_alternate() {
+    let args = (0..30).map(|i| expr().path(format!("argument_{i}").as_str()));
+    let call = expr().call(expr().path("f"), args);
+    assert!(call.to_string().contains('\n'));
+
+    let compact = format!("{call:#}");
+    assert!(!compact.contains('\n'));
+    assert!(compact.starts_with("f(argument_0, argument_1, "));
+    assert!(compact.ends_with(", argument_29)"));
+
+    let chain = expr().method_call(expr().path("a"), "b", []);
+    assert_eq!(format!("{chain:#}"), chain.to_string());
+}
+
+#[cfg(feature = "parser")]
+#[test]
+fn test_display_parsed_nodes() {
+    let ty: Type = "Vec<Option<u8>>".parse().unwrap();
+    assert_eq!(ty.to_string(), "Vec<Option<u8>>");
+    let path: Path = "std::fmt::Display".parse().unwrap();
+    assert_eq!(path.to_string(), "std::fmt::Display");
+    let args: GenericArgs = "<u8, 'a>".parse().unwrap();
+    assert_eq!(args.to_string(), "<u8, 'a>");
+    let some: Pat = "Some((a, _))".parse().unwrap();
+    assert_eq!(some.to_string(), "Some((a, _))");
+    let expr: Expr = "a.b().c().d()".parse().unwrap();
+    assert_eq!(format!("{expr:#}"), expr.to_string());
+}
+
+#[cfg(feature = "parser")]
+#[test]
+fn test_display_alternate_blocks() {
+    let file = parse_file("fn f() { if a { b } }").unwrap();
+    insta::assert_snapshot!(format!("{file:#}"), @"fn f() { if a { b } }");
+}
+
+#[test]
+fn test_display_parts() {
+    let stmt = StmtExpr::from(expr().call(expr().path("f"), []));
+    assert_eq!(stmt.to_string(), "f()");
+    let md = md()
+        .attr(attr().meta("inline"))
+        .comment(comment().line(" Leading."))
+        .eol_comment(" End of line.")
+        .trailing_comment(comment().line(" Trailing."))
+        .build();
+    insta::assert_snapshot!(md.to_string(), @r"
+    // Leading.
+    #[inline] // End of line.
+    // Trailing.
+    ");
+    insta::assert_snapshot!(format!("{md:#}"), @"/* Leading. */ #[inline] /* End of line. */ /* Trailing. */");
+    assert_eq!(Delimiter::Bracket.to_string(), "[]");
+    assert_eq!(RangeLimits::Closed.to_string(), "..=");
+}
@@ -1,3 +1,6 @@
+mod common;
+
+use common::assert_expr_compiles;
 use rasto::ast::{BinOp, Delimiter, RangeLimits, Spacing, TokenStream, UnOp};
 use rasto::builder::*;
 use rasto::pretty;
@@ -9,54 +12,123 @@ fn test_assign_expr() {
     insta::assert_snapshot!(pretty(&expr));
 }
 
+#[test]
+fn test_assign_expr_compiles() {
+    let expr = expr().assign(expr().path("a"), expr().path("b"));
+    insta::assert_snapshot!(assert_expr_compiles(
+        &expr,
+        "fn check(mut a: u8, b: u8) { $0; }"
+    ));
+}
+
 #[test]
 fn test_binary_expr() {
     let expr = expr().binary("a".into(), BinOp::Add, "b".into());
     insta::assert_snapshot!(pretty(&expr));
 }
 
+#[test]
+fn test_binary_expr_compiles() {
+    let expr = expr().binary(expr().path("a"), BinOp::Add, expr().path("b"));
+    insta::assert_snapshot!(assert_expr_compiles(
+        &expr,
+        "fn check(a: u8, b: u8) -> u8 { $0 }"
+    ));
+}
+
 #[test]
 fn test_block_expr() {
     let expr = expr().block(block().statement(expr().lit(42)));
     insta::assert_snapshot!(pretty(&expr));
 }
 
+#[test]
+fn test_block_expr_compiles() {
+    let expr = expr().block(block().statement(expr().lit(42)));
+    insta::assert_snapshot!(assert_expr_compiles(&expr, "fn check() { $0 }"));
+}
+
 #[test]
 fn test_array_expr() {
     let expr = expr().array(vec![expr().lit(1), expr().lit(2), expr().lit(3)]);
     insta::assert_snapshot!(pretty(&expr));
 }
 
+#[test]
+fn test_array_expr_compiles() {
+    let expr = expr().array(vec![expr().lit(1), expr().lit(2), expr().lit(3)]);
+    insta::assert_snapshot!(assert_expr_compiles(&expr, "fn check() -> [u8; 3] { $0 }"));
+}
+
 #[test]
 fn test_async_expr() {
     let expr = expr().async_block(block().statement(expr().lit(42)));
     insta::assert_snapshot!(pretty(&expr));
 }
 
+#[test]
+fn test_async_expr_compiles() {
+    let expr = expr().async_block(block().statement(expr().lit(42)));
+    insta::assert_snapshot!(assert_expr_compiles(&expr, "fn check() { let _ = $0; }"));
+}
+
 #[test]
 fn test_await_expr() {
     let expr = expr().await_expr("future".into());
     insta::assert_snapshot!(pretty(&expr));
 }
 
+#[test]
+fn test_await_expr_compiles() {
+    let expr = expr().await_expr(expr().path("future"));
+    insta::assert_snapshot!(assert_expr_compiles(
+        &expr,
+        "async fn check(future: std::future::Ready<u8>) -> u8 { $0 }"
+    ));
+}
+
 #[test]
 fn test_break_expr() {
     let expr = expr().break_expr();
     insta::assert_snapshot!(pretty(&expr));
 }
 
+#[test]
+fn test_break_expr_compiles() {
+    let expr = expr().break_expr();
+    insta::assert_snapshot!(assert_expr_compiles(&expr, "fn check() { loop { $0 } }"));
+}
+
 #[test]
 fn test_call_expr() {
     let expr = expr().call("my_func".into(), vec![expr().lit(1), "b".into()]);
     insta::assert_snapshot!(pretty(&expr));
 }
 
+#[test]
+fn test_call_expr_compiles() {
+    let expr = expr().call(
+        expr().path("my_func"),
+        vec![expr().lit(1), expr().path("b")],
+    );
+    insta::assert_snapshot!(assert_expr_compiles(
+        &expr,
+        "fn my_func(a: u8, b: u8) {}\nfn check(b: u8) { $0; }"
+    ));
+}
+
 #[test]
 fn test_paren_expr() {
     let expr = expr().paren(expr().binary(expr().lit(1), BinOp::Add, expr().lit(2)));
     insta::assert_snapshot!(pretty(&expr));
 }
 
+#[test]
+fn test_paren_expr_compiles() {
+    let expr = expr().paren(expr().binary(expr().lit(1), BinOp::Add, expr().lit(2)));
+    insta::assert_snapshot!(assert_expr_compiles(&expr, "fn check() -> u8 { $0 }"));
+}
+
 #[test]
 fn test_range_expr() {
     let expr = expr().range(
@@ -67,48 +139,115 @@ fn test_range_expr() {
     insta::assert_snapshot!(pretty(&expr));
 }
 
+#[test]
+fn test_range_expr_compiles() {
+    let expr = expr().range(
+        Some(expr().lit(0)),
+        RangeLimits::Closed,
+        Some(expr().lit(10)),
+    );
+    insta::assert_snapshot!(assert_expr_compiles(&expr, "fn check() { let _ = $0; }"));
+}
+
 #[test]
 fn test_ref_expr() {
     let expr = expr().reference(true, "x".into());
     insta::assert_snapshot!(pretty(&expr));
 }
 
+#[test]
+fn test_ref_expr_compiles() {
+    let expr = expr().reference(true, expr().path("x"));
+    insta::assert_snapshot!(assert_expr_compiles(
+        &expr,
+        "fn check(mut x: u8) { let _ = $0; }"
+    ));
+}
+
 #[test]
 fn test_raw_ref_expr() {
     let expr = expr().raw_ref("x".into()).mutable().build();
     insta::assert_snapshot!(pretty(&expr));
 }
 
+#[test]
+fn test_raw_ref_expr_compiles() {
+    let expr = expr().raw_ref(expr().path("x")).mutable().build();
+    insta::assert_snapshot!(assert_expr_compiles(
+        &expr,
+        "fn check(mut x: u8) { let _ = $0; }"
+    ));
+}
+
 #[test]
 fn test_return_expr() {
     let expr = expr().return_expr(Some(expr().lit(42)));
     insta::assert_snapshot!(pretty(&expr));
 }
 
+#[test]
+fn test_return_expr_compiles() {
+    let expr = expr().return_expr(Some(expr().lit(42)));
+    insta::assert_snapshot!(assert_expr_compiles(&expr, "fn check() -> u8 { $0 }"));
+}
+
 #[test]
 fn test_struct_expr() {
     let expr = expr().struct_expr("MyStruct", vec![field_value("my_field", expr().lit(42))]);
     insta::assert_snapshot!(pretty(&expr));
 }
 
+#[test]
+fn test_struct_expr_compiles() {
+    let expr = expr().struct_expr("MyStruct", vec![field_value("my_field", expr().lit(42))]);
+    insta::assert_snapshot!(assert_expr_compiles(
+        &expr,
+        "struct MyStruct { my_field: u8 }\nfn check() -> MyStruct { $0 }"
+    ));
+}
+
 #[test]
 fn test_try_expr() {
     let expr = expr().try_block(block().statement(expr().lit("may_fail?")));
     insta::assert_snapshot!(pretty(&expr));
 }
 
+#[test]
+fn test_try_expr_compiles() {
+    let expr = expr().try_block(block().statement(expr().lit("may_fail?")));
+    insta::assert_snapshot!(assert_expr_compiles(
+        &expr,
+        "fn check() { let _: Option<()> = $0; }"
+    ));
+}
+
 #[test]
 fn test_tuple_expr() {
     let expr = expr().tuple(vec![expr().lit(1), "a".into()]);
     insta::assert_snapshot!(pretty(&expr));
 }
 
+#[test]
+fn test_tuple_expr_compiles() {
+    let expr = expr().tuple(vec![expr().lit(1), expr().path("a")]);
+    insta::assert_snapshot!(assert_expr_compiles(
+        &expr,
+        "fn check(a: u8) { let _ = $0; }"
+    ));
+}
+
 #[test]
 fn test_unary_expr() {
     let expr = expr().unary(UnOp::Neg, expr().lit(1));
     insta::assert_snapshot!(pretty(&expr));
 }
 
+#[test]
+fn test_unary_expr_compiles() {
+    let expr = expr().unary(UnOp::Neg, expr().lit(1));
+    insta::assert_snapshot!(assert_expr_compiles(&expr, "fn check() -> i8 { $0 }"));
+}
+
 #[test]
 fn test_while_expr() {
     let expr = expr().while_loop(
@@ -118,12 +257,30 @@ fn test_while_expr() {
     insta::assert_snapshot!(pretty(&expr));
 }
 
+#[test]
+fn test_while_expr_compiles() {
+    let expr = expr().while_loop(
+        expr().path("cond"),
+        block().statement(expr().call(expr().path("do_something"), vec![])),
+    );
+    insta::assert_snapshot!(assert_expr_compiles(
+        &expr,
+        "fn do_something() {}\nfn check(cond: bool) { $0 }"
+    ));
+}
+
 #[test]
 fn test_cast_expr() {
     let expr = expr().cast("x".into(), "u32");
     insta::assert_snapshot!(pretty(&expr));
 }
 
+#[test]
+fn test_cast_expr_compiles() {
+    let expr = expr().cast(expr().path("x"), "u32");
+    insta::assert_snapshot!(assert_expr_compiles(&expr, "fn check(x: u8) -> u32 { $0 }"));
+}
+
 #[test]
 fn test_closure_expr() {
     let expr = expr().closure(
@@ -133,24 +290,57 @@ fn test_closure_expr() {
     insta::assert_snapshot!(pretty(&expr));
 }
 
+#[test]
+fn test_closure_expr_compiles() {
+    let expr = expr().closure(
+        vec![pat().ident("a"), pat().ident("b")],
+        expr().binary(expr().path("a"), BinOp::Add, expr().path("b")),
+    );
+    insta::assert_snapshot!(assert_expr_compiles(
+        &expr,
+        "fn check() -> impl Fn(u8, u8) -> u8 { $0 }"
+    ));
+}
+
 #[test]
 fn test_const_block_expr() {
     let expr = expr().const_block(block().statement(expr().lit(42)));
     insta::assert_snapshot!(pretty(&expr));
 }
 
+#[test]
+fn test_const_block_expr_compiles() {
+    let expr = expr().const_block(block().statement(expr().lit(42)));
+    insta::assert_snapshot!(assert_expr_compiles(&expr, "fn check() { let _ = $0; }"));
+}
+
 #[test]
 fn test_continue_expr() {
     let expr = expr().continue_expr();
     insta::assert_snapshot!(pretty(&expr));
 }
 
+#[test]
+fn test_continue_expr_compiles() {
+    let expr = expr().continue_expr();
+    insta::assert_snapshot!(assert_expr_compiles(&expr, "fn check() { loop { $0 } }"));
+}
+
 #[test]
 fn test_field_expr() {
     let expr = expr().field("my_struct".into(), "my_field");
     insta::assert_snapshot!(pretty(&expr));
 }
 
+#[test]
+fn test_field_expr_compiles() {
+    let expr = expr().field(expr().path("my_struct"), "my_field");
+    insta::assert_snapshot!(assert_expr_compiles(
+        &expr,
+        "struct MyStruct { my_field: u8 }\nfn check(my_struct: MyStruct) -> u8 { $0 }"
+    ));
+}
+
 #[test]
 fn test_for_expr() {
     let expr = expr().for_loop(
@@ -161,6 +351,19 @@ fn test_for_expr() {
     insta::assert_snapshot!(pretty(&expr));
 }
 
+#[test]
+fn test_for_expr_compiles() {
+    let expr = expr().for_loop(
+        pat().ident("i"),
+        expr().range(Some(expr().lit(0)), RangeLimits::HalfOpen, None),
+        block().statement(expr().call(expr().path("do_something"), vec![expr().path("i")])),
+    );
+    insta::assert_snapshot!(assert_expr_compiles(
+        &expr,
+        "fn do_something(i: u32) {}\nfn check() { $0 }"
+    ));
+}
+
 #[test]
 fn test_if_expr() {
     let expr = expr().if_expr(
@@ -171,18 +374,43 @@ fn test_if_expr() {
     insta::assert_snapshot!(pretty(&expr));
 }
 
+#[test]
+fn test_if_expr_compiles() {
+    let expr = expr().if_expr(
+        expr().path("cond"),
+        block().statement(expr().lit(1)),
+        Some(expr().block(block().statement(expr().lit(2)))),
+    );
+    insta::assert_snapshot!(assert_expr_compiles(&expr, "fn check(cond: bool) { $0 }"));
+}
+
 #[test]
 fn test_index_expr() {
     let expr = expr().index("my_array".into(), expr().lit(0));
     insta::assert_snapshot!(pretty(&expr));
 }
 
+#[test]
+fn test_index_expr_compiles() {
+    let expr = expr().index(expr().path("my_array"), expr().lit(0));
+    insta::assert_snapshot!(assert_expr_compiles(
+        &expr,
+        "fn check(my_array: [u8; 1]) -> u8 { $0 }"
+    ));
+}
+
 #[test]
 fn test_loop_expr() {
     let expr = expr().loop_expr(block().statement(expr().break_expr()));
     insta::assert_snapshot!(pretty(&expr));
 }
 
+#[test]
+fn test_loop_expr_compiles() {
+    let expr = expr().loop_expr(block().statement(expr().break_expr()));
+    insta::assert_snapshot!(assert_expr_compiles(&expr, "fn check() { $0 }"));
+}
+
 #[test]
 fn test_macro_call_expr() {
     let tokens = TokenStream {
@@ -196,6 +424,22 @@ fn test_macro_call_expr() {
     insta::assert_snapshot!(pretty(&expr));
 }
 
+#[test]
+fn test_macro_call_expr_compiles() {
+    let tokens = TokenStream {
+        tokens: thin_vec![
+            tt().ident("arg1"),
+            tt().punct(',', Spacing::Alone),
+            tt().ident("arg2"),
+        ],
+    };
+    let expr = expr().macro_call("my_macro", Delimiter::Parenthesis, tokens);
+    insta::assert_snapshot!(assert_expr_compiles(
+        &expr,
+        "macro_rules! my_macro { ($a:ident, $b:ident) => {}; }\nfn check() { $0; }"
+    ));
+}
+
 #[test]
 fn test_match_expr() {
     let expr = expr().match_expr(
@@ -205,12 +449,36 @@ fn test_match_expr() {
     insta::assert_snapshot!(pretty(&expr));
 }
 
+#[test]
+fn test_match_expr_compiles() {
+    let expr = expr().match_expr(
+        expr().path("x"),
+        vec![
+            expr().arm(pat().lit(1)).body(expr().lit("one")).build(),
+            expr().arm(pat().wild()).body(expr().lit("other")).build(),
+        ],
+    );
+    insta::assert_snapshot!(assert_expr_compiles(
+        &expr,
+        "fn check(x: u8) -> &'static str { $0 }"
+    ));
+}
+
 #[test]
 fn test_method_call_expr() {
     let expr = expr().method_call("my_obj".into(), "my_method", vec![expr().lit(1)]);
     insta::assert_snapshot!(pretty(&expr));
 }
 
+#[test]
+fn test_method_call_expr_compiles() {
+    let expr = expr().method_call(expr().path("my_obj"), "my_method", vec![expr().lit(1)]);
+    insta::assert_snapshot!(assert_expr_compiles(
+        &expr,
+        "struct MyObj;\nimpl MyObj {\n    fn my_method(&self, _: u8) {}\n}\nfn check(my_obj: MyObj) { $0; }"
+    ));
+}
+
 #[test]
 fn test_binary_expr_parentheses() {
     let inner_expr = expr().binary(expr().path("a"), BinOp::Add, expr().path("b"));
@@ -218,15 +486,80 @@ fn test_binary_expr_parentheses() {
     insta::assert_snapshot!(pretty(&outer_expr));
 }
 
+#[test]
+fn test_binary_expr_parentheses_compiles() {
+    let inner_expr = expr().binary(expr().path("a"), BinOp::Add, expr().path("b"));
+    let outer_expr = expr().binary(inner_expr, BinOp::Mul, expr().path("c"));
+    insta::assert_snapshot!(assert_expr_compiles(
+        &outer_expr,
+        "fn check(a: u8, b: u8, c: u8) -> u8 { $0 }"
+    ));
+}
+
 #[test]
 fn test_gen_expr() {
     let expr = expr().gen_block(block().statement(expr().lit(42)));
     insta::assert_snapshot!(pretty(&expr));
 }
 
+#[test]
+fn test_gen_expr_compiles() {
+    let expr = expr().gen_block(block().statement(expr().lit(42)));
+    insta::assert_snapshot!(assert_expr_compiles(
+        &expr,
+        "fn check() -> impl Iterator<Item = u8> { $0 }"
+    ));
+}
+
 #[test]
 fn test_nested_assign_expr() {
     let inner_expr = expr().assign("b".into(), "c".into());
     let outer_expr = expr().assign("a".into(), inner_expr);
     insta::assert_snapshot!(pretty(&outer_expr));
 }
+
+#[test]
+fn test_nested_assign_expr_compiles() {
+    let inner_expr = expr().assign(expr().path("b"), expr().path("c"));
+    let outer_expr = expr().assign(expr().path("a"), inner_expr);
+    insta::assert_snapshot!(assert_expr_compiles(
+        &outer_expr,
+        "fn check(mut a: (), mut b: u8, c: u8) { $0; }"
+    ));
+}
+
+#[test]
+fn test_yield_expr() {
+    let expr = expr().yield_expr(Some(expr().lit(42)));
+    insta::assert_snapshot!(pretty(&expr), @"yield 42");
+}
+
+#[test]
+fn test_yield_expr_compiles() {
+    let expr = expr().yield_expr(Some(expr().lit(42)));
+    insta::assert_snapshot!(assert_expr_compiles(&expr, "#![feature(stmt_expr_attributes)]\nfn check() { let _ = #[coroutine] || { $0; }; }"), @"yield 42");
+}
+
+#[test]
+fn test_yield_expr_without_value() {
+    let expr = expr().yield_expr(None);
+    insta::assert_snapshot!(pretty(&expr), @"yield");
+}
+
+#[test]
+fn test_yield_expr_without_value_compiles() {
+    let expr = expr().yield_expr(None);
+    insta::assert_snapshot!(assert_expr_compiles(&expr, "#![feature(stmt_expr_attributes)]\nfn check() { let _ = #[coroutine] || { $0; }; }"), @"yield");
+}
+
+#[test]
+fn test_become_expr() {
+    let expr = expr().become_expr(expr().call(expr().path("f"), [expr().path("x")]));
+    insta::assert_snapshot!(pretty(&expr), @"become f(x)");
+}
+
+#[test]
+fn test_become_expr_compiles() {
+    let expr = expr().become_expr(expr().call(expr().path("f"), [expr().path("x")]));
+    insta::assert_snapshot!(assert_expr_compiles(&expr, "fn f(x: u8) -> u8 { x }\nfn check(x: u8) -> u8 { $0 }"), @"become f(x)");
+}
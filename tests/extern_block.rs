@@ -8,10 +8,7 @@ fn test_extern_block() {
     let extern_block = extern_block_item()
         .unsafe_()
         .abi("C")
-        .item(ExternalItem::Static(
-            "FOO".into(),
-            type_().path(path("i32").build()).into(),
-        ))
+        .item(extern_static_item("FOO", type_().path(path("i32").build())))
         .item(ExternalItem::Fn(fn_def("foo").build()))
         .build();
 
@@ -59,3 +56,53 @@ fn test_extern_block_with_macro() {
     }
     "###);
 }
+
+#[test]
+fn test_extern_block_item_safety() {
+    let extern_block = extern_block_item()
+        .unsafe_()
+        .abi("C")
+        .item(
+            extern_static_item("ERRNO", "i32")
+                .vis(Visibility::Public)
+                .safe()
+                .mutable(),
+        )
+        .item(extern_static_item("environ", type_().ptr(false, "u8")).unsafe_())
+        .item(
+            fn_def("abs")
+                .vis(Visibility::Public)
+                .safe()
+                .input_typed("x", "i32")
+                .output("i32"),
+        )
+        .item(
+            fn_def("printf")
+                .unsafe_()
+                .attr(attr().meta("cold"))
+                .input_typed("fmt", type_().ptr(false, "u8"))
+                .variadic(true),
+        )
+        .build();
+
+    assert_snapshot!(extern_block.to_string(), @r###"
+    unsafe extern "C" {
+        pub safe static mut ERRNO: i32;
+        unsafe static environ: *const u8;
+        pub safe fn abs(x: i32) -> i32;
+        #[cold]
+        unsafe fn printf(fmt: *const u8, ...);
+    }
+    "###);
+}
+
+#[test]
+fn test_extern_block_with_implicit_abi() {
+    let extern_block = extern_block_item().item(fn_def("foo")).build();
+
+    assert_snapshot!(extern_block.to_string(), @r###"
+    extern {
+        fn foo();
+    }
+    "###);
+}
@@ -0,0 +1,75 @@
+use rasto::ast::FeatureGated;
+use rasto::builder::*;
+use rasto::pretty;
+
+#[test]
+fn test_gen_fn_needs_gen_blocks_only() {
+    let file = file()
+        .item(
+            fn_def("counter")
+                .gen_()
+                .output(path("u32").build_type())
+                .statement(expr().yield_expr(Some(expr().lit(1))))
+                .build(),
+        )
+        .build();
+    assert_eq!(
+        file.required_features().into_iter().collect::<Vec<_>>(),
+        ["gen_blocks"]
+    );
+}
+
+#[test]
+fn test_nested_gates_are_collected() {
+    let file = file()
+        .item(
+            fn_def("f")
+                .input(pat().ident("x"))
+                .statement(expr().if_expr(
+                    expr().path("x"),
+                    block().statement(expr().yield_expr(None)),
+                    Some(expr().block(block().statement(
+                        expr().become_expr(expr().call(expr().path("f"), [expr().path("x")])),
+                    ))),
+                ))
+                .statement(expr().try_block([expr().lit(1)]))
+                .build(),
+        )
+        .item(extern_type_item("Opaque").build())
+        .build();
+    assert_eq!(
+        file.required_features().into_iter().collect::<Vec<_>>(),
+        ["coroutines", "explicit_tail_calls", "extern_types", "try_blocks"]
+    );
+}
+
+#[test]
+fn test_node_annotations() {
+    let sig = fn_def("f").gen_().build().sig;
+    assert_eq!(sig.feature_gates(), ["gen_blocks"]);
+    let sig = fn_def("f").build().sig;
+    assert!(sig.feature_gates().is_empty());
+}
+
+#[test]
+fn test_feature_attrs() {
+    let mut file = file()
+        .item(
+            fn_def("numbers")
+                .statement(expr().gen_block(block().statement(expr().yield_expr(Some(
+                    expr().lit(1),
+                )))))
+                .build(),
+        )
+        .build();
+    let attrs = file.feature_attrs();
+    file.md.get_or_insert_default().attrs.extend(attrs);
+    insta::assert_snapshot!(pretty(&file), @r"
+    #![feature(gen_blocks)]
+    fn numbers() {
+        gen {
+            yield 1;
+        };
+    }
+    ");
+}
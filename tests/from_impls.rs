@@ -3,6 +3,7 @@ use rasto::ast::{
     *,
 };
 use rasto::builder::*;
+use rasto::pretty;
 use thin_vec::thin_vec;
 
 #[test]
@@ -87,7 +88,7 @@ fn test_item_from_use_item() {
 
 #[test]
 fn test_item_from_asm_item() {
-    let item: Item = asm_item(LitStr::new("nop")).build().into();
+    let item: Item = global_asm_item(LitStr::new("nop")).build().into();
     assert!(matches!(item, Item::Asm(_)));
 }
 
@@ -114,3 +115,25 @@ fn test_trait_item_from_associated_const() {
     let trait_item: TraitItem = associated_const("MY_CONST", "u8").build().into();
     assert!(matches!(trait_item, TraitItem::Const(_)));
 }
+
+#[test]
+fn test_fragments_from_str_print_unchanged() {
+    for ty in ["&str", "()", "Result<T, MyError>"] {
+        assert!(matches!(Type::from(ty), Type::Verbatim(_)));
+        assert_eq!(pretty(&Type::from(ty)), ty);
+    }
+    assert!(matches!(Type::from("std::fmt::Display"), Type::Path(_)));
+    assert_eq!(
+        pretty(&Type::from("std::fmt::Display")),
+        "std::fmt::Display"
+    );
+    assert!(matches!(Pat::from("(a, b)"), Pat::Verbatim(_)));
+    assert_eq!(pretty(&Pat::from("(a, b)")), "(a, b)");
+    assert_eq!(pretty(&Path::from("std::vec::Vec")), "std::vec::Vec");
+}
+
+#[test]
+#[should_panic(expected = "invalid path `Vec<u8>`: expected `::`-separated identifiers")]
+fn test_path_from_str_panics_on_generics() {
+    let _ = Path::from("Vec<u8>");
+}
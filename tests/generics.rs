@@ -1,5 +1,6 @@
 use rasto::ast::generics::{GenericArgs, GenericParam, generic_param};
 use rasto::ast::types::Type;
+use rasto::ast::{Lifetime, LifetimePredicate, WhereClause, WherePredicate};
 use rasto::{builder::*, pretty};
 
 #[test]
@@ -112,3 +113,66 @@ fn test_union_with_generics() {
     }
     ");
 }
+
+#[test]
+fn test_lifetime_param_with_bounds_and_attr() {
+    let ast = struct_def("Deserializer")
+        .generic(generic_param().lifetime("a"))
+        .generic(generic_param().lifetime("'de").with_bound("a"))
+        .generic(
+            generic_param()
+                .lifetime("b")
+                .with_bound("a")
+                .with_bound("de")
+                .attr(attr().meta("may_dangle")),
+        )
+        .field("input", type_().reference(false, "str").lifetime("de"))
+        .build();
+
+    insta::assert_snapshot!(pretty(&ast), @r"
+    struct Deserializer<'a, 'de: 'a, #[may_dangle] 'b: 'a + 'de> {
+        input: &'de str,
+    }
+    ");
+}
+
+#[test]
+fn test_lifetime_static_and_elided() {
+    assert_eq!(Lifetime::from("static"), Lifetime::Static);
+    assert_eq!(Lifetime::from("'_"), Lifetime::Elided);
+    assert_eq!(Lifetime::from("'a").name(), "a");
+
+    let ty = path("Cow")
+        .generic(Lifetime::Elided)
+        .generic("str")
+        .build_type();
+    assert_eq!(pretty(&ty), "Cow<'_, str>");
+
+    let ty = type_()
+        .reference(false, "str")
+        .lifetime(Lifetime::Static)
+        .build();
+    assert_eq!(pretty(&ty), "&'static str");
+}
+
+#[test]
+fn test_lifetime_where_predicate() {
+    let where_clause = WhereClause {
+        predicates: vec![WherePredicate::Lifetime(
+            LifetimePredicate::new("de").with_bound("a"),
+        )],
+    };
+    let ast = fn_def("parse")
+        .generic(generic_param().lifetime("a"))
+        .generic(generic_param().lifetime("de"))
+        .where_clause(where_clause)
+        .build();
+
+    insta::assert_snapshot!(pretty(&ast), @r"
+    fn parse<'a, 'de>()
+    where
+        'de: 'a,
+    {
+    }
+    ");
+}
@@ -0,0 +1,156 @@
+use rasto::builder::*;
+#[cfg(feature = "parser")]
+use rasto::parser::parse_file;
+use rasto::pretty_printer::{Ansi, PrinterConfig, pretty_highlighted};
+#[cfg(feature = "parser")]
+use rasto::pretty_printer::{Html, TokenKind, pretty_with};
+#[cfg(feature = "parser")]
+use std::fmt::Write;
+
+#[cfg(feature = "parser")]
+const SOURCE: &str = r#"
+/// A point.
+#[derive(Debug)]
+pub struct Point<'a, T: Clone> {
+    x: &'a T, // the x coordinate
+}
+
+impl<T: Clone> Point<'_, T> {
+    fn new(r#type: u32) -> Self {
+        println!("{} < 1", r#type);
+        if true { return Self::default(); }
+        0x1F_u8 as usize
+    }
+}
+"#;
+
+/// Lists each token of the highlighted output with its kind.
+#[cfg(feature = "parser")]
+fn tokens(source: &str, config: &PrinterConfig) -> String {
+    let file = parse_file(source).unwrap();
+    let mut tokens = vec![];
+    pretty_highlighted(
+        &file,
+        config,
+        |out: &mut dyn Write, kind: TokenKind, text: &str| {
+            tokens.push(format!("{} {text:?}", kind.as_str()));
+            out.write_str(text)
+        },
+    );
+    tokens.join("\n")
+}
+
+#[cfg(feature = "parser")]
+#[test]
+fn test_highlight_token_kinds() {
+    insta::assert_snapshot!(tokens(SOURCE, &PrinterConfig::default()), @r##"
+    attribute "#[derive(Debug)]"
+    comment "/// A point."
+    keyword "pub"
+    keyword "struct"
+    type "Point"
+    punct "<"
+    lifetime "'a"
+    punct ","
+    ident "T"
+    punct ":"
+    type "Clone"
+    punct ">"
+    punct "{"
+    ident "x"
+    punct ":"
+    punct "&"
+    lifetime "'a"
+    type "T"
+    punct ","
+    comment " // the x coordinate"
+    punct "}"
+    keyword "impl"
+    punct "<"
+    ident "T"
+    punct ":"
+    type "Clone"
+    punct ">"
+    type "Point"
+    punct "<"
+    lifetime "'_"
+    punct ","
+    type "T"
+    punct ">"
+    punct "{"
+    keyword "fn"
+    ident "new"
+    punct "("
+    ident "r#type"
+    punct ":"
+    type "u32"
+    punct ")"
+    punct "->"
+    keyword "Self"
+    punct "{"
+    macro "println!"
+    punct "("
+    literal "\"{} < 1\""
+    punct ","
+    ident "r#type"
+    punct ");"
+    keyword "if"
+    literal "true"
+    punct "{"
+    keyword "return"
+    keyword "Self"
+    punct "::"
+    ident "default"
+    punct "();"
+    punct "};"
+    literal "0x1fu8"
+    keyword "as"
+    type "usize"
+    punct "}"
+    punct "}"
+    "##);
+}
+
+#[cfg(feature = "parser")]
+#[test]
+fn test_highlight_html() {
+    let file = parse_file("fn f<'a>(s: &'a str) -> bool { s < \"&\" }").unwrap();
+    insta::assert_snapshot!(pretty_highlighted(&file, &PrinterConfig::default(), Html), @r#"
+    <span class="keyword">fn</span> <span class="ident">f</span><span class="punct">&lt;</span><span class="lifetime">'a</span><span class="punct">&gt;(</span><span class="ident">s</span><span class="punct">:</span> <span class="punct">&amp;</span><span class="lifetime">'a</span> <span class="type">str</span><span class="punct">)</span> <span class="punct">-&gt;</span> <span class="type">bool</span> <span class="punct">{</span>
+        <span class="ident">s</span> <span class="punct">&lt;</span> <span class="literal">&quot;&amp;&quot;</span>
+    <span class="punct">}</span>
+    "#);
+}
+
+#[test]
+fn test_highlight_ansi() {
+    let file = fn_def("one")
+        .attr(attr().meta("inline"))
+        .output("u8")
+        .statement(expr().lit(1))
+        .has_trailing_semicolon(false)
+        .build();
+    let output = pretty_highlighted(&file, &PrinterConfig::default(), Ansi);
+    insta::assert_snapshot!(output.replace('\x1b', "ESC"), @r#"
+    ESC[94m#[inline]ESC[0m
+    ESC[35mfnESC[0m one() -> ESC[33mu8ESC[0m {
+        ESC[32m1ESC[0m
+    }
+    "#);
+}
+
+#[cfg(feature = "parser")]
+#[test]
+fn test_highlight_keeps_output() {
+    let file = parse_file(SOURCE).unwrap();
+    let config = PrinterConfig {
+        max_width: 24,
+        ..Default::default()
+    };
+    let output = pretty_highlighted(
+        &file,
+        &config,
+        |out: &mut dyn Write, _: TokenKind, text: &str| out.write_str(text),
+    );
+    assert_eq!(output, pretty_with(&file, &config));
+}
@@ -1,7 +1,9 @@
 mod associated_const;
+mod common;
 mod macros;
 
-use rasto::ast::{AsmDirection, AsmOption, LitStr, RegSpec};
+use common::assert_item_compiles;
+use rasto::ast::{AsmOption, LitStr};
 use rasto::builder::*;
 use rasto::pretty;
 use thin_vec::thin_vec;
@@ -12,18 +14,36 @@ fn test_const_item() {
     insta::assert_snapshot!(pretty(&item));
 }
 
+#[test]
+fn test_const_item_compiles() {
+    let item = const_def("MAX", "u16", expr().lit(23434)).build();
+    insta::assert_snapshot!(assert_item_compiles(item, ""));
+}
+
 #[test]
 fn test_type_alias_item() {
     let item = type_alias("MyResult<T>", "Result<T, MyError>").build();
     insta::assert_snapshot!(pretty(&item));
 }
 
+#[test]
+fn test_type_alias_item_compiles() {
+    let item = type_alias("MyResult<T>", "Result<T, MyError>").build();
+    insta::assert_snapshot!(assert_item_compiles(item, "struct MyError;"));
+}
+
 #[test]
 fn test_extern_crate_item() {
     let item = extern_crate_item("serde").build();
     insta::assert_snapshot!(pretty(&item));
 }
 
+#[test]
+fn test_extern_crate_item_compiles() {
+    let item = extern_crate_item("alloc").build();
+    insta::assert_snapshot!(assert_item_compiles(item, ""));
+}
+
 #[test]
 fn test_foreign_mod_item() {
     let item = foreign_mod_item("C").item(fn_def("foo")).build();
@@ -39,6 +59,14 @@ fn test_macro_item() {
     insta::assert_snapshot!(pretty(&item));
 }
 
+#[test]
+fn test_macro_item_compiles() {
+    let item =
+        macro_item(expr().macro_call("my_macro", Delimiter::Parenthesis, thin_vec![])).build();
+    let context = "macro_rules! my_macro { () => {}; }";
+    insta::assert_snapshot!(assert_item_compiles(item, context));
+}
+
 #[test]
 fn test_empty_mod_item() {
     let item = empty_mod_item("empty_module").build();
@@ -51,12 +79,24 @@ fn test_mod_item() {
     insta::assert_snapshot!(pretty(&item));
 }
 
+#[test]
+fn test_mod_item_compiles() {
+    let item = mod_item("my_module").build();
+    insta::assert_snapshot!(assert_item_compiles(item, ""));
+}
+
 #[test]
 fn test_mod_item_with_content() {
     let item = mod_item("my_module").item(fn_def("foo")).build();
     insta::assert_snapshot!(pretty(&item));
 }
 
+#[test]
+fn test_mod_item_with_content_compiles() {
+    let item = mod_item("my_module").item(fn_def("foo")).build();
+    insta::assert_snapshot!(assert_item_compiles(item, ""));
+}
+
 #[test]
 fn test_trait_alias_item() {
     let item = trait_alias_item(
@@ -67,6 +107,16 @@ fn test_trait_alias_item() {
     insta::assert_snapshot!(pretty(&item));
 }
 
+#[test]
+fn test_trait_alias_item_compiles() {
+    let item = trait_alias_item(
+        "ShareableIterator",
+        thin_vec!["Iterator".to_string(), "Sync".to_string()],
+    )
+    .build();
+    insta::assert_snapshot!(assert_item_compiles(item, ""));
+}
+
 #[test]
 fn test_union_item() {
     let item = union_item("MyUnion")
@@ -76,30 +126,63 @@ fn test_union_item() {
     insta::assert_snapshot!(pretty(&item));
 }
 
+#[test]
+fn test_union_item_compiles() {
+    let item = union_item("MyUnion")
+        .field("f1", "u32")
+        .field("f2", "f32")
+        .build();
+    insta::assert_snapshot!(assert_item_compiles(item, ""));
+}
+
 #[test]
 fn test_static_item() {
     let item = static_item("FOO", "u32", expr().lit(42)).build();
     insta::assert_snapshot!(pretty(&item));
 }
 
+#[test]
+fn test_static_item_compiles() {
+    let item = static_item("FOO", "u32", expr().lit(42)).build();
+    insta::assert_snapshot!(assert_item_compiles(item, ""));
+}
+
 #[test]
 fn test_mutable_static_item() {
     let item = static_item("BAR", "u32", expr().lit(0)).mutable().build();
     insta::assert_snapshot!(pretty(&item));
 }
 
+#[test]
+fn test_mutable_static_item_compiles() {
+    let item = static_item("BAR", "u32", expr().lit(0)).mutable().build();
+    insta::assert_snapshot!(assert_item_compiles(item, ""));
+}
+
 #[test]
 fn test_trait_with_function() {
     let item = trait_def("MyTrait").item(trait_item_fn("my_func")).build();
     insta::assert_snapshot!(pretty(&item));
 }
 
+#[test]
+fn test_trait_with_function_compiles() {
+    let item = trait_def("MyTrait").item(trait_item_fn("my_func")).build();
+    insta::assert_snapshot!(assert_item_compiles(item, ""));
+}
+
 #[test]
 fn test_use_item() {
     let item = use_item("std::collections::HashMap").build();
     insta::assert_snapshot!(pretty(&item));
 }
 
+#[test]
+fn test_use_item_compiles() {
+    let item = use_item("std::collections::HashMap").build();
+    insta::assert_snapshot!(assert_item_compiles(item, ""));
+}
+
 #[test]
 fn test_trait_with_associated_type() {
     let item = trait_def("MyTrait")
@@ -108,6 +191,14 @@ fn test_trait_with_associated_type() {
     insta::assert_snapshot!(pretty(&item));
 }
 
+#[test]
+fn test_trait_with_associated_type_compiles() {
+    let item = trait_def("MyTrait")
+        .associated_type(associated_type("MyType"))
+        .build();
+    insta::assert_snapshot!(assert_item_compiles(item, ""));
+}
+
 #[test]
 fn test_impl_item() {
     let item = impl_block("MyType")
@@ -122,33 +213,61 @@ fn test_impl_item() {
     insta::assert_snapshot!(pretty(&item));
 }
 
+#[test]
+fn test_impl_item_compiles() {
+    let item = impl_block("MyType")
+        .trait_("MyTrait")
+        .item(fn_def("my_func").build())
+        .item(associated_type("MyType").default("u8").build())
+        .item(
+            associated_const("MY_CONST", "u8")
+                .expr(expr().lit(5))
+                .build(),
+        )
+        .build();
+    let context = "struct MyType;
+trait MyTrait {
+    fn my_func();
+    type MyType;
+    const MY_CONST: u8;
+}";
+    insta::assert_snapshot!(assert_item_compiles(item, context));
+}
+
 #[test]
 fn test_asm_item() {
     let template = r#""
-        mov {tmp}, {x}
-        shl {tmp}, 1
-        shl {x}, 2
-        add {x}, {tmp}
+        .globl {0}
+        {0}:
+        mov ${1}, %eax
+        ret
     ""#
     .parse::<LitStr>()
     .unwrap();
-    let item = asm_item(template)
-        .operand(asm_operand().reg(
-            AsmDirection::InOut,
-            RegSpec::Class("reg".to_string()),
-            expr().lit(42),
-        ))
-        .operand(asm_operand().reg(
-            AsmDirection::Out,
-            RegSpec::Class("reg".to_string()),
-            expr().lit(0),
-        ))
-        .options(
-            asm_options()
-                .option(AsmOption::Nomem)
-                .option(AsmOption::AttSyntax)
-                .build(),
-        )
+    let item = global_asm_item(template)
+        .operand(asm_operand().sym("answer"))
+        .operand(asm_operand().const_(expr().lit(42)))
+        .options(asm_options().option(AsmOption::AttSyntax).build())
         .build();
     insta::assert_snapshot!(pretty(&item));
 }
+
+#[test]
+fn test_asm_item_compiles() {
+    let template = r#""
+        .globl {0}
+        {0}:
+        mov ${1}, %eax
+        ret
+    ""#
+    .parse::<LitStr>()
+    .unwrap();
+    let item = global_asm_item(template)
+        .operand(asm_operand().sym("answer"))
+        .operand(asm_operand().const_(expr().lit(42)))
+        .options(asm_options().option(AsmOption::AttSyntax).build())
+        .build();
+    let context = "use std::arch::global_asm;
+extern \"C\" fn answer() {}";
+    insta::assert_snapshot!(assert_item_compiles(item, context));
+}
@@ -1,4 +1,5 @@
 use rasto::ast::*;
+use rasto::pretty;
 
 #[test]
 fn test_lit_from_string() {
@@ -55,7 +56,68 @@ fn test_lit_from_u8_slice() {
 }
 
 #[test]
+#[allow(clippy::byte_char_slices)]
 fn test_lit_from_u8_array() {
     let lit: Lit = (&[b'h', b'e', b'l', b'l', b'o'] as &[u8]).into();
     assert!(matches!(lit, Lit::ByteStr(_)));
 }
+
+#[test]
+fn test_lit_from_negative_i32() {
+    let lit: Lit = (-42i32).into();
+    assert_eq!(lit, Lit::Int(LitInt::signed(-42)));
+    assert_eq!(pretty(&lit), "-42");
+}
+
+#[test]
+fn test_lit_from_int_keeps_suffix() {
+    assert_eq!(pretty(&Lit::from(42u32)), "42u32");
+    assert_eq!(pretty(&Lit::from(-1i64)), "-1i64");
+    assert_eq!(
+        pretty(&Lit::from(i128::MIN)),
+        "-170141183460469231731687303715884105728i128"
+    );
+    assert_eq!(pretty(&Lit::from(7usize)), "7usize");
+    assert_eq!(pretty(&LitInt::from(255u8)), "255u8");
+}
+
+#[test]
+fn test_lit_int_radix_and_digit_group() {
+    let lit = LitInt::new(0xdead_beef).radix(IntRadix::Hex).digit_group(4);
+    assert_eq!(pretty(&lit), "0xdead_beef");
+
+    let lit = LitInt::new(1_000_000).digit_group(3).suffix(IntSuffix::U64);
+    assert_eq!(pretty(&lit), "1_000_000u64");
+
+    let lit = LitInt::signed(-8).radix(IntRadix::Octal);
+    assert_eq!(pretty(&lit), "-0o10");
+
+    let lit = LitInt::new(5).radix(IntRadix::Binary).digit_group(4);
+    assert_eq!(pretty(&lit), "0b101");
+}
+
+#[test]
+fn test_lit_int_to_i128() {
+    assert_eq!(LitInt::signed(-5).to_i128(), Some(-5));
+    assert_eq!(LitInt::signed(i128::MIN).to_i128(), Some(i128::MIN));
+    assert_eq!(LitInt::new(u128::MAX).to_i128(), None);
+}
+
+#[test]
+fn test_lit_from_f64_round_trips() {
+    assert_eq!(pretty(&Lit::from(1.0f64)), "1.0");
+    assert_eq!(pretty(&Lit::from(-0.5f64)), "-0.5");
+    assert_eq!(pretty(&Lit::from(1e-7f64)), "1e-7");
+    assert_eq!(pretty(&Lit::from(0.1f32)), "0.1f32");
+    for value in [0.1f64, 1.0 / 3.0, f64::MAX, f64::MIN_POSITIVE, 1e300] {
+        assert_eq!(pretty(&Lit::from(value)).parse::<f64>(), Ok(value));
+    }
+}
+
+#[test]
+fn test_lit_from_non_finite_float() {
+    assert_eq!(pretty(&Lit::from(f64::NAN)), "f64::NAN");
+    assert_eq!(pretty(&Lit::from(f64::INFINITY)), "f64::INFINITY");
+    assert_eq!(pretty(&Lit::from(f64::NEG_INFINITY)), "f64::NEG_INFINITY");
+    assert_eq!(pretty(&Lit::from(f32::NAN)), "f32::NAN");
+}
@@ -1,4 +1,8 @@
-use rasto::ast::{Attribute, Comment, Md, Meta};
+use rasto::ast::{
+    Attribute, ClosureParam, Comment, Field, Md, Meta, Stmt, Variant, generic_param,
+};
+use rasto::builder::*;
+use rasto::pretty;
 use thin_vec::thin_vec;
 
 #[test]
@@ -7,6 +11,8 @@ fn test_metadata_comments() {
         comments: thin_vec![Comment::Doc("A doc comment.".to_string())],
         attrs: thin_vec![],
         trailing_comments: thin_vec![],
+        eol_comment: None,
+        blank_line_before: false,
     };
     assert_eq!(md.comments.len(), 1);
     assert_eq!(md.comments[0], Comment::Doc("A doc comment.".to_string()));
@@ -18,6 +24,8 @@ fn test_metadata_attrs() {
         comments: thin_vec![],
         attrs: thin_vec![Attribute::Outer(Meta::Path("my_attr".into()))],
         trailing_comments: thin_vec![],
+        eol_comment: None,
+        blank_line_before: false,
     };
     assert_eq!(md.attrs.len(), 1);
     assert_eq!(md.attrs[0], Attribute::Outer(Meta::Path("my_attr".into())));
@@ -29,6 +37,8 @@ fn test_metadata_trailing_comments() {
         comments: thin_vec![],
         attrs: thin_vec![],
         trailing_comments: thin_vec![Comment::Line("A trailing comment.".to_string())],
+        eol_comment: None,
+        blank_line_before: false,
     };
     assert_eq!(md.trailing_comments.len(), 1);
     assert_eq!(
@@ -51,6 +61,210 @@ fn test_md_builder() {
             attrs: thin_vec![Attribute::Outer(Meta::Path("foo".into()))],
             comments: thin_vec![Comment::Line(" a comment".into())],
             trailing_comments: thin_vec![Comment::Line(" a trailing comment".into())],
+            eol_comment: None,
+            blank_line_before: false,
         }
     );
 }
+
+fn cfg_feature(name: &str) -> Attribute {
+    attr()
+        .meta(meta().list("cfg", [meta().name_value("feature", name)]))
+        .build()
+}
+
+#[test]
+fn test_statement_metadata() {
+    let item = fn_def("setup")
+        .statement(stmt().local("a").expr(expr().lit(1)).build())
+        .statement(
+            stmt()
+                .local("b")
+                .expr(expr().lit(2))
+                .comment(comment().line(" only with serde"))
+                .attr(cfg_feature("serde"))
+                .build(),
+        )
+        .statement(
+            stmt()
+                .expr(expr().call(expr().path("init"), []))
+                .attr(cfg_feature("log")),
+        )
+        .build();
+    insta::assert_snapshot!(pretty(&item), @r#"
+    fn setup() {
+        let a = 1;
+        // only with serde
+        #[cfg(feature = "serde")]
+        let b = 2;
+        #[cfg(feature = "log")]
+        init();
+    }
+    "#);
+}
+
+#[test]
+fn test_match_arm_metadata() {
+    let ast = expr().match_expr(
+        expr().path("x"),
+        [
+            expr().arm(pat().lit(1)).body(expr().lit("one")).build(),
+            expr()
+                .arm(pat().wild())
+                .comment(comment().line(" explain this arm"))
+                .attr(attr().meta(meta().list("allow", ["unreachable_patterns"])))
+                .body(expr().lit("other"))
+                .build(),
+        ],
+    );
+    insta::assert_snapshot!(pretty(&ast), @r#"
+    match x {
+        1 => "one",
+        // explain this arm
+        #[allow(unreachable_patterns)]
+        _ => "other",
+    }
+    "#);
+}
+
+#[test]
+fn test_field_value_metadata() {
+    let ast = expr().struct_expr(
+        "Config",
+        [
+            field_value("name", expr().lit("app")),
+            field_value("color", expr().lit(true)).attr(cfg_feature("color")),
+        ],
+    );
+    insta::assert_snapshot!(pretty(&ast), @r#"
+    Config {
+        name: "app",
+        #[cfg(feature = "color")]
+        color: true,
+    }
+    "#);
+}
+
+#[test]
+fn test_generic_param_metadata() {
+    let item = fn_def("f")
+        .generic(generic_param().lifetime("a").comment(comment().line(" input")))
+        .generic(generic_param().ty("T").attr(attr().meta("may_dangle")))
+        .generic(generic_param().const_("N", type_().path("usize")).attr(cfg_feature("n")))
+        .build();
+    insta::assert_snapshot!(
+        pretty(&item),
+        @r#"fn f</* input */ 'a, #[may_dangle] T, #[cfg(feature = "n")] const N: usize>() {}"#
+    );
+
+    let item = fn_def("g")
+        .generic(generic_param().ty("T").comment(comment().line(" ends */ early")))
+        .build();
+    assert_eq!(pretty(&item), "fn g</* ends * / early */ T>() {}");
+}
+
+#[test]
+fn test_closure_param_metadata() {
+    let ast = expr().closure(
+        [
+            ClosureParam::from(pat().ident("a")),
+            ClosureParam::from(pat().ident("b")).attr(attr().meta(meta().list("allow", ["unused"]))),
+        ],
+        expr().path("a"),
+    );
+    insta::assert_snapshot!(pretty(&ast), @"|a, #[allow(unused)] b| a");
+}
+
+#[test]
+fn test_eol_comment_struct_fields() {
+    let item = struct_def("Rect")
+        .field_with(Field::new("x", type_().path("u32")).eol_comment(" in pixels"))
+        .field("y", type_().path("u32"))
+        .field_with(
+            Field::new("color", type_().path("u32"))
+                .comment(comment().line(" packed RGB"))
+                .eol_comment(" 0xRRGGBB"),
+        )
+        .build();
+    insta::assert_snapshot!(pretty(&item), @r"
+    struct Rect {
+        x: u32, // in pixels
+        y: u32,
+        // packed RGB
+        color: u32, // 0xRRGGBB
+    }
+    ");
+}
+
+#[test]
+fn test_eol_comment_enum_variants() {
+    let item = enum_def("Color")
+        .variant_with(Variant::new("Red").eol_comment(" 0xff0000"))
+        .variant("Green")
+        .build();
+    insta::assert_snapshot!(pretty(&item), @r"
+    enum Color {
+        Red, // 0xff0000
+        Green,
+    }
+    ");
+}
+
+#[test]
+fn test_eol_comment_statements_and_arms() {
+    let item = fn_def("f")
+        .statement(stmt().local("a").expr(expr().lit(1)).eol_comment(" default").build())
+        .statement(Stmt::from(expr().call(expr().path("init"), [])).eol_comment(" once"))
+        .statement(expr().match_expr(
+            expr().path("a"),
+            [
+                expr().arm(pat().lit(1)).body(expr().lit(true)).eol_comment(" the only one").build(),
+                expr().arm(pat().wild()).body(expr().lit(false)).build(),
+            ],
+        ))
+        .build();
+    insta::assert_snapshot!(pretty(&item), @r"
+    fn f() {
+        let a = 1; // default
+        init(); // once
+        match a {
+            1 => true, // the only one
+            _ => false,
+        };
+    }
+    ");
+}
+
+#[test]
+fn test_eol_comment_breaks_struct_literal() {
+    let ast = expr().struct_expr(
+        "Point",
+        [
+            field_value("x", expr().lit(0)).eol_comment(" origin"),
+            field_value("y", expr().lit(0)),
+        ],
+    );
+    insta::assert_snapshot!(pretty(&ast), @r"
+    Point {
+        x: 0, // origin
+        y: 0,
+    }
+    ");
+}
+
+#[test]
+fn test_eol_comment_on_last_struct_literal_field() {
+    let ast = expr().struct_expr(
+        "Point",
+        [
+            field_value("x", expr().lit(0)),
+            field_value("y", expr().lit(0)).eol_comment(" origin"),
+        ],
+    );
+    insta::assert_snapshot!(pretty(&ast), @r"
+    Point {
+        x: 0,
+        y: 0, // origin
+    }
+    ");
+}
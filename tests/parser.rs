@@ -0,0 +1,380 @@
+#![cfg(feature = "parser")]
+
+mod common;
+
+use common::assert_compiles;
+use rasto::ast::items::Item;
+use rasto::ast::*;
+use rasto::builder::{pat, type_};
+use rasto::parser::{parse_expr, parse_file, parse_item, parse_pat, parse_type};
+use rasto::pretty;
+
+#[test]
+fn test_parse_items() {
+    let file = parse_file(
+        r#"
+use std::collections::{HashMap, HashSet as Set};
+extern crate core;
+pub(crate) const MAX: usize = 1 << 10;
+static mut COUNT: u32 = 0;
+type Map<'a, T> = HashMap<&'a str, Vec<T>>;
+pub struct Point<T: Copy> { x: T, y: T }
+union Bits { int: u32, float: f32 }
+enum Color { Red, Green, Blue }
+impl<T: Copy> Point<T> {
+    const ORIGIN: i32 = 0;
+    pub fn new(x: T, y: T) -> Self { Self { x, y } }
+}
+unsafe impl Send for Bits {}
+impl !Sync for Color {}
+trait Shape { type Output: Clone; const SIDES: u32; fn area(&self) -> f64; }
+trait Cloneable = Clone + Send;
+mod inner { pub async unsafe fn run(mut self: Box<Self>, _: [u8; 4]) -> ! { loop {} } }
+extern "C" { fn abs(x: i32) -> i32; static errno: i32; }
+my_macro! { a b c }
+"#,
+    )
+    .unwrap();
+
+    insta::assert_snapshot!(pretty(&file));
+}
+
+#[test]
+fn test_parse_expressions() {
+    let file = parse_file(
+        r#"
+fn run(v: &mut Vec<u8>, n: usize) -> usize {
+    let mut total = 0;
+    for i in 0..n {
+        if i == 3 { continue; } else if i > 10 { break; }
+        total += i;
+    }
+    let r = match n {
+        0 | 1 => 1,
+        x if x > 5 => { x * 2 }
+        _ => -1i32 as usize,
+    };
+    let c = |a: u8, b| a + b;
+    let t = (1, 2.5, "s", 'c', b'x', true);
+    let s = &v[1..=2];
+    let nested = t.0.1;
+    let value = Vec::<u8>::with_capacity(4).len();
+    let raw = &raw const total;
+    while total > 100 { total -= 1; }
+    total
+}
+"#,
+    )
+    .unwrap();
+
+    insta::assert_snapshot!(pretty(&file));
+}
+
+#[test]
+fn test_parse_comments() {
+    let file = parse_file(
+        r#"//! Crate docs.
+#![allow(dead_code)]
+
+/// A point.
+struct Point {
+    /// The x coordinate.
+    x: i32, // in pixels
+    y: i32,
+    // more fields later
+}
+
+fn main() {
+    // Leading.
+    let a = 1; // the answer
+    /* A block
+       comment. */
+    let b = match a {
+        // The only one.
+        1 => 2, // two
+        _ => 0,
+    };
+}
+// The end.
+"#,
+    )
+    .unwrap();
+
+    insta::assert_snapshot!(pretty(&file));
+}
+
+#[test]
+fn test_parse_comment_placement() {
+    let file = parse_file("fn f() {} // eol\n// trailing").unwrap();
+    let [Item::Fn(item)] = file.items.as_slice() else {
+        panic!("expected a single function");
+    };
+    let md = item.md.as_deref().unwrap();
+    assert_eq!(md.eol_comment.as_deref(), Some(" eol"));
+    assert_eq!(md.trailing_comments[0], Comment::Line(" trailing".into()));
+}
+
+#[test]
+fn test_parse_precedence() {
+    let expr = parse_expr("a + b * c == d && !e || f").unwrap();
+    let Expr::Binary(or) = &expr else {
+        panic!("expected a binary expression");
+    };
+    assert_eq!(or.op, BinOp::Or);
+    let Expr::Binary(and) = or.left.as_ref() else {
+        panic!("expected a binary expression");
+    };
+    assert_eq!(and.op, BinOp::And);
+
+    let expr = parse_expr("a = b = c").unwrap();
+    let Expr::Assign(assign) = &expr else {
+        panic!("expected an assignment");
+    };
+    assert!(matches!(assign.right.as_ref(), Expr::Assign(_)));
+
+    let expr = parse_expr("-x.y()?").unwrap_err();
+    assert_eq!(
+        expr.to_string(),
+        "1:7: `?` operators are not supported by the rasto AST"
+    );
+}
+
+#[test]
+fn test_parse_types() {
+    let types = [
+        "u8",
+        "std::vec::Vec<Option<&'a mut [T; 3]>>",
+        "(u8, (), (i32,))",
+        "*const fn(u8) -> !",
+        "for<'a> unsafe extern \"C\" fn(x: &'a u8, ...)",
+        "Array<u8, 4, -1, true>",
+    ];
+    let printed: Vec<_> = types
+        .iter()
+        .map(|src| pretty(&parse_type(src).unwrap()))
+        .collect();
+
+    insta::assert_snapshot!(printed.join("\n"), @r#"
+    u8
+    std::vec::Vec<Option<&'a mut [T; 3]>>
+    (u8, (), (i32,))
+    *const fn(u8) -> !
+    for<'a> unsafe extern "C" fn(x: &'a u8, ...)
+    Array<u8, 4, -1, true>
+    "#);
+}
+
+#[test]
+fn test_parse_patterns() {
+    let pats = [
+        "(a, mut b, _)",
+        "Some(Point { x: 0, y, .. })",
+        "[first, .., last]",
+        "&mut (1..=5 | 10..)",
+        "std::cmp::Ordering::Less",
+        "-1 | 'a'..='z'",
+        "const { N + 1 }",
+    ];
+    let printed: Vec<_> = pats
+        .iter()
+        .map(|src| pretty(&parse_pat(src).unwrap()))
+        .collect();
+
+    insta::assert_snapshot!(printed.join("\n"), @r"
+    (a, mut b, _)
+    Some(Point { x: 0, y, .. })
+    [first, .., last]
+    &mut (1..=5 | 10..)
+    std::cmp::Ordering::Less
+    -1 | 'a'..='z'
+    const {
+        N + 1
+    }
+    ");
+}
+
+#[test]
+fn test_parse_item() {
+    let item = parse_item("#[inline]\npub fn id<T>(x: T) -> T { x }").unwrap();
+
+    insta::assert_snapshot!(pretty(&item), @r"
+    #[inline]
+    pub fn id<T>(x: T) -> T {
+        x
+    }
+    ");
+}
+
+#[test]
+fn test_parse_errors() {
+    let errors = [
+        parse_file("fn main() {\n    let x = ;\n}").unwrap_err(),
+        parse_file("struct A { x: u8 y: u8 }").unwrap_err(),
+        parse_file("fn f() { \"unterminated }").unwrap_err(),
+        parse_file("fn f() {").unwrap_err(),
+        parse_expr("1 + 2 3").unwrap_err(),
+        parse_type("dyn Trait").unwrap_err(),
+        parse_pat("ref x").unwrap_err(),
+        parse_file("enum E { A(u8) }").unwrap_err(),
+        parse_file("fn f() { if let Some(x) = y {} }").unwrap_err(),
+    ];
+    let messages: Vec<_> = errors.iter().map(ToString::to_string).collect();
+
+    insta::assert_snapshot!(messages.join("\n"));
+}
+
+#[test]
+fn test_parse_error_location() {
+    let src = "fn main() {\n    let x = ;\n}";
+    let error = parse_file(src).unwrap_err();
+    assert_eq!(error.line, 2);
+    assert_eq!(error.column, 13);
+    assert_eq!(&src[error.span.start..error.span.end], ";");
+}
+
+#[test]
+fn test_parse_round_trip_compiles() {
+    let file = parse_file(
+        r#"
+use std::fmt;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Point {
+    x: i32,
+    y: i32,
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+pub fn manhattan(a: Point, b: Point) -> i32 {
+    let dx = (a.x - b.x).abs();
+    let dy = (a.y - b.y).abs();
+    dx + dy
+}
+"#,
+    )
+    .unwrap();
+
+    insta::assert_snapshot!(assert_compiles(&file));
+}
+
+#[test]
+fn test_from_str() {
+    let ty: Type = "HashMap<String, Vec<Option<u8>>>".parse().unwrap();
+    let path: Path = "std::vec::Vec::<u8>::new".parse().unwrap();
+    let expr: Expr = "a.len() + 1".parse().unwrap();
+    let pat: Pat = "Some((x, _))".parse().unwrap();
+    let args: GenericArgs = "<'a, T, 4>".parse().unwrap();
+    let printed = [
+        pretty(&ty),
+        pretty(&path),
+        pretty(&expr),
+        pretty(&pat),
+        pretty(&args),
+    ];
+
+    insta::assert_snapshot!(printed.join("\n"), @r"
+    HashMap<String, Vec<Option<u8>>>
+    std::vec::Vec<u8>::new
+    a.len() + 1
+    Some((x, _))
+    <'a, T, 4>
+    ");
+}
+
+#[test]
+fn test_from_str_errors() {
+    let errors = [
+        "Vec<u8".parse::<Type>().unwrap_err(),
+        "a::".parse::<Path>().unwrap_err(),
+        "1 +".parse::<Expr>().unwrap_err(),
+        "Some(".parse::<Pat>().unwrap_err(),
+        "u8, u16".parse::<GenericArgs>().unwrap_err(),
+    ];
+    let messages: Vec<_> = errors.iter().map(ToString::to_string).collect();
+
+    insta::assert_snapshot!(messages.join("\n"), @r"
+    1:7: expected `,` or `>`, found end of input
+    1:4: expected an identifier, found end of input
+    1:4: expected an expression, found end of input
+    1:6: expected a pattern, found end of input
+    1:1: expected `<`, found `u8`
+    ");
+}
+
+#[test]
+fn test_from_str_slice_keeps_text() {
+    assert_eq!(Type::from("Vec<u8>"), type_().verbatim("Vec<u8>"));
+    assert_ne!(Type::from("Vec<u8>"), "Vec<u8>".parse().unwrap());
+    assert_eq!(Type::from("Vec"), "Vec".parse().unwrap());
+    assert_eq!(Pat::from("(a, b)"), pat().verbatim("(a, b)"));
+    assert_eq!(Path::from("std::fmt"), "std::fmt".parse().unwrap());
+}
+
+#[test]
+#[should_panic(
+    expected = "invalid path `Vec<u8>`: expected `::`-separated identifiers; parse it with `str::parse`"
+)]
+fn test_from_str_slice_points_to_parse() {
+    let _ = Path::from("Vec<u8>");
+}
+
+#[test]
+fn test_parse_block_like_arm_bodies() {
+    let expr = parse_expr("match x { 1 => {} [a, ..] => {} _ => {}.len() }").unwrap();
+
+    insta::assert_snapshot!(pretty(&expr), @r"
+    match x {
+        1 => {},
+        [a, ..] => {},
+        _ => {}.len(),
+    }
+    ");
+}
+
+#[test]
+fn test_parse_round_trips() {
+    let exprs = [
+        "return ..x",
+        "return ..=10",
+        "a as u8 != b",
+        "x as T <<= 1",
+        r#"println!("{}", x)"#,
+        "m!(a += 1, 'a, x.y, #[attr] $x, vec![0; 3])",
+        "S {}",
+        "S::<u8> {}",
+    ];
+    for src in exprs {
+        assert_eq!(pretty(&parse_expr(src).unwrap()), src);
+    }
+    let pats = ["S { a, .. }", "S { a: b, c }", "S { a, mut b }", "S {}"];
+    for src in pats {
+        assert_eq!(pretty(&parse_pat(src).unwrap()), src);
+    }
+    let files = [
+        "//! Crate docs.\nfn f() {}",
+        "/// Docs.\nfn f() {}",
+        "fn f(s: S) {\n    let S { a, .. } = s;\n    S { a }\n}",
+    ];
+    for src in files {
+        assert_eq!(pretty(&parse_file(src).unwrap()), src);
+    }
+}
+
+#[test]
+fn test_parse_unsupported_operands() {
+    let errors = [
+        parse_expr("return #[attr] x").unwrap_err(),
+        parse_expr("break 'a").unwrap_err(),
+    ];
+    let messages: Vec<_> = errors.iter().map(ToString::to_string).collect();
+
+    insta::assert_snapshot!(messages.join("\n"), @r"
+    1:8: attributes on expressions are not supported by the rasto AST
+    1:7: labels are not supported by the rasto AST
+    ");
+}
@@ -44,7 +44,7 @@ use thin_vec::thin_vec;
 #[test]
 fn test_const_pattern() {
     let pat: Pat = pat().const_(expr().struct_expr("MY_CONST", []));
-    insta::assert_snapshot!(pretty(&pat), @"const MY_CONST");
+    insta::assert_snapshot!(pretty(&pat), @"const MY_CONST {}");
 }
 
 #[test]
@@ -0,0 +1,220 @@
+#[cfg(feature = "parser")]
+use rasto::ast::RemoveRedundantParens;
+use rasto::ast::{BinOp, Precedence, RangeLimits, UnOp};
+use rasto::builder::*;
+#[cfg(feature = "parser")]
+use rasto::parser::{parse_expr, parse_file};
+use rasto::pretty;
+#[cfg(feature = "parser")]
+use rasto::visit_mut::VisitMut;
+
+#[cfg(feature = "parser")]
+fn remove_parens(src: &str) -> String {
+    let mut file = parse_file(src).unwrap();
+    RemoveRedundantParens.visit_file_mut(&mut file);
+    pretty(&file)
+}
+
+#[test]
+fn test_expr_precedence() {
+    let (a, b) = (expr().path("a"), expr().path("b"));
+    assert_eq!(
+        expr().assign(a.clone(), b.clone()).precedence(),
+        Precedence::Assign
+    );
+    let range = expr().range(Some(a.clone()), RangeLimits::HalfOpen, Some(b.clone()));
+    assert_eq!(range.precedence(), Precedence::Range);
+    let or = expr().binary(a.clone(), BinOp::Or, b.clone());
+    assert_eq!(or.precedence(), Precedence::Or);
+    let lt = expr().binary(a.clone(), BinOp::Lt, b);
+    assert_eq!(lt.precedence(), Precedence::Compare);
+    assert_eq!(expr().cast(a.clone(), "u8").precedence(), Precedence::Cast);
+    let reference = expr().reference(false, a.clone());
+    assert_eq!(reference.precedence(), Precedence::Prefix);
+    let call = expr().method_call(a.clone(), "b", []);
+    assert_eq!(call.precedence(), Precedence::Postfix);
+    assert_eq!(expr().paren(a.clone()).precedence(), Precedence::Primary);
+    assert_eq!(
+        expr().closure([pat().ident("x")], a).precedence(),
+        Precedence::Jump
+    );
+    assert_eq!(expr().lit(-1).precedence(), Precedence::Prefix);
+}
+
+#[cfg(feature = "parser")]
+#[test]
+fn test_parsed_expr_precedence() {
+    assert_eq!(parse_expr("a..b").unwrap().precedence(), Precedence::Range);
+    assert_eq!(parse_expr("a || b").unwrap().precedence(), Precedence::Or);
+    assert_eq!(parse_expr("(a)").unwrap().precedence(), Precedence::Primary);
+    assert_eq!(parse_expr("|| a").unwrap().precedence(), Precedence::Jump);
+}
+
+#[test]
+fn test_prefix_operand_of_postfix() {
+    let field = expr().field(expr().reference(false, expr().path("a")), "b");
+    assert_eq!(pretty(&field), "(&a).b");
+    let reference = expr().reference(false, expr().field(expr().path("a"), "b"));
+    assert_eq!(pretty(&reference), "&a.b");
+
+    let receiver = expr().unary(UnOp::Neg, expr().path("x"));
+    let pow = expr().method_call(receiver, "pow", [expr().lit(2)]);
+    assert_eq!(pretty(&pow), "(-x).pow(2)");
+    let pow = expr().method_call(expr().path("x"), "pow", [expr().lit(2)]);
+    assert_eq!(pretty(&expr().unary(UnOp::Neg, pow)), "-x.pow(2)");
+
+    let pow = expr().method_call(expr().lit(-2), "pow", [expr().lit(2)]);
+    assert_eq!(pretty(&pow), "(-2).pow(2)");
+    let field = expr().field(expr().lit(1), "0");
+    assert_eq!(pretty(&field), "(1).0");
+}
+
+#[test]
+fn test_binary_operands() {
+    let cast = expr().cast(expr().path("a"), "u8");
+    let lt = expr().binary(cast.clone(), BinOp::Lt, expr().path("b"));
+    assert_eq!(pretty(&lt), "(a as u8) < b");
+    let gt = expr().binary(cast.clone(), BinOp::Gt, expr().path("b"));
+    assert_eq!(pretty(&gt), "a as u8 > b");
+    let shl = expr().binary(cast, BinOp::Shl, expr().path("b"));
+    assert_eq!(pretty(&shl), "(a as u8) << b");
+
+    let eq = expr().binary(expr().path("a"), BinOp::Eq, expr().path("b"));
+    let chain = expr().binary(eq.clone(), BinOp::Eq, expr().path("c"));
+    assert_eq!(pretty(&chain), "(a == b) == c");
+    let and = expr().binary(eq, BinOp::And, expr().path("c"));
+    assert_eq!(pretty(&and), "a == b && c");
+
+    let sum = expr().binary(expr().path("b"), BinOp::Add, expr().path("c"));
+    let sub = expr().binary(expr().path("a"), BinOp::Sub, sum);
+    assert_eq!(pretty(&sub), "a - (b + c)");
+    let assign = expr().assign(expr().path("a"), expr().path("b"));
+    let sum = expr().binary(assign, BinOp::Add, expr().path("c"));
+    assert_eq!(pretty(&sum), "(a = b) + c");
+    let neg = expr().unary(UnOp::Neg, expr().cast(expr().path("a"), "i32"));
+    assert_eq!(pretty(&neg), "-(a as i32)");
+}
+
+#[test]
+fn test_jump_operands() {
+    let closure = expr().closure([pat().ident("x")], expr().path("x"));
+    let call = expr().call(closure.clone(), [expr().lit(1)]);
+    assert_eq!(pretty(&call), "(|x| x)(1)");
+    let sum = expr().binary(closure.clone(), BinOp::Add, expr().lit(1));
+    assert_eq!(pretty(&sum), "(|x| x) + 1");
+    let sum = expr().binary(expr().lit(1), BinOp::Add, closure);
+    assert_eq!(pretty(&sum), "1 + |x| x");
+
+    let ret = expr().return_expr(Some(expr().path("a")));
+    let sum = expr().binary(ret.clone(), BinOp::Add, expr().path("b"));
+    assert_eq!(pretty(&sum), "(return a) + b");
+    let sum = expr().binary(expr().path("b"), BinOp::Add, ret);
+    assert_eq!(pretty(&sum), "b + return a");
+}
+
+#[test]
+fn test_range_and_callee() {
+    let range = expr().range(
+        Some(expr().path("a")),
+        RangeLimits::HalfOpen,
+        Some(expr().path("b")),
+    );
+    let rev = expr().method_call(range, "rev", []);
+    assert_eq!(pretty(&rev), "(a..b).rev()");
+    let inner = expr().range(
+        Some(expr().path("a")),
+        RangeLimits::HalfOpen,
+        Some(expr().path("b")),
+    );
+    let range = expr().range(Some(inner), RangeLimits::HalfOpen, None);
+    assert_eq!(pretty(&range), "(a..b)..");
+
+    let call = expr().call(expr().field(expr().path("a"), "b"), []);
+    assert_eq!(pretty(&call), "(a.b)()");
+    let call = expr().call(expr().method_call(expr().path("a"), "b", []), []);
+    assert_eq!(pretty(&call), "a.b()()");
+}
+
+#[test]
+fn test_condition_struct() {
+    let eq = expr().binary(
+        expr().path("s"),
+        BinOp::Eq,
+        expr().struct_expr("S", [field_value("a", expr().lit(1))]),
+    );
+    let cond = expr().if_expr(eq, block(), None);
+    insta::assert_snapshot!(pretty(&cond), @"if (s == S { a: 1 }) {}");
+}
+
+#[cfg(feature = "parser")]
+#[test]
+fn test_statement_start() {
+    let func =
+        parse_file("fn f() { (match x { _ => 1 }) + 1; ({ v })[0]; ({ a }.len()); }").unwrap();
+    insta::assert_snapshot!(pretty(&func), @r"
+    fn f() {
+        (match x {
+            _ => 1,
+        }) + 1;
+        ({
+            v
+        })[0];
+        ({
+            a
+        }.len());
+    }
+    ");
+}
+
+#[cfg(feature = "parser")]
+#[test]
+fn test_remove_redundant_parens() {
+    let src = "fn f() {
+        let a = (b * c) + (d + e);
+        let f = ((&g).h).i;
+        let j = -(k.l());
+        if (m == n) { (o)(); }
+        while (p < q) { r = (s = t); }
+        u((v), (w as u8) < x);
+    }";
+    insta::assert_snapshot!(remove_parens(src), @r"
+    fn f() {
+        let a = b * c + (d + e);
+        let f = (&g).h.i;
+        let j = -k.l();
+        if m == n {
+            o();
+        };
+        while p < q {
+            r = s = t;
+        };
+        u(v, (w as u8) < x);
+    }
+    ");
+}
+
+#[cfg(feature = "parser")]
+#[test]
+fn test_remove_redundant_parens_keeps_needed() {
+    let src = "fn f() {
+        if (S { a } == s) {}
+        (match z { _ => 1 }) + 1;
+        ((|x| x) + 1);
+    }";
+    insta::assert_snapshot!(remove_parens(src), @r"
+    fn f() {
+        if (S { a } == s) {};
+        (match z {
+            _ => 1,
+        }) + 1;
+        (|x| x) + 1;
+    }
+    ");
+
+    let printed = remove_parens("fn f() { let Some(x) = (a && b) else { return; }; }");
+    assert!(printed.contains("let Some(x) = (a && b) else"));
+    let printed = remove_parens("fn f() { let Some(x) = (S { a }) else { return; }; }");
+    assert!(printed.contains("let Some(x) = (S { a }) else"));
+    let printed = remove_parens("fn f() { let x = (S { a }); }");
+    assert!(printed.contains("let x = S { a };"));
+}
@@ -0,0 +1,299 @@
+use rasto::builder::*;
+#[cfg(feature = "parser")]
+use rasto::parser::parse_file;
+use rasto::ast::items::Item;
+use rasto::ast::{ImplItem, Stmt};
+use rasto::pretty_printer::{ItemSpacing, NewlineStyle, TrailingComma};
+use rasto::{PrinterConfig, pretty, pretty_with};
+
+fn long_array() -> rasto::ast::Expr {
+    expr().array((0..8).map(|i| expr().path(path(format!("element_number_{i}")).build())))
+}
+
+#[test]
+fn test_default_config_matches_pretty() {
+    let ast = struct_def("Foo").field("a", "i32").field("b", "u8").build();
+    assert_eq!(pretty_with(&ast, &PrinterConfig::default()), pretty(&ast));
+}
+
+#[test]
+fn test_max_width() {
+    let ast = long_array();
+    let wide = PrinterConfig {
+        max_width: 300,
+        ..Default::default()
+    };
+    insta::assert_snapshot!(pretty_with(&ast, &wide), @"[element_number_0, element_number_1, element_number_2, element_number_3, element_number_4, element_number_5, element_number_6, element_number_7]");
+
+    let narrow = PrinterConfig {
+        max_width: 40,
+        ..Default::default()
+    };
+    insta::assert_snapshot!(pretty_with(&ast, &narrow), @r"
+    [
+        element_number_0,
+        element_number_1,
+        element_number_2,
+        element_number_3,
+        element_number_4,
+        element_number_5,
+        element_number_6,
+        element_number_7,
+    ]
+    ");
+}
+
+#[test]
+fn test_tab_spaces() {
+    let ast = fn_def("foo").statement(expr().lit(42)).build();
+    let config = PrinterConfig {
+        tab_spaces: 2,
+        ..Default::default()
+    };
+    insta::assert_snapshot!(pretty_with(&ast, &config), @r"
+    fn foo() {
+      42;
+    }
+    ");
+}
+
+#[test]
+fn test_hard_tabs() {
+    let ast = fn_def("foo")
+        .statement(expr().block(block().statement(expr().lit(42))))
+        .build();
+    let config = PrinterConfig {
+        hard_tabs: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        pretty_with(&ast, &config),
+        "fn foo() {\n\t{\n\t\t42;\n\t};\n}"
+    );
+}
+
+#[test]
+fn test_windows_newlines() {
+    let ast = struct_def("Foo").field("a", "i32").build();
+    let config = PrinterConfig {
+        newline_style: NewlineStyle::Windows,
+        final_newline: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        pretty_with(&ast, &config),
+        "struct Foo {\r\n    a: i32,\r\n}\r\n"
+    );
+}
+
+#[test]
+fn test_final_newline() {
+    let ast = file()
+        .item(struct_def("Foo").build())
+        .item(fn_def("foo").build())
+        .build();
+    let config = PrinterConfig {
+        final_newline: true,
+        ..Default::default()
+    };
+    let code = pretty_with(&ast, &config);
+    assert!(code.ends_with("}\n"));
+    assert!(!code.ends_with("\n\n"));
+    assert_eq!(code.trim_end(), pretty(&ast));
+}
+
+#[test]
+fn test_trailing_comma_never() {
+    let config = PrinterConfig {
+        trailing_comma: TrailingComma::Never,
+        max_width: 40,
+        ..Default::default()
+    };
+    let ast = enum_def("Color").variant("Red").variant("Green").build();
+    insta::assert_snapshot!(pretty_with(&ast, &config), @r"
+    enum Color {
+        Red,
+        Green
+    }
+    ");
+    insta::assert_snapshot!(pretty_with(&long_array(), &config), @r"
+    [
+        element_number_0,
+        element_number_1,
+        element_number_2,
+        element_number_3,
+        element_number_4,
+        element_number_5,
+        element_number_6,
+        element_number_7
+    ]
+    ");
+}
+
+#[test]
+fn test_trailing_comma_always() {
+    let config = PrinterConfig {
+        trailing_comma: TrailingComma::Always,
+        ..Default::default()
+    };
+    let ast = expr().struct_expr(
+        "Point",
+        [
+            field_value("x", expr().lit(1)),
+            field_value("y", expr().lit(2)),
+        ],
+    );
+    insta::assert_snapshot!(pretty_with(&ast, &config), @"Point { x: 1, y: 2, }");
+    let ast = expr().array([expr().lit(1), expr().lit(2)]);
+    insta::assert_snapshot!(pretty_with(&ast, &config), @"[1, 2,]");
+}
+
+#[cfg(feature = "parser")]
+#[test]
+fn test_large_max_width() {
+    let config = PrinterConfig {
+        max_width: 100_000,
+        ..Default::default()
+    };
+    let file = parse_file("fn f() { if a { b } }").unwrap();
+    insta::assert_snapshot!(pretty_with(&file, &config), @r"
+    fn f() {
+        if a {
+            b
+        }
+    }
+    ");
+}
+
+#[cfg(feature = "parser")]
+#[test]
+fn test_single_line() {
+    let config = PrinterConfig {
+        single_line: true,
+        final_newline: true,
+        ..Default::default()
+    };
+    let file = parse_file(
+        "
+// A line comment.
+/// A doc comment.
+fn f(a: u8) -> u8 {
+    let x = a; // An end-of-line comment.
+    // Escaped, since it contains */.
+    if x > 1 { x } else { 0 }
+}
+
+struct S {
+    a: u8,
+    b: u8,
+}
+",
+    )
+    .unwrap();
+    insta::assert_snapshot!(pretty_with(&file, &config), @r"
+    /* A line comment. */ /** A doc comment. */ fn f(a: u8) -> u8 { let x = a; /* An end-of-line comment. */ /* Escaped, since it contains * /. */ if x > 1 { x } else { 0 } } struct S { a: u8, b: u8 }
+    ");
+}
+
+#[test]
+fn test_single_line_ignores_max_width() {
+    let narrow = PrinterConfig {
+        max_width: 20,
+        single_line: true,
+        ..Default::default()
+    };
+    insta::assert_snapshot!(pretty_with(&long_array(), &narrow), @"[element_number_0, element_number_1, element_number_2, element_number_3, element_number_4, element_number_5, element_number_6, element_number_7]");
+}
+
+fn spaced_file() -> rasto::ast::File {
+    file()
+        .item(use_item("std::fmt"))
+        .item(use_item("std::io"))
+        .item(const_def("A", "u8", expr().lit(1)))
+        .item(const_def("B", "u8", expr().lit(2)))
+        .item(fn_def("f").build())
+        .item(fn_def("g").comment(comment().doc(" G.")).build())
+        .build()
+}
+
+#[test]
+fn test_item_spacing() {
+    let spacing = |item_spacing| PrinterConfig {
+        item_spacing,
+        ..Default::default()
+    };
+    insta::assert_snapshot!(pretty_with(&spaced_file(), &spacing(ItemSpacing::Compact)), @r"
+    use std::fmt;
+    use std::io;
+    const A: u8 = 1;
+    const B: u8 = 2;
+    fn f() {}
+
+    /// G.
+    fn g() {}
+    ");
+    insta::assert_snapshot!(pretty_with(&spaced_file(), &spacing(ItemSpacing::Grouped)), @r"
+    use std::fmt;
+    use std::io;
+
+    const A: u8 = 1;
+    const B: u8 = 2;
+
+    fn f() {}
+
+    /// G.
+    fn g() {}
+    ");
+    insta::assert_snapshot!(pretty_with(&spaced_file(), &spacing(ItemSpacing::Separated)), @r"
+    use std::fmt;
+
+    use std::io;
+
+    const A: u8 = 1;
+
+    const B: u8 = 2;
+
+    fn f() {}
+
+    /// G.
+    fn g() {}
+    ");
+}
+
+#[test]
+fn test_blank_line_before() {
+    let ast = file()
+        .item(Item::from(fn_def("f").build()).blank_line_before())
+        .item(
+            impl_block("S")
+                .item(associated_const("A", "u8").expr(expr().lit(1)))
+                .item(ImplItem::from(associated_const("B", "u8").expr(expr().lit(2))).blank_line_before()),
+        )
+        .item(
+            Item::from(
+                fn_def("g")
+                    .statement(stmt().local(pat().ident("a")).expr(expr().lit(1)))
+                    .statement(
+                        Stmt::from(stmt().local(pat().ident("b")).expr(expr().lit(2)))
+                            .blank_line_before(),
+                    )
+                    .build(),
+            )
+            .blank_line_before(),
+        )
+        .build();
+    insta::assert_snapshot!(pretty(&ast), @r"
+    fn f() {}
+    impl S {
+        const A: u8 = 1;
+
+        const B: u8 = 2;
+    }
+
+    fn g() {
+        let a = 1;
+
+        let b = 2;
+    }
+    ");
+}
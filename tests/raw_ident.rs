@@ -1,4 +1,3 @@
-use rasto::ast::*;
 use rasto::builder::*;
 use rasto::pretty;
 
@@ -78,3 +78,27 @@ fn test_all_the_things() {
         .build();
     insta::assert_snapshot!(pretty(&item));
 }
+
+#[test]
+fn test_gen_fn() {
+    let item = fn_def("counter")
+        .gen_()
+        .output(path("u32").build_type())
+        .statement(expr().yield_expr(Some(expr().lit(1))))
+        .build();
+    insta::assert_snapshot!(pretty(&item), @r"
+    gen fn counter() -> u32 {
+        yield 1;
+    }
+    ");
+}
+
+#[test]
+fn test_async_gen_fn() {
+    let item = fn_def("ticks")
+        .async_()
+        .gen_()
+        .output(path("u32").build_type())
+        .build();
+    insta::assert_snapshot!(pretty(&item), @"async gen fn ticks() -> u32 {}");
+}
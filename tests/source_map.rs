@@ -0,0 +1,133 @@
+use rasto::ast::items::Item;
+use rasto::ast::*;
+use rasto::builder::*;
+use rasto::pretty_printer::{NewlineStyle, SourceMap, pretty_with_source_map};
+use rasto::{PrinterConfig, pretty_with};
+
+fn sample() -> File {
+    file()
+        .item(
+            fn_def("area")
+                .input_typed("w", "u32")
+                .output("u32")
+                .statement(
+                    stmt()
+                        .local(pat().ident("h"))
+                        .ty("u32")
+                        .expr(expr().call(expr().path("height"), [expr().lit(2)]))
+                        .build(),
+                )
+                .statement(expr().binary(expr().path("w"), BinOp::Mul, expr().path("h")))
+                .has_trailing_semicolon(false)
+                .build(),
+        )
+        .build()
+}
+
+/// Lists each span with its kind, start and end, and the text it covers.
+fn render(output: &str, source_map: &SourceMap) -> String {
+    source_map
+        .iter()
+        .map(|span| {
+            format!(
+                "{} {}:{}-{}:{} {:?}",
+                span.kind,
+                span.start.line,
+                span.start.column,
+                span.end.line,
+                span.end.column,
+                &output[span.range()],
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn test_source_map_spans() {
+    let (output, source_map) = pretty_with_source_map(&sample(), &PrinterConfig::default());
+
+    insta::assert_snapshot!(output, @r"
+    fn area(w: u32) -> u32 {
+        let h: u32 = height(2);
+        w * h
+    }
+    ");
+    insta::assert_snapshot!(render(&output, &source_map), @r#"
+    File 1:1-4:2 "fn area(w: u32) -> u32 {\n    let h: u32 = height(2);\n    w * h\n}"
+    Item 1:1-4:2 "fn area(w: u32) -> u32 {\n    let h: u32 = height(2);\n    w * h\n}"
+    Pat 1:9-1:15 "w: u32"
+    Pat 1:9-1:10 "w"
+    Type 1:12-1:15 "u32"
+    Type 1:20-1:23 "u32"
+    Block 1:24-4:2 "{\n    let h: u32 = height(2);\n    w * h\n}"
+    Stmt 2:5-2:28 "let h: u32 = height(2);"
+    Pat 2:9-2:10 "h"
+    Type 2:12-2:15 "u32"
+    Expr 2:18-2:27 "height(2)"
+    Expr 2:18-2:24 "height"
+    Expr 2:25-2:26 "2"
+    Stmt 3:5-3:10 "w * h"
+    Expr 3:5-3:10 "w * h"
+    Expr 3:5-3:6 "w"
+    Expr 3:9-3:10 "h"
+    "#);
+}
+
+#[test]
+fn test_source_map_finds_nodes() {
+    let file = sample();
+    let (output, source_map) = pretty_with_source_map(&file, &PrinterConfig::default());
+
+    let Item::Fn(item) = &file.items[0] else {
+        unreachable!("the sample is a function");
+    };
+    let Stmt::Local(local) = &item.block.stmts[0] else {
+        unreachable!("the first statement is a `let`");
+    };
+    let init = local.expr.as_ref().unwrap();
+    let span = source_map.span_of(init).unwrap();
+    assert_eq!(&output[span.range()], "height(2)");
+    assert_eq!((span.start.line, span.start.column), (2, 18));
+
+    // A diagnostic pointing at the `2` in `height(2)`.
+    let kinds: Vec<_> = source_map.nodes_at(2, 25).map(|span| span.kind).collect();
+    assert_eq!(kinds, ["Expr", "Expr", "Stmt", "Block", "Item", "File"]);
+    assert!(source_map.nodes_at(2, 25).nth(1).unwrap().is(init));
+    assert!(
+        source_map
+            .nodes_at(2, 25)
+            .nth(4)
+            .unwrap()
+            .is(&file.items[0])
+    );
+    assert_eq!(source_map.nodes_at(9, 1).count(), 0);
+}
+
+#[test]
+fn test_source_map_keeps_output() {
+    let config = PrinterConfig {
+        max_width: 20,
+        hard_tabs: true,
+        newline_style: NewlineStyle::Windows,
+        final_newline: true,
+        ..Default::default()
+    };
+    let (output, source_map) = pretty_with_source_map(&sample(), &config);
+    assert_eq!(output, pretty_with(&sample(), &config));
+
+    let last = source_map.iter().rfind(|span| span.kind == "Stmt").unwrap();
+    assert_eq!(&output[last.range()], "w * h");
+    assert_eq!((last.start.line, last.start.column), (7, 2));
+}
+
+#[test]
+fn test_source_map_counts_characters() {
+    let ast = expr().tuple([expr().lit("héllo"), expr().lit('ü')]);
+    let (output, source_map) = pretty_with_source_map(&ast, &PrinterConfig::default());
+
+    let last = source_map.iter().last().unwrap();
+    assert_eq!(&output[last.range()], "'ü'");
+    assert_eq!(last.start.column, 11);
+    assert_eq!(last.start.offset, 11);
+}
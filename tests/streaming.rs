@@ -0,0 +1,93 @@
+use rasto::ast::*;
+use rasto::builder::*;
+use rasto::pretty_printer::{PrettyPrinter, Printer, PrinterConfig, pretty_to_writer};
+use rasto::{pretty, pretty_with};
+use std::cell::RefCell;
+use std::fmt;
+use std::io;
+use std::rc::Rc;
+
+/// A writer whose output can be read while a printer writes to it.
+struct Shared(Rc<RefCell<String>>);
+
+impl fmt::Write for Shared {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.borrow_mut().push_str(s);
+        Ok(())
+    }
+}
+
+fn large_file(items: usize) -> File {
+    let mut file = file();
+    for i in 0..items {
+        file = file.item(
+            fn_def(format!("f{i}"))
+                .statement(
+                    stmt()
+                        .local(pat().ident("x"))
+                        .expr(expr().call(expr().path("compute"), [expr().lit(i as i32)]))
+                        .build(),
+                )
+                .build(),
+        );
+    }
+    file.build()
+}
+
+#[test]
+fn test_printer_streams_output() {
+    let file = large_file(1000);
+    let output = Rc::new(RefCell::new(String::new()));
+    let mut writer = Shared(output.clone());
+    let mut printer = Printer::new(&mut writer, PrinterConfig::default());
+
+    file.items[0].pretty_print(&mut printer).unwrap();
+    // The function is written before the printer is finished.
+    assert_eq!(*output.borrow(), "fn f0() {\n    let x = compute(0);\n}");
+
+    for item in &file.items[1..] {
+        printer.hard_break();
+        item.pretty_print(&mut printer).unwrap();
+    }
+    let written = output.borrow().len();
+    printer.finish().unwrap();
+    assert_eq!(written, output.borrow().len());
+    assert_eq!(*output.borrow(), pretty(&file));
+}
+
+#[test]
+fn test_pretty_to_writer() {
+    let file = large_file(100);
+    let config = PrinterConfig {
+        max_width: 20,
+        final_newline: true,
+        ..Default::default()
+    };
+    let mut out = Vec::new();
+    pretty_to_writer(&file, &config, &mut out).unwrap();
+    assert_eq!(String::from_utf8(out).unwrap(), pretty_with(&file, &config));
+}
+
+/// A writer that fails after the given number of bytes.
+struct Failing(usize);
+
+impl io::Write for Failing {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if buf.len() > self.0 {
+            return Err(io::Error::new(io::ErrorKind::StorageFull, "disk full"));
+        }
+        self.0 -= buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_pretty_to_writer_returns_io_errors() {
+    let file = large_file(1000);
+    let error = pretty_to_writer(&file, &PrinterConfig::default(), Failing(100)).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::StorageFull);
+}
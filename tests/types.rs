@@ -0,0 +1,47 @@
+//! Tests for the `Type` AST node.
+
+use rasto::ast::*;
+use rasto::builder::*;
+use rasto::pretty;
+
+#[test]
+fn test_bare_fn() {
+    let ty = type_().bare_fn(["usize"], Some("bool"));
+    assert_eq!(pretty(&ty), "fn(usize) -> bool");
+}
+
+#[test]
+fn test_unsafe_extern_variadic_fn_ptr() {
+    let ty = type_()
+        .fn_ptr()
+        .unsafe_()
+        .abi("C")
+        .input_named("ptr", type_().ptr(false, "u8"))
+        .variadic(true)
+        .output("i32")
+        .build();
+    insta::assert_snapshot!(pretty(&ty), @r#"unsafe extern "C" fn(ptr: *const u8, ...) -> i32"#);
+}
+
+#[test]
+fn test_implicit_abi_fn_ptr() {
+    let ty = type_().fn_ptr().abi(Abi::Implicit).input("u32").build();
+    insta::assert_snapshot!(pretty(&ty), @"extern fn(u32)");
+}
+
+#[test]
+fn test_higher_ranked_fn_ptr() {
+    let ty = type_()
+        .fn_ptr()
+        .lifetime("a")
+        .input(type_().reference(false, "str").lifetime("a"))
+        .output(type_().reference(false, "str").lifetime("a"))
+        .build();
+    insta::assert_snapshot!(pretty(&ty), @"for<'a> fn(&'a str) -> &'a str");
+}
+
+#[test]
+fn test_extern_fn_with_implicit_abi() {
+    let item = fn_def("callback").abi(Abi::Implicit).build();
+    insta::assert_snapshot!(pretty(&item), @"extern fn callback() {}");
+}
@@ -0,0 +1,108 @@
+use rasto::ast::BinOp;
+use rasto::ast::items::Item;
+use rasto::builder::*;
+use rasto::pretty;
+use rasto::pretty_printer::{PrinterConfig, pretty_with};
+
+#[test]
+fn test_verbatim_item() {
+    let ast = file()
+        .item(verbatim("macro_rules! m {\n    () => {};\n}").comment(comment().doc(" A macro.")))
+        .item(fn_def("f").build())
+        .build();
+    insta::assert_snapshot!(pretty(&ast), @r"
+    /// A macro.
+    macro_rules! m {
+        () => {};
+    }
+    fn f() {}
+    ");
+}
+
+#[test]
+fn test_verbatim_reindents_to_nesting() {
+    let text = "
+        impl Foo for Bar {
+            fn foo(&self) {}
+
+            fn bar(&self) {}
+        }
+    ";
+    let ast = mod_item("inner").item(verbatim(text)).build();
+    insta::assert_snapshot!(pretty(&ast), @r"
+    mod inner {
+        impl Foo for Bar {
+            fn foo(&self) {}
+
+            fn bar(&self) {}
+        }
+    }
+    ");
+}
+
+#[test]
+fn test_verbatim_expr_type_pat_stmt() {
+    let item = fn_def("f")
+        .statement(
+            stmt()
+                .local(pat().verbatim("box x"))
+                .ty(type_().verbatim("impl Trait<A = B>"))
+                .expr(expr().verbatim("yeet!()")),
+        )
+        .statement(stmt().verbatim("become g();"))
+        .statement(expr().binary(expr().verbatim("a"), BinOp::Add, expr().path("b")))
+        .build();
+    insta::assert_snapshot!(pretty(&item), @r"
+    fn f() {
+        let box x: impl Trait<A = B> = yeet!();
+        become g();
+        a + b;
+    }
+    ");
+}
+
+#[test]
+fn test_verbatim_single_line() {
+    let item: Item = verbatim("struct S {\n    a: u8,\n}").into();
+    let config = PrinterConfig {
+        single_line: true,
+        ..PrinterConfig::default()
+    };
+    assert_eq!(pretty_with(&item, &config), "struct S { a: u8, }");
+    assert_eq!(format!("{item:#}"), "struct S { a: u8, }");
+}
+
+#[test]
+fn test_verbatim_single_line_comments() {
+    let item: Item = verbatim(
+        "//! Inner docs.\n/// Docs.\n// A comment.\n//// Not docs.\n// Ends with */\nfn f() {}",
+    )
+    .into();
+    let config = PrinterConfig {
+        single_line: true,
+        ..PrinterConfig::default()
+    };
+    assert_eq!(
+        pretty_with(&item, &config),
+        "/*! Inner docs. */ /** Docs. */ /* A comment. */ /*// Not docs. */ /* Ends with * / */ fn f() {}"
+    );
+}
+
+#[test]
+fn test_verbatim_single_line_trailing_comments() {
+    let item = fn_def("w")
+        .statement(stmt().verbatim("let a = 1; // c\nlet b = 2;"))
+        .build();
+    assert_eq!(
+        format!("{item:#}"),
+        "fn w() { let a = 1; /* c */ let b = 2; }"
+    );
+
+    let stmt = stmt().verbatim(
+        "let s = \"// not a comment\"; /* // */ let c = '\"'; // \"a\"\nlet r = r#\"//\"#; f('a', 'b); // x",
+    );
+    assert_eq!(
+        format!("{stmt:#}"),
+        "let s = \"// not a comment\"; /* // */ let c = '\"'; /* \"a\" */ let r = r#\"//\"#; f('a', 'b); /* x */"
+    );
+}
@@ -0,0 +1,476 @@
+#[cfg(feature = "parser")]
+use rasto::ast::items::{Item, TraitItem};
+use rasto::ast::*;
+use rasto::builder::*;
+use rasto::fold::{self, Fold};
+#[cfg(feature = "parser")]
+use rasto::parser::parse_file;
+use rasto::pretty;
+#[cfg(feature = "parser")]
+use rasto::pretty_printer::{PrettyPrinter, Printer};
+use rasto::visit::{self, Visit};
+use rasto::visit_mut::{self, VisitMut};
+#[cfg(feature = "parser")]
+use std::collections::BTreeSet;
+#[cfg(feature = "parser")]
+use std::fs;
+#[cfg(feature = "parser")]
+use std::path::Path as FsPath;
+#[cfg(feature = "parser")]
+use thin_vec::thin_vec;
+
+/// Parses a file that uses every variant of the AST enums, and adds the
+/// nodes the parser does not support with the builders.
+#[cfg(feature = "parser")]
+fn kitchen_sink() -> File {
+    let mut file = parse_file(
+        r#"
+#![allow(unused)]
+/// Docs.
+#[cfg(feature = "x")]
+pub struct S<'a, T: Clone, const N: usize> {
+    x: &'a [T; N], // eol
+}
+union U { a: u8 }
+enum E { A }
+const C: (u8,) = (1,);
+static mut M: *const u8 = 0 as *const u8;
+type F = for<'a> unsafe extern "C" fn(&'a u8) -> !;
+type R = &'static &'_ Vec<_, 'a, [u8], (u8)>;
+impl<T> Tr for S<T> {
+    type A = u8;
+    const B: u8 = 1;
+    fn f(&self, x: u8) where T: Copy, 'a: 'b {}
+}
+trait Tr { const B: u8; fn f(&self); }
+trait Al = Clone;
+extern crate core;
+extern "C" { static X: u8; fn g(); m!(); }
+mod m { use std::fmt; }
+m!(a, (b), "s");
+fn exprs() {
+    let x: u8 = [1, 2][0];
+    let Some(y) = z else { return; };
+    x = 1;
+    async { 1 }.await;
+    { 1 }
+    f::<3>(1);
+    g.h(2);
+    x as u8;
+    |a| a;
+    const { 1 };
+    loop { break; continue; }
+    s.f;
+    for i in 0..1 {}
+    if a {} else {}
+    match x {
+        1 | 2 => {}
+        3..=4 => {}
+        [a, ..] => {}
+        S { a: 1, .. } => {}
+        T((a)) => {}
+        (a, b) => {}
+        &mut a => {}
+        m!() => {}
+        A::B => {}
+        const { 1 } => {}
+        _ => {}
+    }
+    (x);
+    &x;
+    &raw mut x;
+    S { a: 1 };
+    (1, 2);
+    -x + 1;
+    while x {}
+    b"a"; c"a"; b'a'; 'a'; 1.0; true;
+    fn inner() {}
+}
+"#,
+    )
+    .unwrap();
+
+    let asm = global_asm_item("nop")
+        .operand(asm_operand().reg(
+            AsmDirection::In,
+            RegSpec::Class("reg".into()),
+            expr().lit(1),
+        ))
+        .operand(asm_operand().sym("f"))
+        .operand(asm_operand().const_(expr().lit(2)))
+        .operand(asm_operand().clobber_abi("C"))
+        .operand(asm_operand().label(block()))
+        .build();
+    file.items.push(asm.into());
+    file.items.push(extern_type_item("Opaque").build().into());
+    let Some(Item::ExternBlock(extern_block)) = file
+        .items
+        .iter_mut()
+        .find(|item| matches!(item, Item::ExternBlock(_)))
+    else {
+        unreachable!("the source has an extern block");
+    };
+    extern_block
+        .items
+        .push(ExternalItem::Type(extern_type_item("Foreign").build()));
+    file.items.push(foreign_mod_item("C").build().into());
+    for ty in [
+        type_().group("u8"),
+        type_().impl_trait(),
+        type_().trait_object(),
+        type_().mac(ExprMacroCall {
+            path: "ty".into(),
+            delimiter: Delimiter::Parenthesis,
+            tokens: TokenStream {
+                tokens: thin_vec![],
+            },
+        }),
+    ] {
+        file.items.push(type_alias("T", ty).build().into());
+    }
+    file.items.push(
+        fn_def("nightly")
+            .statement(asm_expr("nop").build_expr())
+            .statement(expr().become_expr(expr().call(expr().path("f"), [])))
+            .statement(expr().gen_block(block()))
+            .statement(expr().yield_expr(None))
+            .statement(expr().try_block(block()))
+            .statement(expr().infer())
+            .build()
+            .into(),
+    );
+    file.items.push(verbatim("item!();").into());
+    file.items.push(
+        fn_def("verbatim")
+            .statement(
+                stmt()
+                    .local(pat().verbatim("p"))
+                    .ty(type_().verbatim("T"))
+                    .expr(expr().verbatim("e")),
+            )
+            .statement(stmt().verbatim("s;"))
+            .statement(expr().custom(Node))
+            .build()
+            .into(),
+    );
+    file.items.push(custom(Node).into());
+    file
+}
+
+/// A node defined outside of the AST.
+#[cfg(feature = "parser")]
+#[derive(Debug, Clone, PartialEq)]
+struct Node;
+
+#[cfg(feature = "parser")]
+impl PrettyPrinter for Node {
+    fn pretty_print<'a>(&'a self, printer: &mut Printer<'a>) -> std::fmt::Result {
+        printer.string("node!()");
+        Ok(())
+    }
+}
+
+#[cfg(feature = "parser")]
+impl CustomNode for Node {}
+
+/// Records the enum variants it visits, as `Enum::Variant`.
+#[cfg(feature = "parser")]
+#[derive(Default)]
+struct VariantRecorder {
+    seen: BTreeSet<String>,
+}
+
+#[cfg(feature = "parser")]
+impl VariantRecorder {
+    fn record(&mut self, enum_name: &str, node: &impl std::fmt::Debug) {
+        let debug = format!("{node:?}");
+        let variant = debug
+            .split(|c: char| !c.is_alphanumeric() && c != '_')
+            .next()
+            .unwrap();
+        self.seen.insert(format!("{enum_name}::{variant}"));
+    }
+}
+
+#[cfg(feature = "parser")]
+macro_rules! record_variants {
+    ($(($visit:ident, $visit_mut:ident, $fold:ident)($ty:ident) => ($walk:ident, $walk_mut:ident),)*) => {
+        impl<'ast> Visit<'ast> for VariantRecorder {
+            $(
+                fn $visit(&mut self, node: &'ast $ty) {
+                    self.record(stringify!($ty), node);
+                    visit::$walk(self, node);
+                }
+            )*
+        }
+
+        impl VisitMut for VariantRecorder {
+            $(
+                fn $visit_mut(&mut self, node: &mut $ty) {
+                    self.record(stringify!($ty), node);
+                    visit_mut::$walk_mut(self, node);
+                }
+            )*
+        }
+
+        impl Fold for VariantRecorder {
+            $(
+                fn $fold(&mut self, node: $ty) -> $ty {
+                    self.record(stringify!($ty), &node);
+                    fold::$walk(self, node)
+                }
+            )*
+        }
+    };
+}
+
+#[cfg(feature = "parser")]
+record_variants! {
+    (visit_asm_operand, visit_asm_operand_mut, fold_asm_operand)(AsmOperand) => (walk_asm_operand, walk_asm_operand_mut),
+    (visit_attribute, visit_attribute_mut, fold_attribute)(Attribute) => (walk_attribute, walk_attribute_mut),
+    (visit_expr, visit_expr_mut, fold_expr)(Expr) => (walk_expr, walk_expr_mut),
+    (visit_external_item, visit_external_item_mut, fold_external_item)(ExternalItem) => (walk_external_item, walk_external_item_mut),
+    (visit_generic_arg, visit_generic_arg_mut, fold_generic_arg)(GenericArg) => (walk_generic_arg, walk_generic_arg_mut),
+    (visit_generic_param, visit_generic_param_mut, fold_generic_param)(GenericParam) => (walk_generic_param, walk_generic_param_mut),
+    (visit_impl_item, visit_impl_item_mut, fold_impl_item)(ImplItem) => (walk_impl_item, walk_impl_item_mut),
+    (visit_item, visit_item_mut, fold_item)(Item) => (walk_item, walk_item_mut),
+    (visit_lifetime, visit_lifetime_mut, fold_lifetime)(Lifetime) => (walk_lifetime, walk_lifetime_mut),
+    (visit_lit, visit_lit_mut, fold_lit)(Lit) => (walk_lit, walk_lit_mut),
+    (visit_meta, visit_meta_mut, fold_meta)(Meta) => (walk_meta, walk_meta_mut),
+    (visit_pat, visit_pat_mut, fold_pat)(Pat) => (walk_pat, walk_pat_mut),
+    (visit_stmt, visit_stmt_mut, fold_stmt)(Stmt) => (walk_stmt, walk_stmt_mut),
+    (visit_token_tree, visit_token_tree_mut, fold_token_tree)(TokenTree) => (walk_token_tree, walk_token_tree_mut),
+    (visit_trait_item, visit_trait_item_mut, fold_trait_item)(TraitItem) => (walk_trait_item, walk_trait_item_mut),
+    (visit_type, visit_type_mut, fold_type)(Type) => (walk_type, walk_type_mut),
+    (visit_where_predicate, visit_where_predicate_mut, fold_where_predicate)(WherePredicate) => (walk_where_predicate, walk_where_predicate_mut),
+}
+
+/// Reads the variants of `pub enum <name>` from the AST sources.
+#[cfg(feature = "parser")]
+fn enum_variants(name: &str) -> Vec<String> {
+    let dir = FsPath::new(env!("CARGO_MANIFEST_DIR")).join("src/ast");
+    let header = format!("pub enum {name} {{");
+    for entry in fs::read_dir(dir).unwrap() {
+        let src = fs::read_to_string(entry.unwrap().path()).unwrap();
+        let Some((_, body)) = src.split_once(&header) else {
+            continue;
+        };
+        let body = &body[..body.find("\n}").unwrap()];
+        return body
+            .lines()
+            .map(str::trim)
+            .filter(|line| line.starts_with(|c: char| c.is_ascii_uppercase()))
+            .map(|line| {
+                let end = line
+                    .find(|c: char| !c.is_alphanumeric())
+                    .unwrap_or(line.len());
+                format!("{name}::{}", &line[..end])
+            })
+            .collect();
+    }
+    panic!("`pub enum {name}` not found");
+}
+
+/// Lists the variants of the AST enums that `recorder` has not seen.
+#[cfg(feature = "parser")]
+fn missing_variants(recorder: &VariantRecorder) -> Vec<String> {
+    let enums = [
+        "AsmOperand",
+        "Attribute",
+        "Expr",
+        "ExternalItem",
+        "GenericArg",
+        "GenericParam",
+        "ImplItem",
+        "Item",
+        "Lifetime",
+        "Lit",
+        "Meta",
+        "Pat",
+        "Stmt",
+        "TokenTree",
+        "TraitItem",
+        "Type",
+        "WherePredicate",
+    ];
+    enums
+        .iter()
+        .flat_map(|name| enum_variants(name))
+        .filter(|variant| !recorder.seen.contains(variant))
+        .collect()
+}
+
+#[cfg(feature = "parser")]
+#[test]
+fn test_visit_covers_every_variant() {
+    let mut recorder = VariantRecorder::default();
+    recorder.visit_file(&kitchen_sink());
+
+    let missing = missing_variants(&recorder);
+    assert!(missing.is_empty(), "variants not visited: {missing:?}");
+}
+
+#[cfg(feature = "parser")]
+#[test]
+fn test_visit_mut_covers_every_variant() {
+    let mut file = kitchen_sink();
+    let mut recorder = VariantRecorder::default();
+    recorder.visit_file_mut(&mut file);
+
+    let missing = missing_variants(&recorder);
+    assert!(missing.is_empty(), "variants not visited: {missing:?}");
+    assert_eq!(file, kitchen_sink());
+}
+
+#[cfg(feature = "parser")]
+#[test]
+fn test_fold_covers_every_variant() {
+    let mut recorder = VariantRecorder::default();
+    let file = recorder.fold_file(kitchen_sink());
+
+    let missing = missing_variants(&recorder);
+    assert!(missing.is_empty(), "variants not folded: {missing:?}");
+    assert_eq!(file, kitchen_sink());
+}
+
+#[test]
+fn test_visit_counts_nodes() {
+    #[derive(Default)]
+    struct Counter {
+        idents: Vec<String>,
+        calls: usize,
+    }
+
+    impl<'ast> Visit<'ast> for Counter {
+        fn visit_ident(&mut self, ident: &'ast Ident) {
+            self.idents.push(ident.name.clone());
+        }
+
+        fn visit_expr_call(&mut self, call: &'ast ExprCall) {
+            self.calls += 1;
+            visit::walk_expr_call(self, call);
+        }
+    }
+
+    let call = expr().call(expr().path("g"), [expr().path("b")]);
+    let item = fn_def("add")
+        .input_typed("a", "u8")
+        .input_typed("b", "u8")
+        .output("u8")
+        .statement(expr().call(expr().path("f"), [expr().path("a"), call]))
+        .has_trailing_semicolon(false)
+        .build();
+    let mut counter = Counter::default();
+    counter.visit_item_fn(&item);
+
+    assert_eq!(counter.calls, 2);
+    assert_eq!(
+        counter.idents,
+        ["add", "a", "u8", "b", "u8", "u8", "f", "a", "g", "b"]
+    );
+}
+
+#[test]
+fn test_visit_skips_children_without_walk() {
+    struct NoClosures {
+        paths: usize,
+    }
+
+    impl<'ast> Visit<'ast> for NoClosures {
+        fn visit_expr_closure(&mut self, _: &'ast ExprClosure) {}
+
+        fn visit_expr_path(&mut self, path: &'ast ExprPath) {
+            self.paths += 1;
+            visit::walk_expr_path(self, path);
+        }
+    }
+
+    let item = fn_def("f")
+        .statement(expr().path("a"))
+        .statement(expr().closure([pat().ident("b")], expr().path("c")))
+        .statement(expr().path("d"))
+        .build();
+    let mut visitor = NoClosures { paths: 0 };
+    visitor.visit_item_fn(&item);
+
+    assert_eq!(visitor.paths, 2);
+}
+
+#[test]
+fn test_visit_mut_edits_in_place() {
+    struct Rename;
+
+    impl VisitMut for Rename {
+        fn visit_ident_mut(&mut self, ident: &mut Ident) {
+            if ident.name == "old" {
+                ident.name = "new".into();
+            }
+        }
+
+        fn visit_md_mut(&mut self, md: &mut Md) {
+            md.comments.clear();
+            visit_mut::walk_md_mut(self, md);
+        }
+    }
+
+    let mut item = fn_def("run")
+        .comment(comment().line(" Calls `old`."))
+        .statement(expr().call(expr().path("old"), [expr().path("x")]))
+        .statement(expr().method_call(
+            expr().path("old"),
+            "old",
+            [expr().field(expr().path("x"), "old")],
+        ))
+        .build();
+    Rename.visit_item_fn_mut(&mut item);
+
+    insta::assert_snapshot!(pretty(&item), @r"
+    fn run() {
+        new(x);
+        new.new(x.new);
+    }
+    ");
+}
+
+#[test]
+fn test_fold_replaces_variants() {
+    /// Folds additions of integer literals into a single literal.
+    struct ConstFold;
+
+    impl Fold for ConstFold {
+        fn fold_expr(&mut self, node: Expr) -> Expr {
+            match fold::walk_expr(self, node) {
+                Expr::Binary(ExprBinary {
+                    left,
+                    op: BinOp::Add,
+                    right,
+                }) => match (left.into_inner(), right.into_inner()) {
+                    (Expr::Lit(Lit::Int(a)), Expr::Lit(Lit::Int(b))) => {
+                        Expr::Lit(Lit::Int(LitInt {
+                            value: a.value + b.value,
+                            ..a
+                        }))
+                    }
+                    (left, right) => expr().binary(left, BinOp::Add, right),
+                },
+                Expr::Paren(ExprParen { expr }) if matches!(*expr, Expr::Lit(_)) => *expr,
+                node => node,
+            }
+        }
+    }
+
+    let item = fn_def("sum")
+        .statement(expr().binary(
+            expr().paren(expr().binary(expr().lit(1), BinOp::Add, expr().lit(2))),
+            BinOp::Add,
+            expr().binary(expr().path("x"), BinOp::Add, expr().lit(4)),
+        ))
+        .build();
+    let item = ConstFold.fold_item_fn(item);
+
+    insta::assert_snapshot!(pretty(&item), @r"
+    fn sum() {
+        3 + (x + 4);
+    }
+    ");
+}